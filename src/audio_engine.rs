@@ -0,0 +1,783 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use gloo::events::EventListener;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{
+    AudioContext, AudioContextState, AudioParam, BaseAudioContext, DelayNode, GainNode, HtmlMediaElement, MediaElementAudioSourceNode, MediaStream, MediaStreamAudioDestinationNode,
+    MediaStreamAudioSourceNode, MediaStreamConstraints, MediaStreamTrack, MediaStreamTrackState, MediaTrackConstraints,
+};
+use yew::html::Scope;
+use yew::prelude::*;
+
+use crate::event_bus::{emit, AppEvent};
+use crate::input_devices::list_input_devices;
+use crate::settings::{self, InputSettings, RecordingSettings};
+
+// 조건부 로그 매크로 정의
+#[cfg(debug_assertions)]
+macro_rules! console_log {
+    ($($arg:tt)*) => {
+        web_sys::console::log_1(&format!($($arg)*).into());
+    };
+}
+
+#[cfg(not(debug_assertions))]
+macro_rules! console_log {
+    ($($arg:tt)*) => {};
+}
+
+#[cfg(debug_assertions)]
+macro_rules! console_warn {
+    ($($arg:tt)*) => {
+        web_sys::console::warn_1(&format!($($arg)*).into());
+    };
+}
+
+#[cfg(not(debug_assertions))]
+macro_rules! console_warn {
+    ($($arg:tt)*) => {};
+}
+
+// 모든 도구가 공유하는 오디오 엔진
+// AudioContext 하나, 마이크 입력 하나, 도구별 출력 채널(게인/뮤트)과 마스터 버스로 구성
+//
+//   [도구 소리] -> 채널 게인 -> 마스터 게인 -> destination
+//   [입력 장치] -> MediaStreamAudioSourceNode -> 마이크 입력 게인 (분석기/모니터가 연결해서 사용)
+//   입력 장치를 바꾸거나 장치가 뽑히면 앞쪽 소스만 새 장치로 교체하므로 도구 쪽 연결은 유지됨
+//
+// 녹음 버스: 마이크와 메트로놈/스케일/피아노 채널을 소리별 센드 게인을 거쳐 믹스 스트림으로 모음
+//   [마이크 입력]             -> 센드 게인 -> 믹스 (MediaStreamAudioDestinationNode)
+//   [채널 게인] -> 정렬 지연 -> 센드 게인 -> 소리별 스템 (MediaStreamAudioDestinationNode)
+//   마이크 소리는 왕복 지연만큼 늦게 들어오므로 마이크를 녹음할 때는 다른 소리도 그만큼 늦춰 맞춤
+
+// 도구별 출력 채널
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Channel {
+    Monitor,        // 마이크 모니터링
+    Playback,       // 녹음 재생
+    Metronome,
+    ScaleGenerator,
+    Piano,
+    Trainer,        // 음정/화음 청음, 음 맞추기
+}
+
+impl Channel {
+    pub const ALL: [Channel; 6] = [
+        Channel::Monitor,
+        Channel::Playback,
+        Channel::Metronome,
+        Channel::ScaleGenerator,
+        Channel::Piano,
+        Channel::Trainer,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Channel::Monitor => "마이크 모니터",
+            Channel::Playback => "녹음 재생",
+            Channel::Metronome => "메트로놈",
+            Channel::ScaleGenerator => "스케일",
+            Channel::Piano => "피아노",
+            Channel::Trainer => "청음/음 맞추기",
+        }
+    }
+}
+
+// 녹음에 섞을 수 있는 소리
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RecordSource {
+    Mic,
+    Metronome,
+    ScaleGenerator,
+    Piano,
+}
+
+impl RecordSource {
+    pub const ALL: [RecordSource; 4] = [
+        RecordSource::Mic,
+        RecordSource::Metronome,
+        RecordSource::ScaleGenerator,
+        RecordSource::Piano,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            RecordSource::Mic => "마이크",
+            RecordSource::Metronome => "메트로놈",
+            RecordSource::ScaleGenerator => "스케일",
+            RecordSource::Piano => "피아노",
+        }
+    }
+
+    // 이 소리를 내보내는 출력 채널 (마이크는 채널이 아니라 입력)
+    fn channel(&self) -> Option<Channel> {
+        match self {
+            RecordSource::Mic => None,
+            RecordSource::Metronome => Some(Channel::Metronome),
+            RecordSource::ScaleGenerator => Some(Channel::ScaleGenerator),
+            RecordSource::Piano => Some(Channel::Piano),
+        }
+    }
+
+    pub fn is_enabled(&self, settings: &RecordingSettings) -> bool {
+        match self {
+            RecordSource::Mic => settings.mic,
+            RecordSource::Metronome => settings.metronome,
+            RecordSource::ScaleGenerator => settings.scale_generator,
+            RecordSource::Piano => settings.piano,
+        }
+    }
+
+    pub fn set_enabled(&self, settings: &mut RecordingSettings, enabled: bool) {
+        match self {
+            RecordSource::Mic => settings.mic = enabled,
+            RecordSource::Metronome => settings.metronome = enabled,
+            RecordSource::ScaleGenerator => settings.scale_generator = enabled,
+            RecordSource::Piano => settings.piano = enabled,
+        }
+    }
+
+    // 설정에서 녹음하도록 선택된 소리 목록
+    pub fn enabled(settings: &RecordingSettings) -> Vec<RecordSource> {
+        Self::ALL.into_iter().filter(|source| source.is_enabled(settings)).collect()
+    }
+}
+
+// 채널 게인/뮤트 상태
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ChannelState {
+    pub gain: f32,
+    pub muted: bool,
+}
+
+impl Default for ChannelState {
+    fn default() -> Self {
+        Self { gain: 1.0, muted: false }
+    }
+}
+
+impl ChannelState {
+    fn effective_gain(&self) -> f32 {
+        if self.muted { 0.0 } else { self.gain }
+    }
+}
+
+// 게인을 바꿀 때 이어 붙이는 시간 (초) - set_value로 값이 한 번에 튀면 딸깍 소리가 남
+pub const GAIN_RAMP_SECONDS: f64 = 0.03;
+
+// AudioParam을 지금 값에서 목표 값까지 seconds 동안 선형으로 옮김
+// 진행 중인 램프는 취소하고 현재 값에서 다시 시작하므로 슬라이더를 계속 움직여도 끊기지 않음
+pub fn ramp_param(ctx: &BaseAudioContext, param: &AudioParam, target: f32, seconds: f64) {
+    let now = ctx.current_time();
+    let _ = param.cancel_scheduled_values(now);
+    let _ = param.set_value_at_time(param.value(), now);
+    let _ = param.linear_ramp_to_value_at_time(target, now + seconds);
+}
+
+// 게인 노드 값을 클릭 없이 변경 (재생/녹음 중에도 사용 가능)
+pub fn ramp_gain(node: &GainNode, target: f32) {
+    ramp_param(&node.context(), &node.gain(), target, GAIN_RAMP_SECONDS);
+}
+
+// 공유 마이크 입력 (사용하는 도구 수를 세어 마지막 사용자가 놓으면 트랙 정지)
+#[derive(Clone)]
+pub struct MicSource {
+    pub stream: MediaStream, // 열었을 때의 장치 스트림 (장치가 바뀌면 교체됨)
+    pub source: GainNode,    // 엔진의 마이크 입력 노드 (장치가 바뀌어도 그대로)
+}
+
+struct SharedMic {
+    mic: MicSource,
+    users: usize,
+    device_source: MediaStreamAudioSourceNode, // 현재 장치 -> 마이크 입력 노드
+}
+
+// 열린 입력 장치 정보 (믹서 표시용)
+#[derive(Debug, Clone, PartialEq)]
+pub struct InputInfo {
+    pub label: String,
+    pub device_id: Option<String>,
+    pub sample_rate: Option<f64>, // 브라우저가 알려줄 때만
+}
+
+// 녹음 버스로 가는 소리별 센드 (선택되지 않은 소리는 게인 0)
+struct RecordSend {
+    gain: GainNode,
+    stem: MediaStreamAudioDestinationNode,
+    delay: Option<DelayNode>, // 마이크와 맞추는 정렬 지연 (마이크 센드에는 없음)
+}
+
+// 처음 소리를 낼 때 만드는 오디오 그래프
+struct Graph {
+    ctx: AudioContext,
+    master: GainNode,
+    channels: HashMap<Channel, GainNode>,
+    mic_input: GainNode,
+    mic_dry_tap: GainNode, // 마이크 센드로 가는 생소리 (기본)
+    mic_wet_tap: GainNode, // 마이크 센드로 가는 모니터 이펙트 출력 (이펙트 포함 녹음일 때)
+    record_mix: MediaStreamAudioDestinationNode,
+    record_sends: HashMap<RecordSource, RecordSend>,
+}
+
+struct EngineState {
+    graph: Option<Graph>,
+    channels: HashMap<Channel, ChannelState>,
+    master_gain: f32,
+    master_muted: bool, // 전체 음소거 (스피커 출력만, 녹음 버스는 그대로)
+    mic: Option<SharedMic>,
+    reopening_mic: bool,    // 입력 장치 교체 중
+    watching_devices: bool, // devicechange 리스너 등록 여부
+    record_delay: f64,      // 마이크 외 센드에 건 정렬 지연 (초)
+}
+
+impl EngineState {
+    fn effective_master_gain(&self) -> f32 {
+        if self.master_muted { 0.0 } else { self.master_gain }
+    }
+
+    fn apply_master_gain(&self) {
+        if let Some(graph) = &self.graph {
+            ramp_gain(&graph.master, self.effective_master_gain());
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct AudioEngine {
+    state: Rc<RefCell<EngineState>>,
+}
+
+// 같은 엔진 인스턴스면 같은 컨텍스트 값으로 취급
+impl PartialEq for AudioEngine {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.state, &other.state)
+    }
+}
+
+impl Default for AudioEngine {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AudioEngine {
+    pub fn new() -> Self {
+        Self {
+            state: Rc::new(RefCell::new(EngineState {
+                graph: None,
+                channels: Channel::ALL.iter().map(|channel| (*channel, ChannelState::default())).collect(),
+                master_gain: 1.0,
+                master_muted: false,
+                mic: None,
+                reopening_mic: false,
+                watching_devices: false,
+                record_delay: 0.0,
+            })),
+        }
+    }
+
+    // 공유 AudioContext (처음 호출 시 그래프 생성, 일시 정지 상태면 재개)
+    pub fn context(&self) -> Result<AudioContext, JsValue> {
+        let mut state = self.state.borrow_mut();
+        if state.graph.is_none() {
+            let graph = Self::build_graph(&state)?;
+            console_log!("오디오 엔진 생성: {}Hz", graph.ctx.sample_rate());
+            state.graph = Some(graph);
+        }
+
+        let ctx = state.graph.as_ref().map(|graph| graph.ctx.clone()).unwrap();
+        if ctx.state() == AudioContextState::Suspended {
+            let _ = ctx.resume();
+        }
+        Ok(ctx)
+    }
+
+    fn build_graph(state: &EngineState) -> Result<Graph, JsValue> {
+        let ctx = AudioContext::new()?;
+        let master = ctx.create_gain()?;
+        master.gain().set_value(state.effective_master_gain());
+        master.connect_with_audio_node(&ctx.destination())?;
+
+        let mut channels = HashMap::new();
+        for channel in Channel::ALL {
+            let gain = ctx.create_gain()?;
+            gain.gain().set_value(state.channels.get(&channel).copied().unwrap_or_default().effective_gain());
+            gain.connect_with_audio_node(&master)?;
+            channels.insert(channel, gain);
+        }
+
+        let mic_input = ctx.create_gain()?;
+        let mic_dry_tap = ctx.create_gain()?;
+        let mic_wet_tap = ctx.create_gain()?;
+        mic_wet_tap.gain().set_value(0.0);
+        mic_input.connect_with_audio_node(&mic_dry_tap)?;
+
+        // 녹음 버스 (채널 게인 뒤에서 받으므로 믹서 볼륨/뮤트가 녹음에도 반영됨)
+        let record_mix = ctx.create_media_stream_destination()?;
+        let mut record_sends = HashMap::new();
+        for source in RecordSource::ALL {
+            let gain = ctx.create_gain()?;
+            gain.gain().set_value(0.0);
+            let stem = ctx.create_media_stream_destination()?;
+            gain.connect_with_audio_node(&record_mix)?;
+            gain.connect_with_audio_node(&stem)?;
+            let delay = match source.channel().and_then(|channel| channels.get(&channel)) {
+                Some(channel_gain) => {
+                    let delay = ctx.create_delay_with_max_delay_time(settings::MAX_LATENCY_MS / 1000.0)?;
+                    delay.delay_time().set_value(state.record_delay as f32);
+                    channel_gain.connect_with_audio_node(&delay)?;
+                    delay.connect_with_audio_node(&gain)?;
+                    Some(delay)
+                }
+                None => {
+                    mic_dry_tap.connect_with_audio_node(&gain)?;
+                    mic_wet_tap.connect_with_audio_node(&gain)?;
+                    None
+                }
+            };
+            record_sends.insert(source, RecordSend { gain, stem, delay });
+        }
+
+        Ok(Graph { ctx, master, channels, mic_input, mic_dry_tap, mic_wet_tap, record_mix, record_sends })
+    }
+
+    // 녹음할 소리 선택 (선택된 소리의 센드만 열림)
+    // 마이크를 녹음하면 나머지 소리를 왕복 지연만큼 늦춰 녹음 안에서 마이크와 맞춤
+    pub fn set_record_sources(&self, sources: &[RecordSource]) -> Result<(), JsValue> {
+        self.context()?;
+        let record_delay = if sources.contains(&RecordSource::Mic) { self.round_trip_latency() } else { 0.0 };
+        let mut state = self.state.borrow_mut();
+        state.record_delay = record_delay;
+        if let Some(graph) = &state.graph {
+            for (source, send) in &graph.record_sends {
+                ramp_gain(&send.gain, if sources.contains(source) { 1.0 } else { 0.0 });
+                if let Some(delay) = &send.delay {
+                    delay.delay_time().set_value(record_delay as f32);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    // 녹음 버스가 마이크 외 소리에 건 정렬 지연 (초) - 녹음 전체가 연주보다 이만큼 늦게 담김
+    pub fn record_delay(&self) -> f64 {
+        self.state.borrow().record_delay
+    }
+
+    // 저장된 녹음 설정을 녹음 버스에 반영 (녹음을 시작할 때 호출)
+    pub fn apply_recording_settings(&self) -> Result<RecordingSettings, JsValue> {
+        let recording = settings::load_settings().recording;
+        self.set_record_sources(&RecordSource::enabled(&recording))?;
+        Ok(recording)
+    }
+
+    // 모니터 이펙트 출력을 연결할 녹음용 입력 (set_record_monitor_effects로 켰을 때만 녹음됨)
+    pub fn monitor_record_input(&self) -> Result<GainNode, JsValue> {
+        self.context()?;
+        let state = self.state.borrow();
+        state
+            .graph
+            .as_ref()
+            .map(|graph| graph.mic_wet_tap.clone())
+            .ok_or_else(|| JsValue::from_str("녹음 버스 없음"))
+    }
+
+    // 녹음의 마이크 소리를 생소리/모니터 이펙트 출력 중 하나로 선택
+    pub fn set_record_monitor_effects(&self, wet: bool) -> Result<(), JsValue> {
+        self.context()?;
+        let state = self.state.borrow();
+        if let Some(graph) = &state.graph {
+            ramp_gain(&graph.mic_dry_tap, if wet { 0.0 } else { 1.0 });
+            ramp_gain(&graph.mic_wet_tap, if wet { 1.0 } else { 0.0 });
+        }
+        Ok(())
+    }
+
+    // 선택된 소리를 모두 섞은 녹음용 스트림
+    pub fn recording_stream(&self) -> Result<MediaStream, JsValue> {
+        self.context()?;
+        let state = self.state.borrow();
+        state
+            .graph
+            .as_ref()
+            .map(|graph| graph.record_mix.stream())
+            .ok_or_else(|| JsValue::from_str("녹음 버스 없음"))
+    }
+
+    // 소리 하나만 담긴 스템 스트림
+    pub fn stem_stream(&self, source: RecordSource) -> Result<MediaStream, JsValue> {
+        self.context()?;
+        let state = self.state.borrow();
+        state
+            .graph
+            .as_ref()
+            .and_then(|graph| graph.record_sends.get(&source))
+            .map(|send| send.stem.stream())
+            .ok_or_else(|| JsValue::from_str("녹음 센드 없음"))
+    }
+
+    // 도구가 소리를 연결할 채널 입력 노드
+    pub fn channel_input(&self, channel: Channel) -> Result<GainNode, JsValue> {
+        self.context()?;
+        let state = self.state.borrow();
+        state
+            .graph
+            .as_ref()
+            .and_then(|graph| graph.channels.get(&channel).cloned())
+            .ok_or_else(|| JsValue::from_str("오디오 채널 없음"))
+    }
+
+    // <audio> 요소 출력을 전용 게인을 거쳐 채널로 보냄 (요소 하나당 한 번만 호출 가능)
+    // 소스 노드와 게인을 반환하므로 재생이 끝나면 호출한 쪽에서 disconnect 해야 함
+    pub fn connect_media_element(
+        &self,
+        channel: Channel,
+        element: &HtmlMediaElement,
+    ) -> Result<(MediaElementAudioSourceNode, GainNode), JsValue> {
+        let ctx = self.context()?;
+        let output = self.channel_input(channel)?;
+        let gain = ctx.create_gain()?;
+        gain.connect_with_audio_node(&output)?;
+        let source = ctx.create_media_element_source(element)?;
+        source.connect_with_audio_node(&gain)?;
+        Ok((source, gain))
+    }
+
+    // <audio> 요소를 전용 게인을 거쳐 채널로 보냄 (트랙별 볼륨용 게인 노드 반환)
+    pub fn connect_media_element_with_gain(&self, channel: Channel, element: &HtmlMediaElement) -> Result<GainNode, JsValue> {
+        let ctx = self.context()?;
+        let output = self.channel_input(channel)?;
+        let gain = ctx.create_gain()?;
+        gain.connect_with_audio_node(&output)?;
+        let source = ctx.create_media_element_source(element)?;
+        source.connect_with_audio_node(&gain)?;
+        Ok(gain)
+    }
+
+    pub fn channel_state(&self, channel: Channel) -> ChannelState {
+        self.state.borrow().channels.get(&channel).copied().unwrap_or_default()
+    }
+
+    pub fn set_channel_gain(&self, channel: Channel, gain: f32) {
+        self.update_channel(channel, |state| state.gain = gain.clamp(0.0, 1.0));
+    }
+
+    pub fn set_channel_muted(&self, channel: Channel, muted: bool) {
+        self.update_channel(channel, |state| state.muted = muted);
+    }
+
+    fn update_channel(&self, channel: Channel, change: impl FnOnce(&mut ChannelState)) {
+        let mut state = self.state.borrow_mut();
+        let channel_state = state.channels.entry(channel).or_default();
+        change(channel_state);
+        let gain = channel_state.effective_gain();
+        if let Some(node) = state.graph.as_ref().and_then(|graph| graph.channels.get(&channel)) {
+            ramp_gain(node, gain);
+        }
+    }
+
+    pub fn master_gain(&self) -> f32 {
+        self.state.borrow().master_gain
+    }
+
+    pub fn set_master_gain(&self, gain: f32) {
+        let mut state = self.state.borrow_mut();
+        state.master_gain = gain.clamp(0.0, 1.0);
+        state.apply_master_gain();
+    }
+
+    pub fn master_muted(&self) -> bool {
+        self.state.borrow().master_muted
+    }
+
+    // 전체 음소거 - 녹음 센드는 마스터 앞에서 받으므로 녹음에는 영향 없음
+    pub fn set_master_muted(&self, muted: bool) {
+        {
+            let mut state = self.state.borrow_mut();
+            if state.master_muted == muted {
+                return;
+            }
+            state.master_muted = muted;
+            state.apply_master_gain();
+        }
+        console_log!("전체 음소거: {}", muted);
+        emit(AppEvent::MasterMuteChanged { muted });
+    }
+
+    pub fn toggle_mute_all(&self) {
+        self.set_master_muted(!self.master_muted());
+    }
+
+    // 엔진 사운드 카드의 샘플레이트 (아직 그래프를 만들지 않았으면 None)
+    pub fn sample_rate(&self) -> Option<f32> {
+        self.state.borrow().graph.as_ref().map(|graph| graph.ctx.sample_rate())
+    }
+
+    // 장치 스트림이 들어오는 마이크 입력 노드
+    fn mic_input(&self) -> Result<GainNode, JsValue> {
+        self.context()?;
+        let state = self.state.borrow();
+        state
+            .graph
+            .as_ref()
+            .map(|graph| graph.mic_input.clone())
+            .ok_or_else(|| JsValue::from_str("마이크 입력 없음"))
+    }
+
+    // 마이크 입력 사용 시작 (이미 열려 있으면 같은 스트림 공유)
+    pub async fn acquire_mic(&self) -> Result<MicSource, JsValue> {
+        if let Some(shared) = self.state.borrow_mut().mic.as_mut() {
+            shared.users += 1;
+            return Ok(shared.mic.clone());
+        }
+
+        let stream = open_device_stream(&settings::load_settings().input).await?;
+
+        // 권한을 기다리는 동안 다른 도구가 먼저 열었으면 그쪽을 사용
+        if let Some(shared) = self.state.borrow_mut().mic.as_mut() {
+            stop_tracks(&stream);
+            shared.users += 1;
+            return Ok(shared.mic.clone());
+        }
+
+        let device_source = match self.attach_device(&stream) {
+            Ok(device_source) => device_source,
+            Err(err) => {
+                stop_tracks(&stream);
+                return Err(err);
+            }
+        };
+        let mic = MicSource { stream, source: self.mic_input()? };
+        self.state.borrow_mut().mic = Some(SharedMic { mic: mic.clone(), users: 1, device_source });
+        self.watch_devices();
+        console_log!("공유 마이크 입력 열림");
+        dispatch_mic_input_changed();
+        Ok(mic)
+    }
+
+    // 마이크 사용 종료 (마지막 사용자면 트랙 정지)
+    pub fn release_mic(&self) {
+        let closed = {
+            let mut state = self.state.borrow_mut();
+            let Some(shared) = state.mic.as_mut() else {
+                return;
+            };
+
+            shared.users = shared.users.saturating_sub(1);
+            if shared.users == 0 {
+                shared.device_source.disconnect().ok();
+                stop_tracks(&shared.mic.stream);
+                state.mic = None;
+                console_log!("공유 마이크 입력 닫힘");
+            }
+            state.mic.is_none()
+        };
+        if closed {
+            dispatch_mic_input_changed();
+        }
+    }
+
+    // 장치 스트림을 마이크 입력 노드에 연결 (장치가 뽑혀 트랙이 끝나면 다시 엶)
+    fn attach_device(&self, stream: &MediaStream) -> Result<MediaStreamAudioSourceNode, JsValue> {
+        let device_source = self.context()?.create_media_stream_source(stream)?;
+        let mic_input = self.mic_input()?;
+        device_source.connect_with_audio_node(&mic_input)?;
+
+        if let Some(track) = audio_track(stream) {
+            let engine = self.clone();
+            let onended = Closure::once_into_js(move || {
+                console_warn!("마이크 트랙이 끝났습니다 - 입력 장치를 다시 엽니다");
+                wasm_bindgen_futures::spawn_local(async move { engine.recover_mic().await });
+            });
+            track.set_onended(Some(onended.unchecked_ref()));
+        }
+        Ok(device_source)
+    }
+
+    // 입력 설정을 바꾸었거나 장치가 사라졌을 때 열린 마이크를 새 장치 스트림으로 교체
+    // (마이크가 닫혀 있으면 다음에 열 때 새 설정이 적용되므로 아무것도 하지 않음)
+    pub async fn reopen_mic(&self) -> Result<(), JsValue> {
+        {
+            let mut state = self.state.borrow_mut();
+            if state.mic.is_none() || state.reopening_mic {
+                return Ok(());
+            }
+            state.reopening_mic = true;
+        }
+
+        let result = self.swap_device_stream().await;
+        self.state.borrow_mut().reopening_mic = false;
+        dispatch_mic_input_changed();
+        result
+    }
+
+    async fn swap_device_stream(&self) -> Result<(), JsValue> {
+        let stream = open_device_stream(&settings::load_settings().input).await?;
+
+        // 기다리는 동안 모든 도구가 마이크를 놓았으면 버림
+        if self.state.borrow().mic.is_none() {
+            stop_tracks(&stream);
+            return Ok(());
+        }
+
+        let device_source = match self.attach_device(&stream) {
+            Ok(device_source) => device_source,
+            Err(err) => {
+                stop_tracks(&stream);
+                return Err(err);
+            }
+        };
+
+        let mut state = self.state.borrow_mut();
+        if let Some(shared) = state.mic.as_mut() {
+            shared.device_source.disconnect().ok();
+            stop_tracks(&shared.mic.stream);
+            shared.mic.stream = stream;
+            shared.device_source = device_source;
+            console_log!("마이크 입력 장치 교체");
+        }
+        Ok(())
+    }
+
+    async fn recover_mic(&self) {
+        if let Err(err) = self.reopen_mic().await {
+            console_warn!("입력 장치를 다시 열 수 없습니다 (다음 장치 연결 때 재시도): {:?}", err);
+        }
+    }
+
+    // 장치 연결/해제 감지 (엔진당 한 번 등록)
+    fn watch_devices(&self) {
+        if std::mem::replace(&mut self.state.borrow_mut().watching_devices, true) {
+            return;
+        }
+        let Some(media_devices) = web_sys::window().and_then(|w| w.navigator().media_devices().ok()) else {
+            return;
+        };
+
+        let engine = self.clone();
+        EventListener::new(&media_devices, "devicechange", move |_| {
+            let engine = engine.clone();
+            wasm_bindgen_futures::spawn_local(async move { engine.handle_device_change().await });
+        })
+        .forget();
+    }
+
+    // 현재 트랙이 끝났거나, 저장해 둔 장치가 다시 연결되었으면 다시 엶
+    async fn handle_device_change(&self) {
+        let track = self.state.borrow().mic.as_ref().and_then(|shared| audio_track(&shared.mic.stream));
+        let Some(track) = track else {
+            return;
+        };
+
+        let ended = track.ready_state() == MediaStreamTrackState::Ended;
+        let current = track.get_settings().get_device_id();
+        let preferred_returned = match settings::load_settings().input.device_id {
+            Some(preferred) if current.as_ref() != Some(&preferred) => list_input_devices()
+                .await
+                .map(|devices| devices.iter().any(|device| device.id == preferred))
+                .unwrap_or(false),
+            _ => false,
+        };
+
+        if ended || preferred_returned {
+            self.recover_mic().await;
+        }
+    }
+
+    // 열린 입력 장치 정보 (마이크가 닫혀 있으면 None)
+    pub fn input_info(&self) -> Option<InputInfo> {
+        let track = self.state.borrow().mic.as_ref().and_then(|shared| audio_track(&shared.mic.stream))?;
+        let track_settings = track.get_settings();
+        // MediaTrackSettings 바인딩에 sampleRate가 없어 직접 읽음
+        let sample_rate = js_sys::Reflect::get(&track_settings, &JsValue::from_str("sampleRate"))
+            .ok()
+            .and_then(|value| value.as_f64());
+
+        Some(InputInfo {
+            label: track.label(),
+            device_id: track_settings.get_device_id(),
+            sample_rate,
+        })
+    }
+
+    // 브라우저가 알려주는 출력 지연 (baseLatency + outputLatency, 초, 모르면 0)
+    // 소리를 듣고 반응하는 입력(키/터치 탭)은 이만큼만 늦음
+    pub fn output_latency(&self) -> f64 {
+        let state = self.state.borrow();
+        let Some(graph) = &state.graph else {
+            return 0.0;
+        };
+        // AudioContext 바인딩에 baseLatency/outputLatency가 없어 직접 읽음
+        ["baseLatency", "outputLatency"]
+            .iter()
+            .filter_map(|key| js_sys::Reflect::get(&graph.ctx, &JsValue::from_str(key)).ok())
+            .filter_map(|value| value.as_f64())
+            .filter(|latency| latency.is_finite() && *latency >= 0.0)
+            .sum()
+    }
+
+    // 현재 입력 장치로 보정한 왕복 지연 (출력 + 입력, 초, 보정 전이면 0)
+    // 마이크가 닫혀 있으면 저장해 둔 장치 기준
+    pub fn round_trip_latency(&self) -> f64 {
+        let input = settings::load_settings().input;
+        let device_id = self.input_info().and_then(|info| info.device_id).or_else(|| input.device_id.clone());
+        input.latency_ms_for(device_id.as_deref()) / 1000.0
+    }
+}
+
+// 저장된 입력 설정으로 장치 스트림 열기
+// 장치는 ideal로 요청하므로 고른 장치가 없으면 브라우저가 기본 장치로 대체
+async fn open_device_stream(input: &InputSettings) -> Result<MediaStream, JsValue> {
+    let audio = MediaTrackConstraints::new();
+    if let Some(device_id) = &input.device_id {
+        let ideal = js_sys::Object::new();
+        js_sys::Reflect::set(&ideal, &JsValue::from_str("ideal"), &JsValue::from_str(device_id))?;
+        audio.set_device_id(&ideal);
+    }
+    audio.set_echo_cancellation(&JsValue::from_bool(input.echo_cancellation));
+    audio.set_noise_suppression(&JsValue::from_bool(input.noise_suppression));
+    audio.set_auto_gain_control(&JsValue::from_bool(input.auto_gain_control));
+
+    let constraints = MediaStreamConstraints::new();
+    constraints.set_audio(&audio);
+    let media_devices = web_sys::window()
+        .ok_or_else(|| JsValue::from_str("window 없음"))?
+        .navigator()
+        .media_devices()?;
+    JsFuture::from(media_devices.get_user_media_with_constraints(&constraints)?)
+        .await?
+        .dyn_into()
+}
+
+fn audio_track(stream: &MediaStream) -> Option<MediaStreamTrack> {
+    stream.get_audio_tracks().get(0).dyn_into().ok()
+}
+
+// 직접 멈추는 트랙은 장치가 뽑힌 것으로 보지 않도록 onended를 먼저 뗌
+fn stop_tracks(stream: &MediaStream) {
+    let tracks = stream.get_audio_tracks();
+    for i in 0..tracks.length() {
+        let track = MediaStreamTrack::from(tracks.get(i));
+        track.set_onended(None);
+        track.stop();
+    }
+}
+
+// 마이크가 열리거나 닫히거나 입력 장치가 바뀌면 MicInputChanged 이벤트로 알림
+fn dispatch_mic_input_changed() {
+    emit(AppEvent::MicInputChanged);
+}
+
+// 구조체 컴포넌트에서 App이 제공하는 엔진 가져오기
+pub fn engine_from_scope<C: BaseComponent>(link: &Scope<C>) -> AudioEngine {
+    match link.context::<AudioEngine>(Callback::noop()) {
+        Some((engine, _)) => engine,
+        None => {
+            console_warn!("AudioEngine 컨텍스트가 없어 새 엔진을 만듭니다");
+            AudioEngine::new()
+        }
+    }
+}
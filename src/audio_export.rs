@@ -0,0 +1,225 @@
+use js_sys::{ArrayBuffer, Uint8Array};
+use serde::{Deserialize, Serialize};
+use wasm_bindgen_futures::JsFuture;
+use wasm_bindgen::prelude::*;
+
+use crate::encoders::mp3::MP3_SAMPLE_RATES;
+use crate::encoders::wav::WavSampleFormat;
+use crate::encoders::EncodeFormat;
+use crate::tools::take_library::PitchHistory;
+
+// 조건부 로그 매크로 정의
+#[cfg(debug_assertions)]
+macro_rules! console_log {
+    ($($arg:tt)*) => {
+        web_sys::console::log_1(&format!($($arg)*).into());
+    };
+}
+
+#[cfg(not(debug_assertions))]
+macro_rules! console_log {
+    ($($arg:tt)*) => {};
+}
+
+// WAV/FLAC 내보내기에서 고를 수 있는 샘플레이트 (MP3는 MP3_SAMPLE_RATES)
+pub const EXPORT_SAMPLE_RATES: [u32; 4] = [48000, 44100, 32000, 22050];
+
+// 녹음 내보내기 포맷
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    WebM, // 녹음 원본 (MediaRecorder 출력 그대로)
+    Wav,
+    Flac,
+    Mp3,
+}
+
+impl ExportFormat {
+    pub const ALL: [ExportFormat; 4] = [ExportFormat::WebM, ExportFormat::Wav, ExportFormat::Flac, ExportFormat::Mp3];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ExportFormat::WebM => "WebM (원본)",
+            ExportFormat::Wav => "WAV",
+            ExportFormat::Flac => "FLAC",
+            ExportFormat::Mp3 => "MP3",
+        }
+    }
+
+    // 파일 확장자
+    pub fn value(&self) -> &'static str {
+        match self {
+            ExportFormat::WebM => "webm",
+            ExportFormat::Wav => "wav",
+            ExportFormat::Flac => "flac",
+            ExportFormat::Mp3 => "mp3",
+        }
+    }
+
+    pub fn mime_type(&self) -> &'static str {
+        match self {
+            ExportFormat::WebM => "audio/webm",
+            ExportFormat::Wav => "audio/wav",
+            ExportFormat::Flac => "audio/flac",
+            ExportFormat::Mp3 => "audio/mpeg",
+        }
+    }
+
+    // 포맷이 지원하는 샘플레이트 (WebM은 변환하지 않으므로 없음)
+    pub fn sample_rates(&self) -> &'static [u32] {
+        match self {
+            ExportFormat::WebM => &[],
+            ExportFormat::Wav | ExportFormat::Flac => &EXPORT_SAMPLE_RATES,
+            ExportFormat::Mp3 => &MP3_SAMPLE_RATES,
+        }
+    }
+}
+
+// 녹음 내보내기 옵션 (포맷별 설정은 해당 포맷일 때만 사용)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ExportOptions {
+    pub format: ExportFormat,
+    pub wav_format: WavSampleFormat,
+    pub flac_bits: u32,
+    pub mp3_bitrate: u32, // kbps
+    pub sample_rate: u32,
+    pub channels: u16, // 1: 모노, 2: 스테레오
+    pub region: Option<(f64, f64)>, // 선택 구간만 내보내기 (시작, 끝 초)
+}
+
+impl Default for ExportOptions {
+    fn default() -> Self {
+        Self {
+            format: ExportFormat::WebM,
+            wav_format: WavSampleFormat::Pcm16,
+            flac_bits: 16,
+            mp3_bitrate: 128,
+            sample_rate: 48000,
+            channels: 1,
+            region: None,
+        }
+    }
+}
+
+impl ExportOptions {
+    // 포맷을 바꿀 때 그 포맷이 지원하지 않는 샘플레이트는 48kHz로
+    pub fn with_format(self, format: ExportFormat) -> Self {
+        let sample_rate = if format.sample_rates().contains(&self.sample_rate) { self.sample_rate } else { 48000 };
+        Self { format, sample_rate, ..self }
+    }
+
+    // 인코더 워커에 넘길 포맷 (WebM은 인코딩 없이 원본 저장)
+    pub fn encode_format(&self) -> Option<EncodeFormat> {
+        match self.format {
+            ExportFormat::WebM => None,
+            ExportFormat::Wav => Some(EncodeFormat::Wav(self.wav_format)),
+            ExportFormat::Flac => Some(EncodeFormat::Flac { bits_per_sample: self.flac_bits }),
+            ExportFormat::Mp3 => Some(EncodeFormat::Mp3 { bitrate: self.mp3_bitrate }),
+        }
+    }
+}
+
+// 디코딩된 채널을 요청한 채널 수로 맞춤 (모노: 평균, 스테레오: 모노 원본은 복제)
+fn convert_channels(source: Vec<Vec<f32>>, channels: u16) -> Vec<Vec<f32>> {
+    if source.is_empty() {
+        return vec![Vec::new(); channels as usize];
+    }
+
+    if channels == 1 {
+        if source.len() == 1 {
+            return source;
+        }
+        let frame_count = source[0].len();
+        let scale = 1.0 / source.len() as f32;
+        let mono = (0..frame_count)
+            .map(|i| source.iter().map(|c| c.get(i).copied().unwrap_or(0.0)).sum::<f32>() * scale)
+            .collect();
+        return vec![mono];
+    }
+
+    let left = source[0].clone();
+    let right = source.get(1).cloned().unwrap_or_else(|| left.clone());
+    vec![left, right]
+}
+
+// 녹음 URL(WebM/Opus)을 PCM 채널로 디코딩
+// OfflineAudioContext의 샘플레이트로 디코딩되므로 리샘플링은 브라우저가 처리
+pub async fn decode_recording(audio_url: &str, sample_rate: u32, channels: u16) -> Result<Vec<Vec<f32>>, JsValue> {
+    let window = web_sys::window().ok_or_else(|| JsValue::from_str("window 없음"))?;
+
+    let response: web_sys::Response = JsFuture::from(window.fetch_with_str(audio_url)).await?.dyn_into()?;
+    let array_buffer: ArrayBuffer = JsFuture::from(response.array_buffer()?).await?.dyn_into()?;
+
+    let decoder = web_sys::OfflineAudioContext::new_with_number_of_channels_and_length_and_sample_rate(
+        channels as u32,
+        1,
+        sample_rate as f32,
+    )?;
+    let audio_buffer: web_sys::AudioBuffer = JsFuture::from(decoder.decode_audio_data(&array_buffer)?).await?.dyn_into()?;
+
+    let mut source = Vec::with_capacity(audio_buffer.number_of_channels() as usize);
+    for channel in 0..audio_buffer.number_of_channels() {
+        source.push(audio_buffer.get_channel_data(channel)?);
+    }
+    console_log!(
+        "녹음 디코딩: {}ch, {}Hz, {}프레임",
+        audio_buffer.number_of_channels(),
+        audio_buffer.sample_rate(),
+        audio_buffer.length()
+    );
+
+    Ok(convert_channels(source, channels))
+}
+
+// 디코딩된 채널에서 선택 구간만 잘라냄
+pub fn trim_channels(channels: Vec<Vec<f32>>, sample_rate: u32, region: (f64, f64)) -> Vec<Vec<f32>> {
+    let (start, end) = region;
+    channels
+        .into_iter()
+        .map(|samples| {
+            let from = ((start * sample_rate as f64) as usize).min(samples.len());
+            let to = ((end * sample_rate as f64).ceil() as usize).clamp(from, samples.len());
+            samples[from..to].to_vec()
+        })
+        .collect()
+}
+
+// 선택 구간의 피치 이력을 JSON으로 (시간은 구간 시작 기준)
+pub fn pitch_slice_json(history: &PitchHistory, region: (f64, f64)) -> Result<String, serde_json::Error> {
+    let (start, end) = region;
+    let slice: Vec<(f64, &Vec<(f64, f32)>)> = history
+        .iter()
+        .filter(|(t, _)| *t >= start && *t <= end)
+        .map(|(t, freqs)| (t - start, freqs))
+        .collect();
+    serde_json::to_string(&slice)
+}
+
+// 바이트를 다운로드용 Blob으로
+pub fn bytes_to_blob(bytes: &[u8], mime_type: &str) -> Result<web_sys::Blob, JsValue> {
+    let parts = js_sys::Array::new();
+    parts.push(&Uint8Array::from(bytes));
+    let blob_options = web_sys::BlobPropertyBag::new();
+    blob_options.set_type(mime_type);
+    web_sys::Blob::new_with_u8_array_sequence_and_options(&parts, &blob_options)
+}
+
+// Blob을 파일로 내려받기 (임시 object URL은 클릭 후 해제)
+pub fn download_blob(blob: &web_sys::Blob, filename: &str) -> Result<(), JsValue> {
+    let document = web_sys::window()
+        .and_then(|w| w.document())
+        .ok_or_else(|| JsValue::from_str("document 없음"))?;
+    let body = document.body().ok_or_else(|| JsValue::from_str("body 없음"))?;
+    let url = web_sys::Url::create_object_url_with_blob(blob)?;
+
+    let anchor: web_sys::HtmlAnchorElement = document.create_element("a")?.dyn_into()?;
+    anchor.set_href(&url);
+    anchor.set_download(filename);
+    body.append_child(&anchor)?;
+    anchor.click();
+    body.remove_child(&anchor)?;
+    web_sys::Url::revoke_object_url(&url)?;
+
+    console_log!("다운로드: {}", filename);
+    Ok(())
+}
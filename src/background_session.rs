@@ -0,0 +1,142 @@
+use serde::{Deserialize, Serialize};
+use web_sys::Element;
+use yew::prelude::*;
+use yew_router::prelude::*;
+
+use crate::event_bus::{use_app_event, AppEvent};
+use crate::routes::Route;
+use crate::PitchAnalyzer;
+
+// 백그라운드 세션 (선택 기능)
+// 켜 두면 레이아웃이 피치 분석기 하나를 계속 띄워 두고, 분석기가 있는 페이지에서는 그 자리(AnalyzerSlot)로 옮겨 보여줌
+// 다른 도구 페이지로 가도 마이크/녹음/히스토리가 이어지며, 미니 상태 바에 녹음 시간과 현재 음을 표시
+//
+//   MainLayout ── create_portal(<PitchAnalyzer />, host)   // 항상 같은 자리에 있어 상태 유지
+//   AnalyzerSlot ── <div> ← host를 옮겨 붙임               // 분석기 페이지에 있을 때만
+
+// 레이아웃이 제공하는 세션 컨텍스트
+#[derive(Clone, PartialEq)]
+pub struct BackgroundSession {
+    pub enabled: bool,
+    pub set_enabled: Callback<bool>,
+    pub host: Element, // 공유 분석기가 그려지는 요소 (포털 대상)
+}
+
+// 분석기가 알리는 현재 상태 (바뀔 때만 LiveStatus 이벤트로 발행)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct LiveStatus {
+    pub mic_active: bool,
+    pub recording: bool,
+    pub counting_in: bool,    // 녹음 전 카운트인 중
+    pub note: Option<String>, // 지금 부르는 음 (예: "A4")
+}
+
+// 공유 분석기를 붙일 빈 요소
+pub fn create_analyzer_host() -> Element {
+    let host = gloo::utils::document().create_element("div").expect("분석기 요소 생성 실패");
+    host.set_class_name("analyzer-host");
+    host
+}
+
+#[derive(Properties, PartialEq)]
+pub struct AnalyzerSlotProps {
+    #[prop_or(Some(true))]
+    pub show_links: Option<bool>,
+}
+
+// 페이지 안의 분석기 자리
+// 백그라운드 세션이 꺼져 있으면 페이지 전용 분석기를, 켜져 있으면 공유 분석기를 이 자리로 옮겨 보여줌
+#[function_component(AnalyzerSlot)]
+pub fn analyzer_slot(props: &AnalyzerSlotProps) -> Html {
+    let session = use_context::<BackgroundSession>().filter(|session| session.enabled);
+    let slot_ref = use_node_ref();
+
+    {
+        let slot_ref = slot_ref.clone();
+        let host = session.as_ref().map(|session| session.host.clone());
+        use_effect_with(host, move |host| {
+            let slot = slot_ref.cast::<Element>();
+            if let (Some(host), Some(slot)) = (host, &slot) {
+                let _ = slot.append_child(host);
+            }
+
+            // 다음 페이지의 자리로 이미 옮겨졌으면 건드리지 않음
+            let host = host.clone();
+            move || {
+                if let (Some(host), Some(slot)) = (host, slot) {
+                    if host.parent_element().as_ref() == Some(&slot) {
+                        let _ = slot.remove_child(&host);
+                    }
+                }
+            }
+        });
+    }
+
+    if session.is_some() {
+        html! { <div class="analyzer-slot" ref={slot_ref}></div> }
+    } else {
+        html! { <PitchAnalyzer show_links={props.show_links} /> }
+    }
+}
+
+fn format_recording_time(seconds: f64) -> String {
+    let seconds = seconds.max(0.0) as u32;
+    format!("{:02}:{:02}", seconds / 60, seconds % 60)
+}
+
+// 어느 페이지에서든 보이는 세션 상태 바 (마이크가 켜져 있을 때만)
+#[function_component(MiniStatusBar)]
+pub fn mini_status_bar() -> Html {
+    let session = use_context::<BackgroundSession>().filter(|session| session.enabled);
+    let status = use_state(LiveStatus::default);
+    let recording_time = use_state(|| 0.0f64);
+    let navigator = use_navigator();
+    let route = use_route::<Route>().unwrap_or(Route::Home);
+
+    {
+        let status = status.clone();
+        let recording_time = recording_time.clone();
+        use_app_event(move |event| match event {
+            AppEvent::LiveStatus(live) => {
+                if live.recording && !status.recording {
+                    recording_time.set(0.0);
+                }
+                status.set(live.clone());
+            }
+            AppEvent::PlaybackTimeUpdate { current_time, is_recording: true, .. } => recording_time.set(*current_time),
+            _ => {}
+        });
+    }
+
+    if session.is_none() || !(status.mic_active || status.recording) {
+        return html! {};
+    }
+
+    // 분석기 페이지가 아니면 눌러서 대시보드의 분석기로 돌아감
+    let on_click = {
+        let navigator = navigator.clone();
+        Callback::from(move |_: MouseEvent| {
+            if let Some(navigator) = &navigator {
+                navigator.push(&Route::Home);
+            }
+        })
+    };
+    let on_analyzer_page = route.has_pitch_analyzer();
+
+    html! {
+        <div
+            class={classes!("mini-status-bar", status.recording.then_some("recording"))}
+            onclick={(!on_analyzer_page).then_some(on_click)}
+            title={if on_analyzer_page { "백그라운드 세션 진행 중" } else { "눌러서 피치 분석기로 돌아가기" }}
+        >
+            if status.recording {
+                <span class="mini-status-rec">{ format!("● REC {}", format_recording_time(*recording_time)) }</span>
+            } else {
+                <span class="mini-status-mic">{ "🎤 마이크 켜짐" }</span>
+            }
+            <span class="mini-status-note">
+                { status.note.as_deref().map(|note| format!("🎶 {}", note)).unwrap_or_else(|| "🔇 -".to_string()) }
+            </span>
+        </div>
+    }
+}
@@ -0,0 +1,11 @@
+// 녹음 내보내기 인코더 웹 워커 진입점 (Trunk가 data-type="worker"로 /encoder_worker.js 생성)
+use gloo::worker::Registrable;
+
+// 메인 앱과 같은 인코더 모듈을 공유 (워커에서는 UI용 헬퍼를 쓰지 않음)
+#[path = "../encoders/mod.rs"]
+#[allow(dead_code)]
+mod encoders;
+
+fn main() {
+    encoders::EncoderWorker::registrar().register();
+}
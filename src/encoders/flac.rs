@@ -0,0 +1,641 @@
+use super::BitWriter;
+
+// FLAC 인코더 - 고정 예측기(0~4차) + 분할 Rice 부호화, 스테레오는 L/S, S/R, M/S 중 가장 작은 조합 선택
+
+pub const FLAC_BIT_DEPTHS: [u32; 2] = [16, 24];
+
+const BLOCK_SIZE: usize = 4096;
+const MAX_FIXED_ORDER: usize = 4;
+const MAX_PARTITION_ORDER: u32 = 8;
+const MAX_RICE_PARAM: u32 = 14; // 4비트 파라미터 방식 (15는 이스케이프)
+const MAX_RICE2_PARAM: u32 = 30; // 5비트 파라미터 방식 (31은 이스케이프)
+
+// 스테레오 채널 배치 (프레임 헤더 값)
+const CHANNELS_LEFT_SIDE: u64 = 8;
+const CHANNELS_SIDE_RIGHT: u64 = 9;
+const CHANNELS_MID_SIDE: u64 = 10;
+
+// 채널별 샘플(-1.0 ~ 1.0)을 FLAC 파일 바이트로 인코딩
+pub fn encode_flac(
+    channels: &[Vec<f32>],
+    sample_rate: u32,
+    bits_per_sample: u32,
+    progress: &mut dyn FnMut(f32),
+) -> Vec<u8> {
+    let bits_per_sample = if FLAC_BIT_DEPTHS.contains(&bits_per_sample) { bits_per_sample } else { 16 };
+    let scale = ((1i64 << (bits_per_sample - 1)) - 1) as f32;
+    let pcm: Vec<Vec<i64>> = channels
+        .iter()
+        .map(|channel| {
+            channel
+                .iter()
+                .map(|s| {
+                    let s = if s.is_finite() { s.clamp(-1.0, 1.0) } else { 0.0 };
+                    (s * scale).round() as i64
+                })
+                .collect()
+        })
+        .collect();
+    let channel_count = pcm.len().clamp(1, 2);
+    let frame_count = pcm.first().map_or(0, |c| c.len());
+
+    let mut out = BitWriter::with_capacity(frame_count * channel_count * bits_per_sample as usize / 16 + 1024);
+    out.write_bits(u64::from(u32::from_be_bytes(*b"fLaC")), 32);
+
+    // STREAMINFO (프레임 크기 최소/최대는 인코딩 후 채움)
+    out.write_bit(true); // 마지막 메타데이터 블록
+    out.write_bits(0, 7); // STREAMINFO
+    out.write_bits(34, 24);
+    out.write_bits(BLOCK_SIZE as u64, 16);
+    out.write_bits(BLOCK_SIZE as u64, 16);
+    let frame_size_offset = out.bit_len() / 8;
+    out.write_bits(0, 24);
+    out.write_bits(0, 24);
+    out.write_bits(sample_rate as u64, 20);
+    out.write_bits(channel_count as u64 - 1, 3);
+    out.write_bits(bits_per_sample as u64 - 1, 5);
+    out.write_bits((frame_count as u64) >> 32, 4);
+    out.write_bits(frame_count as u64 & 0xffff_ffff, 32);
+    for byte in md5_of_samples(&pcm[..channel_count.min(pcm.len())], bits_per_sample) {
+        out.write_bits(byte as u64, 8);
+    }
+
+    let block_count = frame_count.div_ceil(BLOCK_SIZE);
+    let mut min_frame_size = usize::MAX;
+    let mut max_frame_size = 0;
+    for block in 0..block_count {
+        let start = block * BLOCK_SIZE;
+        let end = (start + BLOCK_SIZE).min(frame_count);
+        let block_channels: Vec<&[i64]> = pcm.iter().take(channel_count).map(|c| &c[start..end]).collect();
+
+        let frame = encode_frame(&block_channels, block as u64, sample_rate, bits_per_sample);
+        let frame_size = frame.bit_len() / 8;
+        min_frame_size = min_frame_size.min(frame_size);
+        max_frame_size = max_frame_size.max(frame_size);
+        out.append(&frame);
+
+        progress((block + 1) as f32 / block_count as f32);
+    }
+
+    let mut bytes = out.into_bytes();
+    if block_count > 0 {
+        bytes[frame_size_offset..frame_size_offset + 3].copy_from_slice(&(min_frame_size as u32).to_be_bytes()[1..]);
+        bytes[frame_size_offset + 3..frame_size_offset + 6].copy_from_slice(&(max_frame_size as u32).to_be_bytes()[1..]);
+    }
+    bytes
+}
+
+// 블록 하나를 FLAC 프레임으로 인코딩
+fn encode_frame(channels: &[&[i64]], frame_number: u64, sample_rate: u32, bits_per_sample: u32) -> BitWriter {
+    let block_size = channels[0].len();
+
+    // 채널 배치와 서브프레임 결정
+    let (channel_assignment, subframes) = if channels.len() == 2 {
+        let (left, right) = (channels[0], channels[1]);
+        let side: Vec<i64> = left.iter().zip(right).map(|(l, r)| l - r).collect();
+        let mid: Vec<i64> = left.iter().zip(right).map(|(l, r)| (l + r) >> 1).collect();
+
+        let left_sub = encode_subframe(left, bits_per_sample);
+        let right_sub = encode_subframe(right, bits_per_sample);
+        let side_sub = encode_subframe(&side, bits_per_sample + 1);
+        let mid_sub = encode_subframe(&mid, bits_per_sample);
+
+        let candidates = [
+            (1, left_sub.bit_len() + right_sub.bit_len()),
+            (CHANNELS_LEFT_SIDE, left_sub.bit_len() + side_sub.bit_len()),
+            (CHANNELS_SIDE_RIGHT, side_sub.bit_len() + right_sub.bit_len()),
+            (CHANNELS_MID_SIDE, mid_sub.bit_len() + side_sub.bit_len()),
+        ];
+        let best = candidates.iter().min_by_key(|(_, bits)| *bits).map_or(1, |(assignment, _)| *assignment);
+        match best {
+            CHANNELS_LEFT_SIDE => (best, vec![left_sub, side_sub]),
+            CHANNELS_SIDE_RIGHT => (best, vec![side_sub, right_sub]),
+            CHANNELS_MID_SIDE => (best, vec![mid_sub, side_sub]),
+            _ => (1, vec![left_sub, right_sub]),
+        }
+    } else {
+        (0, vec![encode_subframe(channels[0], bits_per_sample)])
+    };
+
+    let mut frame = BitWriter::with_capacity(block_size * channels.len() * bits_per_sample as usize / 8 + 32);
+
+    // 프레임 헤더
+    frame.write_bits(0xfff8, 16); // 동기 코드 + 고정 블록 크기
+    let (block_size_code, block_size_extra_bits) = match block_size {
+        BLOCK_SIZE => (12, 0),
+        1..=256 => (6, 8),
+        _ => (7, 16),
+    };
+    frame.write_bits(block_size_code, 4);
+    frame.write_bits(sample_rate_code(sample_rate), 4);
+    frame.write_bits(channel_assignment, 4);
+    frame.write_bits(if bits_per_sample == 24 { 6 } else { 4 }, 3);
+    frame.write_bit(false);
+    write_utf8_number(&mut frame, frame_number);
+    if block_size_extra_bits > 0 {
+        frame.write_bits(block_size as u64 - 1, block_size_extra_bits);
+    }
+    let header_crc = crc8(frame.bytes());
+    frame.write_bits(header_crc as u64, 8);
+
+    for subframe in &subframes {
+        frame.append(subframe);
+    }
+    frame.align_to_byte();
+
+    let footer_crc = crc16(frame.bytes());
+    frame.write_bits(footer_crc as u64, 16);
+    frame
+}
+
+// 서브프레임 인코딩 - 상수, 고정 예측, 원본(verbatim) 중 가장 작은 것
+fn encode_subframe(samples: &[i64], bits_per_sample: u32) -> BitWriter {
+    let mut writer = BitWriter::with_capacity(samples.len() * bits_per_sample as usize / 8 + 16);
+
+    if samples.iter().all(|s| *s == samples[0]) {
+        writer.write_bits(0, 8); // 상수 서브프레임
+        writer.write_bits(samples[0] as u64, bits_per_sample);
+        return writer;
+    }
+
+    let verbatim_bits = 8 + samples.len() * bits_per_sample as usize;
+
+    // 잔차 절대값 합이 가장 작은 예측 차수 선택
+    let max_order = MAX_FIXED_ORDER.min(samples.len().saturating_sub(1));
+    let best = (0..=max_order)
+        .map(|order| {
+            let residual = fixed_residual(samples, order);
+            let sum: u64 = residual.iter().map(|r| r.unsigned_abs()).sum();
+            (order, residual, sum)
+        })
+        .min_by_key(|(_, _, sum)| *sum);
+
+    if let Some((order, residual, _)) = best {
+        // 16비트를 넘는 샘플은 잔차가 커서 5비트 파라미터 방식 사용
+        let max_param = if bits_per_sample > 17 { MAX_RICE2_PARAM } else { MAX_RICE_PARAM };
+        let (partition_order, params) = choose_rice_partitions(&residual, samples.len(), order, max_param);
+        writer.write_bits(0, 1);
+        writer.write_bits(0b001000 | order as u64, 6); // 고정 예측 서브프레임
+        writer.write_bits(0, 1);
+        for warmup in &samples[..order] {
+            writer.write_bits(*warmup as u64, bits_per_sample);
+        }
+        write_residual(&mut writer, &residual, samples.len(), order, partition_order, &params, max_param);
+
+        if writer.bit_len() <= verbatim_bits {
+            return writer;
+        }
+        writer = BitWriter::with_capacity(verbatim_bits / 8 + 1);
+    }
+
+    writer.write_bits(0b0000_0010, 8); // 원본 서브프레임
+    for sample in samples {
+        writer.write_bits(*sample as u64, bits_per_sample);
+    }
+    writer
+}
+
+// 고정 예측기 잔차 (앞의 order개 샘플은 워밍업이므로 제외)
+fn fixed_residual(samples: &[i64], order: usize) -> Vec<i64> {
+    let s = samples;
+    (order..s.len())
+        .map(|i| match order {
+            0 => s[i],
+            1 => s[i] - s[i - 1],
+            2 => s[i] - 2 * s[i - 1] + s[i - 2],
+            3 => s[i] - 3 * s[i - 1] + 3 * s[i - 2] - s[i - 3],
+            _ => s[i] - 4 * s[i - 1] + 6 * s[i - 2] - 4 * s[i - 3] + s[i - 4],
+        })
+        .collect()
+}
+
+fn zigzag(value: i64) -> u64 {
+    ((value << 1) ^ (value >> 63)) as u64
+}
+
+// 파티션 하나의 최적 Rice 파라미터와 예상 비트 수
+fn best_rice_param(residual: &[i64], max_param: u32) -> (u32, u64) {
+    let count = residual.len() as u64;
+    let sum: u64 = residual.iter().map(|r| zigzag(*r)).sum();
+
+    (0..=max_param)
+        .map(|param| (param, count * (param as u64 + 1) + (sum >> param)))
+        .min_by_key(|(_, bits)| *bits)
+        .unwrap_or((0, 0))
+}
+
+// 파티션 구간 (첫 파티션은 워밍업 샘플만큼 짧음)
+fn partition_range(partition: usize, partition_len: usize, order: usize) -> std::ops::Range<usize> {
+    let start = if partition == 0 { 0 } else { partition * partition_len - order };
+    start..(partition + 1) * partition_len - order
+}
+
+// 분할 차수와 파티션별 Rice 파라미터 선택
+fn choose_rice_partitions(residual: &[i64], block_size: usize, order: usize, max_param: u32) -> (u32, Vec<u32>) {
+    let param_bits = if max_param > MAX_RICE_PARAM { 5 } else { 4 };
+    let mut best: Option<(u64, u32, Vec<u32>)> = None;
+    for partition_order in 0..=MAX_PARTITION_ORDER {
+        let partitions = 1usize << partition_order;
+        if !block_size.is_multiple_of(partitions) || block_size / partitions <= order {
+            break;
+        }
+        let partition_len = block_size / partitions;
+
+        let mut total = 0;
+        let mut params = Vec::with_capacity(partitions);
+        for p in 0..partitions {
+            let (param, bits) = best_rice_param(&residual[partition_range(p, partition_len, order)], max_param);
+            total += param_bits + bits;
+            params.push(param);
+        }
+
+        if best.as_ref().is_none_or(|(bits, _, _)| total < *bits) {
+            best = Some((total, partition_order, params));
+        }
+    }
+
+    best.map(|(_, order, params)| (order, params)).unwrap_or((0, vec![0]))
+}
+
+fn write_residual(writer: &mut BitWriter, residual: &[i64], block_size: usize, order: usize, partition_order: u32, params: &[u32], max_param: u32) {
+    let param_bits = if max_param > MAX_RICE_PARAM { 5 } else { 4 };
+    writer.write_bits(if param_bits == 5 { 1 } else { 0 }, 2); // Rice 파라미터 방식 (4비트/5비트)
+    writer.write_bits(partition_order as u64, 4);
+
+    let partition_len = block_size >> partition_order;
+    for (p, param) in params.iter().enumerate() {
+        writer.write_bits(*param as u64, param_bits);
+        for r in &residual[partition_range(p, partition_len, order)] {
+            let u = zigzag(*r);
+            writer.write_unary(u >> param);
+            writer.write_bits(u, *param);
+        }
+    }
+}
+
+fn sample_rate_code(sample_rate: u32) -> u64 {
+    match sample_rate {
+        88200 => 1,
+        176400 => 2,
+        192000 => 3,
+        8000 => 4,
+        16000 => 5,
+        22050 => 6,
+        24000 => 7,
+        32000 => 8,
+        44100 => 9,
+        48000 => 10,
+        96000 => 11,
+        _ => 0, // STREAMINFO 값 사용
+    }
+}
+
+// 프레임 번호를 UTF-8 방식 가변 길이로 기록
+fn write_utf8_number(writer: &mut BitWriter, value: u64) {
+    if value < 0x80 {
+        writer.write_bits(value, 8);
+        return;
+    }
+    let continuation_bytes = match value {
+        0..=0x7ff => 1,
+        0x800..=0xffff => 2,
+        0x1_0000..=0x1f_ffff => 3,
+        0x20_0000..=0x3ff_ffff => 4,
+        _ => 5,
+    };
+    let lead_marker = !(0xffu64 >> (continuation_bytes + 1)) & 0xff;
+    writer.write_bits(lead_marker | (value >> (6 * continuation_bytes)), 8);
+    for i in (0..continuation_bytes).rev() {
+        writer.write_bits(0x80 | ((value >> (6 * i)) & 0x3f), 8);
+    }
+}
+
+fn crc8(bytes: &[u8]) -> u8 {
+    let mut crc = 0u8;
+    for byte in bytes {
+        crc ^= byte;
+        for _ in 0..8 {
+            crc = if crc & 0x80 != 0 { (crc << 1) ^ 0x07 } else { crc << 1 };
+        }
+    }
+    crc
+}
+
+fn crc16(bytes: &[u8]) -> u16 {
+    let mut crc = 0u16;
+    for byte in bytes {
+        crc ^= (*byte as u16) << 8;
+        for _ in 0..8 {
+            crc = if crc & 0x8000 != 0 { (crc << 1) ^ 0x8005 } else { crc << 1 };
+        }
+    }
+    crc
+}
+
+// STREAMINFO에 들어가는 MD5 (채널을 교차 배치한 리틀 엔디언 샘플 바이트)
+fn md5_of_samples(channels: &[Vec<i64>], bits_per_sample: u32) -> [u8; 16] {
+    let bytes_per_sample = (bits_per_sample / 8) as usize;
+    let frame_count = channels.first().map_or(0, |c| c.len());
+    let mut md5 = Md5::new();
+    let mut buffer = Vec::with_capacity(4096 * channels.len() * bytes_per_sample);
+    for frame in 0..frame_count {
+        for channel in channels {
+            buffer.extend_from_slice(&channel[frame].to_le_bytes()[..bytes_per_sample]);
+        }
+        if buffer.len() >= 4096 * channels.len() * bytes_per_sample {
+            md5.update(&buffer);
+            buffer.clear();
+        }
+    }
+    md5.update(&buffer);
+    md5.finish()
+}
+
+// RFC 1321 MD5
+struct Md5 {
+    state: [u32; 4],
+    pending: Vec<u8>,
+    length: u64,
+}
+
+impl Md5 {
+    const SHIFTS: [u32; 64] = [
+        7, 12, 17, 22, 7, 12, 17, 22, 7, 12, 17, 22, 7, 12, 17, 22,
+        5, 9, 14, 20, 5, 9, 14, 20, 5, 9, 14, 20, 5, 9, 14, 20,
+        4, 11, 16, 23, 4, 11, 16, 23, 4, 11, 16, 23, 4, 11, 16, 23,
+        6, 10, 15, 21, 6, 10, 15, 21, 6, 10, 15, 21, 6, 10, 15, 21,
+    ];
+
+    fn new() -> Self {
+        Self {
+            state: [0x6745_2301, 0xefcd_ab89, 0x98ba_dcfe, 0x1032_5476],
+            pending: Vec::with_capacity(64),
+            length: 0,
+        }
+    }
+
+    fn update(&mut self, mut data: &[u8]) {
+        self.length += data.len() as u64;
+        if !self.pending.is_empty() {
+            let take = (64 - self.pending.len()).min(data.len());
+            self.pending.extend_from_slice(&data[..take]);
+            data = &data[take..];
+            if self.pending.len() < 64 {
+                return;
+            }
+            let block: [u8; 64] = self.pending[..].try_into().unwrap();
+            self.process(&block);
+            self.pending.clear();
+        }
+        let mut chunks = data.chunks_exact(64);
+        for chunk in &mut chunks {
+            self.process(chunk.try_into().unwrap());
+        }
+        self.pending.extend_from_slice(chunks.remainder());
+    }
+
+    fn finish(mut self) -> [u8; 16] {
+        let bit_length = self.length.wrapping_mul(8);
+        let mut padding = vec![0x80u8];
+        while (self.length as usize + padding.len()) % 64 != 56 {
+            padding.push(0);
+        }
+        padding.extend_from_slice(&bit_length.to_le_bytes());
+        self.update(&padding);
+
+        let mut digest = [0u8; 16];
+        for (i, word) in self.state.iter().enumerate() {
+            digest[i * 4..i * 4 + 4].copy_from_slice(&word.to_le_bytes());
+        }
+        digest
+    }
+
+    fn process(&mut self, block: &[u8; 64]) {
+        let words: Vec<u32> = block.chunks_exact(4).map(|w| u32::from_le_bytes([w[0], w[1], w[2], w[3]])).collect();
+        let [mut a, mut b, mut c, mut d] = self.state;
+        for i in 0..64 {
+            let (f, g) = match i / 16 {
+                0 => ((b & c) | (!b & d), i),
+                1 => ((d & b) | (!d & c), (5 * i + 1) % 16),
+                2 => (b ^ c ^ d, (3 * i + 5) % 16),
+                _ => (c ^ (b | !d), (7 * i) % 16),
+            };
+            let k = ((i as f64 + 1.0).sin().abs() * 4_294_967_296.0) as u32;
+            let rotated = a.wrapping_add(f).wrapping_add(k).wrapping_add(words[g]).rotate_left(Self::SHIFTS[i]);
+            a = d;
+            d = c;
+            c = b;
+            b = b.wrapping_add(rotated);
+        }
+        self.state[0] = self.state[0].wrapping_add(a);
+        self.state[1] = self.state[1].wrapping_add(b);
+        self.state[2] = self.state[2].wrapping_add(c);
+        self.state[3] = self.state[3].wrapping_add(d);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn md5(data: &[u8]) -> [u8; 16] {
+        let mut md5 = Md5::new();
+        md5.update(data);
+        md5.finish()
+    }
+
+    fn hex(bytes: &[u8]) -> String {
+        bytes.iter().map(|b| format!("{:02x}", b)).collect()
+    }
+
+    fn be(bytes: &[u8]) -> u64 {
+        bytes.iter().fold(0, |acc, b| (acc << 8) | *b as u64)
+    }
+
+    #[test]
+    fn md5_known_vectors() {
+        assert_eq!(hex(&md5(b"")), "d41d8cd98f00b204e9800998ecf8427e");
+        assert_eq!(hex(&md5(b"abc")), "900150983cd24fb0d6963f7d28e17f72");
+        assert_eq!(hex(&md5(b"The quick brown fox jumps over the lazy dog")), "9e107d9d372bb6826bd81d3542a419d6");
+        let long = b"12345678901234567890123456789012345678901234567890123456789012345678901234567890";
+        assert_eq!(hex(&md5(long)), "57edf4a22be3c955ac49da2e2107b67a");
+
+        // 여러 번 나눠 넣어도 같은 결과
+        let mut split = Md5::new();
+        for chunk in long.chunks(7) {
+            split.update(chunk);
+        }
+        assert_eq!(split.finish(), md5(long));
+    }
+
+    #[test]
+    fn md5_of_samples_uses_interleaved_little_endian_bytes() {
+        // 24비트 샘플 0x636261 = "abc"
+        assert_eq!(hex(&md5_of_samples(&[vec![0x63_6261]], 24)), "900150983cd24fb0d6963f7d28e17f72");
+
+        let left = vec![1i64, -2, 300];
+        let right = vec![-1i64, 2, -300];
+        let mut expected = Vec::new();
+        for (l, r) in left.iter().zip(&right) {
+            expected.extend_from_slice(&(*l as i16).to_le_bytes());
+            expected.extend_from_slice(&(*r as i16).to_le_bytes());
+        }
+        assert_eq!(md5_of_samples(&[left, right], 16), md5(&expected));
+    }
+
+    #[test]
+    fn crc_check_values() {
+        // CRC-8 (다항식 0x07)과 CRC-16 (다항식 0x8005)의 표준 검사값
+        assert_eq!(crc8(b"123456789"), 0xf4);
+        assert_eq!(crc16(b"123456789"), 0xfee8);
+        assert_eq!(crc8(&[]), 0);
+        assert_eq!(crc16(&[]), 0);
+    }
+
+    fn test_signal(len: usize, phase: f32) -> Vec<f32> {
+        (0..len).map(|i| 0.5 * (i as f32 * 0.03 + phase).sin()).collect()
+    }
+
+    fn quantize(channel: &[f32], bits_per_sample: u32) -> Vec<i64> {
+        let scale = ((1i64 << (bits_per_sample - 1)) - 1) as f32;
+        channel.iter().map(|s| (s * scale).round() as i64).collect()
+    }
+
+    #[test]
+    fn streaminfo_layout() {
+        let channels = vec![test_signal(5000, 0.0), test_signal(5000, 1.0)];
+        let bytes = encode_flac(&channels, 44100, 24, &mut |_| {});
+
+        assert_eq!(&bytes[0..4], b"fLaC");
+        assert_eq!(bytes[4], 0x80); // 마지막 블록 + STREAMINFO
+        assert_eq!(be(&bytes[5..8]), 34);
+        assert_eq!(be(&bytes[8..10]), BLOCK_SIZE as u64);
+        assert_eq!(be(&bytes[10..12]), BLOCK_SIZE as u64);
+
+        let min_frame = be(&bytes[12..15]) as usize;
+        let max_frame = be(&bytes[15..18]) as usize;
+        assert!(min_frame > 0 && min_frame <= max_frame);
+        // 프레임 두 개가 메타데이터 뒤를 빈틈없이 채움
+        assert_eq!(min_frame + max_frame, bytes.len() - 42);
+
+        let packed = be(&bytes[18..26]);
+        assert_eq!(packed >> 44, 44100);
+        assert_eq!(((packed >> 41) & 0x7) + 1, 2);
+        assert_eq!(((packed >> 36) & 0x1f) + 1, 24);
+        assert_eq!(packed & 0xf_ffff_ffff, 5000);
+
+        let pcm: Vec<Vec<i64>> = channels.iter().map(|c| quantize(c, 24)).collect();
+        assert_eq!(bytes[26..42], md5_of_samples(&pcm, 24));
+    }
+
+    #[test]
+    fn frame_header_and_footer_crcs() {
+        let channels = vec![test_signal(1000, 0.0)];
+        let bytes = encode_flac(&channels, 48000, 16, &mut |_| {});
+        let frame = &bytes[42..];
+
+        assert_eq!(be(&frame[0..2]), 0xfff8);
+        assert_eq!(frame[2] >> 4, 7); // 16비트 블록 크기 필드
+        assert_eq!(frame[2] & 0x0f, 10); // 48kHz
+        assert_eq!(frame[3] >> 4, 0); // 모노
+        assert_eq!((frame[3] >> 1) & 0x7, 4); // 16비트
+        assert_eq!(frame[4], 0); // 프레임 번호
+        assert_eq!(be(&frame[5..7]), 999);
+        assert_eq!(frame[7], crc8(&frame[..7]));
+
+        let footer = frame.len() - 2;
+        assert_eq!(be(&frame[footer..]), crc16(&frame[..footer]) as u64);
+        assert_eq!(be(&bytes[12..15]) as usize, frame.len());
+    }
+
+    #[test]
+    fn every_frame_has_valid_crcs() {
+        let channels = vec![test_signal(3 * BLOCK_SIZE + 100, 0.0), vec![0.25; 3 * BLOCK_SIZE + 100]];
+        let bytes = encode_flac(&channels, 44100, 16, &mut |_| {});
+
+        // 다음 동기 코드까지를 한 프레임으로 보고 순서대로 검사
+        let mut pos = 42;
+        let mut frame_number = 0;
+        while pos < bytes.len() {
+            assert_eq!(be(&bytes[pos..pos + 2]), 0xfff8);
+            let header_len = if frame_number < 3 { 5 } else { 6 }; // 마지막 블록은 8비트 블록 크기 필드
+            assert_eq!(bytes[pos + 4], frame_number);
+            assert_eq!(bytes[pos + header_len], crc8(&bytes[pos..pos + header_len]));
+
+            let end = (pos + header_len + 1..bytes.len() - 1)
+                .find(|&i| bytes[i] == 0xff && bytes[i + 1] == 0xf8 && bytes[i + 4] == frame_number + 1)
+                .unwrap_or(bytes.len());
+            assert_eq!(be(&bytes[end - 2..end]), crc16(&bytes[pos..end - 2]) as u64);
+            pos = end;
+            frame_number += 1;
+        }
+        assert_eq!(frame_number, 4);
+    }
+
+    // claxon으로 디코딩한 채널별 정수 샘플
+    fn decode(bytes: Vec<u8>) -> (claxon::metadata::StreamInfo, Vec<Vec<i64>>) {
+        let mut reader = claxon::FlacReader::new(std::io::Cursor::new(bytes)).expect("FLAC 스트림 인식");
+        let info = reader.streaminfo();
+        let mut channels = vec![Vec::new(); info.channels as usize];
+        let mut buffer = Vec::new();
+        let mut blocks = reader.blocks();
+        while let Some(block) = blocks.read_next_or_eof(buffer).expect("프레임 디코딩") {
+            for (ch, channel) in channels.iter_mut().enumerate() {
+                channel.extend(block.channel(ch as u32).iter().map(|s| *s as i64));
+            }
+            buffer = block.into_buffer();
+        }
+        (info, channels)
+    }
+
+    // 원래 실수 신호 대비 SNR (dB)
+    fn snr(reference: &[f32], decoded: &[i64], bits_per_sample: u32) -> f64 {
+        let scale = ((1i64 << (bits_per_sample - 1)) - 1) as f64;
+        let (signal, noise) = reference.iter().zip(decoded).fold((0.0, 0.0), |(signal, noise), (r, d)| {
+            let r = *r as f64;
+            (signal + r * r, noise + (r - *d as f64 / scale).powi(2))
+        });
+        10.0 * (signal / noise.max(1e-30)).log10()
+    }
+
+    #[test]
+    fn decodes_back_to_the_quantized_input() {
+        let len = 3 * BLOCK_SIZE + 100;
+        // 스테레오 상관 모드가 모두 쓰이도록 같은 신호, 반전 신호, 무관한 신호를 섞음
+        let base = test_signal(len, 0.0);
+        let stereo_pairs = [
+            vec![base.clone(), base.clone()],
+            vec![base.clone(), base.iter().map(|s| -s * 0.5).collect()],
+            vec![base.clone(), test_signal(len, 2.0)],
+            vec![base.clone(), vec![0.0; len]],
+        ];
+        for bits_per_sample in FLAC_BIT_DEPTHS {
+            let min_snr = 6.02 * bits_per_sample as f64 - 20.0;
+            for channels in std::iter::once(vec![base.clone()]).chain(stereo_pairs.iter().cloned()) {
+                let (info, decoded) = decode(encode_flac(&channels, 44100, bits_per_sample, &mut |_| {}));
+                assert_eq!(info.sample_rate, 44100);
+                assert_eq!(info.bits_per_sample, bits_per_sample);
+                assert_eq!(info.samples, Some(len as u64));
+                assert_eq!(decoded.len(), channels.len());
+
+                for (channel, samples) in channels.iter().zip(&decoded) {
+                    // 무손실이므로 양자화한 입력과 정확히 같아야 함
+                    assert_eq!(*samples, quantize(channel, bits_per_sample));
+                    if channel.iter().any(|s| *s != 0.0) {
+                        let snr = snr(channel, samples, bits_per_sample);
+                        assert!(snr > min_snr, "{}비트 SNR {:.1} dB", bits_per_sample, snr);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn frame_numbers_use_utf8_coding() {
+        let mut writer = BitWriter::with_capacity(8);
+        write_utf8_number(&mut writer, 0x7f);
+        write_utf8_number(&mut writer, 0x80);
+        write_utf8_number(&mut writer, 0x800);
+        assert_eq!(writer.into_bytes(), vec![0x7f, 0xc2, 0x80, 0xe0, 0xa0, 0x80]);
+    }
+}
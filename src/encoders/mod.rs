@@ -0,0 +1,147 @@
+use gloo::worker::{HandlerId, Worker, WorkerScope};
+use serde::{Deserialize, Serialize};
+
+// 녹음 내보내기용 오디오 인코더 (순수 Rust)
+// 긴 녹음도 화면이 멈추지 않도록 EncoderWorker로 웹 워커에서 실행 (src/bin/encoder_worker.rs)
+// 워커 바이너리가 #[path]로 이 모듈을 그대로 가져가므로 encoders.rs가 아닌 encoders/mod.rs로 둠
+
+pub mod flac;
+pub mod mp3;
+pub mod wav;
+
+// 인코딩 포맷과 포맷별 설정
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum EncodeFormat {
+    Wav(wav::WavSampleFormat),
+    Flac { bits_per_sample: u32 },
+    Mp3 { bitrate: u32 },
+}
+
+// 메인 스레드 -> 워커: 디코딩된 채널별 샘플(-1.0 ~ 1.0)
+#[derive(Serialize, Deserialize)]
+pub struct EncodeRequest {
+    pub format: EncodeFormat,
+    pub sample_rate: u32,
+    pub channels: Vec<Vec<f32>>,
+}
+
+// 워커 -> 메인 스레드
+#[derive(Serialize, Deserialize)]
+pub enum EncodeResponse {
+    Progress(f32),     // 진행률 (0.0 ~ 1.0)
+    Finished(Vec<u8>), // 완성된 파일 바이트
+}
+
+// 인코딩 전용 웹 워커
+pub struct EncoderWorker;
+
+impl Worker for EncoderWorker {
+    type Message = ();
+    type Input = EncodeRequest;
+    type Output = EncodeResponse;
+
+    fn create(_scope: &WorkerScope<Self>) -> Self {
+        Self
+    }
+
+    fn update(&mut self, _scope: &WorkerScope<Self>, _msg: Self::Message) {}
+
+    fn received(&mut self, scope: &WorkerScope<Self>, request: Self::Input, id: HandlerId) {
+        // 진행률은 1% 단위로 바뀔 때만 전송
+        let mut last_percent = 0;
+        let mut progress = |fraction: f32| {
+            let percent = (fraction * 100.0) as u32;
+            if percent > last_percent {
+                last_percent = percent;
+                scope.respond(id, EncodeResponse::Progress(fraction));
+            }
+        };
+
+        let bytes = match request.format {
+            EncodeFormat::Wav(sample_format) => wav::encode_wav(&request.channels, request.sample_rate, sample_format),
+            EncodeFormat::Flac { bits_per_sample } => {
+                flac::encode_flac(&request.channels, request.sample_rate, bits_per_sample, &mut progress)
+            }
+            EncodeFormat::Mp3 { bitrate } => mp3::encode_mp3(&request.channels, request.sample_rate, bitrate, &mut progress),
+        };
+        scope.respond(id, EncodeResponse::Finished(bytes));
+    }
+}
+
+// MSB부터 채워 나가는 비트 기록기 (FLAC, MP3 비트스트림 공용)
+pub struct BitWriter {
+    bytes: Vec<u8>,
+    acc: u64,      // 아직 바이트로 내보내지 않은 비트
+    acc_bits: u32, // acc에 남은 비트 수 (항상 8 미만)
+}
+
+impl BitWriter {
+    pub fn with_capacity(bytes: usize) -> Self {
+        Self {
+            bytes: Vec::with_capacity(bytes),
+            acc: 0,
+            acc_bits: 0,
+        }
+    }
+
+    // value의 하위 bits 비트를 기록 (bits <= 32)
+    pub fn write_bits(&mut self, value: u64, bits: u32) {
+        debug_assert!(bits <= 32);
+        if bits == 0 {
+            return;
+        }
+        self.acc = (self.acc << bits) | (value & ((1u64 << bits) - 1));
+        self.acc_bits += bits;
+        while self.acc_bits >= 8 {
+            self.acc_bits -= 8;
+            self.bytes.push((self.acc >> self.acc_bits) as u8);
+        }
+    }
+
+    pub fn write_bit(&mut self, bit: bool) {
+        self.write_bits(bit as u64, 1);
+    }
+
+    // 0을 zeros개 쓰고 1로 끝내는 단항 부호
+    pub fn write_unary(&mut self, mut zeros: u64) {
+        while zeros >= 32 {
+            self.write_bits(0, 32);
+            zeros -= 32;
+        }
+        self.write_bits(1, zeros as u32 + 1);
+    }
+
+    // 다른 기록기의 내용을 비트 단위로 이어 붙이기
+    pub fn append(&mut self, other: &BitWriter) {
+        if self.acc_bits == 0 {
+            self.bytes.extend_from_slice(&other.bytes);
+        } else {
+            for byte in &other.bytes {
+                self.write_bits(*byte as u64, 8);
+            }
+        }
+        self.write_bits(other.acc, other.acc_bits);
+    }
+
+    // 바이트 경계까지 0으로 채우기
+    pub fn align_to_byte(&mut self) {
+        if self.acc_bits > 0 {
+            self.write_bits(0, 8 - self.acc_bits);
+        }
+    }
+
+    pub fn bit_len(&self) -> usize {
+        self.bytes.len() * 8 + self.acc_bits as usize
+    }
+
+    // 바이트 경계에 맞춰진 상태에서 지금까지 기록한 바이트
+    pub fn bytes(&self) -> &[u8] {
+        debug_assert_eq!(self.acc_bits, 0);
+        &self.bytes
+    }
+
+    pub fn into_bytes(mut self) -> Vec<u8> {
+        self.align_to_byte();
+        self.bytes
+    }
+}
@@ -0,0 +1,771 @@
+use std::f32::consts::PI;
+
+use super::BitWriter;
+
+mod tables;
+
+use tables::*;
+
+// MP3 인코더 - MPEG-1 Layer III, 긴 블록 전용 CBR
+// 스케일팩터와 비트 저장소 없이, 프레임 안 그래뉼/채널이 같은 양자화 간격이 되도록 global_gain을 이분 탐색
+// 스테레오는 항상 M/S 조인트 스테레오 (모노 녹음을 복제한 스테레오는 S 채널이 거의 0)
+
+pub const MP3_SAMPLE_RATES: [u32; 3] = [48000, 44100, 32000];
+pub const MP3_BITRATES: [u32; 6] = [96, 128, 160, 192, 256, 320];
+
+const GRANULE_SIZE: usize = 576;
+const FRAME_SIZE: usize = 2 * GRANULE_SIZE;
+const SUBBANDS: usize = 32;
+const MAX_QUANTIZED: i32 = 15 + (1 << 13) - 1; // 가장 큰 linbits(13) 표로 표현 가능한 값
+const MAX_PART2_3_BITS: usize = 4095; // part2_3_length 필드 12비트
+
+// 헤더의 비트레이트 인덱스 순서 (MPEG-1 Layer III, kbps)
+const BITRATE_TABLE: [u32; 15] = [0, 32, 40, 48, 56, 64, 80, 96, 112, 128, 160, 192, 224, 256, 320];
+
+// 빅 밸류 밴드 수에 따른 region0/region1 밴드 수 (LAME의 subdv_table과 같은 분할)
+const REGION_SPLIT: [(usize, usize); 23] = [
+    (0, 0), (0, 0), (0, 0), (0, 0), (0, 0), (0, 1), (1, 1), (1, 1), (1, 2), (2, 2), (2, 3), (2, 3),
+    (3, 4), (3, 4), (3, 4), (4, 5), (4, 5), (4, 6), (5, 6), (5, 6), (5, 7), (6, 7), (6, 7),
+];
+
+struct HuffmanTable {
+    codes: &'static [u16],
+    bits: &'static [u8],
+    wrap: usize,
+    linbits: u32,
+}
+
+impl HuffmanTable {
+    // 이 표로 부호화할 수 있는 가장 큰 양자화 값
+    fn max_value(&self) -> i32 {
+        if self.linbits > 0 {
+            15 + (1 << self.linbits) - 1
+        } else {
+            self.wrap as i32 - 1
+        }
+    }
+
+    fn pair_bits(&self, x: i32, y: i32) -> usize {
+        let (xi, yi) = (x.min(15) as usize, y.min(15) as usize);
+        let mut bits = self.bits[xi * self.wrap + yi] as usize + (x != 0) as usize + (y != 0) as usize;
+        if self.linbits > 0 {
+            bits += self.linbits as usize * ((x >= 15) as usize + (y >= 15) as usize);
+        }
+        bits
+    }
+
+    fn write_pair(&self, writer: &mut BitWriter, x: i32, y: i32, x_negative: bool, y_negative: bool) {
+        let (xi, yi) = (x.min(15) as usize, y.min(15) as usize);
+        let index = xi * self.wrap + yi;
+        writer.write_bits(self.codes[index] as u64, self.bits[index] as u32);
+        for (value, negative) in [(x, x_negative), (y, y_negative)] {
+            if self.linbits > 0 && value >= 15 {
+                writer.write_bits((value - 15) as u64, self.linbits);
+            }
+            if value != 0 {
+                writer.write_bit(negative);
+            }
+        }
+    }
+}
+
+const fn table(codes: &'static [u16], bits: &'static [u8], wrap: usize, linbits: u32) -> Option<HuffmanTable> {
+    Some(HuffmanTable { codes, bits, wrap, linbits })
+}
+
+// table_select 값(0~31)별 허프만 표 (0, 4, 14는 부호 없음)
+static HUFFMAN_TABLES: [Option<HuffmanTable>; 32] = [
+    None,
+    table(&HUFFMAN_CODES_1, &HUFFMAN_BITS_1, 2, 0),
+    table(&HUFFMAN_CODES_2, &HUFFMAN_BITS_2, 3, 0),
+    table(&HUFFMAN_CODES_3, &HUFFMAN_BITS_3, 3, 0),
+    None,
+    table(&HUFFMAN_CODES_5, &HUFFMAN_BITS_5, 4, 0),
+    table(&HUFFMAN_CODES_6, &HUFFMAN_BITS_6, 4, 0),
+    table(&HUFFMAN_CODES_7, &HUFFMAN_BITS_7, 6, 0),
+    table(&HUFFMAN_CODES_8, &HUFFMAN_BITS_8, 6, 0),
+    table(&HUFFMAN_CODES_9, &HUFFMAN_BITS_9, 6, 0),
+    table(&HUFFMAN_CODES_10, &HUFFMAN_BITS_10, 8, 0),
+    table(&HUFFMAN_CODES_11, &HUFFMAN_BITS_11, 8, 0),
+    table(&HUFFMAN_CODES_12, &HUFFMAN_BITS_12, 8, 0),
+    table(&HUFFMAN_CODES_13, &HUFFMAN_BITS_13, 16, 0),
+    None,
+    table(&HUFFMAN_CODES_15, &HUFFMAN_BITS_15, 16, 0),
+    table(&HUFFMAN_CODES_16, &HUFFMAN_BITS_16, 16, 1),
+    table(&HUFFMAN_CODES_16, &HUFFMAN_BITS_16, 16, 2),
+    table(&HUFFMAN_CODES_16, &HUFFMAN_BITS_16, 16, 3),
+    table(&HUFFMAN_CODES_16, &HUFFMAN_BITS_16, 16, 4),
+    table(&HUFFMAN_CODES_16, &HUFFMAN_BITS_16, 16, 6),
+    table(&HUFFMAN_CODES_16, &HUFFMAN_BITS_16, 16, 8),
+    table(&HUFFMAN_CODES_16, &HUFFMAN_BITS_16, 16, 10),
+    table(&HUFFMAN_CODES_16, &HUFFMAN_BITS_16, 16, 13),
+    table(&HUFFMAN_CODES_24, &HUFFMAN_BITS_24, 16, 4),
+    table(&HUFFMAN_CODES_24, &HUFFMAN_BITS_24, 16, 5),
+    table(&HUFFMAN_CODES_24, &HUFFMAN_BITS_24, 16, 6),
+    table(&HUFFMAN_CODES_24, &HUFFMAN_BITS_24, 16, 7),
+    table(&HUFFMAN_CODES_24, &HUFFMAN_BITS_24, 16, 8),
+    table(&HUFFMAN_CODES_24, &HUFFMAN_BITS_24, 16, 9),
+    table(&HUFFMAN_CODES_24, &HUFFMAN_BITS_24, 16, 11),
+    table(&HUFFMAN_CODES_24, &HUFFMAN_BITS_24, 16, 13),
+];
+
+// linbits 없는 표 (큰 값은 16~31 중 linbits가 충분한 표로)
+const SMALL_TABLES: [usize; 13] = [1, 2, 3, 5, 6, 7, 8, 9, 10, 11, 12, 13, 15];
+
+// 그래뉼 하나, 채널 하나의 사이드 정보
+#[derive(Default, Clone, Copy)]
+struct GranuleInfo {
+    part2_3_length: usize,
+    big_values: usize,
+    global_gain: i32,
+    table_select: [usize; 3],
+    region0_count: usize,
+    region1_count: usize,
+    count1_table: usize,
+    count1_end: usize, // count1 영역의 끝 (이후는 모두 0)
+}
+
+// 채널별 필터뱅크 상태
+struct ChannelState {
+    fifo: [f32; 512],               // 분석 필터뱅크 입력 (최신 샘플이 앞)
+    overlap: [[f32; 18]; SUBBANDS], // 직전 그래뉼의 서브밴드 샘플 (MDCT 50% 겹침)
+}
+
+impl ChannelState {
+    fn new() -> Self {
+        Self {
+            fifo: [0.0; 512],
+            overlap: [[0.0; 18]; SUBBANDS],
+        }
+    }
+}
+
+// 필터뱅크/MDCT 계수표
+struct Filterbank {
+    window: [f32; 512],
+    matrix: Vec<[f32; 64]>, // 32 x 64 분석 행렬
+    mdct: Vec<[f32; 36]>,   // 18 x 36 창을 곱한 MDCT 코사인
+    alias_cs: [f32; 8],
+    alias_ca: [f32; 8],
+}
+
+impl Filterbank {
+    fn new() -> Self {
+        let mut window = [0.0; 512];
+        for (c, d) in window.iter_mut().zip(SYNTHESIS_WINDOW.iter()) {
+            *c = d / 32.0;
+        }
+
+        let matrix = (0..SUBBANDS)
+            .map(|k| {
+                let mut row = [0.0; 64];
+                for (i, m) in row.iter_mut().enumerate() {
+                    *m = ((2 * k + 1) as f32 * (i as f32 - 16.0) * PI / 64.0).cos();
+                }
+                row
+            })
+            .collect();
+
+        let mdct = (0..18)
+            .map(|k| {
+                let mut row = [0.0; 36];
+                for (n, m) in row.iter_mut().enumerate() {
+                    let window = (PI / 36.0 * (n as f32 + 0.5)).sin();
+                    // 디코더 IMDCT의 이득(N/4 = 9)을 미리 나눠 둠
+                    *m = window / 9.0 * (PI / 72.0 * (2 * n + 19) as f32 * (2 * k + 1) as f32).cos();
+                }
+                row
+            })
+            .collect();
+
+        const ALIAS_C: [f32; 8] = [-0.6, -0.535, -0.33, -0.185, -0.095, -0.041, -0.0142, -0.0037];
+        let mut alias_cs = [0.0; 8];
+        let mut alias_ca = [0.0; 8];
+        for i in 0..8 {
+            let norm = (1.0 + ALIAS_C[i] * ALIAS_C[i]).sqrt();
+            alias_cs[i] = 1.0 / norm;
+            alias_ca[i] = ALIAS_C[i] / norm;
+        }
+
+        Self { window, matrix, mdct, alias_cs, alias_ca }
+    }
+
+    // 32개 입력 샘플 -> 서브밴드 샘플 32개 (폴리페이즈 분석 필터뱅크)
+    fn analyze(&self, state: &mut ChannelState, input: &[f32]) -> [f32; SUBBANDS] {
+        state.fifo.copy_within(0..480, 32);
+        for (i, sample) in input.iter().enumerate() {
+            state.fifo[31 - i] = *sample;
+        }
+
+        let mut y = [0.0f32; 64];
+        for (i, y) in y.iter_mut().enumerate() {
+            *y = (0..8).map(|j| self.window[i + 64 * j] * state.fifo[i + 64 * j]).sum();
+        }
+
+        let mut out = [0.0; SUBBANDS];
+        for (k, out) in out.iter_mut().enumerate() {
+            *out = self.matrix[k].iter().zip(y.iter()).map(|(m, y)| m * y).sum();
+        }
+        out
+    }
+
+    // PCM 576개 -> MDCT 스펙트럼 576개
+    fn granule(&self, state: &mut ChannelState, input: &[f32], spectrum: &mut [f32; GRANULE_SIZE]) {
+        let mut subbands = [[0.0f32; 18]; SUBBANDS];
+        for t in 0..18 {
+            let samples = self.analyze(state, &input[t * SUBBANDS..(t + 1) * SUBBANDS]);
+            for (sb, sample) in samples.iter().enumerate() {
+                // 홀수 서브밴드의 홀수 번째 샘플은 주파수 반전
+                subbands[sb][t] = if sb % 2 == 1 && t % 2 == 1 { -sample } else { *sample };
+            }
+        }
+
+        for sb in 0..SUBBANDS {
+            let mut block = [0.0f32; 36];
+            block[..18].copy_from_slice(&state.overlap[sb]);
+            block[18..].copy_from_slice(&subbands[sb]);
+            state.overlap[sb] = subbands[sb];
+
+            for k in 0..18 {
+                spectrum[sb * 18 + k] = self.mdct[k].iter().zip(block.iter()).map(|(m, x)| m * x).sum();
+            }
+        }
+
+        // 서브밴드 경계의 에일리어싱 감소 (디코더 버터플라이의 역변환)
+        for sb in 1..SUBBANDS {
+            for i in 0..8 {
+                let lower = sb * 18 - 1 - i;
+                let upper = sb * 18 + i;
+                let (bu, bd) = (spectrum[lower], spectrum[upper]);
+                spectrum[lower] = bu * self.alias_cs[i] + bd * self.alias_ca[i];
+                spectrum[upper] = bd * self.alias_cs[i] - bu * self.alias_ca[i];
+            }
+        }
+    }
+}
+
+fn quantize(xr34: &[f32; GRANULE_SIZE], global_gain: i32, ix: &mut [i32; GRANULE_SIZE]) -> i32 {
+    let step = 2f32.powf(-(global_gain - 210) as f32 * 3.0 / 16.0);
+    let mut max = 0;
+    for (q, x) in ix.iter_mut().zip(xr34.iter()) {
+        *q = (x * step + 0.4054) as i32;
+        max = max.max(*q);
+    }
+    max
+}
+
+// 구간에 가장 적은 비트를 쓰는 허프만 표 선택
+fn choose_table(values: &[i32]) -> (usize, usize) {
+    let max = values.iter().copied().max().unwrap_or(0);
+    if max == 0 {
+        return (0, 0);
+    }
+
+    let count = |select: usize| {
+        let table = HUFFMAN_TABLES[select].as_ref().unwrap();
+        values.chunks(2).map(|pair| table.pair_bits(pair[0], pair[1])).sum::<usize>()
+    };
+    let fits = |select: &usize| HUFFMAN_TABLES[*select].as_ref().is_some_and(|t| t.max_value() >= max);
+
+    let candidates: Vec<usize> = if max <= 15 {
+        SMALL_TABLES.iter().copied().filter(fits).collect()
+    } else {
+        // linbits 표는 두 계열에서 각각 가장 작은 linbits만 비교
+        [(16..24).find(fits), (24..32).find(fits)].into_iter().flatten().collect()
+    };
+
+    candidates.into_iter().map(|select| (select, count(select))).min_by_key(|(_, bits)| *bits).unwrap_or((0, 0))
+}
+
+fn quad_index(quad: &[i32]) -> usize {
+    (quad[0] as usize) << 3 | (quad[1] as usize) << 2 | (quad[2] as usize) << 1 | quad[3] as usize
+}
+
+// 양자화 결과의 영역 분할, 표 선택, 비트 수 계산
+fn granule_info(ix: &[i32; GRANULE_SIZE], sfb: &[usize; 23]) -> GranuleInfo {
+    let mut count1_end = GRANULE_SIZE;
+    while count1_end >= 2 && ix[count1_end - 1] == 0 && ix[count1_end - 2] == 0 {
+        count1_end -= 2;
+    }
+    let mut big_end = count1_end;
+    while big_end >= 4 && ix[big_end - 4..big_end].iter().all(|v| *v <= 1) {
+        big_end -= 4;
+    }
+
+    let bands = sfb.iter().position(|start| *start >= big_end).unwrap_or(22);
+    let (region0, region1) = REGION_SPLIT[bands];
+    let region1_start = sfb[region0 + 1].min(big_end);
+    let region2_start = sfb[(region0 + region1 + 2).min(22)].min(big_end);
+
+    let mut info = GranuleInfo {
+        big_values: big_end / 2,
+        region0_count: region0,
+        region1_count: region1,
+        count1_end,
+        ..Default::default()
+    };
+
+    let regions = [0..region1_start, region1_start..region2_start, region2_start..big_end];
+    for (i, region) in regions.into_iter().enumerate() {
+        let (select, bits) = choose_table(&ix[region]);
+        info.table_select[i] = select;
+        info.part2_3_length += bits;
+    }
+
+    let quads = &ix[big_end..count1_end];
+    let quad_bits = |table: usize| {
+        quads
+            .chunks(4)
+            .map(|quad| QUAD_BITS[table][quad_index(quad)] as usize + quad.iter().filter(|v| **v != 0).count())
+            .sum::<usize>()
+    };
+    let (bits_a, bits_b) = (quad_bits(0), quad_bits(1));
+    info.count1_table = if bits_b < bits_a { 1 } else { 0 };
+    info.part2_3_length += bits_a.min(bits_b);
+
+    info
+}
+
+fn write_huffman(writer: &mut BitWriter, ix: &[i32; GRANULE_SIZE], xr: &[f32; GRANULE_SIZE], info: &GranuleInfo, sfb: &[usize; 23]) {
+    let big_end = info.big_values * 2;
+    let region1_start = sfb[info.region0_count + 1].min(big_end);
+    let region2_start = sfb[(info.region0_count + info.region1_count + 2).min(22)].min(big_end);
+
+    let regions = [0..region1_start, region1_start..region2_start, region2_start..big_end];
+    for (region, select) in regions.into_iter().zip(info.table_select) {
+        let Some(table) = HUFFMAN_TABLES[select].as_ref() else {
+            continue;
+        };
+        for i in region.step_by(2) {
+            table.write_pair(writer, ix[i], ix[i + 1], xr[i] < 0.0, xr[i + 1] < 0.0);
+        }
+    }
+
+    for i in (big_end..info.count1_end).step_by(4) {
+        let quad = &ix[i..i + 4];
+        let index = quad_index(quad);
+        writer.write_bits(QUAD_CODES[info.count1_table][index] as u64, QUAD_BITS[info.count1_table][index] as u32);
+        for j in 0..4 {
+            if quad[j] != 0 {
+                writer.write_bit(xr[i + j] < 0.0);
+            }
+        }
+    }
+}
+
+// global_gain으로 양자화해 사이드 정보 계산 (부호화할 수 없는 값이 나오면 None)
+fn try_gain(xr34: &[f32; GRANULE_SIZE], gain: i32, sfb: &[usize; 23], ix: &mut [i32; GRANULE_SIZE]) -> Option<GranuleInfo> {
+    if quantize(xr34, gain, ix) > MAX_QUANTIZED {
+        return None;
+    }
+    let mut info = granule_info(ix, sfb);
+    info.global_gain = gain;
+    (info.part2_3_length <= MAX_PART2_3_BITS).then_some(info)
+}
+
+// 부호화할 수 있는 가장 세밀한(작은) global_gain (255에서는 모든 값이 0이 되어 항상 가능)
+fn min_gain(xr34: &[f32; GRANULE_SIZE], sfb: &[usize; 23], ix: &mut [i32; GRANULE_SIZE]) -> i32 {
+    let (mut low, mut high) = (0, 255);
+    while low < high {
+        let mid = (low + high) / 2;
+        if try_gain(xr34, mid, sfb, ix).is_some() {
+            high = mid;
+        } else {
+            low = mid + 1;
+        }
+    }
+    low
+}
+
+// 프레임의 그래뉼/채널들을 같은 양자화 간격(같은 잡음 수준)으로 맞추고 남는 비트는 앞에서부터 더 세밀하게
+fn allocate_frame(
+    xr34: &[[f32; GRANULE_SIZE]],
+    budget: usize,
+    sfb: &[usize; 23],
+    quantized: &mut [[i32; GRANULE_SIZE]],
+) -> Vec<GranuleInfo> {
+    let floors: Vec<i32> = xr34.iter().zip(quantized.iter_mut()).map(|(x, ix)| min_gain(x, sfb, ix)).collect();
+    let bits_at = |slot: usize, gain: i32, ix: &mut [i32; GRANULE_SIZE]| {
+        try_gain(&xr34[slot], gain.max(floors[slot]), sfb, ix).map_or(usize::MAX, |info| info.part2_3_length)
+    };
+
+    let mut scratch = [0i32; GRANULE_SIZE];
+    let (mut low, mut high) = (0, 255);
+    while low < high {
+        let mid = (low + high) / 2;
+        let total: usize = (0..xr34.len()).map(|slot| bits_at(slot, mid, &mut scratch)).sum();
+        if total <= budget {
+            high = mid;
+        } else {
+            low = mid + 1;
+        }
+    }
+
+    let mut gains: Vec<i32> = floors.iter().map(|floor| low.max(*floor)).collect();
+    let mut bits: Vec<usize> = (0..xr34.len()).map(|slot| bits_at(slot, gains[slot], &mut scratch)).collect();
+    let mut total: usize = bits.iter().sum();
+    for slot in 0..xr34.len() {
+        while gains[slot] > floors[slot] {
+            let finer = bits_at(slot, gains[slot] - 1, &mut scratch);
+            if total - bits[slot] + finer > budget {
+                break;
+            }
+            total = total - bits[slot] + finer;
+            bits[slot] = finer;
+            gains[slot] -= 1;
+        }
+    }
+
+    gains
+        .iter()
+        .zip(quantized.iter_mut())
+        .enumerate()
+        .map(|(slot, (gain, ix))| try_gain(&xr34[slot], *gain, sfb, ix).unwrap_or_default())
+        .collect()
+}
+
+// 비트레이트별 저역 통과 주파수 (높은 대역에 비트를 쓰지 않도록)
+fn lowpass_hz(bitrate: u32, channels: usize) -> f32 {
+    match bitrate / channels as u32 {
+        0..=56 => 13_000.0,
+        57..=80 => 15_500.0,
+        81..=112 => 17_000.0,
+        _ => 19_500.0,
+    }
+}
+
+// 채널별 샘플(-1.0 ~ 1.0)을 MP3(CBR) 파일 바이트로 인코딩
+pub fn encode_mp3(channels: &[Vec<f32>], sample_rate: u32, bitrate: u32, progress: &mut dyn FnMut(f32)) -> Vec<u8> {
+    let sample_rate = if MP3_SAMPLE_RATES.contains(&sample_rate) { sample_rate } else { 48000 };
+    let bitrate = if MP3_BITRATES.contains(&bitrate) { bitrate } else { 128 };
+    let sample_rate_index = match sample_rate {
+        44100 => 0,
+        48000 => 1,
+        _ => 2,
+    };
+    let bitrate_index = BITRATE_TABLE.iter().position(|kbps| *kbps == bitrate).unwrap_or(9);
+    let sfb = &SFB_LONG_BANDS[sample_rate_index];
+
+    let channel_count = channels.len().clamp(1, 2);
+    let stereo = channel_count == 2;
+    let sample_count = channels.first().map_or(0, |c| c.len());
+    let side_info_bits = if stereo { 256 } else { 136 };
+
+    // 필터뱅크 지연만큼 뒤를 0으로 채워 마지막 샘플까지 내보냄
+    let frame_total = (sample_count + GRANULE_SIZE).div_ceil(FRAME_SIZE);
+    let lowpass_bin = ((lowpass_hz(bitrate, channel_count) * 2.0 * GRANULE_SIZE as f32 / sample_rate as f32) as usize).min(GRANULE_SIZE);
+
+    let filterbank = Filterbank::new();
+    let mut states: Vec<ChannelState> = (0..channel_count).map(|_| ChannelState::new()).collect();
+
+    // 44.1kHz는 프레임 길이가 나누어떨어지지 않아 패딩 바이트를 누적 오차로 결정
+    let frame_bytes = (144_000 * bitrate / sample_rate) as usize;
+    let padding_step = 144_000 * bitrate % sample_rate;
+    let mut padding_acc = 0;
+    let mut out = BitWriter::with_capacity(frame_total * (frame_bytes + 1));
+
+    let mut pcm = [0.0f32; FRAME_SIZE];
+    let mut spectra = vec![[[0.0f32; GRANULE_SIZE]; 2]; 2]; // [그래뉼][채널]
+    let mut xr34 = vec![[0.0f32; GRANULE_SIZE]; 2 * channel_count]; // 그래뉼 * 채널 순
+    let mut quantized = vec![[0i32; GRANULE_SIZE]; 2 * channel_count];
+
+    for frame in 0..frame_total {
+        padding_acc += padding_step;
+        let padding = padding_acc >= sample_rate;
+        if padding {
+            padding_acc -= sample_rate;
+        }
+        let frame_len = frame_bytes + padding as usize;
+
+        for (ch, state) in states.iter_mut().enumerate() {
+            let start = frame * FRAME_SIZE;
+            for (i, sample) in pcm.iter_mut().enumerate() {
+                let s = channels[ch].get(start + i).copied().unwrap_or(0.0);
+                *sample = if s.is_finite() { s.clamp(-1.0, 1.0) } else { 0.0 };
+            }
+            for gr in 0..2 {
+                filterbank.granule(state, &pcm[gr * GRANULE_SIZE..(gr + 1) * GRANULE_SIZE], &mut spectra[gr][ch]);
+                spectra[gr][ch][lowpass_bin..].fill(0.0);
+            }
+        }
+
+        if stereo {
+            // L/R -> M/S (디코더가 L = (M + S) / √2, R = (M - S) / √2로 복원)
+            for spectrum in spectra.iter_mut() {
+                let [left, right] = spectrum;
+                for (l, r) in left.iter_mut().zip(right.iter_mut()) {
+                    let (mid, side) = ((*l + *r) * std::f32::consts::FRAC_1_SQRT_2, (*l - *r) * std::f32::consts::FRAC_1_SQRT_2);
+                    *l = mid;
+                    *r = side;
+                }
+            }
+        }
+
+        for (slot, x34) in xr34.iter_mut().enumerate() {
+            for (x34, x) in x34.iter_mut().zip(spectra[slot / channel_count][slot % channel_count].iter()) {
+                *x34 = x.abs().powf(0.75);
+            }
+        }
+        // 마지막 그래뉼이 0비트여도 시작 위치가 메인 데이터 안에 있도록 1바이트는 남김
+        let budget = frame_len * 8 - 32 - side_info_bits - 8;
+        let allocation = allocate_frame(&xr34, budget, sfb, &mut quantized);
+
+        // 프레임 헤더
+        out.write_bits(0x7ff, 11);
+        out.write_bits(0b11, 2); // MPEG-1
+        out.write_bits(0b01, 2); // Layer III
+        out.write_bit(true); // CRC 없음
+        out.write_bits(bitrate_index as u64, 4);
+        out.write_bits(sample_rate_index as u64, 2);
+        out.write_bit(padding);
+        out.write_bit(false);
+        out.write_bits(if stereo { 0b01 } else { 0b11 }, 2); // 조인트 스테레오 / 모노
+        out.write_bits(if stereo { 0b10 } else { 0b00 }, 2); // M/S 사용, 인텐시티 미사용
+        out.write_bit(false); // 저작권
+        out.write_bit(true); // 원본
+        out.write_bits(0, 2); // 엠퍼시스 없음
+
+        // 사이드 정보
+        out.write_bits(0, 9); // main_data_begin (비트 저장소 미사용)
+        out.write_bits(0, if stereo { 3 } else { 5 });
+        out.write_bits(0, 4 * channel_count as u32); // scfsi
+        for info in &allocation {
+            out.write_bits(info.part2_3_length as u64, 12);
+            out.write_bits(info.big_values as u64, 9);
+            out.write_bits(info.global_gain as u64, 8);
+            out.write_bits(0, 4); // scalefac_compress: 스케일팩터 없음
+            out.write_bit(false); // 창 전환 없음 (긴 블록)
+            for select in info.table_select {
+                out.write_bits(select as u64, 5);
+            }
+            out.write_bits(info.region0_count as u64, 4);
+            out.write_bits(info.region1_count as u64, 3);
+            out.write_bit(false); // preflag
+            out.write_bit(false); // scalefac_scale
+            out.write_bit(info.count1_table == 1);
+        }
+
+        // 메인 데이터 (허프만 부호), 남는 비트는 0으로 채움
+        let main_data_start = out.bit_len();
+        for (slot, info) in allocation.iter().enumerate() {
+            write_huffman(&mut out, &quantized[slot], &spectra[slot / channel_count][slot % channel_count], info, sfb);
+        }
+        let used = out.bit_len() - main_data_start;
+        let mut padding_bits = frame_len * 8 - 32 - side_info_bits - used;
+        while padding_bits > 0 {
+            let bits = padding_bits.min(32);
+            out.write_bits(0, bits as u32);
+            padding_bits -= bits;
+        }
+
+        progress((frame + 1) as f32 / frame_total as f32);
+    }
+
+    out.into_bytes()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 헤더/사이드 정보 검사용 MSB 우선 비트 읽기
+    struct BitReader<'a> {
+        bytes: &'a [u8],
+        pos: usize,
+    }
+
+    impl BitReader<'_> {
+        fn read(&mut self, bits: usize) -> u32 {
+            let mut value = 0;
+            for _ in 0..bits {
+                let bit = (self.bytes[self.pos / 8] >> (7 - self.pos % 8)) & 1;
+                value = (value << 1) | bit as u32;
+                self.pos += 1;
+            }
+            value
+        }
+    }
+
+    fn test_signal(len: usize, freq: f32, sample_rate: u32) -> Vec<f32> {
+        (0..len)
+            .map(|i| 0.4 * (2.0 * PI * freq * i as f32 / sample_rate as f32).sin())
+            .collect()
+    }
+
+    // 모든 프레임의 동기 코드, 헤더 필드, 길이, 패딩과 사이드 정보의 비트 수 검사
+    fn check_stream(bytes: &[u8], sample_rate: u32, bitrate: u32, stereo: bool, sample_count: usize) {
+        let exact_len = 144_000 * bitrate as usize;
+        let side_info_bytes = if stereo { 32 } else { 17 };
+        let expected_frames = (sample_count + GRANULE_SIZE).div_ceil(FRAME_SIZE);
+
+        let mut pos = 0;
+        let mut frames = 0;
+        while pos < bytes.len() {
+            let mut reader = BitReader { bytes: &bytes[pos..], pos: 0 };
+            assert_eq!(reader.read(11), 0x7ff, "프레임 {} 동기 코드", frames);
+            assert_eq!(reader.read(2), 0b11); // MPEG-1
+            assert_eq!(reader.read(2), 0b01); // Layer III
+            assert_eq!(reader.read(1), 1); // CRC 없음
+            assert_eq!(BITRATE_TABLE[reader.read(4) as usize], bitrate);
+            let sample_rate_index = reader.read(2) as usize;
+            assert_eq!([44100, 48000, 32000][sample_rate_index], sample_rate);
+            let padding = reader.read(1) as usize;
+            reader.read(1);
+            assert_eq!(reader.read(2), if stereo { 0b01 } else { 0b11 });
+            assert_eq!(reader.read(2), if stereo { 0b10 } else { 0b00 });
+            reader.read(4);
+
+            // 패딩은 누적 길이가 정확한 평균 길이를 따라가도록 배치
+            let frame_len = exact_len / sample_rate as usize + padding;
+            assert_eq!(pos + frame_len, (frames + 1) * exact_len / sample_rate as usize, "프레임 {} 길이", frames);
+            if sample_rate != 44100 {
+                assert_eq!(padding, 0);
+            }
+
+            assert_eq!(reader.read(9), 0); // main_data_begin
+            reader.read(if stereo { 3 + 8 } else { 5 + 4 });
+            let mut main_data_bits = 0;
+            for _ in 0..if stereo { 4 } else { 2 } {
+                main_data_bits += reader.read(12) as usize;
+                assert!(reader.read(9) <= 288); // big_values
+                reader.read(8 + 4 + 1 + 15 + 4 + 3 + 3);
+            }
+            assert_eq!(reader.pos, (4 + side_info_bytes) * 8);
+            assert!(main_data_bits <= (frame_len - 4 - side_info_bytes) * 8);
+
+            pos += frame_len;
+            frames += 1;
+        }
+
+        assert_eq!(pos, bytes.len());
+        assert_eq!(frames, expected_frames);
+    }
+
+    #[test]
+    fn frames_at_every_bitrate_and_sample_rate() {
+        let sample_count = 4 * FRAME_SIZE + 100;
+        for sample_rate in MP3_SAMPLE_RATES {
+            let mono = vec![test_signal(sample_count, 440.0, sample_rate)];
+            let stereo = vec![test_signal(sample_count, 440.0, sample_rate), test_signal(sample_count, 660.0, sample_rate)];
+            for bitrate in MP3_BITRATES {
+                let bytes = encode_mp3(&mono, sample_rate, bitrate, &mut |_| {});
+                check_stream(&bytes, sample_rate, bitrate, false, sample_count);
+
+                let bytes = encode_mp3(&stereo, sample_rate, bitrate, &mut |_| {});
+                check_stream(&bytes, sample_rate, bitrate, true, sample_count);
+            }
+        }
+    }
+
+    #[test]
+    fn padding_follows_fractional_frame_length_at_44100() {
+        // 128kbps/44.1kHz의 프레임 길이는 417.959...바이트 - 49프레임 중 48개에 패딩
+        let sample_count = 48 * FRAME_SIZE;
+        let bytes = encode_mp3(&[vec![0.0; sample_count]], 44100, 128, &mut |_| {});
+        check_stream(&bytes, 44100, 128, false, sample_count);
+        assert_eq!(bytes.len(), 49 * 144_000 * 128 / 44100);
+    }
+
+    #[test]
+    fn unsupported_settings_fall_back() {
+        let bytes = encode_mp3(&[vec![0.0; FRAME_SIZE]], 22050, 100, &mut |_| {});
+        check_stream(&bytes, 48000, 128, false, FRAME_SIZE);
+    }
+
+    // symphonia로 디코딩한 채널별 PCM
+    fn decode(bytes: Vec<u8>) -> Vec<Vec<f32>> {
+        use symphonia::core::audio::SampleBuffer;
+        use symphonia::core::codecs::DecoderOptions;
+        use symphonia::core::errors::Error;
+        use symphonia::core::formats::FormatOptions;
+        use symphonia::core::io::MediaSourceStream;
+        use symphonia::core::meta::MetadataOptions;
+        use symphonia::core::probe::Hint;
+
+        let source = MediaSourceStream::new(Box::new(std::io::Cursor::new(bytes)), Default::default());
+        let mut hint = Hint::new();
+        hint.with_extension("mp3");
+        let mut format = symphonia::default::get_probe()
+            .format(&hint, source, &FormatOptions::default(), &MetadataOptions::default())
+            .expect("MP3 스트림 인식")
+            .format;
+        let track = format.default_track().expect("오디오 트랙");
+        let mut decoder = symphonia::default::get_codecs()
+            .make(&track.codec_params, &DecoderOptions::default())
+            .expect("MP3 디코더");
+
+        let mut channels: Vec<Vec<f32>> = Vec::new();
+        loop {
+            let packet = match format.next_packet() {
+                Ok(packet) => packet,
+                Err(Error::IoError(_)) => break,
+                Err(err) => panic!("패킷 읽기 실패: {:?}", err),
+            };
+            let decoded = decoder.decode(&packet).expect("프레임 디코딩");
+            let count = decoded.spec().channels.count();
+            let mut buffer = SampleBuffer::<f32>::new(decoded.capacity() as u64, *decoded.spec());
+            buffer.copy_interleaved_ref(decoded);
+            channels.resize(count, Vec::new());
+            for frame in buffer.samples().chunks(count) {
+                for (channel, sample) in channels.iter_mut().zip(frame) {
+                    channel.push(*sample);
+                }
+            }
+        }
+        channels
+    }
+
+    // 인코더/디코더 지연을 찾아 맞춘 뒤 앞뒤 프레임을 뺀 구간의 SNR (dB)
+    fn aligned_snr(reference: &[f32], decoded: &[f32]) -> f64 {
+        let error_energy = |window: std::ops::Range<usize>, delay: usize| -> f64 {
+            window
+                .map(|i| {
+                    let diff = reference[i] as f64 - decoded.get(i + delay).copied().unwrap_or(0.0) as f64;
+                    diff * diff
+                })
+                .sum()
+        };
+
+        // 지연은 한 그래뉼 구간의 오차가 가장 작은 위치로 찾음
+        let probe = FRAME_SIZE..FRAME_SIZE + GRANULE_SIZE;
+        let delay = (0..3 * GRANULE_SIZE)
+            .min_by(|a, b| error_energy(probe.clone(), *a).total_cmp(&error_energy(probe.clone(), *b)))
+            .unwrap();
+
+        let window = FRAME_SIZE..reference.len() - FRAME_SIZE;
+        let signal: f64 = window.clone().map(|i| (reference[i] as f64).powi(2)).sum();
+        10.0 * (signal / error_energy(window, delay).max(1e-20)).log10()
+    }
+
+    // 현재 인코더는 약 80 dB - 양자화나 허프만 부호화가 깨지면 크게 떨어짐
+    const MIN_SNR_DB: f64 = 60.0;
+
+    #[test]
+    fn decodes_back_to_the_input_signal() {
+        let sample_count = 20 * FRAME_SIZE;
+        for (sample_rate, bitrate) in [(44100, 128), (48000, 192), (32000, 96)] {
+            let left = test_signal(sample_count, 440.0, sample_rate);
+            let right = test_signal(sample_count, 660.0, sample_rate);
+
+            let decoded = decode(encode_mp3(std::slice::from_ref(&left), sample_rate, bitrate, &mut |_| {}));
+            assert_eq!(decoded.len(), 1);
+            let snr = aligned_snr(&left, &decoded[0]);
+            assert!(snr > MIN_SNR_DB, "모노 {}Hz {}kbps SNR {:.1} dB", sample_rate, bitrate, snr);
+
+            let decoded = decode(encode_mp3(&[left.clone(), right.clone()], sample_rate, bitrate, &mut |_| {}));
+            assert_eq!(decoded.len(), 2);
+            for (channel, reference) in decoded.iter().zip([&left, &right]) {
+                let snr = aligned_snr(reference, channel);
+                assert!(snr > MIN_SNR_DB, "스테레오 {}Hz {}kbps SNR {:.1} dB", sample_rate, bitrate, snr);
+            }
+        }
+    }
+
+    #[test]
+    fn reports_progress_per_frame() {
+        let mut reports = Vec::new();
+        encode_mp3(&[vec![0.1; 3 * FRAME_SIZE]], 48000, 192, &mut |fraction| reports.push(fraction));
+        assert_eq!(reports.len(), 4);
+        assert_eq!(reports.last().copied(), Some(1.0));
+    }
+}
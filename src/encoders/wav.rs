@@ -0,0 +1,191 @@
+use serde::{Deserialize, Serialize};
+
+const WAVE_FORMAT_PCM: u16 = 1;
+const WAVE_FORMAT_IEEE_FLOAT: u16 = 3;
+
+// WAV 샘플 형식 (비트 깊이)
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum WavSampleFormat {
+    Pcm16,
+    Pcm24,
+    Float32,
+}
+
+impl WavSampleFormat {
+    pub const ALL: [WavSampleFormat; 3] = [WavSampleFormat::Pcm16, WavSampleFormat::Pcm24, WavSampleFormat::Float32];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            WavSampleFormat::Pcm16 => "16-bit",
+            WavSampleFormat::Pcm24 => "24-bit",
+            WavSampleFormat::Float32 => "32-bit float",
+        }
+    }
+
+    // 이벤트 detail에 담는 값
+    pub fn value(&self) -> &'static str {
+        match self {
+            WavSampleFormat::Pcm16 => "16",
+            WavSampleFormat::Pcm24 => "24",
+            WavSampleFormat::Float32 => "32f",
+        }
+    }
+
+    pub fn from_value(value: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|format| format.value() == value)
+    }
+
+    fn bits_per_sample(&self) -> u16 {
+        match self {
+            WavSampleFormat::Pcm16 => 16,
+            WavSampleFormat::Pcm24 => 24,
+            WavSampleFormat::Float32 => 32,
+        }
+    }
+
+    fn format_tag(&self) -> u16 {
+        match self {
+            WavSampleFormat::Float32 => WAVE_FORMAT_IEEE_FLOAT,
+            _ => WAVE_FORMAT_PCM,
+        }
+    }
+}
+
+// 채널별 샘플(-1.0 ~ 1.0)을 WAV 파일 바이트로 인코딩
+pub fn encode_wav(channels: &[Vec<f32>], sample_rate: u32, sample_format: WavSampleFormat) -> Vec<u8> {
+    let channel_count = channels.len().max(1) as u16;
+    let frame_count = channels.first().map_or(0, |c| c.len());
+    let bytes_per_sample = (sample_format.bits_per_sample() / 8) as u32;
+    let block_align = channel_count as u32 * bytes_per_sample;
+    let data_size = frame_count as u32 * block_align;
+
+    // float 형식은 cbSize가 있는 18바이트 fmt 청크와 fact 청크를 사용
+    let is_float = sample_format == WavSampleFormat::Float32;
+    let fmt_size: u32 = if is_float { 18 } else { 16 };
+    let fact_size: u32 = if is_float { 12 } else { 0 };
+    let riff_size = 4 + (8 + fmt_size) + fact_size + (8 + data_size);
+
+    let mut bytes = Vec::with_capacity(8 + riff_size as usize);
+    bytes.extend_from_slice(b"RIFF");
+    bytes.extend_from_slice(&riff_size.to_le_bytes());
+    bytes.extend_from_slice(b"WAVE");
+
+    bytes.extend_from_slice(b"fmt ");
+    bytes.extend_from_slice(&fmt_size.to_le_bytes());
+    bytes.extend_from_slice(&sample_format.format_tag().to_le_bytes());
+    bytes.extend_from_slice(&channel_count.to_le_bytes());
+    bytes.extend_from_slice(&sample_rate.to_le_bytes());
+    bytes.extend_from_slice(&(sample_rate * block_align).to_le_bytes());
+    bytes.extend_from_slice(&(block_align as u16).to_le_bytes());
+    bytes.extend_from_slice(&sample_format.bits_per_sample().to_le_bytes());
+    if is_float {
+        bytes.extend_from_slice(&0u16.to_le_bytes());
+        bytes.extend_from_slice(b"fact");
+        bytes.extend_from_slice(&4u32.to_le_bytes());
+        bytes.extend_from_slice(&(frame_count as u32).to_le_bytes());
+    }
+
+    bytes.extend_from_slice(b"data");
+    bytes.extend_from_slice(&data_size.to_le_bytes());
+
+    // 채널을 프레임 단위로 교차 배치
+    for frame in 0..frame_count {
+        for channel in channels {
+            let sample = channel.get(frame).copied().unwrap_or(0.0);
+            let sample = if sample.is_finite() { sample.clamp(-1.0, 1.0) } else { 0.0 };
+            match sample_format {
+                WavSampleFormat::Pcm16 => {
+                    let value = (sample * i16::MAX as f32).round() as i16;
+                    bytes.extend_from_slice(&value.to_le_bytes());
+                }
+                WavSampleFormat::Pcm24 => {
+                    let value = (sample * 8_388_607.0).round() as i32;
+                    bytes.extend_from_slice(&value.to_le_bytes()[..3]);
+                }
+                WavSampleFormat::Float32 => {
+                    bytes.extend_from_slice(&sample.to_le_bytes());
+                }
+            }
+        }
+    }
+
+    bytes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn u16_at(bytes: &[u8], pos: usize) -> u16 {
+        u16::from_le_bytes([bytes[pos], bytes[pos + 1]])
+    }
+
+    fn u32_at(bytes: &[u8], pos: usize) -> u32 {
+        u32::from_le_bytes([bytes[pos], bytes[pos + 1], bytes[pos + 2], bytes[pos + 3]])
+    }
+
+    #[test]
+    fn pcm16_stereo_header_and_samples() {
+        let left = vec![0.0, 1.0, -1.0, 2.0];
+        let right = vec![0.5, f32::NAN, -0.5, -2.0];
+        let bytes = encode_wav(&[left, right], 48000, WavSampleFormat::Pcm16);
+
+        assert_eq!(&bytes[0..4], b"RIFF");
+        assert_eq!(u32_at(&bytes, 4) as usize, bytes.len() - 8);
+        assert_eq!(&bytes[8..12], b"WAVE");
+        assert_eq!(&bytes[12..16], b"fmt ");
+        assert_eq!(u32_at(&bytes, 16), 16);
+        assert_eq!(u16_at(&bytes, 20), WAVE_FORMAT_PCM);
+        assert_eq!(u16_at(&bytes, 22), 2); // 채널 수
+        assert_eq!(u32_at(&bytes, 24), 48000);
+        assert_eq!(u32_at(&bytes, 28), 48000 * 4); // 바이트/초
+        assert_eq!(u16_at(&bytes, 32), 4); // 블록 정렬
+        assert_eq!(u16_at(&bytes, 34), 16);
+        assert_eq!(&bytes[36..40], b"data");
+        assert_eq!(u32_at(&bytes, 40), 16);
+        assert_eq!(bytes.len(), 44 + 16);
+
+        let samples: Vec<i16> = bytes[44..].chunks_exact(2).map(|b| i16::from_le_bytes([b[0], b[1]])).collect();
+        assert_eq!(samples, vec![0, 16384, 32767, 0, -32767, -16384, 32767, -32767]);
+    }
+
+    #[test]
+    fn pcm24_mono_header_and_samples() {
+        let bytes = encode_wav(&[vec![1.0, -1.0, 0.25]], 44100, WavSampleFormat::Pcm24);
+
+        assert_eq!(u16_at(&bytes, 22), 1);
+        assert_eq!(u32_at(&bytes, 28), 44100 * 3);
+        assert_eq!(u16_at(&bytes, 32), 3);
+        assert_eq!(u16_at(&bytes, 34), 24);
+        assert_eq!(u32_at(&bytes, 40), 9);
+        assert_eq!(&bytes[44..], &[0xff, 0xff, 0x7f, 0x01, 0x00, 0x80, 0x00, 0x00, 0x20]);
+    }
+
+    #[test]
+    fn float32_uses_extended_fmt_and_fact_chunk() {
+        let bytes = encode_wav(&[vec![0.5, -0.25], vec![0.0, 1.5]], 32000, WavSampleFormat::Float32);
+
+        assert_eq!(u32_at(&bytes, 4) as usize, bytes.len() - 8);
+        assert_eq!(u32_at(&bytes, 16), 18);
+        assert_eq!(u16_at(&bytes, 20), WAVE_FORMAT_IEEE_FLOAT);
+        assert_eq!(u16_at(&bytes, 32), 8);
+        assert_eq!(u16_at(&bytes, 34), 32);
+        assert_eq!(u16_at(&bytes, 36), 0); // cbSize
+        assert_eq!(&bytes[38..42], b"fact");
+        assert_eq!(u32_at(&bytes, 42), 4);
+        assert_eq!(u32_at(&bytes, 46), 2); // 프레임 수
+        assert_eq!(&bytes[50..54], b"data");
+        assert_eq!(u32_at(&bytes, 54), 16);
+
+        let samples: Vec<f32> = bytes[58..].chunks_exact(4).map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]])).collect();
+        assert_eq!(samples, vec![0.5, 0.0, -0.25, 1.0]);
+    }
+
+    #[test]
+    fn empty_input_writes_header_only() {
+        let bytes = encode_wav(&[], 48000, WavSampleFormat::Pcm16);
+        assert_eq!(bytes.len(), 44);
+        assert_eq!(u16_at(&bytes, 22), 1);
+        assert_eq!(u32_at(&bytes, 40), 0);
+    }
+}
@@ -0,0 +1,145 @@
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+
+use serde::{Deserialize, Serialize};
+use yew::prelude::*;
+
+use crate::audio_export::ExportOptions;
+use crate::background_session::LiveStatus;
+
+// 조건부 로그 매크로 정의
+#[cfg(debug_assertions)]
+macro_rules! console_log {
+    ($($arg:tt)*) => {
+        web_sys::console::log_1(&format!($($arg)*).into());
+    };
+}
+
+#[cfg(not(debug_assertions))]
+macro_rules! console_log {
+    ($($arg:tt)*) => {};
+}
+
+// 앱 내부 이벤트 버스
+// 컴포넌트/모듈 사이의 알림을 문자열 CustomEvent 대신 타입이 있는 AppEvent로 주고받음
+// 구독은 Subscription이 살아 있는 동안만 유효 (컴포넌트 필드나 훅에 두면 언마운트 때 자동 해제)
+//
+//   emit(AppEvent::PlaybackEnded);
+//   let _subscription = subscribe(move |event| if let AppEvent::PlaybackEnded = event { ... });
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", content = "detail", rename_all = "camelCase")]
+pub enum AppEvent {
+    // 컨트롤 바 -> 피치 분석기
+    ToggleAudio,                          // 마이크 켜기/끄기
+    ToggleMonitor,                        // 모니터링 켜기/끄기
+    ToggleLinks,                          // 대시보드 링크 표시 전환
+    TogglePlayback { playing: bool },     // 녹음 재생/일시정지
+    SeekPlayback { progress: f64 },       // 재생 위치 이동 (0.0 ~ 1.0)
+    UpdateSensitivity { value: f32 },
+    UpdateSpeakerVolume { value: f32 },
+    DownloadRecording(ExportOptions),
+    StopAudioResources,                   // 라우트 변경 시 오디오 자원 정리
+    ResetPitchAnalyzer,                   // 라우트 변경 시 분석 상태 초기화
+
+    // 피치 분석기 -> 컨트롤 바/차트
+    MicStateChanged { active: bool },     // 분석기가 스스로 마이크를 끄거나 켰을 때
+    ControlsEnabled { enabled: bool },    // 녹음 마무리 중 컨트롤 잠금/해제
+    PlaybackTimeUpdate { current_time: f64, duration: f64, is_recording: bool },
+    PlaybackStateChange { playing: bool },
+    PlaybackEnded,
+    PlaybackReset,                        // 새 녹음 시작 - 재생선을 0초로
+    RecordingStateChange { recording: bool },
+    RecordingComplete { audio_url: String },
+    ExportProgress { progress: Option<f64> }, // None이면 내보내기 끝
+    ExportFailed { message: String },     // 디코딩/인코딩 실패 또는 응답 없음
+    LiveStatus(LiveStatus),               // 마이크/녹음/현재 음 (바뀔 때만)
+
+    // 공용 상태 변경 알림
+    RegionSelected { region: Option<(f64, f64)> }, // 피치 차트 구간 선택 (시작, 끝 초)
+    TakesChanged { count: usize },
+    TargetMelodyChanged,
+    PracticeLogUpdated,
+    SettingsReset,
+    MicInputChanged,                      // 마이크 열림/닫힘/입력 장치 교체
+    MonitorEffectsChanged,                // 모니터 이펙트/이펙트 포함 녹음 설정 변경
+    MasterMuteChanged { muted: bool },    // 전체 음소거 (믹서 버튼/Alt+M)
+}
+
+impl AppEvent {
+    // 디버그 로그에서 제외할 잦은 이벤트
+    fn is_frequent(&self) -> bool {
+        matches!(
+            self,
+            AppEvent::PlaybackTimeUpdate { .. } | AppEvent::ExportProgress { .. } | AppEvent::LiveStatus(_)
+        )
+    }
+}
+
+type Handler = Rc<dyn Fn(&AppEvent)>;
+
+thread_local! {
+    static SUBSCRIBERS: RefCell<Vec<(u64, Handler)>> = const { RefCell::new(Vec::new()) };
+    static NEXT_ID: Cell<u64> = const { Cell::new(0) };
+}
+
+// 구독 핸들 - 버리면 구독 해제
+#[must_use = "Subscription을 버리면 바로 구독이 해제됩니다"]
+pub struct Subscription {
+    id: u64,
+}
+
+impl Drop for Subscription {
+    fn drop(&mut self) {
+        let id = self.id;
+        SUBSCRIBERS.with(|subscribers| subscribers.borrow_mut().retain(|(other, _)| *other != id));
+    }
+}
+
+pub fn subscribe(handler: impl Fn(&AppEvent) + 'static) -> Subscription {
+    let id = NEXT_ID.with(|next| {
+        let id = next.get();
+        next.set(id + 1);
+        id
+    });
+    SUBSCRIBERS.with(|subscribers| subscribers.borrow_mut().push((id, Rc::new(handler))));
+    Subscription { id }
+}
+
+// 모든 구독자에게 이벤트 전달
+// 핸들러 안에서 구독/해제/발행해도 되도록 목록을 복사해서 돌고,
+// 전달 도중 해제된 구독자는 건너뜀
+pub fn emit(event: AppEvent) {
+    if !event.is_frequent() {
+        console_log!("이벤트: {}", serde_json::to_string(&event).unwrap_or_else(|_| format!("{:?}", event)));
+    }
+
+    let handlers: Vec<(u64, Handler)> = SUBSCRIBERS.with(|subscribers| subscribers.borrow().clone());
+    for (id, handler) in handlers {
+        let subscribed = SUBSCRIBERS.with(|subscribers| subscribers.borrow().iter().any(|(other, _)| *other == id));
+        if subscribed {
+            handler(&event);
+        }
+    }
+}
+
+// 함수형 컴포넌트용 - 마운트 동안 구독하고, 핸들러는 매 렌더의 최신 것을 사용
+#[hook]
+pub fn use_app_event<F>(handler: F)
+where
+    F: Fn(&AppEvent) + 'static,
+{
+    let latest = use_mut_ref(|| None::<Handler>);
+    *latest.borrow_mut() = Some(Rc::new(handler));
+
+    use_effect_with((), move |_| {
+        let subscription = subscribe(move |event| {
+            // 핸들러가 다시 렌더링을 일으켜도 빌림이 겹치지 않게 복사해서 호출
+            let handler = latest.borrow().clone();
+            if let Some(handler) = handler {
+                handler(event);
+            }
+        });
+        move || drop(subscription)
+    });
+}
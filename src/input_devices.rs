@@ -0,0 +1,274 @@
+use std::rc::Rc;
+
+use gloo::events::EventListener;
+use gloo::utils::window;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{HtmlSelectElement, MediaDeviceInfo, MediaDeviceKind};
+use yew::prelude::*;
+
+use crate::audio_engine::AudioEngine;
+use crate::event_bus::{subscribe, AppEvent};
+use crate::latency::measure_round_trip;
+use crate::settings::{self, InputSettings};
+
+// 조건부 로그 매크로 정의
+#[cfg(debug_assertions)]
+macro_rules! console_error {
+    ($($arg:tt)*) => {
+        web_sys::console::error_1(&format!($($arg)*).into());
+    };
+}
+
+#[cfg(not(debug_assertions))]
+macro_rules! console_error {
+    ($($arg:tt)*) => {};
+}
+
+// 마이크 입력 장치 선택과 브라우저 음성 처리(에코 제거/잡음 억제/자동 게인) 설정
+// 바꾸면 설정에 저장하고, 마이크가 열려 있으면 엔진이 새 장치 스트림으로 바로 교체함
+// 장치별 왕복 지연도 여기서 측정해 저장 (녹음 박자 격자, 겹쳐 녹음, 리듬 훈련에 반영)
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct InputDevice {
+    pub id: String,
+    pub label: String,
+}
+
+// 오디오 입력 장치 목록 (마이크 권한을 받기 전에는 브라우저가 이름을 알려주지 않음)
+pub async fn list_input_devices() -> Result<Vec<InputDevice>, JsValue> {
+    let media_devices = window().navigator().media_devices()?;
+    let devices: js_sys::Array = JsFuture::from(media_devices.enumerate_devices()?).await?.dyn_into()?;
+
+    Ok(devices
+        .iter()
+        .filter_map(|device| device.dyn_into::<MediaDeviceInfo>().ok())
+        .filter(|device| device.kind() == MediaDeviceKind::Audioinput)
+        .enumerate()
+        .map(|(index, device)| {
+            let label = device.label();
+            InputDevice {
+                id: device.device_id(),
+                label: if label.is_empty() { format!("입력 장치 {}", index + 1) } else { label },
+            }
+        })
+        .collect())
+}
+
+fn format_rate(rate: f64) -> String {
+    format!("{} Hz", rate.round() as u32)
+}
+
+// 믹서 패널 안의 입력 장치 섹션
+#[function_component(InputDevicePanel)]
+pub fn input_device_panel() -> Html {
+    let engine = use_context::<AudioEngine>().unwrap_or_default();
+    let devices = use_state(Vec::<InputDevice>::new);
+    let input = use_state(|| settings::load_settings().input);
+    let info = use_state(|| engine.input_info());
+    let error = use_state(|| None::<String>);
+    let calibrating = use_state(|| false);
+    let calibration_message = use_state(|| None::<String>);
+
+    // 설정을 저장하고 열린 마이크에 적용 (지연 보정값은 유지)
+    let apply = {
+        let engine = engine.clone();
+        let input = input.clone();
+        let error = error.clone();
+        Callback::from(move |change: InputSettings| {
+            settings::update_settings(|s| {
+                s.input = InputSettings {
+                    latency_ms: std::mem::take(&mut s.input.latency_ms),
+                    ..change
+                }
+            });
+            input.set(settings::load_settings().input);
+
+            let engine = engine.clone();
+            let error = error.clone();
+            wasm_bindgen_futures::spawn_local(async move {
+                match engine.reopen_mic().await {
+                    Ok(()) => error.set(None),
+                    Err(err) => {
+                        console_error!("입력 장치 적용 실패: {:?}", err);
+                        error.set(Some("입력 장치를 열 수 없습니다. 연결 상태를 확인하세요.".to_string()));
+                    }
+                }
+            });
+        })
+    };
+
+    // 장치 목록과 입력 정보 갱신: 처음 열 때, 장치 연결/해제, 마이크 열림/닫힘/교체 때
+    // 설정 초기화 시에는 기본값을 열린 마이크에도 적용
+    {
+        let devices = devices.clone();
+        let info = info.clone();
+        let engine = engine.clone();
+        let apply = apply.clone();
+
+        use_effect_with((), move |_| {
+            let refresh = Rc::new(move || {
+                info.set(engine.input_info());
+                let devices = devices.clone();
+                wasm_bindgen_futures::spawn_local(async move {
+                    match list_input_devices().await {
+                        Ok(list) => devices.set(list),
+                        Err(err) => {
+                            console_error!("입력 장치 목록을 가져올 수 없습니다: {:?}", err);
+                        }
+                    }
+                });
+            });
+            refresh();
+
+            let app_subscription = {
+                let refresh = refresh.clone();
+                subscribe(move |event| match event {
+                    AppEvent::MicInputChanged => refresh(),
+                    AppEvent::SettingsReset => apply.emit(InputSettings::default()),
+                    _ => {}
+                })
+            };
+            let device_listener = window()
+                .navigator()
+                .media_devices()
+                .ok()
+                .map(|media_devices| EventListener::new(&media_devices, "devicechange", move |_| refresh()));
+
+            move || {
+                drop(app_subscription);
+                drop(device_listener);
+            }
+        });
+    }
+
+    let on_device_change = {
+        let input = input.clone();
+        let apply = apply.clone();
+        Callback::from(move |e: Event| {
+            let select: HtmlSelectElement = e.target_unchecked_into();
+            let value = select.value();
+            apply.emit(InputSettings {
+                device_id: (!value.is_empty()).then_some(value),
+                ..(*input).clone()
+            });
+        })
+    };
+
+    let option_toggle = |label: &'static str, enabled: bool, set: fn(&mut InputSettings, bool)| {
+        let input = input.clone();
+        let apply = apply.clone();
+        let on_change = Callback::from(move |_: Event| {
+            let mut change = (*input).clone();
+            set(&mut change, !enabled);
+            apply.emit(change);
+        });
+
+        html! {
+            <label class="mixer-input-option">
+                <input type="checkbox" checked={enabled} onchange={on_change} />
+                { label }
+            </label>
+        }
+    };
+
+    let on_calibrate = {
+        let engine = engine.clone();
+        let input = input.clone();
+        let calibrating = calibrating.clone();
+        let calibration_message = calibration_message.clone();
+        Callback::from(move |_: MouseEvent| {
+            let engine = engine.clone();
+            let input = input.clone();
+            let calibrating = calibrating.clone();
+            let calibration_message = calibration_message.clone();
+            calibrating.set(true);
+            calibration_message.set(None);
+            wasm_bindgen_futures::spawn_local(async move {
+                match measure_round_trip(&engine).await {
+                    Ok(measurement) => {
+                        settings::update_settings(|s| {
+                            s.input.set_latency_ms(measurement.device_id.as_deref(), measurement.latency_ms)
+                        });
+                        input.set(settings::load_settings().input);
+                        calibration_message.set(Some(format!("측정 완료: {:.0} ms", measurement.latency_ms)));
+                    }
+                    Err(err) => calibration_message.set(Some(err)),
+                }
+                calibrating.set(false);
+            });
+        })
+    };
+    let latency_ms = engine.round_trip_latency() * 1000.0;
+
+    // 저장한 장치가 지금 연결되어 있지 않으면 목록에 따로 표시 (다시 연결되면 자동으로 돌아감)
+    let saved_missing = input
+        .device_id
+        .as_ref()
+        .filter(|id| !devices.iter().any(|device| &device.id == *id))
+        .is_some();
+
+    let rates = match ((*info).as_ref().and_then(|info| info.sample_rate), engine.sample_rate()) {
+        (Some(input_rate), Some(engine_rate)) => {
+            format!("입력 {} · 엔진 {}", format_rate(input_rate), format_rate(engine_rate as f64))
+        }
+        (None, Some(engine_rate)) => format!("엔진 {}", format_rate(engine_rate as f64)),
+        (Some(input_rate), None) => format!("입력 {}", format_rate(input_rate)),
+        (None, None) => String::new(),
+    };
+
+    html! {
+        // 마이크가 열려 있으면 바로, 아니면 다음에 열 때 적용
+        <div class="mixer-input">
+            <span class="mixer-channel-label">{ "입력 장치" }</span>
+            <select class="mixer-input-device" onchange={on_device_change}>
+                <option value="" selected={input.device_id.is_none()}>{ "브라우저 기본 장치" }</option>
+                { for devices.iter().map(|device| html! {
+                    <option value={device.id.clone()} selected={input.device_id.as_ref() == Some(&device.id)}>
+                        { &device.label }
+                    </option>
+                }) }
+                if saved_missing {
+                    <option value={input.device_id.clone().unwrap_or_default()} selected=true>
+                        { "저장한 장치 (연결 안 됨)" }
+                    </option>
+                }
+            </select>
+            <div class="mixer-input-options" title="음성 통화용 처리라 피치 분석에는 끄는 것이 좋습니다">
+                { option_toggle("에코 제거", input.echo_cancellation, |s, on| s.echo_cancellation = on) }
+                { option_toggle("잡음 억제", input.noise_suppression, |s, on| s.noise_suppression = on) }
+                { option_toggle("자동 게인", input.auto_gain_control, |s, on| s.auto_gain_control = on) }
+            </div>
+            <div class="mixer-input-info">
+                {
+                    match &*info {
+                        Some(info) => html! { <span class="mixer-input-label">{ format!("🎤 {}", info.label) }</span> },
+                        None => html! { <span class="mixer-input-label">{ "마이크를 켜면 입력 정보가 표시됩니다" }</span> },
+                    }
+                }
+                if !rates.is_empty() {
+                    <span class="mixer-value">{ rates }</span>
+                }
+            </div>
+            <div class="mixer-input-latency">
+                <span>
+                    { if latency_ms > 0.0 { format!("지연 보정 {:.0} ms", latency_ms) } else { "지연 보정 안 됨".to_string() } }
+                </span>
+                <button
+                    class="mixer-input-calibrate"
+                    onclick={on_calibrate}
+                    disabled={*calibrating}
+                    title="스피커로 클릭을 울려 마이크로 돌아오기까지 걸리는 시간을 잽니다"
+                >
+                    { if *calibrating { "측정 중…" } else { "측정" } }
+                </button>
+            </div>
+            if let Some(message) = &*calibration_message {
+                <div class="mixer-input-info">{ message }</div>
+            }
+            if let Some(error) = &*error {
+                <div class="mixer-input-error">{ error }</div>
+            }
+        </div>
+    }
+}
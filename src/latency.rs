@@ -0,0 +1,201 @@
+use web_sys::AnalyserNode;
+
+use crate::audio_engine::AudioEngine;
+use crate::tools::metronome::schedule_click_on;
+use crate::wait_ms;
+
+// 조건부 로그 매크로 정의
+#[cfg(debug_assertions)]
+macro_rules! console_log {
+    ($($arg:tt)*) => {
+        web_sys::console::log_1(&format!($($arg)*).into());
+    };
+}
+
+#[cfg(not(debug_assertions))]
+macro_rules! console_log {
+    ($($arg:tt)*) => {};
+}
+
+// 왕복 지연 측정 - 스피커로 클릭을 울리고 마이크로 되돌아오기까지 걸린 시간
+// 클릭은 AudioContext 시간으로 예약하므로, 분석기 버퍼에서 클릭이 시작된 샘플의 시각과 예약 시각의 차이가 지연
+//
+//   [예약 시각] -> 출력 지연 -> 스피커 -> 마이크 -> 입력 지연 -> [분석기 버퍼]
+
+const CALIBRATION_CLICKS: usize = 8;
+const CLICK_SPACING_SECS: f64 = 0.5;
+const CAPTURE_DELAY_SECS: f64 = 0.35; // 클릭 후 이만큼 지나서 버퍼를 읽음 (측정 가능한 최대 지연)
+const ANALYSER_FFT_SIZE: u32 = 32768; // 48kHz에서 약 0.68초 (앞쪽은 잡음 수준 추정용)
+const MIN_CLICK_LEVEL: f32 = 0.02;    // 이보다 작으면 클릭이 들리지 않은 것으로 봄
+const MIN_DETECTIONS: usize = 5;      // 결과로 인정하는 최소 검출 수
+const MAX_DEVIATION_SECS: f64 = 0.005; // 중앙값에서 이보다 벗어난 검출은 버림
+
+// 측정 결과
+pub struct LatencyMeasurement {
+    pub device_id: Option<String>, // 측정한 입력 장치
+    pub latency_ms: f64,
+}
+
+// 마이크를 열어 왕복 지연을 측정 (끝나면 마이크 반납)
+pub async fn measure_round_trip(engine: &AudioEngine) -> Result<LatencyMeasurement, String> {
+    let mic = engine
+        .acquire_mic()
+        .await
+        .map_err(|err| format!("마이크를 열 수 없습니다: {:?}", err))?;
+    let device_id = engine.input_info().and_then(|info| info.device_id);
+
+    let result = match engine.context().and_then(|ctx| ctx.create_analyser()) {
+        Ok(analyser) => {
+            analyser.set_fft_size(ANALYSER_FFT_SIZE);
+            let result = match mic.source.connect_with_audio_node(&analyser) {
+                Ok(_) => detect_clicks(engine, &analyser).await,
+                Err(err) => Err(format!("마이크 연결 실패: {:?}", err)),
+            };
+            let _ = mic.source.disconnect_with_audio_node(&analyser);
+            result
+        }
+        Err(err) => Err(format!("분석기 생성 실패: {:?}", err)),
+    };
+    engine.release_mic();
+
+    let latency = result?;
+    console_log!("왕복 지연 측정: {:.1}ms", latency * 1000.0);
+    Ok(LatencyMeasurement { device_id, latency_ms: latency * 1000.0 })
+}
+
+// 클릭을 하나씩 울리고 되돌아온 시각을 모아 중앙값 근처의 평균을 냄
+async fn detect_clicks(engine: &AudioEngine, analyser: &AnalyserNode) -> Result<f64, String> {
+    let ctx = engine.context().map_err(|err| format!("오디오 컨텍스트 없음: {:?}", err))?;
+    let sample_rate = ctx.sample_rate() as f64;
+    let start = ctx.current_time() + 0.3;
+    let mut buffer = vec![0.0f32; analyser.fft_size() as usize];
+    let mut latencies = Vec::new();
+    // 메트로놈 채널/마스터 볼륨이나 음소거와 상관없이 들리도록 출력 장치로 바로 보냄
+    let destination = ctx.destination();
+
+    for i in 0..CALIBRATION_CLICKS {
+        let when = start + i as f64 * CLICK_SPACING_SECS;
+        schedule_click_on(&ctx, &destination, when, true).map_err(|err| format!("클릭 재생 실패: {:?}", err))?;
+
+        wait_ms(((when + CAPTURE_DELAY_SECS - ctx.current_time()) * 1000.0).max(0.0) as i32).await;
+        analyser.get_float_time_domain_data(&mut buffer[..]);
+        // 분석기 버퍼의 마지막 샘플이 현재 시각
+        let buffer_start = ctx.current_time() - buffer.len() as f64 / sample_rate;
+        if let Some(latency) = find_click(&buffer, sample_rate, buffer_start, when) {
+            latencies.push(latency);
+        }
+    }
+
+    if latencies.len() < MIN_DETECTIONS {
+        return Err("클릭이 마이크에 잡히지 않았습니다. 스피커 볼륨을 올리거나 이어폰을 마이크 가까이 대 주세요.".to_string());
+    }
+
+    latencies.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+    let median = latencies[latencies.len() / 2];
+    let consistent: Vec<f64> = latencies.into_iter().filter(|latency| (latency - median).abs() <= MAX_DEVIATION_SECS).collect();
+    if consistent.len() < MIN_DETECTIONS {
+        return Err("측정값이 고르지 않습니다. 조용한 곳에서 다시 측정해 주세요.".to_string());
+    }
+    Ok(consistent.iter().sum::<f64>() / consistent.len() as f64)
+}
+
+// 예약 시각 이후 처음으로 클릭 크기에 도달한 샘플까지의 시간 (초)
+fn find_click(buffer: &[f32], sample_rate: f64, buffer_start: f64, when: f64) -> Option<f64> {
+    let first = ((when - buffer_start) * sample_rate).max(0.0) as usize;
+    if first >= buffer.len() {
+        return None;
+    }
+
+    // 클릭 전 구간으로 잡음 수준 추정
+    let noise = buffer[..first].iter().fold(0.0f32, |peak, s| peak.max(s.abs()));
+    let peak = buffer[first..].iter().fold(0.0f32, |peak, s| peak.max(s.abs()));
+    if peak < MIN_CLICK_LEVEL || peak < noise * 3.0 {
+        return None;
+    }
+
+    let threshold = (peak * 0.5).max(noise * 2.0);
+    let index = buffer[first..].iter().position(|s| s.abs() >= threshold)?;
+    Some(buffer_start + (first + index) as f64 / sample_rate - when)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_RATE: f64 = 48000.0;
+    const BUFFER_START: f64 = 10.0;
+    const WHEN: f64 = 10.1; // 클릭 예약 시각 (버퍼 시작 0.1초 뒤)
+
+    // 0.5초 버퍼에서 예약 시각 + latency 위치에 감쇠하는 클릭을 넣음
+    fn buffer_with_click(latency: f64, amplitude: f32) -> Vec<f32> {
+        let mut buffer = vec![0.0f32; (SAMPLE_RATE * 0.5) as usize];
+        let onset = ((WHEN + latency - BUFFER_START) * SAMPLE_RATE).round() as usize;
+        for (i, sample) in buffer[onset..onset + 480].iter_mut().enumerate() {
+            let sign = if i % 2 == 0 { 1.0 } else { -1.0 };
+            *sample = sign * amplitude * (-(i as f32) / 60.0).exp();
+        }
+        buffer
+    }
+
+    // 재현 가능한 잡음 (선형 합동 생성기, ±amplitude)
+    fn add_noise(buffer: &mut [f32], amplitude: f32) {
+        let mut state: u32 = 12345;
+        for sample in buffer.iter_mut() {
+            state = state.wrapping_mul(1103515245).wrapping_add(12345);
+            *sample += ((state >> 16) as f32 / 32768.0 - 1.0) * amplitude;
+        }
+    }
+
+    fn assert_latency(found: Option<f64>, expected: f64) {
+        let found = found.expect("클릭을 찾지 못함");
+        assert!((found - expected).abs() <= 1.0 / SAMPLE_RATE, "{} != {}", found, expected);
+    }
+
+    #[test]
+    fn finds_click_at_known_offset() {
+        for latency in [0.0, 0.012, 0.0875, 0.3] {
+            let buffer = buffer_with_click(latency, 0.5);
+            assert_latency(find_click(&buffer, SAMPLE_RATE, BUFFER_START, WHEN), latency);
+        }
+    }
+
+    #[test]
+    fn silence_has_no_click() {
+        let buffer = vec![0.0f32; (SAMPLE_RATE * 0.5) as usize];
+        assert_eq!(find_click(&buffer, SAMPLE_RATE, BUFFER_START, WHEN), None);
+
+        // 예약 시각이 버퍼 밖이면 찾지 않음
+        let buffer = buffer_with_click(0.01, 0.5);
+        assert_eq!(find_click(&buffer, SAMPLE_RATE, BUFFER_START, BUFFER_START + 1.0), None);
+    }
+
+    #[test]
+    fn finds_click_over_background_noise() {
+        let mut buffer = buffer_with_click(0.025, 0.5);
+        add_noise(&mut buffer, 0.02);
+        assert_latency(find_click(&buffer, SAMPLE_RATE, BUFFER_START, WHEN), 0.025);
+
+        // 클릭 전 잡음이 클릭 크기의 1/3을 넘으면 믿을 수 없는 측정으로 버림
+        let mut buffer = buffer_with_click(0.025, 0.5);
+        add_noise(&mut buffer, 0.3);
+        assert_eq!(find_click(&buffer, SAMPLE_RATE, BUFFER_START, WHEN), None);
+    }
+
+    #[test]
+    fn quiet_clicks_below_the_minimum_level_are_ignored() {
+        let buffer = buffer_with_click(0.01, MIN_CLICK_LEVEL * 0.9);
+        assert_eq!(find_click(&buffer, SAMPLE_RATE, BUFFER_START, WHEN), None);
+
+        let buffer = buffer_with_click(0.01, MIN_CLICK_LEVEL);
+        assert_latency(find_click(&buffer, SAMPLE_RATE, BUFFER_START, WHEN), 0.01);
+    }
+
+    #[test]
+    fn onset_is_the_first_sample_at_half_the_peak() {
+        // 클릭이 서서히 커지면 최대값의 절반에 처음 도달한 샘플이 도착 시각
+        let mut buffer = vec![0.0f32; (SAMPLE_RATE * 0.5) as usize];
+        let onset = ((WHEN + 0.01 - BUFFER_START) * SAMPLE_RATE).round() as usize;
+        buffer[onset..onset + 5].copy_from_slice(&[0.1, 0.3, 0.49, 0.5, 1.0]);
+        assert_latency(find_click(&buffer, SAMPLE_RATE, BUFFER_START, WHEN), 0.01 + 3.0 / SAMPLE_RATE);
+    }
+}
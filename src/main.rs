@@ -1,13 +1,10 @@
 use crate::dashboard::{Dashboard, DashboardItem, DashboardLayout};
 use crate::routes::{switch, Route};
-use gloo::events::EventListener;
-use js_sys::{Object};
 use std::collections::VecDeque;
 use wasm_bindgen::prelude::*;
 use wasm_bindgen_futures::JsFuture;
 use web_sys::{
     AnalyserNode, AudioContext, MediaStream,
-    MediaStreamConstraints, CustomEvent, CustomEventInit,
     HtmlAnchorElement,
 };
 use yew::prelude::*;
@@ -57,23 +54,53 @@ mod tools {
     pub mod metronome;
     pub mod scale_generator;
     pub mod piano;
+    pub mod target_melody;
+    pub mod piano_sampler;
+    pub mod quiz;
+    pub mod interval_trainer;
+    pub mod chord_trainer;
+    pub mod pitch_match;
+    pub mod practice_stats;
+    pub mod take_library;
+    pub mod overdub;
 }
 
 // tools 모듈 컴포넌트 import
-use crate::tools::pitch_plot::PitchPlot;
+use crate::tools::pitch_plot::{dispatch_region_selected, PitchPlot};
 use crate::tools::amplitude_visualizer::AmplitudeVisualizer;
 use crate::tools::metronome::Metronome;
 use crate::tools::scale_generator::ScaleGenerator;
 use crate::tools::piano::Piano;
-
+use crate::tools::target_melody::{TargetMelody, TargetMelodyPanel};
+use crate::tools::metronome::{schedule_count_in, CountIn};
+use crate::tools::take_library::{BeatGrid, Take, TakeLibraryPanel, TakeSummary};
+use crate::tools::overdub::{next_track_color, OverdubPanel, OverdubTrack, PitchTrace, TrackKey, TrackMix, TrackRow, CURRENT_TRACK_COLOR};
+use crate::audio_engine::{engine_from_scope, ramp_gain, AudioEngine, Channel, MicSource, RecordSource};
+use crate::session_recorder::StemRecorders;
+use crate::audio_export::{ExportFormat, ExportOptions};
+use crate::encoders::{EncodeRequest, EncodeResponse, EncoderWorker};
+use crate::event_bus::{emit, subscribe, AppEvent, Subscription};
+use crate::background_session::LiveStatus;
+use crate::monitor_effects::MonitorChain;
+use gloo::worker::{Spawnable, WorkerBridge};
+
+mod audio_engine;
+mod audio_export;
+mod background_session;
 mod dashboard;
+mod encoders;
+mod event_bus;
+mod input_devices;
+mod latency;
+mod mixer;
+mod monitor_effects;
 mod routes;
-
-#[wasm_bindgen]
-extern "C" {
-    #[wasm_bindgen(js_namespace = navigator, js_name = mediaDevices)]
-    pub static MEDIA_DEVICES: web_sys::MediaDevices;
-}
+mod session_recorder;
+mod pitch_input;
+mod practice_log;
+mod settings;
+mod share_link;
+mod take_store;
 
 // 🎶 주어진 주파수를 가장 가까운 음으로 변환하는 함수
 fn frequency_to_note(freq: f64) -> &'static str {
@@ -247,9 +274,11 @@ fn analyze_multiple_frequencies(
 
 // 🎤 실시간 피치 분석기
 pub struct PitchAnalyzer {
+    engine: AudioEngine, // 공유 오디오 엔진 (컨텍스트, 마이크, 출력 채널)
     audio_ctx: Option<AudioContext>,
     analyser: Option<AnalyserNode>,
     _stream: Option<MediaStream>,
+    mic_source: Option<web_sys::GainNode>, // 엔진의 공유 마이크 입력 (분석기/모니터 연결용)
     pitch: String,
     prev_freqs: VecDeque<f64>,
     // 여러 주파수를 저장하는 이력 - (timestamp, [(frequency, amplitude)])
@@ -262,12 +291,22 @@ pub struct PitchAnalyzer {
     mic_active: bool,                         // 🎤 마이크 활성화 상태
     monitor_active: bool,                     // 🔊 마이크 모니터링 활성화 상태
     speaker_node: Option<web_sys::GainNode>,  // 스피커 출력용 노드
+    monitor_chain: Option<MonitorChain>,      // 🎛️ 모니터 이펙트 체인
+    monitor_filter: Option<web_sys::BiquadFilterNode>, // 모니터 피드백 방지 필터 (체인 출력에서 분기)
     
     // 오디오 녹음 관련 필드
     is_recording: bool,                       // 녹음 중인지 여부
     is_playing: bool,                         // 재생 중인지 여부
     recorder: Option<web_sys::MediaRecorder>, // 미디어 레코더
     recorded_chunks: Vec<web_sys::Blob>,      // 녹음된 오디오 청크
+    stem_recorders: Option<StemRecorders>,    // 스템 저장을 켠 경우 소리별 레코더
+    count_in: Option<CountIn>,                // 녹음 전 카운트인 (다운비트에 녹음 시작)
+    beat_grid: Option<BeatGrid>,              // 카운트인으로 시작한 녹음의 마디/박 격자
+    take_latency: f64,                        // 현재 테이크 녹음 파일의 정렬 지연 (초, 카운트인 앞부분 + 마이크를 녹음했으면 왕복 지연)
+    record_delay: f64,                        // 녹음 중 피치 기록을 당기는 시간 (초, 마이크를 녹음했으면 왕복 지연)
+    overdub_tracks: Vec<OverdubTrack>,        // 함께 재생하는 이전 테이크 (겹쳐 녹음)
+    current_track_mix: TrackMix,              // 현재 테이크의 트랙 볼륨/뮤트/솔로
+    current_track_gain: Option<web_sys::GainNode>, // 현재 테이크 오디오 요소의 트랙 게인
     recorded_audio_url: Option<String>,       // 녹음된 오디오 URL
     audio_element: Option<web_sys::HtmlAudioElement>, // 오디오 재생 요소
     playback_time: f64,                       // 재생 위치 (초)
@@ -294,57 +333,500 @@ pub struct PitchAnalyzer {
     // 진폭 히스토리를 (시간, 진폭 데이터 배열) 형태로 저장
     amplitude_history: VecDeque<(f64, Vec<f32>)>,  // 진폭 히스토리 (시간, 진폭 데이터)
     current_rms: f32,                         // 현재 RMS 레벨
+    
+    // 🎯 채점용 목표 멜로디
+    target_melody: Option<TargetMelody>,
+
+    // 녹음 내보내기 인코더 (첫 내보내기 때 워커 생성)
+    encoder: Option<WorkerBridge<EncoderWorker>>,
+    pending_export: Option<(String, ExportFormat)>, // 인코딩 중인 파일 (파일명, 포맷)
+    export_watchdog: Option<gloo::timers::callback::Timeout>, // 응답이 끊긴 내보내기 감지
+
+    // 녹음 테이크 목록 (take_library에 보관, TakesChanged 이벤트로 갱신)
+    takes: Vec<TakeSummary>,
+    active_take_id: Option<u64>,
+    loaded_take_id: Option<u64>, // 현재 차트와 오디오 요소에 불러온 테이크
+
+    // 피치 차트에서 Shift+드래그로 선택한 구간 (RegionSelected 이벤트로 갱신)
+    selected_region: Option<(f64, f64)>,
+    preview_end: Option<f64>, // 구간 미리듣기 중이면 멈출 시간
+    loop_enabled: bool,       // 선택 구간(A-B) 반복 재생
+    playback_rate: f64,       // 재생 속도 (0.5 ~ 1.5, 음정 유지)
+
+    _event_subscription: Subscription, // 앱 이벤트 구독 (컴포넌트와 함께 해제)
+    last_live_status: Option<LiveStatus>, // 마지막으로 알린 마이크/녹음/현재 음 상태
 }
 
 // PitchAnalyzer 일반 메서드 구현
 impl PitchAnalyzer {
     // 최대 녹음 시간 상수 (10분 = 600초)
     const MAX_RECORDING_TIME: u32 = 600;
+
+    // 겹쳐 들을 수 있는 반주 트랙 수 (트랙 색 수만큼)
+    const MAX_OVERDUB_TRACKS: usize = crate::tools::overdub::TRACK_COLORS.len();
+
+    // 내보내기 중 이 시간(밀리초) 동안 디코딩/인코더 응답이 없으면 실패로 처리
+    const EXPORT_STALL_TIMEOUT_MS: u32 = 30_000;
     
     // 재생 시간 UI 업데이트 메서드
     fn update_playback_time_ui(&self, time: f64) {
+        emit(AppEvent::PlaybackTimeUpdate {
+            current_time: time,
+            duration: self.last_recording_time,
+            is_recording: self.is_recording,
+        });
+    }
+    
+    // 내보내기 진행률 알림 (0.0 ~ 1.0, 끝나면 None)
+    fn notify_export_progress(progress: Option<f64>) {
+        emit(AppEvent::ExportProgress { progress });
+    }
+
+    // 내보내기 감시 타이머 재설정 - 응답이 올 때마다 다시 시작
+    // gloo 워커 브리지는 오류를 알려주지 않으므로 워커가 로드/실행에 실패하면 응답이 끊긴 것으로 판단
+    fn arm_export_watchdog(&mut self, ctx: &Context<Self>) {
+        let link = ctx.link().clone();
+        self.export_watchdog = Some(gloo::timers::callback::Timeout::new(Self::EXPORT_STALL_TIMEOUT_MS, move || {
+            link.send_message(Msg::ExportTimedOut);
+        }));
+    }
+
+    // 내보내기 실패 처리 - 진행 중 상태를 지우고 컨트롤 바에 오류 표시
+    fn fail_export(&mut self, message: String) {
+        console_error!("{}", message);
+        self.pending_export = None;
+        self.export_watchdog = None;
+        Self::notify_export_progress(None);
+        emit(AppEvent::ExportFailed { message });
+    }
+
+    // 재생용 오디오 요소를 만들어 숨겨진 컨테이너에 추가
+    fn attach_audio_element(&mut self, ctx: &Context<Self>, audio_url: &str) {
         if let Some(window) = web_sys::window() {
             if let Some(document) = window.document() {
-                // 재생 시간 업데이트 이벤트 발행
-                let mut detail = Object::new();
-                // currentTime 속성 설정
-                let _ = js_sys::Reflect::set(
-                    &detail,
-                    &JsValue::from_str("currentTime"),
-                    &JsValue::from_f64(time),
-                );
-                // duration 속성 설정
-                let _ = js_sys::Reflect::set(
-                    &detail,
-                    &JsValue::from_str("duration"),
-                    &JsValue::from_f64(self.last_recording_time),
-                );
-                // 녹음 중인지 여부 설정
-                let _ = js_sys::Reflect::set(
-                    &detail, 
-                    &JsValue::from_str("isRecording"),
-                    &JsValue::from_bool(self.is_recording),
-                );
-                
-                let event = CustomEvent::new_with_event_init_dict(
-                    "playbackTimeUpdate",
-                    CustomEventInit::new()
-                        .bubbles(true)
-                        .detail(&detail),
-                ).unwrap();
-                
-                let _ = document.dispatch_event(&event);
+                if let Ok(element) = document.create_element("audio") {
+                    let audio_element: web_sys::HtmlAudioElement = element
+                        .dyn_into()
+                        .expect("Failed to create audio element");
+                    
+                    audio_element.set_src(audio_url);
+                    audio_element.set_controls(false);
+                    Self::apply_playback_rate(&audio_element, self.playback_rate);
+
+                    // 재생 소리는 트랙 게인을 거쳐 엔진의 녹음 재생 채널로
+                    match self.engine.connect_media_element_with_gain(Channel::Playback, &audio_element) {
+                        Ok(gain) => {
+                            self.current_track_gain = Some(gain);
+                            self.apply_track_gains();
+                        }
+                        Err(err) => {
+                            console_error!("녹음 재생 채널 연결 실패: {:?}", err);
+                        }
+                    }
+                    
+                    // 재생 종료 이벤트 리스너 추가
+                    let link = ctx.link().clone();
+                    let onended = Closure::wrap(Box::new(move |_: web_sys::Event| {
+                        link.send_message(Msg::PlaybackEnded);
+                    }) as Box<dyn FnMut(web_sys::Event)>);
+                    
+                    // 로드 완료 이벤트 리스너 추가 - 실제 오디오 파일 길이 확인
+                    let link_load = ctx.link().clone();
+                    let last_recording_time = self.last_recording_time;
+                    let onloadedmetadata = Closure::wrap(Box::new(move |e: web_sys::Event| {
+                        if let Some(target) = e.target() {
+                            if let Ok(audio) = target.dyn_into::<web_sys::HtmlAudioElement>() {
+                                let actual_duration = audio.duration();
+                                
+                                // 로그로 실제 오디오 길이와 기록된 길이 비교
+                                console_log!("오디오 메타데이터 로드됨: 실제 길이 = {:.2}초, 기록된 길이 = {:.2}초", 
+                                    actual_duration, last_recording_time);
+                                
+                                // 실제 오디오 길이로 last_recording_time 업데이트
+                                link_load.send_message(Msg::UpdateRecordingDuration(actual_duration));
+                            }
+                        }
+                    }) as Box<dyn FnMut(web_sys::Event)>);
+                    
+                    audio_element.set_onended(Some(onended.as_ref().unchecked_ref()));
+                    audio_element.set_onloadedmetadata(Some(onloadedmetadata.as_ref().unchecked_ref()));
+                    onended.forget();
+                    onloadedmetadata.forget();
+                    
+                    // 오디오 요소에 고유 ID 부여 (추적 및 선택 가능하도록)
+                    audio_element.set_id("pitch-analyzer-audio");
+                    
+                    // 오디오 요소를 DOM에 추가 (숨겨진 컨테이너에)
+                    if let Some(document) = web_sys::window().unwrap().document() {
+                        // 오디오 컨테이너가 있는지 확인하고 없으면 생성
+                        let container_id = "pitch-analyzer-audio-container";
+                        if document.get_element_by_id(container_id).is_none() {
+                            if let Ok(container) = document.create_element("div") {
+                                // 컨테이너 설정
+                                container.set_id(container_id);
+                                // 화면에 표시되지 않도록 스타일 설정
+                                if container.set_attribute("style", "display: none; position: absolute; width: 0; height: 0;").is_ok() {
+                                    // 문서에 추가
+                                    if let Some(body) = document.body() {
+                                        let _ = body.append_child(&container);
+                                        console_log!("오디오 컨테이너 DOM에 추가됨");
+                                    }
+                                }
+                            }
+                        }
+                        
+                        // 기존 오디오 요소가 있으면 제거
+                        if let Some(old_audio) = document.get_element_by_id("pitch-analyzer-audio") {
+                            if let Some(parent) = old_audio.parent_node() {
+                                let _ = parent.remove_child(&old_audio);
+                            }
+                        }
+                        
+                        // 새 오디오 요소를 컨테이너에 추가
+                        if let Some(container) = document.get_element_by_id(container_id) {
+                            let _ = container.append_child(&audio_element);
+                            console_log!("오디오 요소 DOM에 추가됨");
+                        }
+                    }
+                    
+                    self.audio_element = Some(audio_element);
+                }
             }
         }
     }
-    
+
+    // 오디오 요소의 이벤트 핸들러 제거 (object URL은 테이크가 소유하므로 해제하지 않음)
+    fn detach_audio_element(&self) {
+        if let Some(old_audio) = &self.audio_element {
+            old_audio.set_onloadeddata(None);
+            old_audio.set_onloadedmetadata(None);
+            old_audio.set_onended(None);
+        }
+    }
+
+    // 선택한 테이크의 오디오와 피치/진폭 이력을 불러옴
+    fn load_take(&mut self, ctx: &Context<Self>, take: Take) {
+        if self.is_playing {
+            Component::update(self, ctx, Msg::PausePlayback);
+        }
+        self.detach_audio_element();
+
+        // 반주 트랙으로 겹쳐 둔 테이크를 고르면 트랙 목록에서 뺌
+        if let Some(index) = self.overdub_tracks.iter().position(|track| track.take_id == take.id) {
+            self.overdub_tracks.remove(index).close();
+            self.apply_track_gains();
+        }
+
+        self.history = take.history;
+        self.amplitude_history = take.amplitude_history;
+        self.beat_grid = take.beat_grid;
+        self.take_latency = take.latency;
+        self.amplitude_data = None;
+        self.current_freq = 0.0;
+        self.created_at_time = take.created_at;
+        self.last_recording_time = take.duration;
+        self.playback_time = 0.0;
+        self.is_frozen = true;
+        self.recorded_audio_url = Some(take.audio_url.clone());
+        self.attach_audio_element(ctx, &take.audio_url);
+        self.loaded_take_id = Some(take.id);
+        self.selected_region = None;
+        dispatch_region_selected(None);
+
+        self.update_playback_time_ui(0.0);
+        emit(AppEvent::PlaybackReset);
+        console_log!("테이크 불러옴: {} ({:.2}초)", take.name, take.duration);
+    }
+
+    // 테이크가 모두 삭제되면 재생할 녹음 없음 상태로
+    fn clear_loaded_take(&mut self, ctx: &Context<Self>) {
+        if self.is_playing {
+            Component::update(self, ctx, Msg::PausePlayback);
+        }
+        self.detach_audio_element();
+        if let Some(document) = web_sys::window().and_then(|w| w.document()) {
+            if let Some(audio_element) = document.get_element_by_id("pitch-analyzer-audio") {
+                if let Some(parent) = audio_element.parent_node() {
+                    let _ = parent.remove_child(&audio_element);
+                }
+            }
+        }
+
+        self.history.clear();
+        self.amplitude_history.clear();
+        self.beat_grid = None;
+        self.take_latency = 0.0;
+        self.amplitude_data = None;
+        self.current_freq = 0.0;
+        self.recorded_audio_url = None;
+        self.audio_element = None;
+        self.last_recording_time = 0.0;
+        self.playback_time = 0.0;
+        self.is_frozen = false;
+        self.loaded_take_id = None;
+        self.selected_region = None;
+        dispatch_region_selected(None);
+        self.update_playback_time_ui(0.0);
+    }
+
     // 녹음된 오디오가 있는지 확인하는 헬퍼 메서드
     fn has_recorded_audio(&self) -> bool {
         self.recorded_audio_url.is_some() && self.audio_element.is_some()
     }
+
+    // 모니터 경로 연결
+    // 엔진의 마이크 입력에서 분기하므로 입력 장치가 바뀌어도 계속 들림
+    // 마이크 -> 이펙트 체인 -> 로우패스 -> 딜레이 -> 게인 -> 모니터 채널
+    fn connect_monitor(&mut self) -> Result<(), JsValue> {
+        let (Some(audio_ctx), Some(monitor_source)) = (self.audio_ctx.clone(), self.mic_source.clone()) else {
+            return Err(JsValue::from_str("마이크 없음"));
+        };
+        let monitor = crate::settings::load_settings().monitor;
+
+        // 1. 이펙트 체인 (EQ/컴프레서/리버브/딜레이)
+        let chain = MonitorChain::new(&audio_ctx, &monitor.effects)?;
+        monitor_source.connect_with_audio_node(chain.input())?;
+
+        // 2. 로우패스 필터 (고주파 제거로 피드백 방지)
+        let filter_node = audio_ctx.create_biquad_filter()?;
+        filter_node.set_type(web_sys::BiquadFilterType::Lowpass);
+        filter_node.frequency().set_value(1500.0); // 1.5kHz 이상 감쇠
+        filter_node.q().set_value(1.0);
+        chain.output().connect_with_audio_node(&filter_node)?;
+
+        // 3. 딜레이 노드 (50ms 지연 추가)
+        let delay_node = audio_ctx.create_delay()?;
+        delay_node.delay_time().set_value(0.05);
+        filter_node.connect_with_audio_node(&delay_node)?;
+
+        // 4. 게인 노드 (피드백 방지를 위해 낮게, 저장된 스피커 게인 - 기본 2%)
+        let gain_node = audio_ctx.create_gain()?;
+        gain_node.gain().set_value(crate::settings::load_settings().analyzer.speaker_gain);
+        delay_node.connect_with_audio_node(&gain_node)?;
+        let monitor_output = self.engine.channel_input(Channel::Monitor)?;
+        gain_node.connect_with_audio_node(&monitor_output)?;
+
+        // 이펙트 포함 녹음이면 피드백 방지 단계 전의 체인 출력을 녹음 버스로
+        let record_input = self.engine.monitor_record_input()?;
+        chain.output().connect_with_audio_node(&record_input)?;
+        self.engine.set_record_monitor_effects(monitor.record_wet)?;
+
+        // 나중에 연결 해제용
+        self.monitor_filter = Some(filter_node);
+        self.monitor_chain = Some(chain);
+        self.speaker_node = Some(gain_node);
+        Ok(())
+    }
+
+    // 모니터 경로 해제 (녹음은 다시 생소리로)
+    fn disconnect_monitor(&mut self) {
+        if let Some(chain) = self.monitor_chain.take() {
+            if let Some(source) = &self.mic_source {
+                let _ = source.disconnect_with_audio_node(chain.input());
+            }
+            chain.disconnect();
+        }
+        if let Some(filter_node) = self.monitor_filter.take() {
+            let _ = filter_node.disconnect();
+        }
+        // 웹오디오 API는 disconnect() 메서드로 모든 연결을 해제
+        if let Some(speaker_node) = self.speaker_node.take() {
+            let _ = speaker_node.disconnect();
+        }
+        let _ = self.engine.set_record_monitor_effects(false);
+    }
+
+    // 분석기/모니터를 마이크에서 떼고 엔진에 마이크 반납
+    fn release_microphone(&mut self) {
+        self.disconnect_monitor();
+        if let Some(source) = self.mic_source.take() {
+            if let Some(analyser) = &self.analyser {
+                let _ = source.disconnect_with_audio_node(analyser);
+            }
+            self.engine.release_mic();
+        }
+    }
+
+    // 반복 재생 중인 A-B 구간
+    fn loop_region(&self) -> Option<(f64, f64)> {
+        self.selected_region.filter(|_| self.loop_enabled)
+    }
+
+    // 재생 속도 적용 (음정은 유지)
+    // 커서는 오디오 요소의 currentTime을 따라가므로 속도를 바꿔도 녹음 시간과 맞음
+    fn apply_playback_rate(audio_element: &web_sys::HtmlAudioElement, rate: f64) {
+        for key in ["preservesPitch", "webkitPreservesPitch", "mozPreservesPitch"] {
+            let _ = js_sys::Reflect::set(audio_element, &JsValue::from_str(key), &JsValue::TRUE);
+        }
+        audio_element.set_default_playback_rate(rate);
+        audio_element.set_playback_rate(rate);
+    }
+
+    // 오디오 요소의 재생 위치를 테이크 시간축으로 (피치 기록은 왕복 지연만큼 당겨 저장됨)
+    fn audio_position(&self, audio_element: &web_sys::HtmlAudioElement) -> f64 {
+        (audio_element.current_time() - self.take_latency).max(0.0)
+    }
+
+    // 테이크 시간축의 time 위치로 오디오 요소를 이동
+    fn seek_audio(&self, audio_element: &web_sys::HtmlAudioElement, time: f64) {
+        audio_element.set_current_time((time + self.take_latency).max(0.0));
+    }
+
+    // 반복 구간의 시작(A)으로 되돌림
+    fn jump_to_loop_start(&mut self, start: f64) {
+        if let Some(audio_element) = &self.audio_element {
+            self.seek_audio(audio_element, start);
+        }
+        if self.is_playing {
+            self.play_overdub_tracks(start);
+        }
+        self.playback_time = start;
+        self.update_playback_time_ui(start);
+    }
+
+    // 솔로/뮤트를 반영해 현재 테이크와 반주 트랙의 게인 적용
+    fn apply_track_gains(&self) {
+        let any_solo = self.current_track_mix.solo || self.overdub_tracks.iter().any(|track| track.mix.solo);
+        if let Some(gain) = &self.current_track_gain {
+            ramp_gain(gain, self.current_track_mix.effective_gain(any_solo));
+        }
+        for track in &self.overdub_tracks {
+            track.apply_gain(any_solo);
+        }
+    }
+
+    fn track_mix_mut(&mut self, key: TrackKey) -> Option<&mut TrackMix> {
+        match key {
+            TrackKey::Current => Some(&mut self.current_track_mix),
+            TrackKey::Overdub(id) => self.overdub_tracks.iter_mut().find(|track| track.take_id == id).map(|track| &mut track.mix),
+        }
+    }
+
+    // 반주 트랙을 현재 테이크의 time 위치에 맞춰 재생
+    fn play_overdub_tracks(&self, time: f64) {
+        for track in &self.overdub_tracks {
+            track.play_from(time);
+        }
+    }
+
+    fn pause_overdub_tracks(&self) {
+        for track in &self.overdub_tracks {
+            track.pause();
+        }
+    }
+
+    // 피치 차트 아래 겹쳐 녹음 트랙 패널
+    fn overdub_panel(&self, ctx: &Context<Self>) -> Html {
+        let mut rows = Vec::new();
+        if let Some(id) = self.loaded_take_id.filter(|_| self.has_recorded_audio()) {
+            let name = self.takes.iter().find(|take| take.id == id).map(|take| take.name.clone()).unwrap_or_default();
+            rows.push(TrackRow { key: TrackKey::Current, name, color: CURRENT_TRACK_COLOR, mix: self.current_track_mix });
+        }
+        rows.extend(self.overdub_tracks.iter().map(|track| TrackRow {
+            key: TrackKey::Overdub(track.take_id),
+            name: track.name.clone(),
+            color: track.color,
+            mix: track.mix,
+        }));
+        let candidates: Vec<TakeSummary> = self
+            .takes
+            .iter()
+            .filter(|take| Some(take.id) != self.loaded_take_id && self.overdub_tracks.iter().all(|track| track.take_id != take.id))
+            .cloned()
+            .collect();
+
+        let link = ctx.link();
+        html! {
+            <OverdubPanel
+                rows={rows}
+                candidates={candidates}
+                can_add={self.overdub_tracks.len() < Self::MAX_OVERDUB_TRACKS}
+                is_recording={self.is_recording}
+                on_add={link.callback(Msg::AddOverdubTrack)}
+                on_remove={link.callback(Msg::RemoveOverdubTrack)}
+                on_gain={link.callback(|(key, gain)| Msg::SetTrackGain(key, gain))}
+                on_toggle_mute={link.callback(Msg::ToggleTrackMute)}
+                on_toggle_solo={link.callback(Msg::ToggleTrackSolo)}
+            />
+        }
+    }
+
+    // 피치 차트 아래 재생 속도 / A-B 반복 / 구간 선택 바
+    fn region_bar(&self, ctx: &Context<Self>) -> Html {
+        if self.is_recording || !self.has_recorded_audio() {
+            return html! {};
+        }
+
+        let on_rate_input = ctx.link().callback(|e: InputEvent| {
+            let input = e.target_unchecked_into::<web_sys::HtmlInputElement>();
+            Msg::SetPlaybackRate(input.value().parse::<f64>().unwrap_or(100.0) / 100.0)
+        });
+        let rate_percent = (self.playback_rate * 100.0).round() as u32;
+
+        let region_controls = match self.selected_region {
+            Some((start, end)) => html! {
+                <>
+                    <span class="region-range">
+                        { format!("✂️ {:.2}초 ~ {:.2}초 ({:.2}초)", start, end, end - start) }
+                    </span>
+                    <button
+                        class={classes!("region-button", self.loop_enabled.then_some("active"))}
+                        onclick={ctx.link().callback(|_| Msg::ToggleLoop)}
+                        title="선택 구간을 반복 재생"
+                    >
+                        { "🔁 A-B 반복" }
+                    </button>
+                    <button class="region-button" onclick={ctx.link().callback(|_| Msg::PreviewRegion)}>
+                        { "▶ 구간 미리듣기" }
+                    </button>
+                    <button class="region-button" onclick={ctx.link().callback(|_| Msg::ClearRegion)}>
+                        { "✕ 선택 해제" }
+                    </button>
+                    <span class="region-hint">
+                        { "다운로드 메뉴의 \"선택 구간만\"으로 이 구간의 오디오와 피치 데이터를 내보낼 수 있습니다" }
+                    </span>
+                </>
+            },
+            None => html! {
+                <span class="region-hint">{ "Shift+드래그 또는 A/B 버튼으로 차트에서 구간을 선택할 수 있습니다" }</span>
+            },
+        };
+
+        html! {
+            <div class="region-bar">
+                <label class="playback-rate" title="재생 속도 (음정 유지)">
+                    <span>{ "속도" }</span>
+                    <input
+                        type="range"
+                        min="50"
+                        max="150"
+                        step="5"
+                        value={rate_percent.to_string()}
+                        oninput={on_rate_input}
+                    />
+                    <span class="playback-rate-value">{ format!("{}%", rate_percent) }</span>
+                </label>
+                <button
+                    class="region-button"
+                    onclick={ctx.link().callback(|_| Msg::SetLoopPoint(true))}
+                    title="현재 재생 위치를 반복 시작(A)으로"
+                >
+                    { "A" }
+                </button>
+                <button
+                    class="region-button"
+                    onclick={ctx.link().callback(|_| Msg::SetLoopPoint(false))}
+                    title="현재 재생 위치를 반복 끝(B)으로"
+                >
+                    { "B" }
+                </button>
+                { region_controls }
+            </div>
+        }
+    }
     
     // 오디오 파일 다운로드 헬퍼 메서드
-    fn download_audio_file(&self, audio_url: &str, filename: &str) {
+    fn download_audio_file(audio_url: &str, filename: &str) {
         if let Some(window) = web_sys::window() {
             if let Some(document) = window.document() {
                 if let Ok(element) = document.create_element("a") {
@@ -376,6 +858,29 @@ impl PitchAnalyzer {
             console_error!("window 객체를 찾을 수 없음");
         }
     }
+    
+    // 녹음 한 건을 연습 기록에 저장 - 목표 멜로디가 있으면 음정 정확도를 점수로
+    fn save_recording_practice(&self) {
+        let mut entry = crate::practice_log::PracticeEntry::new(crate::practice_log::PracticeTool::Recording, self.created_at_time);
+        entry.duration_secs = self.last_recording_time;
+        
+        if let Some(melody) = &self.target_melody {
+            let scores = crate::tools::target_melody::score_notes(
+                &melody.shifted_notes(),
+                &self.history,
+                crate::tools::target_melody::DEFAULT_TOLERANCE_CENTS,
+            );
+            let summary = crate::tools::target_melody::summarize_scores(&scores);
+            if summary.scored_notes > 0 {
+                entry = entry
+                    .with_exercise(melody.name.clone())
+                    .with_score(summary.in_tune_ratio * 100.0)
+                    .with_details(format!("성공 {}/{}음", summary.hit_notes, summary.scored_notes));
+            }
+        }
+        
+        crate::practice_log::save_practice_entry(entry);
+    }
 }
 
 pub enum Msg {
@@ -383,18 +888,23 @@ pub enum Msg {
     StopAudio,   // 🔇 마이크 비활성화 메시지 추가
     ToggleAudio, // 🎤 마이크 활성화/비활성화 토글
     UpdatePitch,
-    AudioReady(AudioContext, AnalyserNode, MediaStream),
+    AudioReady(AudioContext, AnalyserNode, MicSource),
     UpdateSensitivity(f32),
     ToggleLinks,   // 🔗 링크 표시 여부 토글
     ToggleMonitor, // 🔊 마이크 모니터링 토글
     UpdateSpeakerVolume(f32), // 🔊 스피커 볼륨 업데이트
+    ApplyMonitorEffects,      // 🎛️ 저장된 모니터 이펙트를 체인에 반영
     
     // 녹음 관련 메시지
-    StartRecording,          // 녹음 시작
+    StartRecording(f64),     // 녹음 시작 (레코더가 실제로 시작한 AudioContext 시간)
     StopRecording,           // 녹음 중지
     RecordingDataAvailable(web_sys::Blob), // 녹음 데이터 가용
     RecordingComplete(String), // 녹음 완료 (오디오 URL)
-    DownloadRecording(String), // 녹음 파일 다운로드
+    DownloadRecording(ExportOptions), // 녹음 파일 다운로드 (포맷, 포맷별 옵션)
+    EncodeRecording(EncodeRequest),   // 디코딩된 녹음을 인코더 워커로 전달
+    EncoderResponse(EncodeResponse),  // 인코더 워커 응답 (진행률/완성된 파일)
+    ExportFailed(String),             // 내보내기 실패
+    ExportTimedOut,                   // 내보내기 응답 없음 (워커 로드/실행 실패)
     
     // 재생 관련 메시지
     TogglePlayback,          // 재생/일시정지 토글
@@ -403,6 +913,13 @@ pub enum Msg {
     UpdatePlaybackTime(f64), // 재생 시간 업데이트
     PlaybackEnded,           // 재생 완료
     RecorderReady(web_sys::MediaRecorder), // 새로 추가된 메시지 타입
+    StemsReady(StemRecorders),             // 스템 레코더 시작됨
+    CountInStarted(CountIn),               // 카운트인 클릭 예약됨
+    AddOverdubTrack(u64),                  // 테이크를 반주 트랙으로 추가
+    RemoveOverdubTrack(u64),
+    SetTrackGain(TrackKey, f32),
+    ToggleTrackMute(TrackKey),
+    ToggleTrackSolo(TrackKey),
     
     // 새로운 메시지 타입 추가: 시크 (재생 위치 변경)
     SeekPlayback(f64),
@@ -418,6 +935,22 @@ pub enum Msg {
     
     // 새 메시지 추가: 컴포넌트 상태 완전 초기화
     ResetComponent,
+    
+    // 목표 멜로디 변경 (TargetMelodyChanged 이벤트)
+    TargetMelodyChanged,
+
+    // 테이크 목록 변경 (TakesChanged 이벤트)
+    TakesChanged,
+
+    // 구간 선택/미리듣기
+    RegionSelected(Option<(f64, f64)>), // RegionSelected 이벤트
+    PreviewRegion,                      // 선택 구간만 재생
+    ClearRegion,                        // 선택 해제
+
+    // A-B 반복 / 재생 속도
+    SetLoopPoint(bool),    // 현재 재생 위치를 A(true) 또는 B(false) 지점으로
+    ToggleLoop,            // 선택 구간 반복 재생 토글
+    SetPlaybackRate(f64),  // 재생 속도 변경
 }
 
 // 컴포넌트 Properties 정의 추가
@@ -425,6 +958,9 @@ pub enum Msg {
 pub struct PitchAnalyzerProps {
     #[prop_or(Some(true))]
     pub show_links: Option<bool>,
+    // 백그라운드 세션의 공유 분석기처럼 페이지를 옮겨 다니는 경우 현재 페이지 (없으면 주소로 판단)
+    #[prop_or_default]
+    pub route: Option<Route>,
 }
 
 impl Component for PitchAnalyzer {
@@ -432,180 +968,75 @@ impl Component for PitchAnalyzer {
     type Properties = PitchAnalyzerProps;
 
     fn create(ctx: &Context<Self>) -> Self {
-        // 이벤트 리스너 추가 - 커스텀 이벤트 수신
+        // 앱 이벤트 구독 - 컴포넌트가 사라지면 구독도 해제됨
         let link = ctx.link().clone();
-        let window = web_sys::window().unwrap();
-        let document = window.document().unwrap();
-
-        // 마이크 토글 이벤트 리스너
-        let toggle_audio_callback = Callback::from(move |_: web_sys::Event| {
-            link.send_message(Msg::ToggleAudio);
-        });
-
-        let toggle_audio_listener = EventListener::new(&document, "toggleAudio", move |e| {
-            toggle_audio_callback.emit(e.clone());
-        });
-
-        // 감도 조절 이벤트 리스너
-        let sensitivity_link = ctx.link().clone();
-        let sensitivity_callback = Callback::from(move |e: web_sys::Event| {
-            let custom_event = e.dyn_into::<web_sys::CustomEvent>().unwrap();
-            let detail = custom_event.detail();
-            let value = js_sys::Number::from(detail).value_of() as f32;
-            sensitivity_link.send_message(Msg::UpdateSensitivity(value));
-        });
-
-        let sensitivity_listener = EventListener::new(&document, "updateSensitivity", move |e| {
-            sensitivity_callback.emit(e.clone());
-        });
-
-        // 링크 토글 이벤트 리스너
-        let toggle_link = ctx.link().clone();
-        let toggle_callback = Callback::from(move |_: web_sys::Event| {
-            toggle_link.send_message(Msg::ToggleLinks);
-        });
-
-        let toggle_listener = EventListener::new(&document, "toggleLinks", move |e| {
-            toggle_callback.emit(e.clone());
-        });
-
-        // 모니터링 토글 이벤트 리스너
-        let monitor_link = ctx.link().clone();
-        let monitor_callback = Callback::from(move |_: web_sys::Event| {
-            monitor_link.send_message(Msg::ToggleMonitor);
-        });
-
-        let monitor_listener = EventListener::new(&document, "toggleMonitor", move |e| {
-            monitor_callback.emit(e.clone());
-        });
-
-        // 스피커 볼륨 조절 이벤트 리스너
-        let volume_link = ctx.link().clone();
-        let volume_callback = Callback::from(move |e: web_sys::Event| {
-            let custom_event = e.dyn_into::<web_sys::CustomEvent>().unwrap();
-            let detail = custom_event.detail();
-            let value = js_sys::Number::from(detail).value_of() as f32;
-            volume_link.send_message(Msg::UpdateSpeakerVolume(value));
-        });
-
-        let volume_listener = EventListener::new(&document, "updateSpeakerVolume", move |e| {
-            volume_callback.emit(e.clone());
-        });
-
-        // 재생 토글 이벤트 리스너
-        let playback_link = ctx.link().clone();
-        let playback_callback = Callback::from(move |e: web_sys::Event| {
-            let custom_event = e.dyn_into::<web_sys::CustomEvent>().unwrap();
-            let detail = custom_event.detail();
-            let is_playing = js_sys::Boolean::from(detail).value_of();
-            
-            if is_playing {
-                playback_link.send_message(Msg::StartPlayback);
-            } else {
-                playback_link.send_message(Msg::PausePlayback);
-            }
-        });
-        
-        let playback_listener = EventListener::new(&document, "togglePlayback", move |e| {
-            playback_callback.emit(e.clone());
-        });
-        
-        // 재생 시크 이벤트 리스너 추가
-        let seek_link = ctx.link().clone();
-        let seek_callback = Callback::from(move |e: web_sys::Event| {
-            let custom_event = e.dyn_into::<web_sys::CustomEvent>().unwrap();
-            let detail = custom_event.detail();
-            let progress = js_sys::Number::from(detail).value_of() as f64;
-            
-            // 진행률 값 검증 (0.0 ~ 1.0 범위로 제한)
-            let progress = progress.max(0.0).min(1.0);
-            
-            // SeekPlayback 메시지 전송
-            seek_link.send_message(Msg::SeekPlayback(progress));
-        });
-        
-        let seek_listener = EventListener::new(&document, "seekPlayback", move |e| {
-            seek_callback.emit(e.clone());
-        });
-        
-        // 다운로드 이벤트 리스너 추가
-        let download_link = ctx.link().clone();
-        let download_callback = Callback::from(move |e: web_sys::Event| {
-            // CustomEvent로 캐스팅하여 detail 정보 추출
-            if let Ok(custom_event) = e.dyn_into::<web_sys::CustomEvent>() {
-                let detail = custom_event.detail();
-                if let Some(format) = detail.as_string() {
-                    download_link.send_message(Msg::DownloadRecording(format));
-                } else {
-                    // 기본 포맷으로 다운로드
-                    download_link.send_message(Msg::DownloadRecording("webm".to_string()));
+        let event_subscription = subscribe(move |event| {
+            let msg = match event {
+                AppEvent::ToggleAudio => Msg::ToggleAudio,
+                AppEvent::UpdateSensitivity { value } => Msg::UpdateSensitivity(*value),
+                AppEvent::ToggleLinks => Msg::ToggleLinks,
+                AppEvent::ToggleMonitor => Msg::ToggleMonitor,
+                AppEvent::UpdateSpeakerVolume { value } => Msg::UpdateSpeakerVolume(*value),
+                AppEvent::TogglePlayback { playing: true } => Msg::StartPlayback,
+                AppEvent::TogglePlayback { playing: false } => Msg::PausePlayback,
+                // 진행률 값 검증 (0.0 ~ 1.0 범위로 제한)
+                AppEvent::SeekPlayback { progress } => Msg::SeekPlayback(progress.clamp(0.0, 1.0)),
+                AppEvent::DownloadRecording(options) => Msg::DownloadRecording(*options),
+                AppEvent::StopAudioResources => Msg::StopAudioResources,
+                AppEvent::ResetPitchAnalyzer => Msg::ResetComponent,
+                AppEvent::TargetMelodyChanged => Msg::TargetMelodyChanged,
+                AppEvent::TakesChanged { .. } => Msg::TakesChanged,
+                AppEvent::MonitorEffectsChanged => Msg::ApplyMonitorEffects,
+                // 설정 초기화 시 재생 속도와 모니터 이펙트도 기본값으로
+                AppEvent::SettingsReset => {
+                    link.send_message(Msg::ApplyMonitorEffects);
+                    Msg::SetPlaybackRate(crate::settings::AnalyzerSettings::default().playback_rate)
                 }
-            } else {
-                // 기본 포맷으로 다운로드
-                download_link.send_message(Msg::DownloadRecording("webm".to_string()));
-            }
-        });
-        
-        let download_listener = EventListener::new(&document, "downloadRecording", move |e| {
-            download_callback.emit(e.clone());
-        });
-        
-        // 오디오 리소스 정리 이벤트 리스너 추가
-        let resources_link = ctx.link().clone();
-        let resources_callback = Callback::from(move |_: web_sys::Event| {
-            resources_link.send_message(Msg::StopAudioResources);
-        });
-        
-        let resources_listener = EventListener::new(&document, "stopAudioResources", move |e| {
-            resources_callback.emit(e.clone());
-        });
-        
-        // 컴포넌트 상태 초기화 이벤트 리스너 추가
-        let reset_link = ctx.link().clone();
-        let reset_callback = Callback::from(move |_: web_sys::Event| {
-            reset_link.send_message(Msg::ResetComponent);
-        });
-        
-        let reset_listener = EventListener::new(&document, "resetPitchAnalyzer", move |e| {
-            reset_callback.emit(e.clone());
+                AppEvent::RegionSelected { region } => Msg::RegionSelected(*region),
+                _ => return,
+            };
+            link.send_message(msg);
         });
-        
-        // 모든 이벤트 리스너 forget 호출
-        download_listener.forget();
-        seek_listener.forget();
-        playback_listener.forget();
-        toggle_audio_listener.forget();
-        sensitivity_listener.forget();
-        toggle_listener.forget();
-        monitor_listener.forget();
-        volume_listener.forget();
-        resources_listener.forget();
-        reset_listener.forget();
+
+        // 페이지 이동 전에 선택한 테이크 불러오기
+        ctx.link().send_message(Msg::TakesChanged);
 
         // Props에서 show_links 값 가져오기
         let show_links = ctx.props().show_links.unwrap_or(true);
 
         Self {
+            engine: engine_from_scope(ctx.link()),
             audio_ctx: None,
             analyser: None,
             _stream: None,
+            mic_source: None,
             pitch: "🎤 음성 입력 대기...".to_string(),
             prev_freqs: VecDeque::with_capacity(5),
             history: VecDeque::new(),
             canvas_ref: NodeRef::default(),
             elapsed_time: 0.0,
             current_freq: 0.0,
-            sensitivity: 0.01,     // 기본 감도 값
+            sensitivity: crate::settings::load_settings().analyzer.sensitivity, // 저장된 감도 (기본 0.01)
             show_links,            // props에서 가져온 값으로 초기화
             mic_active: false,     // 처음에는 마이크 비활성화 상태
             monitor_active: false, // 처음에는 모니터링 비활성화 상태
             speaker_node: None,    // 스피커 노드는 초기화되지 않음
+            monitor_chain: None,
+            monitor_filter: None,
             
             // 오디오 녹음 관련 필드
             is_recording: false,                       // 녹음 중인지 여부
             is_playing: false,                         // 재생 중인지 여부
             recorder: None::<web_sys::MediaRecorder>,  // 미디어 레코더
             recorded_chunks: Vec::new(),                // 녹음된 오디오 청크
+            stem_recorders: None,
+            count_in: None,
+            beat_grid: None,
+            take_latency: 0.0,
+            record_delay: 0.0,
+            overdub_tracks: Vec::new(),
+            current_track_mix: TrackMix::default(),
+            current_track_gain: None,
             recorded_audio_url: None,                   // 녹음된 오디오 URL
             audio_element: None,                         // 오디오 재생 요소
             playback_time: 0.0,                           // 재생 위치 (초)
@@ -631,6 +1062,23 @@ impl Component for PitchAnalyzer {
             amplitude_data: None,
             amplitude_history: VecDeque::with_capacity(1000),
             current_rms: 0.0,
+            
+            // 페이지 이동 전에 선택한 목표 멜로디 유지
+            target_melody: crate::tools::target_melody::current_target_melody(),
+
+            encoder: None,
+            pending_export: None,
+            export_watchdog: None,
+
+            takes: crate::tools::take_library::take_summaries(),
+            active_take_id: crate::tools::take_library::active_take_id(),
+            loaded_take_id: None,
+            selected_region: None,
+            preview_end: None,
+            loop_enabled: false,
+            playback_rate: crate::settings::load_settings().analyzer.playback_rate,
+            _event_subscription: event_subscription,
+            last_live_status: None,
         }
     }
 
@@ -646,37 +1094,36 @@ impl Component for PitchAnalyzer {
                 self.recorded_chunks.clear();
                 
                 let link = ctx.link().clone();
-                let mut constraints = MediaStreamConstraints::new();
-                constraints.set_audio(&JsValue::TRUE);
-
-                let user_media_promise = MEDIA_DEVICES
-                    .get_user_media_with_constraints(&constraints)
-                    .expect("Failed to request user media");
+                let engine = self.engine.clone();
 
                 wasm_bindgen_futures::spawn_local(async move {
-                    match JsFuture::from(user_media_promise).await {
-                        Ok(stream_value) => {
-                            let stream = MediaStream::from(stream_value);
-                            let audio_ctx =
-                                AudioContext::new().expect("Failed to create AudioContext");
+                    match engine.acquire_mic().await {
+                        Ok(mic) => {
+                            // 엔진의 공유 컨텍스트와 마이크 소스에 분석기 연결
+                            let audio_ctx = engine.context().expect("Failed to get AudioContext");
                             let analyser = audio_ctx
                                 .create_analyser()
                                 .expect("Failed to create AnalyserNode");
-                            let source = audio_ctx
-                                .create_media_stream_source(&stream)
-                                .expect("Failed to create MediaStreamAudioSourceNode");
 
                             analyser.set_fft_size(2048);
-                            source
+                            mic.source
                                 .connect_with_audio_node(&analyser)
                                 .expect("Failed to connect audio source");
 
-                            // 분석기, 스트림, 컨텍스트를 Msg에 담아 보냄
-                            link.send_message(Msg::AudioReady(audio_ctx, analyser, stream.clone()));
-                            
-                            // 마이크 활성화와 함께 녹음 시작
-                            link.send_message(Msg::StartRecording);
-                            
+                            // 믹서에서 고른 소리(마이크, 메트로놈, 스케일, 피아노)를 섞은 스트림을 녹음
+                            // 녹음 버스를 쓸 수 없으면 마이크만 녹음
+                            let recording = engine.apply_recording_settings().ok();
+                            let stream = match recording.as_ref().map(|_| engine.recording_stream()) {
+                                Some(Ok(stream)) => stream,
+                                _ => {
+                                    console_warn!("녹음 버스를 사용할 수 없어 마이크만 녹음합니다");
+                                    mic.stream.clone()
+                                }
+                            };
+
+                            // 분석기, 마이크, 컨텍스트를 Msg에 담아 보냄
+                            link.send_message(Msg::AudioReady(audio_ctx.clone(), analyser, mic));
+
                             // MediaRecorder 설정
                             let recorder_options = web_sys::MediaRecorderOptions::new();
                             // 오디오 품질을 높이기 위해 bitsPerSecond 값 설정 (높은 비트레이트)
@@ -689,42 +1136,96 @@ impl Component for PitchAnalyzer {
                             // options_obj를 recorder_options로 변환
                             let recorder_options = options_obj.unchecked_into::<web_sys::MediaRecorderOptions>();
 
-                            if let Ok(recorder) = web_sys::MediaRecorder::new_with_media_stream_and_media_recorder_options(&stream, &recorder_options) {
-                                // 데이터 가용 이벤트 핸들러 설정
-                                let link_clone = link.clone();
-                                let ondataavailable = Closure::wrap(Box::new(move |event: web_sys::Event| {
-                                    let blob_event = event.dyn_into::<web_sys::BlobEvent>().unwrap();
-                                    if let Some(blob) = blob_event.data() {
-                                        link_clone.send_message(Msg::RecordingDataAvailable(blob));
+                            let recorder = match web_sys::MediaRecorder::new_with_media_stream_and_media_recorder_options(&stream, &recorder_options) {
+                                Ok(recorder) => recorder,
+                                Err(_) => {
+                                    console_error!("Failed to create MediaRecorder");
+                                    return;
+                                }
+                            };
+
+                            // 데이터 가용 이벤트 핸들러 설정
+                            let link_clone = link.clone();
+                            let ondataavailable = Closure::wrap(Box::new(move |event: web_sys::Event| {
+                                let blob_event = event.dyn_into::<web_sys::BlobEvent>().unwrap();
+                                if let Some(blob) = blob_event.data() {
+                                    link_clone.send_message(Msg::RecordingDataAvailable(blob));
+                                }
+                            }) as Box<dyn FnMut(web_sys::Event)>);
+                            
+                            // 녹음 완료 이벤트 핸들러 설정
+                            let onstop = Closure::wrap(Box::new(move |_: web_sys::Event| {
+                                // 녹음이 중지되면 명시적으로 중지됐다는 로그 기록
+                                console_log!("레코더 중지 이벤트 발생 - 사후 처리 시작");
+                            }) as Box<dyn FnMut(web_sys::Event)>);
+                            
+                            recorder.set_ondataavailable(Some(ondataavailable.as_ref().unchecked_ref()));
+                            recorder.set_onstop(Some(onstop.as_ref().unchecked_ref()));
+                            
+                            // 이벤트 핸들러 메모리 릭 방지를 위해 forget 호출
+                            ondataavailable.forget();
+                            onstop.forget();
+
+                            // 레코더가 실제로 녹음을 시작하면 start 이벤트가 옴
+                            let started = js_sys::Promise::new(&mut |resolve, _| {
+                                recorder.set_onstart(Some(&resolve));
+                            });
+                            
+                            // 50ms 간격으로 데이터 수집하도록 설정 (더 작은 청크로 세밀하게 수집)
+                            // 이전보다 더 짧은 간격으로 설정하여 데이터 손실 최소화
+                            // 카운트인이 있어도 먼저 시작해 두고, 다운비트까지의 앞부분은 테이크 지연으로 건너뜀
+                            if let Err(err) = recorder.start_with_time_slice(50) {
+                                console_error!("Failed to start recorder: {:?}", err);
+                                return;
+                            }
+                            let _ = JsFuture::from(started).await;
+                            recorder.set_onstart(None);
+                            let recorder_started = audio_ctx.current_time();
+                            console_log!("🎙️ 미디어 레코더 시작 - 50ms 간격으로 데이터 수집 ({:.3}초)", recorder_started);
+
+                            // 스템 저장을 켰으면 선택된 소리마다 따로 녹음 (믹스와 같은 시점에 시작)
+                            let stems = recording
+                                .filter(|recording| recording.stems)
+                                .and_then(|recording| match StemRecorders::start(&engine, &RecordSource::enabled(&recording)) {
+                                    Ok(stems) => Some(stems),
+                                    Err(err) => {
+                                        console_error!("스템 녹음 시작 실패: {:?}", err);
+                                        None
+                                    }
+                                });
+
+                            // 카운트인: 메트로놈 BPM/박자로 N마디를 울리고 다운비트부터 녹음으로 침
+                            let settings = crate::settings::load_settings();
+                            if settings.analyzer.count_in_bars > 0 {
+                                match schedule_count_in(&engine, &settings.metronome, settings.analyzer.count_in_bars) {
+                                    Ok(count_in) => {
+                                        let cancelled = count_in.cancelled_flag();
+                                        let wait_secs = count_in.downbeat - audio_ctx.current_time();
+                                        link.send_message(Msg::CountInStarted(count_in));
+                                        wait_ms((wait_secs * 1000.0).max(0.0) as i32).await;
+                                        if cancelled.get() {
+                                            console_log!("카운트인 취소됨 - 녹음하지 않음");
+                                            recorder.set_ondataavailable(None);
+                                            let _ = recorder.stop();
+                                            if let Some(stems) = stems {
+                                                stems.cancel();
+                                            }
+                                            return;
+                                        }
+                                    }
+                                    Err(err) => {
+                                        console_error!("카운트인 예약 실패: {:?}", err);
                                     }
-                                }) as Box<dyn FnMut(web_sys::Event)>);
-                                
-                                // 녹음 완료 이벤트 핸들러 설정
-                                let link_clone = link.clone();
-                                let onstop = Closure::wrap(Box::new(move |_: web_sys::Event| {
-                                    // 녹음이 중지되면 명시적으로 중지됐다는 로그 기록
-                                    console_log!("레코더 중지 이벤트 발생 - 사후 처리 시작");
-                                }) as Box<dyn FnMut(web_sys::Event)>);
-                                
-                                recorder.set_ondataavailable(Some(ondataavailable.as_ref().unchecked_ref()));
-                                recorder.set_onstop(Some(onstop.as_ref().unchecked_ref()));
-                                
-                                // 이벤트 핸들러 메모리 릭 방지를 위해 forget 호출
-                                ondataavailable.forget();
-                                onstop.forget();
-                                
-                                // 50ms 간격으로 데이터 수집하도록 설정 (더 작은 청크로 세밀하게 수집)
-                                // 이전보다 더 짧은 간격으로 설정하여 데이터 손실 최소화
-                                if let Err(err) = recorder.start_with_time_slice(50) {
-                                    console_error!("Failed to start recorder: {:?}", err);
-                                } else {
-                                    console_log!("🎙️ 미디어 레코더 시작 - 50ms 간격으로 데이터 수집");
                                 }
-                                
-                                // 레코더 객체를 컴포넌트에 저장
-                                link.send_message(Msg::RecorderReady(recorder));
-                            } else {
-                                console_error!("Failed to create MediaRecorder");
+                            }
+
+                            // 녹음 상태로 전환 (카운트인이 있으면 다운비트가 0초)
+                            link.send_message(Msg::StartRecording(recorder_started));
+                            
+                            // 레코더 객체를 컴포넌트에 저장
+                            link.send_message(Msg::RecorderReady(recorder));
+                            if let Some(stems) = stems {
+                                link.send_message(Msg::StemsReady(stems));
                             }
                         }
                         Err(err) => {
@@ -760,6 +1261,10 @@ impl Component for PitchAnalyzer {
                         self.elapsed_time + 0.1
                     };
                     
+                    // 테이크에 기록할 시간 - 소리는 왕복 지연만큼 늦게 들어오므로 그만큼 당김
+                    let take_time = current_time - self.record_delay;
+                    let record_frame = self.is_recording && take_time >= 0.0;
+
                     // 여러 주파수 분석
                     let freqs = analyze_multiple_frequencies(&buffer, sample_rate, self.sensitivity);
 
@@ -779,9 +1284,9 @@ impl Component for PitchAnalyzer {
                         self.pitch = format!("🎶 현재 음: {} ({:.2} Hz)", note, average_freq);
 
                         // 녹음 중인 경우에만 주파수 기록 업데이트
-                        if self.is_recording {
+                        if record_frame {
                             // 현재 상대 시간과 함께 주파수 목록 기록
-                            self.history.push_back((current_time, freqs));
+                            self.history.push_back((take_time, freqs));
                             
                             // 로그 출력 (디버깅용)
                             console_log!("🕒 녹음 경과 시간: {:.2}s, 주파수: {:.2}Hz", current_time, average_freq);
@@ -792,12 +1297,20 @@ impl Component for PitchAnalyzer {
                         self.current_freq = 0.0;
 
                         // 녹음 중인 경우에만 빈 주파수 목록 기록
-                        if self.is_recording {
+                        if record_frame {
                             // 빈 주파수 목록 기록 (시간은 계속 유지)
-                            self.history.push_back((current_time, Vec::new()));
+                            self.history.push_back((take_time, Vec::new()));
                         }
                     }
                     
+                    // 카운트인 중에는 남은 박을 표시
+                    if let (Some(count_in), Some(audio_ctx)) = (&self.count_in, &self.audio_ctx) {
+                        let beats_left = ((count_in.downbeat - audio_ctx.current_time()) / count_in.grid.beat_secs()).ceil();
+                        if beats_left > 0.0 {
+                            self.pitch = format!("🥁 카운트인 {}", beats_left as u32);
+                        }
+                    }
+
                     // 외부 참조용 시간 업데이트
                     self.elapsed_time = current_time;
                     
@@ -816,9 +1329,9 @@ impl Component for PitchAnalyzer {
                     self.amplitude_data = Some(buffer.clone());
                     
                     // 녹음 중인 경우에만 진폭 히스토리 업데이트
-                    if self.is_recording {
+                    if record_frame {
                         // 현재 상대 시간과 함께 진폭 데이터 기록 (전체 진폭 데이터 저장)
-                        self.amplitude_history.push_back((current_time, buffer.clone()));
+                        self.amplitude_history.push_back((take_time, buffer.clone()));
                         
                         // 히스토리 크기 제한 (최대 1000개 데이터 포인트 유지)
                         if self.amplitude_history.len() > 1000 {
@@ -832,12 +1345,13 @@ impl Component for PitchAnalyzer {
                 }
             }
 
-            Msg::AudioReady(audio_ctx, analyser, stream) => {
+            Msg::AudioReady(audio_ctx, analyser, mic) => {
+                let stream = mic.stream;
                 self.audio_ctx = Some(audio_ctx);
                 self.analyser = Some(analyser);
                 self._stream = Some(stream.clone());
+                self.mic_source = Some(mic.source);
                 self.mic_active = true;
-                self.is_recording = true;
 
                 // 녹음기 초기화
                 if let Ok(recorder) = web_sys::MediaRecorder::new_with_media_stream(&stream) {
@@ -879,6 +1393,11 @@ impl Component for PitchAnalyzer {
             }
 
             Msg::StopAudio => {
+                // 카운트인 중이면 남은 클릭과 녹음 시작 취소
+                if let Some(count_in) = self.count_in.take() {
+                    count_in.cancel();
+                }
+
                 // 녹음 중지 먼저 처리
                 if self.is_recording {
                     // 진행 중인 녹음이 있으면 중지 요청만 하고 종료
@@ -894,28 +1413,9 @@ impl Component for PitchAnalyzer {
                         link.send_message(Msg::StopAudioResources);
                     }).forget();
                     
-                    // UI 상태 업데이트를 위한 이벤트 발생
-                    if let Some(window) = web_sys::window() {
-                        if let Some(document) = window.document() {
-                            // 마이크 비활성화 이벤트 발생
-                            let event = CustomEvent::new_with_event_init_dict(
-                                "toggleAudio",
-                                CustomEventInit::new()
-                                    .bubbles(true)
-                                    .detail(&JsValue::from_bool(false)),
-                            ).unwrap_or_else(|_| web_sys::CustomEvent::new("toggleAudio").unwrap());
-                            
-                            let _ = document.dispatch_event(&event);
-                            
-                            // 컨트롤 버튼 비활성화 이벤트 발생 (명시적으로 분리하여 처리)
-                            let disable_event = web_sys::Event::new("disableControlButtons").expect("disableControlButtons 이벤트 생성 실패");
-                            if let Err(err) = document.dispatch_event(&disable_event) {
-                                console_error!("disableControlButtons 이벤트 발생 실패: {:?}", err);
-                            } else {
-                                console_log!("컨트롤 버튼 비활성화 이벤트 발생 성공 (StopAudio)");
-                            }
-                        }
-                    }
+                    // UI 상태 업데이트: 마이크 꺼짐, 녹음 마무리 동안 컨트롤 잠금
+                    emit(AppEvent::MicStateChanged { active: false });
+                    emit(AppEvent::ControlsEnabled { enabled: false });
                     
                     return true;
                 }
@@ -949,111 +1449,37 @@ impl Component for PitchAnalyzer {
                     return false;
                 }
 
-                self.monitor_active = !self.monitor_active;
+                if self.audio_ctx.is_none() || self.analyser.is_none() {
+                    return false;
+                }
 
-                if let (Some(audio_ctx), Some(analyser)) = (&self.audio_ctx, &self.analyser) {
-                    if self.monitor_active {
-                        // 모니터링 활성화: 새로운 연결 설정
-                        if let Some(stream) = &self._stream {
-                            // 분석기 노드를 그대로 두고, 스트림에서 새로운 소스 노드를 생성
-                            match audio_ctx.clone().create_media_stream_source(stream) {
-                                Ok(monitor_source) => {
-                                    // 1. 로우패스 필터 생성 (고주파 제거)
-                                    match audio_ctx.clone().create_biquad_filter() {
-                                        Ok(filter_node) => {
-                                            // 로우패스 필터 타입 설정 (0은 lowpass)
-                                            filter_node.set_type(web_sys::BiquadFilterType::Lowpass);
-                                            filter_node.frequency().set_value(1500.0); // 1.5kHz 이상 감쇠
-                                            filter_node.q().set_value(1.0);
-                                            
-                                            // 2. 딜레이 노드 생성 (약간의 지연 추가)
-                                            match audio_ctx.clone().create_delay() {
-                                                Ok(delay_node) => {
-                                                    // 50ms 딜레이 설정
-                                                    delay_node.delay_time().set_value(0.05);
-                                                    
-                                                    // 3. 게인 노드 생성 (볼륨 조절)
-                                                    match audio_ctx.clone().create_gain() {
-                                                        Ok(gain_node) => {
-                                                            // 볼륨 설정 (피드백 방지를 위해 매우 낮게 설정)
-                                                            let gain_param = gain_node.gain();
-                                                            gain_param.set_value(0.02); // 2% 볼륨으로 감소
-                                                            
-                                                            // 오디오 그래프 연결:
-                                                            // 소스 -> 필터 -> 딜레이 -> 게인 -> 출력
-                                                            
-                                                            // 소스를 필터에 연결
-                                                            if monitor_source.connect_with_audio_node(&filter_node).is_err() {
-                                                                console_log!("Failed to connect source to filter");
-                                                                self.monitor_active = false;
-                                                                return false;
-                                                            }
-                                                            
-                                                            // 필터를 딜레이에 연결
-                                                            if filter_node.connect_with_audio_node(&delay_node).is_err() {
-                                                                console_log!("Failed to connect filter to delay");
-                                                                self.monitor_active = false;
-                                                                return false;
-                                                            }
-                                                            
-                                                            // 딜레이를 게인에 연결
-                                                            if delay_node.connect_with_audio_node(&gain_node).is_err() {
-                                                                console_log!("Failed to connect delay to gain");
-                                                                self.monitor_active = false;
-                                                                return false;
-                                                            }
-                                                            
-                                                            // 게인 노드를 출력에 연결
-                                                            if gain_node.connect_with_audio_node(&audio_ctx.clone().destination()).is_err() {
-                                                                console_log!("Failed to connect gain to destination");
-                                                                self.monitor_active = false;
-                                                                return false;
-                                                            }
-                                                            
-                                                            // 스피커 노드 저장 (나중에 연결 해제용)
-                                                            self.speaker_node = Some(gain_node);
-                                                            console_log!("Monitor activated with anti-feedback measures");
-                                                        }
-                                                        Err(_) => {
-                                                            console_log!("Failed to create gain node");
-                                                            self.monitor_active = false;
-                                                            return false;
-                                                        }
-                                                    }
-                                                }
-                                                Err(_) => {
-                                                    console_log!("Failed to create delay node");
-                                                    self.monitor_active = false;
-                                                    return false;
-                                                }
-                                            }
-                                        }
-                                        Err(_) => {
-                                            console_log!("Failed to create filter node");
-                                            self.monitor_active = false;
-                                            return false;
-                                        }
-                                    }
-                                }
-                                Err(_) => {
-                                    console_log!("Failed to create monitor source");
-                                    self.monitor_active = false;
-                                    return false;
-                                }
-                            }
-                        }
-                    } else {
-                        // 모니터링 비활성화: 연결 해제
-                        if let Some(speaker_node) = &self.speaker_node {
-                            // 웹오디오 API는 disconnect() 메서드로 모든 연결을 해제
-                            speaker_node.disconnect();
-                            self.speaker_node = None;
-                            console_log!("Monitor deactivated");
-                        }
+                self.monitor_active = !self.monitor_active;
+                if self.monitor_active {
+                    // 모니터링 활성화: 새로운 연결 설정
+                    if let Err(err) = self.connect_monitor() {
+                        console_log!("Failed to start monitor: {:?}", err);
+                        self.disconnect_monitor();
+                        self.monitor_active = false;
+                        return false;
                     }
-                    return true;
+                    console_log!("Monitor activated with anti-feedback measures");
+                } else {
+                    // 모니터링 비활성화: 연결 해제
+                    self.disconnect_monitor();
+                    console_log!("Monitor deactivated");
                 }
+                true
+            }
 
+            // 모니터 이펙트 설정이 바뀌면 모니터링 중인 체인에 바로 반영
+            Msg::ApplyMonitorEffects => {
+                let monitor = crate::settings::load_settings().monitor;
+                if let Some(chain) = &mut self.monitor_chain {
+                    if let Err(err) = chain.apply(&monitor.effects) {
+                        console_log!("Failed to apply monitor effects: {:?}", err);
+                    }
+                    let _ = self.engine.set_record_monitor_effects(monitor.record_wet);
+                }
                 false
             }
 
@@ -1061,23 +1487,32 @@ impl Component for PitchAnalyzer {
                 if let Some(gain_node) = &self.speaker_node {
                     // 값이 0.0~1.0 범위를 벗어나지 않도록 보장
                     let volume = value.max(0.0).min(1.0);
-                    gain_node.gain().set_value(volume);
+                    ramp_gain(gain_node, volume);
                     console_log!("Speaker volume updated to: {:.2}", volume);
                 } else {
                     console_log!("Cannot update volume - speaker not initialized");
                 }
-                true
+                // 슬라이더를 움직이는 동안 계속 들어오므로 다시 그리지 않음
+                false
             }
 
-            Msg::StartRecording => {
+            Msg::StartRecording(recorder_started) => {
                 self.is_recording = true;
                 self.is_playing = false;
                 self.recorder = None;
-                self.recorded_chunks.clear(); // 기존 녹음 데이터 초기화
-                self.recorded_audio_url = None;
+                // 카운트인 동안 받은 청크는 파일 앞부분이므로 남겨 둠 (StartAudio에서 이미 비움)
+                if let Some(stems) = self.stem_recorders.take() {
+                    stems.cancel();
+                }
+                self.detach_audio_element();
+                self.recorded_audio_url = None; // 이전 녹음은 테이크 목록에 남아 있음
+                self.loaded_take_id = None;
                 self.audio_element = None;
                 self.playback_time = 0.0;
                 self.last_recording_time = 0.0;
+                self.selected_region = None;
+                self.preview_end = None;
+                dispatch_region_selected(None);
                 
                 // 녹음 시작 시간 갱신
                 self.created_at_time = js_sys::Date::new_0().get_time();
@@ -1085,13 +1520,19 @@ impl Component for PitchAnalyzer {
                 // 화면 고정 해제 - 새로운 녹음 시작 시
                 self.is_frozen = false;
 
-                // 녹음 시작 시간 저장
-                if let Some(audio_ctx) = &self.audio_ctx {
-                    self.recording_start_time = audio_ctx.current_time();
-                    console_log!("녹음 시작 절대 시간: {:.2}초", self.recording_start_time);
+                // 녹음 시작 시간 저장 (카운트인 후라면 예약된 다운비트를 0초로 맞춤)
+                // 레코더는 카운트인 전에 시작했으므로 다운비트까지의 앞부분도 테이크 지연에 더함
+                let count_in = self.count_in.take();
+                self.beat_grid = count_in.as_ref().map(|count_in| count_in.grid);
+                if let Some(count_in) = &count_in {
+                    self.recording_start_time = count_in.downbeat;
+                    console_log!("카운트인 후 다운비트에 녹음 시작: {:.3}초", self.recording_start_time);
                 } else {
-                    self.recording_start_time = 0.0;
+                    self.recording_start_time = recorder_started;
+                    console_log!("녹음 시작 절대 시간: {:.2}초", self.recording_start_time);
                 }
+                self.record_delay = self.engine.record_delay();
+                self.take_latency = self.record_delay + (self.recording_start_time - recorder_started).max(0.0);
                 
                 // 시간 초기화
                 self.elapsed_time = 0.0;
@@ -1103,16 +1544,12 @@ impl Component for PitchAnalyzer {
                 
                 // 게이지 바 초기화를 위해 UI 업데이트
                 self.update_playback_time_ui(0.0);
+
+                // 반주 트랙은 새 녹음과 같은 0초에서 시작
+                self.play_overdub_tracks(0.0);
                 
-                // PitchPlot의 재생 위치 초기화를 위한 이벤트 발행
-                if let Some(window) = web_sys::window() {
-                    if let Some(document) = window.document() {
-                        // playbackReset 이벤트 발행: pitch plot의 playback 선을 0초로 초기화
-                        let event = web_sys::Event::new("playbackReset").unwrap();
-                        let _ = document.dispatch_event(&event);
-                        console_log!("녹음 시작: playbackReset 이벤트 발행");
-                    }
-                }
+                // PitchPlot의 재생선을 0초로 초기화
+                emit(AppEvent::PlaybackReset);
                 
                 // 최대 녹음 시간 타이머 설정 (10분 후 자동 중지)
                 let link = ctx.link().clone();
@@ -1124,21 +1561,8 @@ impl Component for PitchAnalyzer {
                         link.send_message(Msg::StopRecording);
                         link.send_message(Msg::StopAudio);
                         
-                        // 마이크 비활성화 UI 상태 업데이트를 위한 이벤트 발생
-                        if let Some(window) = web_sys::window() {
-                            if let Some(document) = window.document() {
-                                // 마이크 비활성화 이벤트 발생
-                                let event = CustomEvent::new_with_event_init_dict(
-                                    "toggleAudio",
-                                    CustomEventInit::new()
-                                        .bubbles(true)
-                                        .detail(&JsValue::from_bool(false)),
-                                ).unwrap_or_else(|_| web_sys::CustomEvent::new("toggleAudio").unwrap());
-                                
-                                let _ = document.dispatch_event(&event);
-                                console_log!("마이크 비활성화 이벤트 발생 (최대 녹음 시간 도달)");
-                            }
-                        }
+                        // 마이크 비활성화 UI 상태 업데이트
+                        emit(AppEvent::MicStateChanged { active: false });
                         
                         // 사용자에게 알림 표시
                         if let Some(window) = web_sys::window() {
@@ -1168,6 +1592,7 @@ impl Component for PitchAnalyzer {
                 
                 // 최대 녹음 시간 타이머 취소
                 self.max_recording_timer = None;
+                self.pause_overdub_tracks();
                 
                 // 화면 고정 활성화 - 녹음 중지 시
                 self.is_frozen = true;
@@ -1186,19 +1611,11 @@ impl Component for PitchAnalyzer {
                     1.0 // 안전 기본값
                 };
                 
+                // 연습 기록 저장 (목표 멜로디가 있으면 음정 정확도를 점수로)
+                self.save_recording_practice();
+                
                 // UI 알림용 "녹음 종료됨" 상태 이벤트 발행
-                if let Some(window) = web_sys::window() {
-                    if let Some(document) = window.document() {
-                        let event = CustomEvent::new_with_event_init_dict(
-                            "recordingStateChange",
-                            CustomEventInit::new()
-                                .bubbles(true)
-                                .detail(&JsValue::from_bool(false)),
-                        ).unwrap_or_else(|_| web_sys::CustomEvent::new("recordingStateChange").unwrap());
-                        
-                        let _ = document.dispatch_event(&event);
-                    }
-                }
+                emit(AppEvent::RecordingStateChange { recording: false });
                 
                 // MediaRecorder가 있는 경우에만 처리
                 if let Some(recorder) = &self.recorder {
@@ -1296,20 +1713,11 @@ impl Component for PitchAnalyzer {
                 // 녹음 완료
                 self.is_recording = false;
                 
-                // 기존 오디오 요소가 있으면 이벤트 리스너 제거 및 리소스 정리
-                if let Some(old_audio) = &self.audio_element {
-                    old_audio.set_onloadeddata(None);
-                    old_audio.set_onloadedmetadata(None);
-                    old_audio.set_onended(None);
-                    
-                    // URL 리소스 정리
-                    if let Some(old_url) = &self.recorded_audio_url {
-                        let _ = web_sys::Url::revoke_object_url(old_url);
-                    }
-                }
+                // 기존 오디오 요소의 이벤트 리스너 제거 (URL은 이전 테이크가 계속 사용)
+                self.detach_audio_element();
                 
                 // url 파라미터가 비어있는 경우, 직접 녹음된 청크로 URL 생성 (StopRecording에서 전달됨)
-                let audio_url = if url.is_empty() {
+                let (audio_url, audio_blob) = if url.is_empty() {
                     // 데이터 이벤트 핸들러 제거
                     if let Some(recorder) = &self.recorder {
                         // 이벤트 핸들러 제거 및 정리
@@ -1348,131 +1756,71 @@ impl Component for PitchAnalyzer {
                                 let blob_size = combined_blob.size();
                                 console_log!("생성된 Blob 크기: {:.2} KB", blob_size / 1024.0);
                                 
-                                // Blob URL 생성
-                                match web_sys::Url::create_object_url_with_blob(&combined_blob) {
-                                    Ok(new_url) => new_url,
-                                    Err(err) => {
-                                        console_error!("URL 생성 실패: {:?}", err);
-                                        return false;
-                                    }
-                                }
-                            },
-                            Err(err) => {
-                                console_error!("Blob 결합 실패: {:?}", err);
-                                return false;
-                            }
-                        }
-                    } else {
-                        console_error!("처리할 녹음 청크가 없습니다");
-                        return false;
-                    }
-                } else {
-                    // 이미 생성된 URL이 전달된 경우 그대로 사용
-                    url
-                };
-                
-                // 새 URL 저장
-                self.recorded_audio_url = Some(audio_url.clone());
-                
-                // 오디오 요소 생성
-                if let Some(window) = web_sys::window() {
-                    if let Some(document) = window.document() {
-                        if let Ok(element) = document.create_element("audio") {
-                            let audio_element: web_sys::HtmlAudioElement = element
-                                .dyn_into()
-                                .expect("Failed to create audio element");
-                            
-                            audio_element.set_src(&audio_url);
-                            audio_element.set_controls(false);
-                            
-                            // 재생 종료 이벤트 리스너 추가
-                            let link = ctx.link().clone();
-                            let onended = Closure::wrap(Box::new(move |_: web_sys::Event| {
-                                link.send_message(Msg::PlaybackEnded);
-                            }) as Box<dyn FnMut(web_sys::Event)>);
-                            
-                            // 로드 완료 이벤트 리스너 추가 - 실제 오디오 파일 길이 확인
-                            let link_load = ctx.link().clone();
-                            let last_recording_time = self.last_recording_time;
-                            let onloadedmetadata = Closure::wrap(Box::new(move |e: web_sys::Event| {
-                                if let Some(target) = e.target() {
-                                    if let Ok(audio) = target.dyn_into::<web_sys::HtmlAudioElement>() {
-                                        let actual_duration = audio.duration();
-                                        
-                                        // 로그로 실제 오디오 길이와 기록된 길이 비교
-                                        console_log!("오디오 메타데이터 로드됨: 실제 길이 = {:.2}초, 기록된 길이 = {:.2}초", 
-                                            actual_duration, last_recording_time);
-                                        
-                                        // 실제 오디오 길이로 last_recording_time 업데이트
-                                        link_load.send_message(Msg::UpdateRecordingDuration(actual_duration));
-                                    }
-                                }
-                            }) as Box<dyn FnMut(web_sys::Event)>);
-                            
-                            audio_element.set_onended(Some(onended.as_ref().unchecked_ref()));
-                            audio_element.set_onloadedmetadata(Some(onloadedmetadata.as_ref().unchecked_ref()));
-                            onended.forget();
-                            onloadedmetadata.forget();
-                            
-                            // 오디오 요소에 고유 ID 부여 (추적 및 선택 가능하도록)
-                            audio_element.set_id("pitch-analyzer-audio");
-                            
-                            // 오디오 요소를 DOM에 추가 (숨겨진 컨테이너에)
-                            if let Some(document) = web_sys::window().unwrap().document() {
-                                // 오디오 컨테이너가 있는지 확인하고 없으면 생성
-                                let container_id = "pitch-analyzer-audio-container";
-                                if document.get_element_by_id(container_id).is_none() {
-                                    if let Ok(container) = document.create_element("div") {
-                                        // 컨테이너 설정
-                                        container.set_id(container_id);
-                                        // 화면에 표시되지 않도록 스타일 설정
-                                        if let Ok(_) = container.set_attribute("style", "display: none; position: absolute; width: 0; height: 0;") {
-                                            // 문서에 추가
-                                            if let Some(body) = document.body() {
-                                                let _ = body.append_child(&container);
-                                                console_log!("오디오 컨테이너 DOM에 추가됨");
-                                            }
-                                        }
-                                    }
-                                }
-                                
-                                // 기존 오디오 요소가 있으면 제거
-                                if let Some(old_audio) = document.get_element_by_id("pitch-analyzer-audio") {
-                                    if let Some(parent) = old_audio.parent_node() {
-                                        let _ = parent.remove_child(&old_audio);
-                                    }
-                                }
-                                
-                                // 새 오디오 요소를 컨테이너에 추가
-                                if let Some(container) = document.get_element_by_id(container_id) {
-                                    let _ = container.append_child(&audio_element);
-                                    console_log!("오디오 요소 DOM에 추가됨");
+                                // Blob URL 생성
+                                match web_sys::Url::create_object_url_with_blob(&combined_blob) {
+                                    Ok(new_url) => (new_url, Some(combined_blob)),
+                                    Err(err) => {
+                                        console_error!("URL 생성 실패: {:?}", err);
+                                        return false;
+                                    }
                                 }
+                            },
+                            Err(err) => {
+                                console_error!("Blob 결합 실패: {:?}", err);
+                                return false;
                             }
-                            
-                            self.audio_element = Some(audio_element);
-                            
-                            // 녹음 데이터 초기화 - 메모리 누수 방지
-                            self.recorded_chunks.clear();
                         }
+                    } else {
+                        console_error!("처리할 녹음 청크가 없습니다");
+                        return false;
                     }
-                }
+                } else {
+                    // 이미 생성된 URL이 전달된 경우 그대로 사용
+                    (url, None)
+                };
                 
-                // 녹음 완료 이벤트 발행
-                if let Some(window) = web_sys::window() {
-                    if let Some(document) = window.document() {
-                        let event = CustomEvent::new_with_event_init_dict(
-                            "recordingComplete",
-                            CustomEventInit::new()
-                                .bubbles(true)
-                                .detail(&JsValue::from_str(&audio_url)),
-                        ).unwrap_or_else(|_| web_sys::CustomEvent::new("recordingComplete").unwrap());
-                        
-                        let _ = document.dispatch_event(&event);
-                        console_log!("recordingComplete 이벤트 발행");
+                // 새 URL 저장
+                self.recorded_audio_url = Some(audio_url.clone());
+                self.attach_audio_element(ctx, &audio_url);
+                
+                // 녹음 데이터 초기화 - 메모리 누수 방지
+                self.recorded_chunks.clear();
+                
+                // 새 테이크로 보관 (차트에 이미 표시 중이므로 다시 불러오지 않음)
+                let stems = self.stem_recorders.take();
+                if let Some(audio_blob) = audio_blob {
+                    let take_id = crate::tools::take_library::add_take(Take {
+                        id: 0,
+                        name: String::new(),
+                        starred: false,
+                        created_at: self.created_at_time,
+                        duration: self.last_recording_time,
+                        audio_blob,
+                        audio_url: audio_url.clone(),
+                        history: self.history.clone(),
+                        amplitude_history: self.amplitude_history.clone(),
+                        stems: Vec::new(),
+                        beat_grid: self.beat_grid,
+                        latency: self.take_latency,
+                    });
+                    self.loaded_take_id = Some(take_id);
+
+                    // 스템은 레코더가 남은 청크를 넘겨준 뒤에 붙임
+                    if let Some(stems) = stems {
+                        wasm_bindgen_futures::spawn_local(async move {
+                            let stems = stems.finish().await;
+                            if !stems.is_empty() {
+                                crate::tools::take_library::set_take_stems(take_id, stems);
+                            }
+                        });
                     }
+                } else if let Some(stems) = stems {
+                    stems.cancel();
                 }
                 
+                // 녹음 완료 이벤트 발행
+                emit(AppEvent::RecordingComplete { audio_url: audio_url.clone() });
+                
                 true
             },
 
@@ -1491,6 +1839,11 @@ impl Component for PitchAnalyzer {
                     console_log!("녹음 중에는 재생할 수 없습니다");
                     return false;
                 }
+
+                // 녹음 재생 채널이 소리를 내도록 공유 컨텍스트 재개 (사용자 입력 시점)
+                if let Err(err) = self.engine.context() {
+                    console_error!("오디오 엔진 재개 실패: {:?}", err);
+                }
                 
                 // 화면 고정 해제 - 재생 중에는 화면이 업데이트되어야 함
                 self.is_frozen = false;
@@ -1542,13 +1895,22 @@ impl Component for PitchAnalyzer {
                     
                     // 재생이 끝나서 다시 시작하는 경우만 처음부터 재생
                     if audio_element.ended() {
-                        audio_element.set_current_time(0.0);
+                        self.seek_audio(audio_element, 0.0);
                         self.playback_time = 0.0;
                         console_log!("재생이 끝난 상태에서 다시 시작하므로 처음부터 재생");
                     } else {
                         // 일시 정지된 위치에서 계속 재생
                         console_log!("재생 위치 유지: {:.2}초", audio_element.current_time());
                     }
+
+                    // 반복 구간 밖에서 시작하면 A 지점부터
+                    if let Some((start, end)) = self.loop_region() {
+                        let position = self.audio_position(audio_element);
+                        if position < start || position >= end {
+                            self.seek_audio(audio_element, start);
+                            self.playback_time = start;
+                        }
+                    }
                     
                     // 기존 이벤트 리스너들 명시적으로 제거
                     audio_element.set_onended(None);
@@ -1571,28 +1933,20 @@ impl Component for PitchAnalyzer {
                         self.is_playing = false;
                         return false;
                     }
+                    self.play_overdub_tracks(self.audio_position(audio_element));
                     
                     console_log!("재생 시작됨, is_playing={}", self.is_playing);
                     
                     // 재생 상태 이벤트 발행
-                    if let Some(window) = web_sys::window() {
-                        if let Some(document) = window.document() {
-                            let event = CustomEvent::new_with_event_init_dict(
-                                "playbackStateChange",
-                                CustomEventInit::new()
-                                    .bubbles(true)
-                                    .detail(&JsValue::from_bool(true)),
-                            ).unwrap();
-                            let _ = document.dispatch_event(&event);
-                        }
-                    }
+                    emit(AppEvent::PlaybackStateChange { playing: true });
                     
                     // 재생 시간 UI 업데이트 (초기 로딩 시)
-                    self.update_playback_time_ui(audio_element.current_time());
+                    self.update_playback_time_ui(self.audio_position(audio_element));
                     
                     // 재생 상태 업데이트를 위한 인터벌 설정
                     let link = ctx.link().clone();
                     let audio_element_clone = audio_element.clone();
+                    let take_latency = self.take_latency;
                     
                     // 새 인터벌 생성
                     let interval = gloo::timers::callback::Interval::new(30, move || {
@@ -1603,8 +1957,8 @@ impl Component for PitchAnalyzer {
                             return;
                         }
                         
-                        // 현재 재생 시간 가져오기
-                        let current_time = audio_element_clone.current_time();
+                        // 현재 재생 시간 가져오기 (테이크 시간축)
+                        let current_time = audio_element_clone.current_time() - take_latency;
                         
                         // 시간 업데이트 메시지 전송 - 모든 시간값 전송
                         link.send_message(Msg::UpdatePlaybackTime(current_time));
@@ -1622,6 +1976,8 @@ impl Component for PitchAnalyzer {
             }
 
             Msg::PausePlayback => {
+                self.preview_end = None;
+
                 // 이미 정지 상태면 중복 호출 무시
                 if !self.is_playing {
                     return false;
@@ -1629,7 +1985,7 @@ impl Component for PitchAnalyzer {
                 
                 if let Some(audio_element) = &self.audio_element {
                     // 현재 재생 시간 기록
-                    self.playback_time = audio_element.current_time();
+                    self.playback_time = self.audio_position(audio_element);
                     console_log!("일시 정지 시점 시간 저장: {:.2}초", self.playback_time);
                     
                     // 오디오 요소가 있으면 일시정지
@@ -1637,6 +1993,7 @@ impl Component for PitchAnalyzer {
                         console_error!("재생 일시정지 실패: {:?}", err);
                         return false;
                     }
+                    self.pause_overdub_tracks();
                     
                     // 인터벌 타이머 제거
                     self.playback_interval = None;
@@ -1646,17 +2003,7 @@ impl Component for PitchAnalyzer {
                     console_log!("재생 일시정지됨");
                     
                     // 재생 상태 이벤트 발행
-                    if let Some(window) = web_sys::window() {
-                        if let Some(document) = window.document() {
-                            let event = CustomEvent::new_with_event_init_dict(
-                                "playbackStateChange",
-                                CustomEventInit::new()
-                                    .bubbles(true)
-                                    .detail(&JsValue::from_bool(false)),
-                            ).unwrap();
-                            let _ = document.dispatch_event(&event);
-                        }
-                    }
+                    emit(AppEvent::PlaybackStateChange { playing: false });
                     
                     true
                 } else {
@@ -1678,7 +2025,27 @@ impl Component for PitchAnalyzer {
                     console_log!("시간이 너무 작아서 무시 (0에 가까움)");
                     return false;
                 }
+
+                // A-B 반복: B 지점을 지나면 A 지점으로
+                if let Some((start, end)) = self.loop_region() {
+                    if time >= end {
+                        console_log!("🔁 반복 구간 끝 {:.2}초 -> {:.2}초", end, start);
+                        self.jump_to_loop_start(start);
+                        return true;
+                    }
+                }
+
+                // 구간 미리듣기는 구간 끝에서 정지
+                if self.preview_end.is_some_and(|end| time >= end) {
+                    console_log!("구간 미리듣기 끝: {:.2}초", time);
+                    return Component::update(self, ctx, Msg::PausePlayback);
+                }
                 
+                // 반주 트랙이 밀리거나 앞서면 현재 테이크 위치로 맞춤
+                for track in &self.overdub_tracks {
+                    track.resync(time);
+                }
+
                 // 작은 변화는 무시 (성능 향상)
                 if (time - self.playback_time).abs() < 0.05 {
                     return false;
@@ -1688,33 +2055,7 @@ impl Component for PitchAnalyzer {
                 self.playback_time = time;
                 
                 // UI에 재생 시간과 총 녹음 시간 정보 전달
-                if let Some(window) = web_sys::window() {
-                    if let Some(document) = window.document() {
-                        // 재생 시간 업데이트 이벤트 발행
-                        let mut detail = Object::new();
-                        // currentTime 속성 설정
-                        let _ = js_sys::Reflect::set(
-                            &detail,
-                            &JsValue::from_str("currentTime"),
-                            &JsValue::from_f64(time),
-                        );
-                        // duration 속성 설정
-                        let _ = js_sys::Reflect::set(
-                            &detail,
-                            &JsValue::from_str("duration"),
-                            &JsValue::from_f64(self.last_recording_time),
-                        );
-                        
-                        let event = CustomEvent::new_with_event_init_dict(
-                            "playbackTimeUpdate",
-                            CustomEventInit::new()
-                                .bubbles(true)
-                                .detail(&detail),
-                        ).unwrap();
-                        
-                        let _ = document.dispatch_event(&event);
-                    }
-                }
+                self.update_playback_time_ui(time);
                 
                 // 현재 재생 시점의 주파수 찾기
                 if let Some((closest_t, freqs)) = self.history.iter()
@@ -1793,11 +2134,23 @@ impl Component for PitchAnalyzer {
                     return false;
                 }
                 
+                // B 지점이 녹음 끝이면 종료 대신 A 지점부터 다시 재생
+                if let Some((start, _)) = self.loop_region() {
+                    self.jump_to_loop_start(start);
+                    if let Some(audio_element) = &self.audio_element {
+                        if audio_element.play().is_ok() {
+                            return true;
+                        }
+                    }
+                }
+
                 // 재생 완료 로그
                 console_log!("⏹️ 재생 종료, 재생 상태 초기화");
+                self.pause_overdub_tracks();
                 
                 // 인터벌 타이머 제거
                 self.playback_interval = None;
+                self.preview_end = None;
                 
                 // 상태 초기화
                 self.is_playing = false;
@@ -1841,24 +2194,9 @@ impl Component for PitchAnalyzer {
                 // 재생 시간 UI 업데이트 (게이지바를 정확히 끝까지 채움)
                 self.update_playback_time_ui(self.last_recording_time);
                 
-                // 재생 상태 이벤트 발행
-                if let Some(window) = web_sys::window() {
-                    if let Some(document) = window.document() {
-                        // 재생 상태 변경 이벤트 발행
-                        let event = CustomEvent::new_with_event_init_dict(
-                            "playbackStateChange",
-                            CustomEventInit::new()
-                                .bubbles(true)
-                                .detail(&JsValue::from_bool(false)),
-                        ).unwrap();
-                        let _ = document.dispatch_event(&event);
-                        
-                        // 재생 종료 이벤트 발행
-                        let event = web_sys::Event::new("playbackEnded").unwrap();
-                        let _ = document.dispatch_event(&event);
-                        console_log!("playbackEnded 이벤트 발행");
-                    }
-                }
+                // 재생 상태 변경, 재생 종료 이벤트 발행
+                emit(AppEvent::PlaybackStateChange { playing: false });
+                emit(AppEvent::PlaybackEnded);
                 
                 true
             },
@@ -1867,7 +2205,7 @@ impl Component for PitchAnalyzer {
             Msg::ResetAudioPosition => {
                 // 오디오 요소 위치 초기화
                 if let Some(audio_element) = &self.audio_element {
-                    audio_element.set_current_time(0.0);
+                    self.seek_audio(audio_element, 0.0);
                     self.playback_time = 0.0;
                     console_log!("오디오 요소의 위치 초기화됨");
                     
@@ -1882,6 +2220,90 @@ impl Component for PitchAnalyzer {
                 self.recorder = Some(recorder);
                 true
             }
+
+            Msg::CountInStarted(count_in) => {
+                if self.mic_active {
+                    self.count_in = Some(count_in);
+                } else {
+                    count_in.cancel();
+                }
+                true
+            }
+
+            Msg::AddOverdubTrack(id) => {
+                if self.is_recording
+                    || Some(id) == self.loaded_take_id
+                    || self.overdub_tracks.len() >= Self::MAX_OVERDUB_TRACKS
+                    || self.overdub_tracks.iter().any(|track| track.take_id == id)
+                {
+                    return false;
+                }
+                let Some(take) = crate::tools::take_library::take_by_id(id) else {
+                    return false;
+                };
+
+                match OverdubTrack::open(&self.engine, &take, next_track_color(&self.overdub_tracks)) {
+                    Ok(track) => {
+                        Self::apply_playback_rate(track.audio(), self.playback_rate);
+                        if self.is_playing {
+                            track.play_from(self.playback_time);
+                        }
+                        console_log!("반주 트랙 추가: {}", track.name);
+                        self.overdub_tracks.push(track);
+                        self.apply_track_gains();
+                        true
+                    }
+                    Err(err) => {
+                        console_error!("반주 트랙 열기 실패: {:?}", err);
+                        false
+                    }
+                }
+            }
+
+            Msg::RemoveOverdubTrack(id) => {
+                let Some(index) = self.overdub_tracks.iter().position(|track| track.take_id == id) else {
+                    return false;
+                };
+                self.overdub_tracks.remove(index).close();
+                self.apply_track_gains();
+                true
+            }
+
+            Msg::SetTrackGain(key, gain) => {
+                let Some(mix) = self.track_mix_mut(key) else {
+                    return false;
+                };
+                mix.gain = gain.clamp(0.0, 1.0);
+                self.apply_track_gains();
+                true
+            }
+
+            Msg::ToggleTrackMute(key) => {
+                let Some(mix) = self.track_mix_mut(key) else {
+                    return false;
+                };
+                mix.muted = !mix.muted;
+                self.apply_track_gains();
+                true
+            }
+
+            Msg::ToggleTrackSolo(key) => {
+                let Some(mix) = self.track_mix_mut(key) else {
+                    return false;
+                };
+                mix.solo = !mix.solo;
+                self.apply_track_gains();
+                true
+            }
+
+            Msg::StemsReady(stems) => {
+                if self.is_recording {
+                    self.stem_recorders = Some(stems);
+                } else {
+                    stems.cancel();
+                }
+                false
+            }
             
             // 새로운 메시지 타입 추가: 시크 (재생 위치 변경)
             Msg::SeekPlayback(progress) => {
@@ -1963,7 +2385,7 @@ impl Component for PitchAnalyzer {
                         }
                         
                         // 오디오 요소의 재생 위치 변경
-                        audio_element.set_current_time(seek_time);
+                        self.seek_audio(audio_element, seek_time);
                         
                         console_log!("🎯 재생 위치 변경: {:.2}초 ({:.1}%)", 
                             seek_time, progress * 100.0);
@@ -1972,12 +2394,15 @@ impl Component for PitchAnalyzer {
                         if let Err(err) = audio_element.play() {
                             console_error!("시크 후 재생 시작 실패: {:?}", err);
                         } else {
+                            self.play_overdub_tracks(seek_time);
+
                             // 재생 상태 유지
                             
                             // 재생 인터벌이 없으면 다시 설정
                             if self.playback_interval.is_none() {
                                 let link = ctx.link().clone();
                                 let audio_element_clone = audio_element.clone();
+                                let take_latency = self.take_latency;
                                 
                                 // 새 인터벌 생성
                                 let interval = gloo::timers::callback::Interval::new(100, move || {
@@ -1989,7 +2414,7 @@ impl Component for PitchAnalyzer {
                                     }
                                     
                                     // 현재 재생 시간 가져오기
-                                    let current_time = audio_element_clone.current_time();
+                                    let current_time = audio_element_clone.current_time() - take_latency;
                                     
                                     // 시간 업데이트 메시지 전송 - 모든 시간값 전송
                                     link.send_message(Msg::UpdatePlaybackTime(current_time));
@@ -2001,7 +2426,7 @@ impl Component for PitchAnalyzer {
                         }
                     } else {
                         // 일시정지 상태에서는 오디오 요소의 currentTime만 업데이트하고, 재생은 시작하지 않음
-                        audio_element.set_current_time(seek_time);
+                        self.seek_audio(audio_element, seek_time);
                         console_log!("🎯 재생 위치만 변경: {:.2}초 ({:.1}%)", 
                             seek_time, progress * 100.0);
                     }
@@ -2030,6 +2455,9 @@ impl Component for PitchAnalyzer {
                     
                     // 마지막 녹음 시간 업데이트
                     self.last_recording_time = actual_duration;
+                    if let Some(take_id) = self.loaded_take_id {
+                        crate::tools::take_library::set_take_duration(take_id, actual_duration);
+                    }
                     
                     // 현재 재생 위치와 최종 녹음 시간의 비율 계산 (진행률)
                     let current_progress = if previous_recording_time > 0.0 {
@@ -2064,20 +2492,11 @@ impl Component for PitchAnalyzer {
 
             // 새 메시지 추가: 오디오 리소스 정리
             Msg::StopAudioResources => {
-                // 오디오 컨텍스트가 있으면 정지
-                if let Some(ctx) = &self.audio_ctx {
-                    let _ = ctx.close();
-                }
-
-                // 스트림 트랙 정지
-                if let Some(stream) = &self._stream {
-                    let tracks = stream.get_audio_tracks();
-                    for i in 0..tracks.length() {
-                        let track_js = tracks.get(i);
-                        let track = web_sys::MediaStreamTrack::from(track_js);
-                        track.stop();
-                    }
+                if let Some(count_in) = self.count_in.take() {
+                    count_in.cancel();
                 }
+                // 공유 컨텍스트는 그대로 두고 이 분석기의 마이크 사용만 종료
+                self.release_microphone();
 
                 // 상태 초기화
                 self.audio_ctx = None;
@@ -2095,39 +2514,26 @@ impl Component for PitchAnalyzer {
                 // 최대 녹음 시간 타이머 취소
                 self.max_recording_timer = None;
 
-                // 컨트롤 버튼 활성화 이벤트 발생
-                if let Some(window) = web_sys::window() {
-                    if let Some(document) = window.document() {
-                        // 이벤트 생성 및 발생
-                        let enable_event = web_sys::Event::new("enableControlButtons").expect("enableControlButtons 이벤트 생성 실패");
-                        if let Err(err) = document.dispatch_event(&enable_event) {
-                            console_error!("enableControlButtons 이벤트 발생 실패: {:?}", err);
-                        } else {
-                            console_log!("컨트롤 버튼 활성화 이벤트 발생 성공 (StopAudioResources)");
-                        }
-                    }
-                }
+                // 컨트롤 버튼 다시 활성화
+                emit(AppEvent::ControlsEnabled { enabled: true });
 
                 console_log!("오디오 리소스 및 모든 인터벌 중지됨");
 
                 true
             },
 
-            Msg::DownloadRecording(format) => {
+            Msg::DownloadRecording(options) => {
                 // 녹음된 오디오가 없으면 다운로드 불가
                 if !self.has_recorded_audio() {
                     console_log!("다운로드할 녹음된 오디오가 없습니다");
                     return false;
                 }
-                
-                // 포맷에 따른 MIME 타입과 확장자 결정
-                let (mime_type, extension) = match format.as_str() {
-                    "mp3" => ("audio/mpeg", "mp3"),
-                    "wav" => ("audio/wav", "wav"),
-                    "ogg" => ("audio/ogg", "ogg"),
-                    "m4a" => ("audio/mp4", "m4a"),
-                    _ => ("audio/webm", "webm"), // 기본값
-                };
+
+                // 이전 내보내기가 끝나기 전에는 새로 시작하지 않음
+                if self.pending_export.is_some() {
+                    console_warn!("이전 녹음 내보내기가 아직 진행 중입니다");
+                    return false;
+                }
                 
                 // 오디오 URL로부터 다운로드 진행
                 if let Some(audio_url) = &self.recorded_audio_url {
@@ -2142,41 +2548,149 @@ impl Component for PitchAnalyzer {
                     let minutes = date.get_minutes();
                     let seconds = date.get_seconds();
                     
-                    let filename = format!(
-                        "recording_{:04}-{:02}-{:02}_{:02}-{:02}-{:02}.{}",
-                        year, month, day, hours, minutes, seconds, extension
+                    // WebM 원본은 자를 수 없으므로 구간 선택은 인코딩하는 포맷에서만 적용
+                    let region = match options.region {
+                        Some(_) if options.format == ExportFormat::WebM => {
+                            console_warn!("WebM 원본은 구간 내보내기를 지원하지 않아 전체를 저장합니다");
+                            None
+                        }
+                        region => region,
+                    };
+                    let region_suffix = region
+                        .map(|(start, end)| format!("_{:.1}-{:.1}s", start, end))
+                        .unwrap_or_default();
+                    let basename = format!(
+                        "recording_{:04}-{:02}-{:02}_{:02}-{:02}-{:02}{}",
+                        year, month, day, hours, minutes, seconds, region_suffix
                     );
+                    let filename = format!("{}.{}", basename, options.format.value());
+
+                    // 구간 내보내기는 같은 구간의 피치 데이터도 함께 저장
+                    if let Some(region) = region {
+                        let url = crate::audio_export::pitch_slice_json(&self.history, region)
+                            .map_err(|e| JsValue::from_str(&e.to_string()))
+                            .and_then(|json| crate::audio_export::bytes_to_blob(json.as_bytes(), "application/json"))
+                            .and_then(|blob| web_sys::Url::create_object_url_with_blob(&blob));
+                        match url {
+                            Ok(url) => {
+                                Self::download_audio_file(&url, &format!("{}_pitch.json", basename));
+                                let _ = web_sys::Url::revoke_object_url(&url);
+                            }
+                            Err(err) => {
+                                console_error!("피치 데이터 내보내기 실패: {:?}", err);
+                            }
+                        }
+                    }
 
-                    // WebM이 아닌 다른 포맷이 선택된 경우 포맷 변환 시도
-                    if format != "webm" {
-                        // 포맷 변환을 위한 오디오 컨텍스트 생성 시도
-                        if let Ok(audio_context) = web_sys::AudioContext::new() {
-                            // 현재 WebM 오디오를 다른 포맷으로 변환하는 로직
-                            // 이는 복잡한 작업이므로 현재는 경고 메시지만 표시
-                            console_warn!("포맷 변환 기능은 아직 구현되지 않았습니다. {} 포맷으로 변환하려면 추가 구현이 필요합니다.", format);
-                            
-                            // 현재는 WebM 파일을 선택된 확장자로 다운로드 (실제 변환은 미구현)
-                            self.download_audio_file(&audio_url, &filename);
-                        } else {
-                            console_error!("오디오 컨텍스트 생성 실패");
-                            return false;
+                    match options.encode_format() {
+                        None => {
+                            // WebM은 녹음된 파일을 그대로 다운로드
+                            Self::download_audio_file(audio_url, &filename);
+                        }
+                        Some(encode_format) => {
+                            // PCM으로 디코딩한 뒤 인코더 워커에서 선택한 포맷으로 인코딩
+                            let link = ctx.link().clone();
+                            let audio_url = audio_url.clone();
+                            // 선택 구간은 테이크 시간축이므로 녹음 파일 위치로 옮김 (녹음은 정렬 지연만큼 늦게 담김)
+                            let latency = self.take_latency;
+                            let audio_region = region.map(|(start, end)| (start + latency, end + latency));
+                            self.pending_export = Some((filename.clone(), options.format));
+                            self.arm_export_watchdog(ctx);
+                            Self::notify_export_progress(Some(0.0));
+
+                            wasm_bindgen_futures::spawn_local(async move {
+                                match crate::audio_export::decode_recording(&audio_url, options.sample_rate, options.channels).await {
+                                    Ok(channels) => link.send_message(Msg::EncodeRecording(EncodeRequest {
+                                        format: encode_format,
+                                        sample_rate: options.sample_rate,
+                                        channels: match audio_region {
+                                            Some(region) => crate::audio_export::trim_channels(channels, options.sample_rate, region),
+                                            None => channels,
+                                        },
+                                    })),
+                                    Err(err) => link.send_message(Msg::ExportFailed(format!("녹음 디코딩 실패: {:?}", err))),
+                                }
+                            });
                         }
-                    } else {
-                        // WebM 포맷인 경우 직접 다운로드
-                        self.download_audio_file(&audio_url, &filename);
                     }
                     
-                    console_log!("오디오 다운로드 시작: {} (포맷: {})", filename, format);
+                    console_log!("오디오 다운로드 시작: {} (포맷: {})", filename, options.format.value());
                     return true;
                 }
                 
                 console_error!("오디오 다운로드 실패");
                 false
             },
+
+            Msg::EncodeRecording(request) => {
+                // 디코딩 중 실패/시간 초과로 취소된 내보내기
+                if self.pending_export.is_none() {
+                    return false;
+                }
+                self.arm_export_watchdog(ctx);
+
+                let link = ctx.link().clone();
+                let encoder = self.encoder.get_or_insert_with(|| {
+                    EncoderWorker::spawner()
+                        .callback(move |response| link.send_message(Msg::EncoderResponse(response)))
+                        .spawn("/encoder_worker.js")
+                });
+                encoder.send(request);
+                false
+            },
+
+            Msg::EncoderResponse(EncodeResponse::Progress(progress)) => {
+                if self.pending_export.is_none() {
+                    return false;
+                }
+                self.arm_export_watchdog(ctx);
+                Self::notify_export_progress(Some(progress as f64));
+                false
+            },
+
+            Msg::EncoderResponse(EncodeResponse::Finished(bytes)) => {
+                let Some((filename, format)) = self.pending_export.take() else {
+                    return false;
+                };
+                self.export_watchdog = None;
+                Self::notify_export_progress(None);
+
+                let url = crate::audio_export::bytes_to_blob(&bytes, format.mime_type())
+                    .and_then(|blob| web_sys::Url::create_object_url_with_blob(&blob));
+                match url {
+                    Ok(url) => {
+                        Self::download_audio_file(&url, &filename);
+                        let _ = web_sys::Url::revoke_object_url(&url);
+                        console_log!("녹음 내보내기 완료: {} ({} bytes)", filename, bytes.len());
+                    }
+                    Err(err) => {
+                        self.fail_export(format!("내보낸 파일 URL 생성 실패: {:?}", err));
+                    }
+                }
+                false
+            },
+
+            Msg::ExportFailed(message) => {
+                if self.pending_export.is_some() {
+                    self.fail_export(message);
+                }
+                false
+            },
+
+            Msg::ExportTimedOut => {
+                // 멈춘 워커는 버리고 다음 내보내기 때 새로 생성
+                self.encoder = None;
+                self.fail_export("녹음 내보내기 응답이 없어 취소했습니다".to_string());
+                false
+            },
             
             // 새 메시지 추가: 컴포넌트 상태 완전 초기화
             Msg::ResetComponent => {
                 console_log!("PitchAnalyzer 컴포넌트 상태 초기화 시작");
+                self.pause_overdub_tracks();
+                if let Some(count_in) = self.count_in.take() {
+                    count_in.cancel();
+                }
                 
                 // 오디오 재생/녹음 관련 상태 초기화
                 if self.is_playing {
@@ -2195,26 +2709,12 @@ impl Component for PitchAnalyzer {
                     }
                     self.is_recording = false;
                 }
-                
-                // 오디오 컨텍스트 정리
-                if let Some(ctx) = &self.audio_ctx {
-                    let _ = ctx.close();
-                }
-                
-                // 스트림 트랙 정지
-                if let Some(stream) = &self._stream {
-                    let tracks = stream.get_audio_tracks();
-                    for i in 0..tracks.length() {
-                        let track_js = tracks.get(i);
-                        let track = web_sys::MediaStreamTrack::from(track_js);
-                        track.stop();
-                    }
+                if let Some(stems) = self.stem_recorders.take() {
+                    stems.cancel();
                 }
                 
-                // URL 리소스 정리
-                if let Some(url) = &self.recorded_audio_url {
-                    let _ = web_sys::Url::revoke_object_url(url);
-                }
+                // 마이크 사용 종료 (공유 컨텍스트는 닫지 않음)
+                self.release_microphone();
                 
                 // 모든 인터벌 및 타이머 정리
                 self.analysis_interval = None;
@@ -2234,10 +2734,8 @@ impl Component for PitchAnalyzer {
                     recorder.set_onstop(None);
                 }
                 
-                // 스피커 노드 연결 해제
-                if let Some(speaker_node) = &self.speaker_node {
-                    speaker_node.disconnect();
-                }
+                // 모니터 경로 연결 해제
+                self.disconnect_monitor();
                 
                 // 모든 데이터 컬렉션 비우기
                 self.prev_freqs.clear();
@@ -2285,11 +2783,172 @@ impl Component for PitchAnalyzer {
                 self.amplitude_history.clear();
                 self.current_rms = 0.0;
                 
+                // 녹음 URL은 테이크가 소유하므로 해제하지 않고, 선택된 테이크를 다시 불러옴
+                self.loaded_take_id = None;
+                ctx.link().send_message(Msg::TakesChanged);
+                
                 console_log!("PitchAnalyzer 컴포넌트 상태 초기화 완료");
                 
                 true
             },
+            
+            Msg::TargetMelodyChanged => {
+                // 전역에 보관된 목표 멜로디를 다시 읽어옴
+                self.target_melody = crate::tools::target_melody::current_target_melody();
+                true
+            },
+
+            Msg::TakesChanged => {
+                self.takes = crate::tools::take_library::take_summaries();
+                self.active_take_id = crate::tools::take_library::active_take_id();
+
+                // 삭제된 테이크는 반주 트랙에서도 뺌
+                let takes = &self.takes;
+                let (kept, removed): (Vec<OverdubTrack>, Vec<OverdubTrack>) = std::mem::take(&mut self.overdub_tracks)
+                    .into_iter()
+                    .partition(|track| takes.iter().any(|take| take.id == track.take_id));
+                self.overdub_tracks = kept;
+                removed.iter().for_each(OverdubTrack::close);
+
+                // 녹음 중에는 실시간 차트를 유지
+                if self.is_recording || self.active_take_id == self.loaded_take_id {
+                    return true;
+                }
+
+                match crate::tools::take_library::active_take() {
+                    Some(take) => self.load_take(ctx, take),
+                    None => self.clear_loaded_take(ctx),
+                }
+                true
+            },
+
+            Msg::RegionSelected(region) => {
+                if self.selected_region == region {
+                    return false;
+                }
+                self.selected_region = region;
+                self.preview_end = None;
+                if region.is_none() {
+                    self.loop_enabled = false;
+                }
+                true
+            },
+
+            Msg::PreviewRegion => {
+                let Some((start, end)) = self.selected_region else {
+                    return false;
+                };
+                if !self.has_recorded_audio() || self.is_recording || self.last_recording_time <= 0.0 {
+                    return false;
+                }
+
+                // 구간 시작으로 이동한 뒤 재생, 구간 끝은 UpdatePlaybackTime에서 확인
+                Component::update(self, ctx, Msg::SeekPlayback(start / self.last_recording_time));
+                if !self.is_playing {
+                    Component::update(self, ctx, Msg::StartPlayback);
+                }
+                self.preview_end = Some(end);
+                console_log!("구간 미리듣기: {:.2}초 ~ {:.2}초", start, end);
+                true
+            },
+
+            Msg::ClearRegion => {
+                self.selected_region = None;
+                self.preview_end = None;
+                self.loop_enabled = false;
+                dispatch_region_selected(None);
+                true
+            },
+
+            Msg::SetLoopPoint(is_start) => {
+                if !self.has_recorded_audio() || self.is_recording {
+                    return false;
+                }
+
+                // 반대쪽 지점이 없거나 순서가 뒤집히면 녹음 처음/끝을 사용
+                let time = self.playback_time.clamp(0.0, self.last_recording_time);
+                let region = if is_start {
+                    let end = self.selected_region.map(|(_, end)| end).filter(|end| *end > time).unwrap_or(self.last_recording_time);
+                    (time, end)
+                } else {
+                    let start = self.selected_region.map(|(start, _)| start).filter(|start| *start < time).unwrap_or(0.0);
+                    (start, time)
+                };
+                if region.1 - region.0 < 0.1 {
+                    console_warn!("반복 구간이 너무 짧습니다: {:.2}초 ~ {:.2}초", region.0, region.1);
+                    return false;
+                }
+
+                self.selected_region = Some(region);
+                dispatch_region_selected(Some(region));
+                true
+            },
+
+            Msg::ToggleLoop => {
+                if self.selected_region.is_none() {
+                    return false;
+                }
+                self.loop_enabled = !self.loop_enabled;
+                self.preview_end = None;
+
+                // 재생 중에 켜면 구간 밖에 있을 때 바로 A 지점으로
+                if let Some((start, end)) = self.loop_region() {
+                    if self.is_playing && (self.playback_time < start || self.playback_time >= end) {
+                        self.jump_to_loop_start(start);
+                    }
+                }
+                console_log!("A-B 반복: {}", self.loop_enabled);
+                true
+            },
+
+            Msg::SetPlaybackRate(rate) => {
+                let rate = rate.clamp(0.5, 1.5);
+                if (rate - self.playback_rate).abs() < f64::EPSILON {
+                    return false;
+                }
+                self.playback_rate = rate;
+                if let Some(audio_element) = &self.audio_element {
+                    Self::apply_playback_rate(audio_element, rate);
+                }
+                for track in &self.overdub_tracks {
+                    Self::apply_playback_rate(track.audio(), rate);
+                }
+                crate::settings::update_settings(|s| s.analyzer.playback_rate = rate);
+                true
+            },
+        }
+    }
+
+    fn changed(&mut self, ctx: &Context<Self>, _old_props: &Self::Properties) -> bool {
+        self.show_links = ctx.props().show_links.unwrap_or(true);
+        true
+    }
+
+    // 상태가 바뀌었으면 미니 상태 바 등에 알림
+    fn rendered(&mut self, _ctx: &Context<Self>, _first_render: bool) {
+        let status = LiveStatus {
+            mic_active: self.mic_active,
+            recording: self.is_recording,
+            counting_in: self.count_in.is_some(),
+            note: (self.mic_active && !self.is_playing && self.current_freq > 0.0)
+                .then(|| frequency_to_note_octave(self.current_freq)),
+        };
+        if self.last_live_status.as_ref() != Some(&status) {
+            self.last_live_status = Some(status.clone());
+            emit(AppEvent::LiveStatus(status));
+        }
+    }
+
+    // 페이지 이동이나 백그라운드 세션 전환으로 분석기가 사라지면 마이크 반납
+    fn destroy(&mut self, _ctx: &Context<Self>) {
+        if let Some(count_in) = self.count_in.take() {
+            count_in.cancel();
         }
+        if let Some(stems) = self.stem_recorders.take() {
+            stems.cancel();
+        }
+        self.release_microphone();
+        emit(AppEvent::LiveStatus(LiveStatus::default()));
     }
 
     fn view(&self, ctx: &Context<Self>) -> Html {
@@ -2328,16 +2987,23 @@ impl Component for PitchAnalyzer {
         let is_playing = self.is_playing;
         let is_recording = self.is_recording;
         let is_frozen = self.is_frozen;
+        let target_notes = self.target_melody.as_ref().map(|m| m.shifted_notes()).unwrap_or_default();
+        // 뮤트하지 않은 반주 트랙의 피치를 트랙 색으로 겹쳐 그림
+        let traces: Vec<PitchTrace> = self.overdub_tracks.iter().filter(|track| !track.mix.muted).map(OverdubTrack::trace).collect();
 
         // 피치 플롯 컴포넌트
         let pitch_plot = html! {
             <PitchPlot 
                 current_freq={current_freq} 
-                history={history} 
+                history={history.clone()} 
                 playback_time={playback_time}
                 is_playing={is_playing}
                 is_recording={is_recording}
                 is_frozen={is_frozen}
+                target_notes={target_notes}
+                region={self.selected_region}
+                beat_grid={self.beat_grid}
+                traces={traces}
             />
         };
 
@@ -2433,7 +3099,13 @@ impl Component for PitchAnalyzer {
         } else {
             // 직접 렌더링 (상세 페이지)
             // 현재 라우트에 따라 해당 컴포넌트만 렌더링
-            let current_route = if let Some(window) = web_sys::window() {
+            let current_route = if let Some(route) = &ctx.props().route {
+                match route {
+                    Route::AmplitudeVisualizer => "amplitude",
+                    Route::Metronome => "metronome",
+                    _ => "pitch",
+                }
+            } else if let Some(window) = web_sys::window() {
                 if let Some(location) = window.location().pathname().ok() {
                     if location.contains("amplitude") {
                         "amplitude"
@@ -2465,7 +3137,23 @@ impl Component for PitchAnalyzer {
                         } else if current_route == "piano-keyboard" {
                             html! { <Piano /> }
                         } else {
-                            pitch_plot
+                            html! {
+                                <>
+                                    { pitch_plot }
+                                    { self.region_bar(ctx) }
+                                    { self.overdub_panel(ctx) }
+                                    <TakeLibraryPanel
+                                        takes={self.takes.clone()}
+                                        active_id={self.active_take_id}
+                                        is_recording={is_recording}
+                                    />
+                                    <TargetMelodyPanel
+                                        history={history}
+                                        melody={self.target_melody.clone()}
+                                        is_recording={is_recording}
+                                    />
+                                </>
+                            }
                         }
                     }
                 </div>
@@ -2477,15 +3165,32 @@ impl Component for PitchAnalyzer {
 // Yew 앱 진입점
 #[function_component(App)]
 fn app() -> Html {
+    // 모든 도구가 공유하는 오디오 엔진
+    let engine = use_memo((), |_| AudioEngine::new());
+
     html! {
-        <BrowserRouter>
-            <Switch<Route> render={switch} />
-        </BrowserRouter>
+        <ContextProvider<AudioEngine> context={(*engine).clone()}>
+            <BrowserRouter>
+                <Switch<Route> render={switch} />
+            </BrowserRouter>
+        </ContextProvider<AudioEngine>>
     }
 }
 
+// setTimeout을 기다리는 Future (카운트인, 지연 측정 대기용)
+pub(crate) async fn wait_ms(ms: i32) {
+    let promise = js_sys::Promise::new(&mut |resolve, _reject| {
+        if let Some(window) = web_sys::window() {
+            let _ = window.set_timeout_with_callback_and_timeout_and_arguments_0(&resolve, ms);
+        }
+    });
+    let _ = JsFuture::from(promise).await;
+}
+
 // main 함수 정의 (wasm 앱 진입점)
 fn main() {
     wasm_logger::init(wasm_logger::Config::default());
+    // 지난 세션에 저장된 녹음 테이크 복원
+    wasm_bindgen_futures::spawn_local(crate::tools::take_library::restore_saved_takes());
     yew::Renderer::<App>::new().render();
 }
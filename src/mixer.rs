@@ -0,0 +1,210 @@
+use gloo::events::EventListener;
+use wasm_bindgen::JsCast;
+use web_sys::KeyboardEvent;
+use yew::prelude::*;
+
+use crate::audio_engine::{AudioEngine, Channel, RecordSource};
+use crate::event_bus::{use_app_event, AppEvent};
+use crate::input_devices::InputDevicePanel;
+use crate::monitor_effects::MonitorEffectsPanel;
+use crate::settings;
+
+// 상단 헤더의 믹서 (채널별 볼륨/뮤트, 마스터 볼륨)
+#[function_component(MixerPanel)]
+pub fn mixer_panel() -> Html {
+    let engine = use_context::<AudioEngine>().unwrap_or_default();
+    let is_open = use_state(|| false);
+    // 엔진 값은 RefCell에 있으므로 변경 후 다시 그리도록 카운터 사용
+    let revision = use_state(|| 0u32);
+
+    let toggle_open = {
+        let is_open = is_open.clone();
+        Callback::from(move |_| is_open.set(!*is_open))
+    };
+
+    let on_master_input = {
+        let engine = engine.clone();
+        let revision = revision.clone();
+        Callback::from(move |e: InputEvent| {
+            let input = e.target_unchecked_into::<web_sys::HtmlInputElement>();
+            engine.set_master_gain(input.value().parse::<f32>().unwrap_or(100.0) / 100.0);
+            revision.set(*revision + 1);
+        })
+    };
+
+    // 단축키/헤더 버튼으로 전체 음소거가 바뀌어도 다시 그림
+    {
+        let revision = revision.clone();
+        use_app_event(move |event| {
+            if let AppEvent::MasterMuteChanged { .. } = event {
+                revision.set(*revision + 1);
+            }
+        });
+    }
+
+    let on_master_mute_click = {
+        let engine = engine.clone();
+        Callback::from(move |_| engine.toggle_mute_all())
+    };
+    let master_muted = engine.master_muted();
+
+    let recording = settings::load_settings().recording;
+
+    let source_toggle = |source: RecordSource| {
+        let enabled = source.is_enabled(&recording);
+        let on_change = {
+            let revision = revision.clone();
+            Callback::from(move |_: Event| {
+                settings::update_settings(|s| source.set_enabled(&mut s.recording, !enabled));
+                revision.set(*revision + 1);
+            })
+        };
+
+        html! {
+            <label class="mixer-record-source">
+                <input type="checkbox" checked={enabled} onchange={on_change} />
+                { source.label() }
+            </label>
+        }
+    };
+
+    let on_stems_change = {
+        let revision = revision.clone();
+        let stems = recording.stems;
+        Callback::from(move |_: Event| {
+            settings::update_settings(|s| s.recording.stems = !stems);
+            revision.set(*revision + 1);
+        })
+    };
+
+    let channel_row = |channel: Channel| {
+        let state = engine.channel_state(channel);
+        let on_gain_input = {
+            let engine = engine.clone();
+            let revision = revision.clone();
+            Callback::from(move |e: InputEvent| {
+                let input = e.target_unchecked_into::<web_sys::HtmlInputElement>();
+                engine.set_channel_gain(channel, input.value().parse::<f32>().unwrap_or(100.0) / 100.0);
+                revision.set(*revision + 1);
+            })
+        };
+        let on_mute_click = {
+            let engine = engine.clone();
+            let revision = revision.clone();
+            Callback::from(move |_| {
+                engine.set_channel_muted(channel, !state.muted);
+                revision.set(*revision + 1);
+            })
+        };
+
+        html! {
+            <div class={classes!("mixer-channel", state.muted.then_some("muted"))}>
+                <span class="mixer-channel-label">{ channel.label() }</span>
+                <input
+                    type="range"
+                    min="0"
+                    max="100"
+                    value={((state.gain * 100.0).round() as u32).to_string()}
+                    oninput={on_gain_input}
+                />
+                <button
+                    class="mixer-mute"
+                    onclick={on_mute_click}
+                    title={if state.muted { "음소거 해제" } else { "음소거" }}
+                >
+                    { if state.muted { "🔇" } else { "🔊" } }
+                </button>
+            </div>
+        }
+    };
+
+    html! {
+        <div class="mixer-dropdown">
+            <button class="icon-button" onclick={toggle_open} title="믹서">
+                { "🎚️" }
+            </button>
+            if *is_open {
+                <div class="mixer-panel">
+                    <InputDevicePanel />
+                    { for Channel::ALL.into_iter().map(channel_row) }
+                    <MonitorEffectsPanel />
+                    <div class={classes!("mixer-channel", "mixer-master", master_muted.then_some("muted"))}>
+                        <span class="mixer-channel-label">{ "마스터" }</span>
+                        <input
+                            type="range"
+                            min="0"
+                            max="100"
+                            value={((engine.master_gain() * 100.0).round() as u32).to_string()}
+                            oninput={on_master_input}
+                        />
+                        <span class="mixer-value">{ format!("{}%", (engine.master_gain() * 100.0).round() as u32) }</span>
+                        <button
+                            class="mixer-mute"
+                            onclick={on_master_mute_click}
+                            title={if master_muted { "전체 음소거 해제 (Alt+M)" } else { "전체 음소거 (Alt+M)" }}
+                        >
+                            { if master_muted { "🔇" } else { "🔊" } }
+                        </button>
+                    </div>
+                    // 다음 녹음부터 적용
+                    <div class="mixer-recording">
+                        <span class="mixer-channel-label">{ "녹음에 포함" }</span>
+                        <div class="mixer-record-sources">
+                            { for RecordSource::ALL.into_iter().map(source_toggle) }
+                        </div>
+                        <label class="mixer-record-source">
+                            <input type="checkbox" checked={recording.stems} onchange={on_stems_change} />
+                            { "소리별 스템도 저장" }
+                        </label>
+                    </div>
+                </div>
+            }
+        </div>
+    }
+}
+
+// 헤더의 전체 음소거 버튼 (어느 페이지에서든 Alt+M으로도 전환)
+// 녹음/재생은 계속되고 스피커로 나가는 소리만 끔
+#[function_component(MuteAllButton)]
+pub fn mute_all_button() -> Html {
+    let engine = use_context::<AudioEngine>().unwrap_or_default();
+    let muted = use_state(|| engine.master_muted());
+
+    {
+        let muted = muted.clone();
+        use_app_event(move |event| {
+            if let AppEvent::MasterMuteChanged { muted: value } = event {
+                muted.set(*value);
+            }
+        });
+    }
+
+    {
+        let engine = engine.clone();
+        use_effect_with((), move |_| {
+            let listener = EventListener::new(&gloo::utils::document(), "keydown", move |event| {
+                let Some(event) = event.dyn_ref::<KeyboardEvent>() else {
+                    return;
+                };
+                // 피아노 건반 입력과 겹치지 않도록 Alt 조합만 사용
+                if event.alt_key() && !event.ctrl_key() && !event.meta_key() && event.code() == "KeyM" && !event.repeat() {
+                    event.prevent_default();
+                    engine.toggle_mute_all();
+                }
+            });
+            move || drop(listener)
+        });
+    }
+
+    let on_click = Callback::from(move |_| engine.toggle_mute_all());
+
+    html! {
+        <button
+            class={classes!("icon-button", "mute-all-button", muted.then_some("muted"))}
+            onclick={on_click}
+            title={if *muted { "전체 음소거 해제 (Alt+M)" } else { "전체 음소거 (Alt+M)" }}
+        >
+            { if *muted { "🔇" } else { "🔉" } }
+        </button>
+    }
+}
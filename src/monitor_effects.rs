@@ -0,0 +1,599 @@
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::*;
+use web_sys::{
+    AudioBuffer, AudioContext, AudioNode, AudioParam, BiquadFilterNode, BiquadFilterType, ConvolverNode, DelayNode,
+    DynamicsCompressorNode, GainNode, HtmlInputElement, HtmlSelectElement,
+};
+use yew::prelude::*;
+
+use crate::audio_engine::{ramp_gain, ramp_param, GAIN_RAMP_SECONDS};
+use crate::event_bus::{emit, use_app_event, AppEvent};
+use crate::settings::{self, MonitorSettings};
+
+// 조건부 로그 매크로 정의
+#[cfg(debug_assertions)]
+macro_rules! console_log {
+    ($($arg:tt)*) => {
+        web_sys::console::log_1(&format!($($arg)*).into());
+    };
+}
+
+#[cfg(not(debug_assertions))]
+macro_rules! console_log {
+    ($($arg:tt)*) => {};
+}
+
+// 모니터 이펙트 - 헤드폰으로 듣는 내 목소리에만 거는 EQ/컴프레서/리버브/딜레이
+// 녹음 버스의 마이크 센드는 엔진의 마이크 입력에서 바로 받으므로 녹음은 기본적으로 드라이
+// "이펙트 포함 녹음"을 켜면 모니터링 중에는 체인 출력이 마이크 센드로 대신 들어감
+//
+//   마이크 ─ 로우셸프 ─ 피킹 ─ 하이셸프 ─ 컴프레서 ─┬─ 드라이 ─────────────────┬─ 출력
+//                                                  ├─ 컨볼버(리버브) ─ 웻 ────┤
+//                                                  └─ 딜레이 ⟲ 피드백 ─ 웻 ───┘
+//
+// 꺼진 이펙트는 노드를 빼지 않고 값만 중립으로 두어, 모니터링 중에도 끊김 없이 켜고 끌 수 있음
+
+// 딜레이 노드가 허용하는 최대 지연 (초)
+const MAX_DELAY_SECONDS: f64 = 1.0;
+
+// 리버브 임펄스 응답 길이 범위 (초)
+const MIN_REVERB_DECAY: f32 = 0.2;
+const MAX_REVERB_DECAY: f32 = 6.0;
+
+// 꺼진 컴프레서 값 (임계값 0dB, 비율 1:1이면 소리에 손대지 않음)
+const BYPASS_THRESHOLD_DB: f32 = 0.0;
+const BYPASS_RATIO: f32 = 1.0;
+
+// 파라메트릭 EQ (로우셸프, 피킹, 하이셸프)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EqSettings {
+    pub enabled: bool,
+    pub low_freq: f32,
+    pub low_gain_db: f32,
+    pub mid_freq: f32,
+    pub mid_gain_db: f32,
+    pub mid_q: f32,
+    pub high_freq: f32,
+    pub high_gain_db: f32,
+}
+
+impl Default for EqSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            low_freq: 150.0,
+            low_gain_db: 0.0,
+            mid_freq: 1000.0,
+            mid_gain_db: 0.0,
+            mid_q: 1.0,
+            high_freq: 5000.0,
+            high_gain_db: 0.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CompressorSettings {
+    pub enabled: bool,
+    pub threshold_db: f32,
+    pub ratio: f32,
+    pub attack_ms: f32,
+    pub release_ms: f32,
+}
+
+impl Default for CompressorSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            threshold_db: -24.0,
+            ratio: 4.0,
+            attack_ms: 5.0,
+            release_ms: 150.0,
+        }
+    }
+}
+
+// 리버브 - 잔향 길이로 만든 임펄스 응답을 컨볼버에 넣음
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ReverbSettings {
+    pub enabled: bool,
+    pub decay_seconds: f32,
+    pub mix: f32, // 웻 레벨 (0.0 ~ 1.0)
+}
+
+impl Default for ReverbSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            decay_seconds: 1.5,
+            mix: 0.25,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DelaySettings {
+    pub enabled: bool,
+    pub time_ms: f32,
+    pub feedback: f32, // 되먹임 비율 (0.0 ~ 0.9)
+    pub mix: f32,      // 웻 레벨 (0.0 ~ 1.0)
+}
+
+impl Default for DelaySettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            time_ms: 250.0,
+            feedback: 0.3,
+            mix: 0.2,
+        }
+    }
+}
+
+// 모니터 체인 전체 값 (기본값은 모두 꺼짐 = 예전과 같은 생소리)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MonitorEffects {
+    pub eq: EqSettings,
+    pub compressor: CompressorSettings,
+    pub reverb: ReverbSettings,
+    pub delay: DelaySettings,
+}
+
+impl MonitorEffects {
+    // 저장소에서 읽은 값이 허용 범위를 벗어나면 보정
+    pub fn sanitized(mut self) -> Self {
+        let eq = &mut self.eq;
+        eq.low_freq = eq.low_freq.clamp(40.0, 500.0);
+        eq.mid_freq = eq.mid_freq.clamp(200.0, 8000.0);
+        eq.high_freq = eq.high_freq.clamp(2000.0, 16000.0);
+        for gain in [&mut eq.low_gain_db, &mut eq.mid_gain_db, &mut eq.high_gain_db] {
+            *gain = gain.clamp(-12.0, 12.0);
+        }
+        eq.mid_q = eq.mid_q.clamp(0.3, 10.0);
+
+        let compressor = &mut self.compressor;
+        compressor.threshold_db = compressor.threshold_db.clamp(-60.0, 0.0);
+        compressor.ratio = compressor.ratio.clamp(1.0, 20.0);
+        compressor.attack_ms = compressor.attack_ms.clamp(0.0, 1000.0);
+        compressor.release_ms = compressor.release_ms.clamp(10.0, 1000.0);
+
+        self.reverb.decay_seconds = self.reverb.decay_seconds.clamp(MIN_REVERB_DECAY, MAX_REVERB_DECAY);
+        self.reverb.mix = self.reverb.mix.clamp(0.0, 1.0);
+
+        self.delay.time_ms = self.delay.time_ms.clamp(20.0, (MAX_DELAY_SECONDS * 1000.0) as f32);
+        self.delay.feedback = self.delay.feedback.clamp(0.0, 0.9);
+        self.delay.mix = self.delay.mix.clamp(0.0, 1.0);
+        self
+    }
+}
+
+// 이름 붙은 이펙트 묶음
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MonitorPreset {
+    pub name: String,
+    pub effects: MonitorEffects,
+}
+
+// 기본 제공 프리셋 (사용자 프리셋은 설정에 저장)
+pub fn builtin_presets() -> Vec<MonitorPreset> {
+    let preset = |name: &str, effects: MonitorEffects| MonitorPreset { name: name.to_string(), effects };
+
+    vec![
+        preset("드라이", MonitorEffects::default()),
+        preset(
+            "보컬 룸",
+            MonitorEffects {
+                eq: EqSettings { enabled: true, low_gain_db: -3.0, high_gain_db: 2.0, ..Default::default() },
+                compressor: CompressorSettings { enabled: true, ..Default::default() },
+                reverb: ReverbSettings { enabled: true, decay_seconds: 0.8, mix: 0.2 },
+                ..Default::default()
+            },
+        ),
+        preset(
+            "홀",
+            MonitorEffects {
+                compressor: CompressorSettings { enabled: true, threshold_db: -18.0, ratio: 3.0, ..Default::default() },
+                reverb: ReverbSettings { enabled: true, decay_seconds: 3.0, mix: 0.35 },
+                ..Default::default()
+            },
+        ),
+        preset(
+            "슬랩백",
+            MonitorEffects {
+                compressor: CompressorSettings { enabled: true, ..Default::default() },
+                delay: DelaySettings { enabled: true, time_ms: 110.0, feedback: 0.1, mix: 0.3 },
+                ..Default::default()
+            },
+        ),
+    ]
+}
+
+fn is_builtin_preset(name: &str) -> bool {
+    builtin_presets().iter().any(|preset| preset.name == name)
+}
+
+// 잔향 길이만큼 지수적으로 줄어드는 스테레오 노이즈 (끝에서 약 -60dB)
+fn impulse_response(ctx: &AudioContext, decay_seconds: f32) -> Result<AudioBuffer, JsValue> {
+    let sample_rate = ctx.sample_rate();
+    let length = ((decay_seconds * sample_rate) as u32).max(1);
+    let buffer = ctx.create_buffer(2, length, sample_rate)?;
+
+    for channel in 0..2 {
+        let samples: Vec<f32> = (0..length)
+            .map(|i| {
+                let t = i as f32 / length as f32;
+                let noise = js_sys::Math::random() as f32 * 2.0 - 1.0;
+                noise * (-6.9 * t).exp()
+            })
+            .collect();
+        buffer.copy_to_channel(&samples, channel)?;
+    }
+    Ok(buffer)
+}
+
+// 모니터 경로에 끼우는 이펙트 노드 묶음
+pub struct MonitorChain {
+    ctx: AudioContext,
+    low_shelf: BiquadFilterNode,
+    peaking: BiquadFilterNode,
+    high_shelf: BiquadFilterNode,
+    compressor: DynamicsCompressorNode,
+    dry: GainNode,
+    convolver: ConvolverNode,
+    reverb_wet: GainNode,
+    delay: DelayNode,
+    delay_feedback: GainNode,
+    delay_wet: GainNode,
+    output: GainNode,
+    reverb_decay: Option<f32>, // 지금 컨볼버에 들어 있는 임펄스 응답의 잔향 길이
+}
+
+impl MonitorChain {
+    pub fn new(ctx: &AudioContext, effects: &MonitorEffects) -> Result<Self, JsValue> {
+        let low_shelf = ctx.create_biquad_filter()?;
+        low_shelf.set_type(BiquadFilterType::Lowshelf);
+        let peaking = ctx.create_biquad_filter()?;
+        peaking.set_type(BiquadFilterType::Peaking);
+        let high_shelf = ctx.create_biquad_filter()?;
+        high_shelf.set_type(BiquadFilterType::Highshelf);
+        let compressor = ctx.create_dynamics_compressor()?;
+        let dry = ctx.create_gain()?;
+        let convolver = ctx.create_convolver()?;
+        let reverb_wet = ctx.create_gain()?;
+        let delay = ctx.create_delay_with_max_delay_time(MAX_DELAY_SECONDS)?;
+        let delay_feedback = ctx.create_gain()?;
+        let delay_wet = ctx.create_gain()?;
+        let output = ctx.create_gain()?;
+
+        low_shelf.connect_with_audio_node(&peaking)?;
+        peaking.connect_with_audio_node(&high_shelf)?;
+        high_shelf.connect_with_audio_node(&compressor)?;
+
+        compressor.connect_with_audio_node(&dry)?;
+        dry.connect_with_audio_node(&output)?;
+
+        compressor.connect_with_audio_node(&convolver)?;
+        convolver.connect_with_audio_node(&reverb_wet)?;
+        reverb_wet.connect_with_audio_node(&output)?;
+
+        compressor.connect_with_audio_node(&delay)?;
+        delay.connect_with_audio_node(&delay_feedback)?;
+        delay_feedback.connect_with_audio_node(&delay)?;
+        delay.connect_with_audio_node(&delay_wet)?;
+        delay_wet.connect_with_audio_node(&output)?;
+
+        let mut chain = Self {
+            ctx: ctx.clone(),
+            low_shelf,
+            peaking,
+            high_shelf,
+            compressor,
+            dry,
+            convolver,
+            reverb_wet,
+            delay,
+            delay_feedback,
+            delay_wet,
+            output,
+            reverb_decay: None,
+        };
+        chain.apply(effects)?;
+        Ok(chain)
+    }
+
+    // 마이크 소리를 연결할 입력
+    pub fn input(&self) -> &AudioNode {
+        &self.low_shelf
+    }
+
+    // 이펙트가 걸린 출력
+    pub fn output(&self) -> &AudioNode {
+        &self.output
+    }
+
+    // 값 반영 (모니터링 중에도 클릭 없이 바로 들림)
+    pub fn apply(&mut self, effects: &MonitorEffects) -> Result<(), JsValue> {
+        let ramp = |param: AudioParam, value: f32| ramp_param(&self.ctx, &param, value, GAIN_RAMP_SECONDS);
+
+        let eq = &effects.eq;
+        let eq_gain = |gain_db: f32| if eq.enabled { gain_db } else { 0.0 };
+        ramp(self.low_shelf.frequency(), eq.low_freq);
+        ramp(self.low_shelf.gain(), eq_gain(eq.low_gain_db));
+        ramp(self.peaking.frequency(), eq.mid_freq);
+        ramp(self.peaking.q(), eq.mid_q);
+        ramp(self.peaking.gain(), eq_gain(eq.mid_gain_db));
+        ramp(self.high_shelf.frequency(), eq.high_freq);
+        ramp(self.high_shelf.gain(), eq_gain(eq.high_gain_db));
+
+        let compressor = &effects.compressor;
+        if compressor.enabled {
+            ramp(self.compressor.threshold(), compressor.threshold_db);
+            ramp(self.compressor.ratio(), compressor.ratio);
+        } else {
+            ramp(self.compressor.threshold(), BYPASS_THRESHOLD_DB);
+            ramp(self.compressor.ratio(), BYPASS_RATIO);
+        }
+        ramp(self.compressor.attack(), compressor.attack_ms / 1000.0);
+        ramp(self.compressor.release(), compressor.release_ms / 1000.0);
+
+        // 임펄스 응답은 잔향 길이가 바뀔 때만 다시 만듦
+        let reverb = &effects.reverb;
+        if reverb.enabled && self.reverb_decay != Some(reverb.decay_seconds) {
+            self.convolver.set_buffer(Some(&impulse_response(&self.ctx, reverb.decay_seconds)?));
+            self.reverb_decay = Some(reverb.decay_seconds);
+            console_log!("리버브 임펄스 응답 생성: {:.1}초", reverb.decay_seconds);
+        }
+        ramp_gain(&self.reverb_wet, if reverb.enabled { reverb.mix } else { 0.0 });
+
+        let delay = &effects.delay;
+        ramp(self.delay.delay_time(), delay.time_ms / 1000.0);
+        ramp_gain(&self.delay_feedback, if delay.enabled { delay.feedback } else { 0.0 });
+        ramp_gain(&self.delay_wet, if delay.enabled { delay.mix } else { 0.0 });
+        Ok(())
+    }
+
+    // 모든 노드 연결 해제 (딜레이 되먹임 고리도 끊음)
+    pub fn disconnect(&self) {
+        let nodes: [&AudioNode; 11] = [
+            &self.low_shelf,
+            &self.peaking,
+            &self.high_shelf,
+            &self.compressor,
+            &self.dry,
+            &self.convolver,
+            &self.reverb_wet,
+            &self.delay,
+            &self.delay_feedback,
+            &self.delay_wet,
+            &self.output,
+        ];
+        for node in nodes {
+            let _ = node.disconnect();
+        }
+    }
+}
+
+// 설정을 저장하고 모니터링 중인 분석기에 알림
+fn save_monitor_settings(monitor: &MonitorSettings) {
+    settings::update_settings(|s| s.monitor = monitor.clone());
+    emit(AppEvent::MonitorEffectsChanged);
+}
+
+// 믹서 패널 안의 모니터 이펙트 섹션
+#[function_component(MonitorEffectsPanel)]
+pub fn monitor_effects_panel() -> Html {
+    let monitor = use_state(|| settings::load_settings().monitor);
+    let preset_name = use_state(String::new);
+
+    {
+        let monitor = monitor.clone();
+        use_app_event(move |event| {
+            if let AppEvent::SettingsReset = event {
+                monitor.set(settings::load_settings().monitor);
+            }
+        });
+    }
+
+    let update = {
+        let monitor = monitor.clone();
+        Callback::from(move |change: MonitorSettings| {
+            save_monitor_settings(&change);
+            monitor.set(change);
+        })
+    };
+
+    let presets: Vec<MonitorPreset> = builtin_presets().into_iter().chain(monitor.presets.iter().cloned()).collect();
+    let current_preset = presets.iter().find(|preset| preset.effects == monitor.effects).map(|preset| preset.name.clone());
+
+    let on_preset_change = {
+        let monitor = monitor.clone();
+        let update = update.clone();
+        let presets = presets.clone();
+        Callback::from(move |e: Event| {
+            let select: HtmlSelectElement = e.target_unchecked_into();
+            if let Some(preset) = presets.iter().find(|preset| preset.name == select.value()) {
+                update.emit(MonitorSettings { effects: preset.effects.clone(), ..(*monitor).clone() });
+            }
+        })
+    };
+
+    let on_name_input = {
+        let preset_name = preset_name.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            preset_name.set(input.value());
+        })
+    };
+
+    // 같은 이름의 사용자 프리셋은 덮어씀
+    let name = preset_name.trim().to_string();
+    let can_save = !name.is_empty() && !is_builtin_preset(&name);
+    let on_save = {
+        let monitor = monitor.clone();
+        let update = update.clone();
+        let preset_name = preset_name.clone();
+        let name = name.clone();
+        Callback::from(move |_: MouseEvent| {
+            let mut change = (*monitor).clone();
+            change.presets.retain(|preset| preset.name != name);
+            change.presets.push(MonitorPreset { name: name.clone(), effects: change.effects.clone() });
+            update.emit(change);
+            preset_name.set(String::new());
+        })
+    };
+
+    let deletable = current_preset.clone().filter(|name| !is_builtin_preset(name));
+    let on_delete = {
+        let monitor = monitor.clone();
+        let update = update.clone();
+        let deletable = deletable.clone();
+        Callback::from(move |_: MouseEvent| {
+            if let Some(name) = &deletable {
+                let mut change = (*monitor).clone();
+                change.presets.retain(|preset| &preset.name != name);
+                update.emit(change);
+            }
+        })
+    };
+
+    let on_record_wet_change = {
+        let monitor = monitor.clone();
+        let update = update.clone();
+        Callback::from(move |_: Event| {
+            update.emit(MonitorSettings { record_wet: !monitor.record_wet, ..(*monitor).clone() });
+        })
+    };
+
+    // 이펙트 켜기/끄기 체크박스
+    let effect_toggle = |label: &'static str, enabled: bool, set: fn(&mut MonitorEffects, bool)| {
+        let monitor = monitor.clone();
+        let update = update.clone();
+        let on_change = Callback::from(move |_: Event| {
+            let mut change = (*monitor).clone();
+            set(&mut change.effects, !enabled);
+            update.emit(change);
+        });
+
+        html! {
+            <label class="monitor-effect-toggle">
+                <input type="checkbox" checked={enabled} onchange={on_change} />
+                { label }
+            </label>
+        }
+    };
+
+    // 이펙트 값 슬라이더 (움직이는 동안 바로 반영)
+    let slider = |label: &'static str,
+                  value: f32,
+                  (min, max, step): (f32, f32, f32),
+                  display: String,
+                  set: fn(&mut MonitorEffects, f32)| {
+        let monitor = monitor.clone();
+        let update = update.clone();
+        let on_input = Callback::from(move |e: InputEvent| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            if let Ok(value) = input.value().parse::<f32>() {
+                let mut change = (*monitor).clone();
+                set(&mut change.effects, value);
+                update.emit(change);
+            }
+        });
+
+        html! {
+            <div class="monitor-effect-slider">
+                <span class="monitor-effect-label">{ label }</span>
+                <input
+                    type="range"
+                    min={min.to_string()}
+                    max={max.to_string()}
+                    step={step.to_string()}
+                    value={value.to_string()}
+                    oninput={on_input}
+                />
+                <span class="mixer-value">{ display }</span>
+            </div>
+        }
+    };
+
+    let effects = &monitor.effects;
+    let eq = &effects.eq;
+    let compressor = &effects.compressor;
+    let reverb = &effects.reverb;
+    let delay = &effects.delay;
+
+    html! {
+        // 모니터링 중이면 바로, 아니면 다음에 켤 때 적용
+        <details class="monitor-effects">
+            <summary class="mixer-channel-label">{ "모니터 이펙트" }</summary>
+            <div class="monitor-effects-presets">
+                <select class="mixer-input-device" onchange={on_preset_change}>
+                    if current_preset.is_none() {
+                        <option value="" selected=true>{ "사용자 설정" }</option>
+                    }
+                    { for presets.iter().map(|preset| html! {
+                        <option value={preset.name.clone()} selected={current_preset.as_ref() == Some(&preset.name)}>
+                            { &preset.name }
+                        </option>
+                    }) }
+                </select>
+                <button class="mixer-input-calibrate" onclick={on_delete} disabled={deletable.is_none()} title="선택한 사용자 프리셋 삭제">
+                    { "삭제" }
+                </button>
+            </div>
+            <div class="monitor-effects-presets">
+                <input
+                    type="text"
+                    class="monitor-preset-name"
+                    placeholder="프리셋 이름"
+                    value={(*preset_name).clone()}
+                    oninput={on_name_input}
+                />
+                <button class="mixer-input-calibrate" onclick={on_save} disabled={!can_save} title="지금 값을 프리셋으로 저장">
+                    { "저장" }
+                </button>
+            </div>
+
+            <div class={classes!("monitor-effect", (!eq.enabled).then_some("disabled"))}>
+                { effect_toggle("EQ", eq.enabled, |e, on| e.eq.enabled = on) }
+                { slider("저역", eq.low_gain_db, (-12.0, 12.0, 0.5), format!("{:+.1}dB", eq.low_gain_db), |e, v| e.eq.low_gain_db = v) }
+                { slider("저역 주파수", eq.low_freq, (40.0, 500.0, 10.0), format!("{:.0}Hz", eq.low_freq), |e, v| e.eq.low_freq = v) }
+                { slider("중역", eq.mid_gain_db, (-12.0, 12.0, 0.5), format!("{:+.1}dB", eq.mid_gain_db), |e, v| e.eq.mid_gain_db = v) }
+                { slider("중역 주파수", eq.mid_freq, (200.0, 8000.0, 50.0), format!("{:.0}Hz", eq.mid_freq), |e, v| e.eq.mid_freq = v) }
+                { slider("중역 Q", eq.mid_q, (0.3, 10.0, 0.1), format!("{:.1}", eq.mid_q), |e, v| e.eq.mid_q = v) }
+                { slider("고역", eq.high_gain_db, (-12.0, 12.0, 0.5), format!("{:+.1}dB", eq.high_gain_db), |e, v| e.eq.high_gain_db = v) }
+                { slider("고역 주파수", eq.high_freq, (2000.0, 16000.0, 100.0), format!("{:.0}Hz", eq.high_freq), |e, v| e.eq.high_freq = v) }
+            </div>
+
+            <div class={classes!("monitor-effect", (!compressor.enabled).then_some("disabled"))}>
+                { effect_toggle("컴프레서", compressor.enabled, |e, on| e.compressor.enabled = on) }
+                { slider("임계값", compressor.threshold_db, (-60.0, 0.0, 1.0), format!("{:.0}dB", compressor.threshold_db), |e, v| e.compressor.threshold_db = v) }
+                { slider("비율", compressor.ratio, (1.0, 20.0, 0.5), format!("{:.1}:1", compressor.ratio), |e, v| e.compressor.ratio = v) }
+                { slider("어택", compressor.attack_ms, (0.0, 200.0, 1.0), format!("{:.0}ms", compressor.attack_ms), |e, v| e.compressor.attack_ms = v) }
+                { slider("릴리스", compressor.release_ms, (10.0, 1000.0, 10.0), format!("{:.0}ms", compressor.release_ms), |e, v| e.compressor.release_ms = v) }
+            </div>
+
+            <div class={classes!("monitor-effect", (!reverb.enabled).then_some("disabled"))}>
+                { effect_toggle("리버브", reverb.enabled, |e, on| e.reverb.enabled = on) }
+                { slider("잔향", reverb.decay_seconds, (MIN_REVERB_DECAY, MAX_REVERB_DECAY, 0.1), format!("{:.1}초", reverb.decay_seconds), |e, v| e.reverb.decay_seconds = v) }
+                { slider("양", reverb.mix, (0.0, 1.0, 0.01), format!("{}%", (reverb.mix * 100.0).round() as u32), |e, v| e.reverb.mix = v) }
+            </div>
+
+            <div class={classes!("monitor-effect", (!delay.enabled).then_some("disabled"))}>
+                { effect_toggle("딜레이", delay.enabled, |e, on| e.delay.enabled = on) }
+                { slider("시간", delay.time_ms, (20.0, 1000.0, 5.0), format!("{:.0}ms", delay.time_ms), |e, v| e.delay.time_ms = v) }
+                { slider("피드백", delay.feedback, (0.0, 0.9, 0.01), format!("{}%", (delay.feedback * 100.0).round() as u32), |e, v| e.delay.feedback = v) }
+                { slider("양", delay.mix, (0.0, 1.0, 0.01), format!("{}%", (delay.mix * 100.0).round() as u32), |e, v| e.delay.mix = v) }
+            </div>
+
+            <label class="mixer-record-source" title="끄면 이펙트는 모니터에만 걸리고 녹음은 생소리로 남습니다">
+                <input type="checkbox" checked={monitor.record_wet} onchange={on_record_wet_change} />
+                { "이펙트 포함 녹음 (모니터링 중일 때)" }
+            </label>
+        </details>
+    }
+}
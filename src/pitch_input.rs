@@ -0,0 +1,122 @@
+use web_sys::{AnalyserNode, AudioContext, GainNode};
+use yew::Callback;
+
+use crate::analyze_multiple_frequencies;
+use crate::audio_engine::{AudioEngine, MicSource};
+
+// 조건부 로그 매크로 정의
+#[cfg(debug_assertions)]
+macro_rules! console_log {
+    ($($arg:tt)*) => {
+        web_sys::console::log_1(&format!($($arg)*).into());
+    };
+}
+
+#[cfg(not(debug_assertions))]
+macro_rules! console_log {
+    ($($arg:tt)*) => {};
+}
+
+// 연습 도구용 기본 마이크 감도 (피치 분석기 기본값과 동일)
+pub const DEFAULT_SENSITIVITY: f32 = 0.01;
+
+// 연습 도구(스케일 연습, 음 맞추기 등)에서 사용하는 마이크 입력
+// 피치 분석기 페이지가 아니어도 음높이를 검출할 수 있도록 엔진의 공유 마이크에 분석기를 연결
+pub struct MicInput {
+    engine: AudioEngine,
+    audio_ctx: AudioContext,
+    analyser: AnalyserNode,
+    source: GainNode, // 엔진의 마이크 입력
+}
+
+impl MicInput {
+    // 현재 버퍼에서 가장 강한 주파수 검출 (소리가 작으면 None)
+    pub fn detect_pitch(&self, sensitivity: f32) -> Option<f64> {
+        let mut buffer = vec![0.0f32; self.analyser.fft_size() as usize];
+        self.analyser.get_float_time_domain_data(&mut buffer[..]);
+
+        analyze_multiple_frequencies(&buffer, self.audio_ctx.sample_rate() as f64, sensitivity)
+            .first()
+            .map(|(freq, _)| *freq)
+            .filter(|freq| *freq > 0.0)
+    }
+
+    // 최근 샘플 구간의 최대 진폭 (박수 등 온셋 검출용)
+    pub fn peak_level(&self, recent_samples: usize) -> f32 {
+        let mut buffer = vec![0.0f32; self.analyser.fft_size() as usize];
+        self.analyser.get_float_time_domain_data(&mut buffer[..]);
+
+        let start = buffer.len().saturating_sub(recent_samples);
+        buffer[start..].iter().fold(0.0f32, |peak, s| peak.max(s.abs()))
+    }
+
+    // 분석기를 마이크에서 떼고 엔진에 마이크 반납 (공유 컨텍스트는 유지)
+    pub fn close(&self) {
+        let _ = self.source.disconnect_with_audio_node(&self.analyser);
+        self.engine.release_mic();
+        console_log!("연습용 마이크 입력 종료");
+    }
+}
+
+// 마이크를 열고 준비되면 콜백으로 전달
+pub fn open_microphone(engine: &AudioEngine, on_ready: Callback<Result<MicInput, String>>) {
+    let engine = engine.clone();
+    wasm_bindgen_futures::spawn_local(async move {
+        let result = match engine.acquire_mic().await {
+            Ok(mic) => build_input(&engine, mic),
+            Err(err) => Err(format!("마이크를 열 수 없습니다: {:?}", err)),
+        };
+        on_ready.emit(result);
+    });
+}
+
+fn build_input(engine: &AudioEngine, mic: MicSource) -> Result<MicInput, String> {
+    let connect = || -> Result<(AudioContext, AnalyserNode), String> {
+        let audio_ctx = engine.context().map_err(|e| format!("AudioContext 생성 실패: {:?}", e))?;
+        let analyser = audio_ctx
+            .create_analyser()
+            .map_err(|e| format!("AnalyserNode 생성 실패: {:?}", e))?;
+        analyser.set_fft_size(2048);
+        mic.source
+            .connect_with_audio_node(&analyser)
+            .map_err(|e| format!("마이크 소스 연결 실패: {:?}", e))?;
+        Ok((audio_ctx, analyser))
+    };
+
+    match connect() {
+        Ok((audio_ctx, analyser)) => {
+            console_log!("연습용 마이크 입력 준비 완료");
+            Ok(MicInput {
+                engine: engine.clone(),
+                audio_ctx,
+                analyser,
+                source: mic.source,
+            })
+        }
+        Err(err) => {
+            engine.release_mic();
+            Err(err)
+        }
+    }
+}
+
+// 두 주파수 간의 센트 차이
+pub fn cents_between(freq: f64, reference: f64) -> f64 {
+    1200.0 * (freq / reference).log2()
+}
+
+// 옥타브 차이를 무시한 센트 차이 (-600 ~ +600)
+pub fn cents_ignoring_octave(freq: f64, reference: f64) -> f64 {
+    let cents = cents_between(freq, reference);
+    cents - 1200.0 * (cents / 1200.0).round()
+}
+
+// 주파수를 MIDI 노트 번호(실수)로 변환
+pub fn frequency_to_midi(freq: f64) -> f64 {
+    12.0 * (freq / 440.0).log2() + 69.0
+}
+
+// MIDI 노트 번호를 주파수로 변환
+pub fn midi_to_frequency(midi: i32) -> f64 {
+    440.0 * 2f64.powf((midi as f64 - 69.0) / 12.0)
+}
@@ -0,0 +1,344 @@
+use std::cell::{Cell, RefCell};
+use std::collections::{BTreeMap, BTreeSet};
+
+use gloo::events::EventListener;
+use gloo::storage::{LocalStorage, Storage};
+use gloo_timers::callback::Timeout;
+use serde::{Deserialize, Serialize};
+
+use crate::event_bus::{emit, AppEvent};
+
+// 조건부 로그 매크로 정의
+#[cfg(debug_assertions)]
+macro_rules! console_log {
+    ($($arg:tt)*) => {
+        web_sys::console::log_1(&format!($($arg)*).into());
+    };
+}
+
+#[cfg(not(debug_assertions))]
+macro_rules! console_log {
+    ($($arg:tt)*) => {};
+}
+
+#[cfg(debug_assertions)]
+macro_rules! console_error {
+    ($($arg:tt)*) => {
+        web_sys::console::error_1(&format!($($arg)*).into());
+    };
+}
+
+#[cfg(not(debug_assertions))]
+macro_rules! console_error {
+    ($($arg:tt)*) => {};
+}
+
+const STORAGE_KEY: &str = "musicalmind.practiceLog";
+const MAX_ENTRIES: usize = 5000;      // 오래된 기록부터 삭제
+const MIN_DURATION_SECS: f64 = 3.0;   // 점수 없이 이보다 짧은 활동은 기록하지 않음
+const MS_PER_DAY: f64 = 86_400_000.0;
+const FLUSH_DELAY_MS: u32 = 5000;     // 저장 요청을 모아 이 시간 뒤에 한 번에 기록
+
+// 연습 도구 종류
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum PracticeTool {
+    Recording,
+    Metronome,
+    RhythmTrainer,
+    ScaleGenerator,
+    Piano,
+    IntervalTrainer,
+    ChordTrainer,
+    PitchMatch,
+}
+
+impl PracticeTool {
+    pub fn label(&self) -> &'static str {
+        match self {
+            PracticeTool::Recording => "녹음",
+            PracticeTool::Metronome => "메트로놈",
+            PracticeTool::RhythmTrainer => "리듬 훈련",
+            PracticeTool::ScaleGenerator => "스케일 연습",
+            PracticeTool::Piano => "피아노",
+            PracticeTool::IntervalTrainer => "음정 청음",
+            PracticeTool::ChordTrainer => "화음 청음",
+            PracticeTool::PitchMatch => "음 맞추기",
+        }
+    }
+}
+
+// 연습 기록 한 건
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PracticeEntry {
+    pub id: u64,                 // 시작 시각 기반 고유 ID (같은 ID로 저장하면 갱신)
+    pub tool: PracticeTool,
+    pub started_at: f64,         // 시작 시각 (Date::now(), ms)
+    pub duration_secs: f64,
+    #[serde(default)]
+    pub bpm: Option<u32>,
+    #[serde(default)]
+    pub exercise: Option<String>, // 연습 내용 (스케일, 박자, 난이도 등)
+    #[serde(default)]
+    pub score: Option<f64>,       // 0 ~ 100
+    #[serde(default)]
+    pub details: Option<String>,  // 기타 결과 요약
+}
+
+impl PracticeEntry {
+    pub fn new(tool: PracticeTool, started_at: f64) -> Self {
+        Self {
+            id: started_at as u64,
+            tool,
+            started_at,
+            duration_secs: 0.0,
+            bpm: None,
+            exercise: None,
+            score: None,
+            details: None,
+        }
+    }
+
+    // 시작 시각부터 지금까지를 연습 시간으로 설정
+    pub fn finish_now(mut self) -> Self {
+        self.duration_secs = ((js_sys::Date::now() - self.started_at) / 1000.0).max(0.0);
+        self
+    }
+
+    pub fn with_bpm(mut self, bpm: u32) -> Self {
+        self.bpm = Some(bpm);
+        self
+    }
+
+    pub fn with_exercise(mut self, exercise: impl Into<String>) -> Self {
+        self.exercise = Some(exercise.into());
+        self
+    }
+
+    pub fn with_score(mut self, score: f64) -> Self {
+        self.score = Some(score.clamp(0.0, 100.0));
+        self
+    }
+
+    pub fn with_details(mut self, details: impl Into<String>) -> Self {
+        self.details = Some(details.into());
+        self
+    }
+}
+
+thread_local! {
+    // 아직 localStorage에 쓰지 않은 기록 (퀴즈는 답할 때마다 같은 ID로 갱신하므로 모아서 저장)
+    static PENDING: RefCell<Vec<PracticeEntry>> = const { RefCell::new(Vec::new()) };
+    static FLUSH_SCHEDULED: Cell<bool> = const { Cell::new(false) };
+    static UNLOAD_HOOKED: Cell<bool> = const { Cell::new(false) };
+}
+
+// 연습 기록 전체 (오래된 순, 아직 저장 대기 중인 기록 포함)
+pub fn load_practice_log() -> Vec<PracticeEntry> {
+    let mut entries = load_stored_entries();
+    PENDING.with(|pending| merge_entries(&mut entries, pending.borrow().iter().cloned()));
+    entries
+}
+
+// 연습 기록 저장 - 같은 ID가 있으면 갱신
+// 실제 쓰기는 FLUSH_DELAY_MS 동안 모았다가 한 번에 하고, 페이지를 닫을 때도 남은 기록을 씀
+pub fn save_practice_entry(entry: PracticeEntry) {
+    if entry.score.is_none() && entry.duration_secs < MIN_DURATION_SECS {
+        return;
+    }
+
+    PENDING.with(|pending| merge_entries(&mut pending.borrow_mut(), std::iter::once(entry)));
+    hook_unload();
+
+    if !FLUSH_SCHEDULED.with(|scheduled| scheduled.replace(true)) {
+        Timeout::new(FLUSH_DELAY_MS, flush_practice_log).forget();
+    }
+}
+
+// 대기 중인 기록을 localStorage에 씀
+pub fn flush_practice_log() {
+    FLUSH_SCHEDULED.with(|scheduled| scheduled.set(false));
+    let pending = PENDING.with(|pending| std::mem::take(&mut *pending.borrow_mut()));
+    if pending.is_empty() {
+        return;
+    }
+
+    console_log!("연습 기록 {}건 저장", pending.len());
+    let mut entries = load_stored_entries();
+    merge_entries(&mut entries, pending);
+    store_entries(&entries);
+}
+
+pub fn clear_practice_log() {
+    PENDING.with(|pending| pending.borrow_mut().clear());
+    LocalStorage::delete(STORAGE_KEY);
+    notify_updated();
+}
+
+fn load_stored_entries() -> Vec<PracticeEntry> {
+    LocalStorage::get(STORAGE_KEY).unwrap_or_default()
+}
+
+// 페이지를 닫거나 숨길 때 대기 중인 기록 저장 (한 번만 등록)
+fn hook_unload() {
+    if UNLOAD_HOOKED.with(|hooked| hooked.replace(true)) {
+        return;
+    }
+    EventListener::new(&gloo::utils::window(), "pagehide", |_| flush_practice_log()).forget();
+}
+
+// 기록 병합 - 같은 도구와 ID는 갱신하고, 최대 개수를 넘으면 오래된 기록부터 삭제
+fn merge_entries(entries: &mut Vec<PracticeEntry>, updates: impl IntoIterator<Item = PracticeEntry>) {
+    for entry in updates {
+        match entries.iter_mut().find(|e| e.id == entry.id && e.tool == entry.tool) {
+            Some(existing) => *existing = entry,
+            None => entries.push(entry),
+        }
+    }
+
+    if entries.len() > MAX_ENTRIES {
+        let excess = entries.len() - MAX_ENTRIES;
+        entries.drain(..excess);
+    }
+}
+
+fn store_entries(entries: &[PracticeEntry]) {
+    if let Err(err) = LocalStorage::set(STORAGE_KEY, entries) {
+        console_error!("연습 기록 저장 실패: {:?}", err);
+        return;
+    }
+    notify_updated();
+}
+
+// 통계 페이지 등에 기록 변경 알림
+fn notify_updated() {
+    emit(AppEvent::PracticeLogUpdated);
+}
+
+// 타임스탬프(ms)를 로컬 날짜 기준 1970-01-01부터의 일 번호로 변환
+pub fn local_day_index(timestamp_ms: f64) -> i64 {
+    let date = js_sys::Date::new(&wasm_bindgen::JsValue::from_f64(timestamp_ms));
+    let offset_ms = date.get_timezone_offset() * 60_000.0;
+    ((timestamp_ms - offset_ms) / MS_PER_DAY).floor() as i64
+}
+
+// 일별 연습 시간 (분) - day_of는 타임스탬프(ms)를 일 번호로 변환 (보통 local_day_index)
+pub fn daily_minutes(entries: &[PracticeEntry], day_of: impl Fn(f64) -> i64) -> BTreeMap<i64, f64> {
+    let mut daily = BTreeMap::new();
+    for entry in entries {
+        *daily.entry(day_of(entry.started_at)).or_insert(0.0) += entry.duration_secs / 60.0;
+    }
+    daily
+}
+
+// (현재 연속 연습 일수, 최장 연속 연습 일수) - 오늘 아직 연습하지 않았으면 어제까지로 계산
+pub fn streaks(daily: &BTreeMap<i64, f64>, today: i64) -> (u32, u32) {
+    let days: BTreeSet<i64> = daily.keys().copied().collect();
+
+    let mut day = if days.contains(&today) { today } else { today - 1 };
+    let mut current = 0;
+    while days.contains(&day) {
+        current += 1;
+        day -= 1;
+    }
+
+    let mut longest = 0;
+    let mut run = 0;
+    let mut previous: Option<i64> = None;
+    for day in &days {
+        run = if previous == Some(day - 1) { run + 1 } else { 1 };
+        longest = longest.max(run);
+        previous = Some(*day);
+    }
+
+    (current, longest)
+}
+
+// 일 번호를 월요일 시작 주 번호로 변환 (1970-01-01은 목요일)
+pub fn week_index(day: i64) -> i64 {
+    (day + 3).div_euclid(7)
+}
+
+// 일 번호를 "M/D" 형식으로
+pub fn day_label(day: i64) -> String {
+    let date = js_sys::Date::new(&wasm_bindgen::JsValue::from_f64(day as f64 * MS_PER_DAY + MS_PER_DAY / 2.0));
+    format!("{}/{}", date.get_utc_month() + 1, date.get_utc_date())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // UTC 기준 일 번호 (테스트에서는 브라우저 시간대 대신 사용)
+    fn utc_day(timestamp_ms: f64) -> i64 {
+        (timestamp_ms / MS_PER_DAY).floor() as i64
+    }
+
+    fn entry(tool: PracticeTool, started_at: f64, duration_secs: f64) -> PracticeEntry {
+        let mut entry = PracticeEntry::new(tool, started_at);
+        entry.duration_secs = duration_secs;
+        entry
+    }
+
+    fn days(list: &[i64]) -> BTreeMap<i64, f64> {
+        list.iter().map(|day| (*day, 10.0)).collect()
+    }
+
+    #[test]
+    fn week_index_starts_on_monday() {
+        // 1970-01-01(목) ~ 1970-01-04(일)은 같은 주, 01-05(월)부터 다음 주
+        assert_eq!(week_index(0), week_index(3));
+        assert_eq!(week_index(4), week_index(3) + 1);
+        assert_eq!(week_index(4), week_index(10));
+        assert_eq!(week_index(11), week_index(10) + 1);
+        // 1970년 이전 날짜도 월요일 경계를 따름
+        assert_eq!(week_index(-3), week_index(0));
+        assert_eq!(week_index(-4), week_index(-3) - 1);
+    }
+
+    #[test]
+    fn daily_minutes_sums_entries_per_day() {
+        let entries = vec![
+            entry(PracticeTool::Metronome, 1000.0, 600.0),
+            entry(PracticeTool::Piano, 2.0 * MS_PER_DAY - 1.0, 300.0),
+            entry(PracticeTool::Piano, 2.0 * MS_PER_DAY + 1.0, 90.0),
+        ];
+        let daily = daily_minutes(&entries, utc_day);
+        assert_eq!(daily.len(), 3);
+        assert!((daily[&0] - 10.0).abs() < 1e-9);
+        assert!((daily[&1] - 5.0).abs() < 1e-9);
+        assert!((daily[&2] - 1.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn streaks_count_current_and_longest_runs() {
+        assert_eq!(streaks(&BTreeMap::new(), 100), (0, 0));
+        // 오늘 연습함
+        assert_eq!(streaks(&days(&[90, 91, 92, 98, 99, 100]), 100), (3, 3));
+        // 오늘은 아직이지만 어제까지 이어짐
+        assert_eq!(streaks(&days(&[97, 98, 99]), 100), (3, 3));
+        // 이틀 이상 쉬면 끊김
+        assert_eq!(streaks(&days(&[90, 91, 92, 93, 97, 98]), 100), (0, 4));
+    }
+
+    #[test]
+    fn merge_updates_same_session_and_trims_oldest() {
+        let mut entries = vec![entry(PracticeTool::IntervalTrainer, 1000.0, 10.0)];
+        merge_entries(
+            &mut entries,
+            vec![
+                entry(PracticeTool::IntervalTrainer, 1000.0, 20.0).with_score(80.0),
+                entry(PracticeTool::ChordTrainer, 1000.0, 5.0),
+            ],
+        );
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].duration_secs, 20.0);
+        assert_eq!(entries[0].score, Some(80.0));
+
+        let mut entries: Vec<PracticeEntry> =
+            (0..MAX_ENTRIES).map(|i| entry(PracticeTool::Piano, i as f64, 60.0)).collect();
+        merge_entries(&mut entries, vec![entry(PracticeTool::Piano, MAX_ENTRIES as f64, 60.0)]);
+        assert_eq!(entries.len(), MAX_ENTRIES);
+        assert_eq!(entries[0].id, 1);
+        assert_eq!(entries.last().unwrap().id, MAX_ENTRIES as u64);
+    }
+}
@@ -1,13 +1,21 @@
 use wasm_bindgen::prelude::*;
 use wasm_bindgen::JsCast;
-use web_sys::{self, CustomEvent, CustomEventInit, HtmlElement, MouseEvent, TouchEvent, Event};
+use web_sys::{self, MouseEvent, Event};
 use yew::prelude::*;
 use yew_router::prelude::*;
 use std::collections::VecDeque;
-use gloo::events::EventListener;
 use gloo::utils::window;
 
 use crate::PitchAnalyzer;
+use crate::settings::{self, AnalyzerSettings};
+use crate::mixer::{MixerPanel, MuteAllButton};
+use crate::session_recorder::{is_session_recording, SessionRecordButton};
+use crate::audio_export::{ExportFormat, ExportOptions};
+use crate::encoders::flac::FLAC_BIT_DEPTHS;
+use crate::encoders::mp3::MP3_BITRATES;
+use crate::encoders::wav::WavSampleFormat;
+use crate::event_bus::{emit, use_app_event, AppEvent};
+use crate::background_session::{create_analyzer_host, AnalyzerSlot, BackgroundSession, MiniStatusBar};
 
 // 조건부 로그 매크로 정의
 #[cfg(debug_assertions)]
@@ -51,16 +59,41 @@ pub enum Route {
     ScaleGenerator,
     #[at("/piano-keyboard")]
     PianoKeyboard,
+    #[at("/interval-trainer")]
+    IntervalTrainer,
+    #[at("/chord-trainer")]
+    ChordTrainer,
+    #[at("/pitch-match")]
+    PitchMatch,
+    #[at("/statistics")]
+    Statistics,
     #[not_found]
     #[at("/404")]
     NotFound,
 }
 
+impl Route {
+    // 피치 분석기(마이크 버튼으로 녹음)가 있는 페이지
+    pub fn has_pitch_analyzer(&self) -> bool {
+        matches!(self, Route::Home | Route::PitchPlot | Route::AmplitudeVisualizer | Route::Metronome)
+    }
+}
+
 // 사이드바 컴포넌트
 #[function_component(Sidebar)]
 pub fn sidebar() -> Html {
     let current_route = use_route::<Route>().unwrap_or(Route::Home);
     
+    // 모든 도구의 저장된 설정을 기본값으로 초기화
+    let on_reset_settings = Callback::from(|_: MouseEvent| {
+        let confirmed = window()
+            .confirm_with_message("모든 도구의 설정을 기본값으로 되돌릴까요? (지연 보정값과 저장한 모니터 프리셋은 유지됩니다)")
+            .unwrap_or(false);
+        if confirmed {
+            settings::reset_settings();
+        }
+    });
+    
     html! {
         <div class="sidebar">
             <div class="sidebar-header">
@@ -108,8 +141,35 @@ pub fn sidebar() -> Html {
                     <span class="nav-icon">{"🎹"}</span>
                     <span class="nav-text">{"Piano Keyboard"}</span>
                 </Link<Route>>
+                
+                <Link<Route> to={Route::IntervalTrainer} classes={classes!("nav-item", if current_route == Route::IntervalTrainer { "active" } else { "" })}>
+                    <span class="nav-icon">{"👂"}</span>
+                    <span class="nav-text">{"Interval Trainer"}</span>
+                </Link<Route>>
+                
+                <Link<Route> to={Route::ChordTrainer} classes={classes!("nav-item", if current_route == Route::ChordTrainer { "active" } else { "" })}>
+                    <span class="nav-icon">{"🎼"}</span>
+                    <span class="nav-text">{"Chord Trainer"}</span>
+                </Link<Route>>
+                
+                <Link<Route> to={Route::PitchMatch} classes={classes!("nav-item", if current_route == Route::PitchMatch { "active" } else { "" })}>
+                    <span class="nav-icon">{"🎤"}</span>
+                    <span class="nav-text">{"Pitch Match"}</span>
+                </Link<Route>>
+                
+                <Link<Route> to={Route::Statistics} classes={classes!("nav-item", if current_route == Route::Statistics { "active" } else { "" })}>
+                    <span class="nav-icon">{"📅"}</span>
+                    <span class="nav-text">{"Statistics"}</span>
+                </Link<Route>>
             </nav>
             
+            <div class="sidebar-footer">
+                <div class="nav-item" onclick={on_reset_settings} title="저장된 설정을 기본값으로 초기화">
+                    <span class="nav-icon">{"⚙️"}</span>
+                    <span class="nav-text">{"Reset Settings"}</span>
+                </div>
+            </div>
+            
             // <div class="sidebar-footer">
             //     <div class="nav-item logout">
             //         <span class="nav-icon">{"👤"}</span>
@@ -138,6 +198,10 @@ pub fn top_header(props: &TopHeaderProps) -> Html {
         Route::Metronome => "Metronome",
         Route::ScaleGenerator => "Scale Generator",
         Route::PianoKeyboard => "Piano Keyboard",
+        Route::IntervalTrainer => "Interval Trainer",
+        Route::ChordTrainer => "Chord Trainer",
+        Route::PitchMatch => "Pitch Match",
+        Route::Statistics => "Statistics",
         _ => "Dashboard",
     };
     
@@ -159,7 +223,14 @@ pub fn top_header(props: &TopHeaderProps) -> Html {
                 </div>
                 
                 <div class="header-right">
-                    
+                    // 분석기가 없는 페이지에서는 헤더 버튼으로 녹음 (녹음 중이면 페이지를 옮겨도 표시)
+                    if !current_route.has_pitch_analyzer() || is_session_recording() {
+                        <SessionRecordButton />
+                    }
+
+                    // 전체 음소거와 도구별 출력 채널 믹서
+                    <MuteAllButton />
+                    <MixerPanel />
                     
                     // 기존 피치 컨트롤 유지
                     <div class="pitch-controls-container">
@@ -176,21 +247,38 @@ pub fn top_header(props: &TopHeaderProps) -> Html {
 pub fn main_layout() -> Html {
     let route = use_route::<Route>().unwrap_or(Route::Home);
     let is_mobile_menu_open = use_state(|| false);
+
+    // 백그라운드 세션: 분석기 하나를 레이아웃에 두고 페이지를 옮겨도 유지
+    let background_enabled = use_state(|| settings::load_settings().analyzer.background_session);
+    let analyzer_host = use_memo((), |_| create_analyzer_host());
+    let background_session = {
+        let background_enabled = background_enabled.clone();
+        BackgroundSession {
+            enabled: *background_enabled,
+            set_enabled: Callback::from(move |enabled: bool| {
+                settings::update_settings(|s| s.analyzer.background_session = enabled);
+                background_enabled.set(enabled);
+            }),
+            host: (*analyzer_host).clone(),
+        }
+    };
+    {
+        let background_enabled = background_enabled.clone();
+        use_app_event(move |event| {
+            if let AppEvent::SettingsReset = event {
+                background_enabled.set(AnalyzerSettings::default().background_session);
+            }
+        });
+    }
     
-    // 페이지 변경 시 오디오 리소스 정리
+    // 페이지 변경 시 오디오 리소스 정리 (백그라운드 세션이면 그대로 유지)
     {
-        let route = route.clone();
-        use_effect(move || {
-            // 페이지 변경 시 PitchAnalyzer 상태 초기화 이벤트 발생
-            if let Some(document) = web_sys::window().and_then(|w| w.document()) {
-                // ResetComponent 이벤트 발생 - 컴포넌트 완전 초기화
-                let reset_event = web_sys::Event::new("resetPitchAnalyzer").unwrap();
-                document.dispatch_event(&reset_event).unwrap();
-                
-                // StopAudioResources 이벤트 발생 - 모든 오디오 리소스 정리
-                let stop_resources_event = web_sys::Event::new("stopAudioResources").unwrap();
-                document.dispatch_event(&stop_resources_event).unwrap();
-                
+        let background_enabled = *background_enabled;
+        use_effect_with(route.clone(), move |_| {
+            if !background_enabled {
+                // 페이지 변경 시 PitchAnalyzer 상태 완전 초기화, 모든 오디오 리소스 정리
+                emit(AppEvent::ResetPitchAnalyzer);
+                emit(AppEvent::StopAudioResources);
                 console_log!("페이지 이동 감지: 마이크 비활성화 및 PitchAnalyzer 상태 초기화 이벤트 발생");
             }
             
@@ -201,13 +289,17 @@ pub fn main_layout() -> Html {
 
     // 현재 라우트에 따른 컨텐츠 선택
     let content = match route {
-        Route::Home => html! { <PitchAnalyzer /> },
+        Route::Home => html! { <AnalyzerSlot /> },
         Route::PitchControls => html! { <PitchControlsDetail /> },
         Route::PitchPlot => html! { <PitchPlotDetail /> },
         Route::AmplitudeVisualizer => html! { <AmplitudeVisualizerDetail /> },
         Route::Metronome => html! { <MetronomeDetail /> },
         Route::ScaleGenerator => html! { <ScaleGeneratorDetail /> },
         Route::PianoKeyboard => html! { <PianoKeyboardDetail /> },
+        Route::IntervalTrainer => html! { <IntervalTrainerDetail /> },
+        Route::ChordTrainer => html! { <ChordTrainerDetail /> },
+        Route::PitchMatch => html! { <PitchMatchDetail /> },
+        Route::Statistics => html! { <StatisticsDetail /> },
         Route::NotFound => html! { <NotFound /> },
     };
 
@@ -225,21 +317,35 @@ pub fn main_layout() -> Html {
         })
     };
 
+    // 공유 분석기는 항상 같은 자리에 두어 상태를 유지하고, 그리는 요소만 AnalyzerSlot이 옮김
+    let shared_analyzer = if *background_enabled {
+        create_portal(
+            html! { <PitchAnalyzer show_links={Some(route == Route::Home)} route={route.clone()} /> },
+            (*analyzer_host).clone(),
+        )
+    } else {
+        html! {}
+    };
+
     html! {
-        <div class={classes!("app-layout", if *is_mobile_menu_open { "mobile-menu-open" } else { "" })}>
-            <Sidebar />
-            <div class="main-content">
-                <TopHeader on_mobile_menu_toggle={toggle_mobile_menu.clone()} />
-                <main class="content-area">
-                    { content }
-                </main>
+        <ContextProvider<BackgroundSession> context={background_session}>
+            <div class={classes!("app-layout", if *is_mobile_menu_open { "mobile-menu-open" } else { "" })}>
+                <Sidebar />
+                <div class="main-content">
+                    <TopHeader on_mobile_menu_toggle={toggle_mobile_menu.clone()} />
+                    <MiniStatusBar />
+                    <main class="content-area">
+                        { content }
+                    </main>
+                </div>
+                
+                // 모바일 오버레이
+                if *is_mobile_menu_open {
+                    <div class="mobile-overlay" onclick={on_overlay_click}></div>
+                }
+                { shared_analyzer }
             </div>
-            
-            // 모바일 오버레이
-            if *is_mobile_menu_open {
-                <div class="mobile-overlay" onclick={on_overlay_click}></div>
-            }
-        </div>
+        </ContextProvider<BackgroundSession>>
     }
 }
 
@@ -264,7 +370,8 @@ pub fn pitch_controls_detail() -> Html {
                         <li>{"🔊 모니터 버튼: 입력 소리를 스피커로 직접 들을 수 있습니다."}</li>
                         <li>{"▶️ 재생 버튼: 녹음된 소리를 재생/일시정지합니다."}</li>
                         <li>{"�� 다운로드 버튼: 녹음 파일을 저장할 수 있습니다."}</li>
-                        <li>{"🎚️ 감도/스피커 게인: 마이크 감도와 스피커 볼륨을 조절할 수 있습니다."}</li>
+                        <li>{"🎚️ 감도/스피커 게인: 마이크 감도와 스피커 볼륨을 조절할 수 있습니다. 녹음/재생 중에도 조절할 수 있습니다."}</li>
+                        <li>{"🔉 전체 음소거: 헤더 버튼이나 Alt+M으로 스피커 출력을 모두 끕니다. 녹음은 그대로 계속됩니다."}</li>
                         <li>{"진행 바: 녹음/재생 위치를 확인하고 이동할 수 있습니다."}</li>
                     </ul>
                     <p>{"각 버튼에 마우스를 올리면 기능 설명이 툴팁으로 표시됩니다."}</p>
@@ -285,7 +392,7 @@ pub fn pitch_plot_detail() -> Html {
             <div class="content full-width">
                 <h2>{"음높이 시각화"}</h2>
                 <div class="analyzer-container">
-                    <PitchAnalyzer show_links={Some(false)} />
+                    <AnalyzerSlot show_links={Some(false)} />
                 </div>
                 <div class="description">
                     <h3>{"음높이 시각화 도구 활용법"}</h3>
@@ -320,7 +427,7 @@ pub fn amplitude_visualizer_detail() -> Html {
             <div class="content full-width">
                 <h2>{"진폭 시각화"}</h2>
                 <div class="analyzer-container">
-                    <PitchAnalyzer show_links={Some(false)} />
+                    <AnalyzerSlot show_links={Some(false)} />
                 </div>
                 <div class="description">
                     <h3>{"진폭 시각화 도구 활용법"}</h3>
@@ -344,13 +451,14 @@ pub fn metronome_detail() -> Html {
             <div class="content full-width">
                 <h2>{"메트로놈"}</h2>
                 <div class="analyzer-container">
-                    <PitchAnalyzer show_links={Some(false)} />
+                    <AnalyzerSlot show_links={Some(false)} />
                 </div>
                 <div class="description">
                     <h3>{"메트로놈 사용법"}</h3>
                     <p>{"메트로놈은 음악의 박자를 측정하는 도구입니다."}</p>
                     <p>{"마이크를 활성화하고 음악을 재생하면 박자를 확인할 수 있습니다."}</p>
                     <p>{"음악 연습, 발성 훈련, 음악 분석 등에 활용해보세요."}</p>
+                    <p>{"👏 버튼으로 리듬 훈련을 켜면 클릭에 맞춘 탭(터치, 스페이스바, 박수)이 얼마나 빠르거나 늦었는지 ms 단위로 측정하고 분포와 흐름을 보여줍니다."}</p>
                 </div>
             </div>
         </div>
@@ -405,6 +513,102 @@ pub fn piano_keyboard_detail() -> Html {
     }
 }
 
+// 음정 청음 훈련 상세 페이지 컴포넌트
+#[function_component(IntervalTrainerDetail)]
+pub fn interval_trainer_detail() -> Html {
+    html! {
+        <div class="detail-page">
+            <div class="back-link">
+                <Link<Route> to={Route::Home}>{"🏠 메인화면으로 돌아가기"}</Link<Route>>
+            </div>
+            <div class="content full-width">
+                <h2>{"음정 청음 훈련"}</h2>
+                <div class="analyzer-container">
+                    <crate::tools::interval_trainer::IntervalTrainer />
+                </div>
+                <div class="description">
+                    <h3>{"음정 청음 훈련 사용법"}</h3>
+                    <p>{"새 문제를 누르면 피아노로 두 음이 재생됩니다. 두 음 사이의 음정을 듣고 알맞은 답을 고르세요."}</p>
+                    <p>{"선율(차례로) 또는 화성(동시에) 재생 방식과 상행/하행 방향, 출제 음역과 음정을 선택할 수 있습니다."}</p>
+                    <p>{"틀린 음정 집중 출제를 켜면 정확도가 낮은 음정이 더 자주 출제됩니다. 정확도가 60% 미만인 음정은 ⚠️로 표시됩니다."}</p>
+                </div>
+            </div>
+        </div>
+    }
+}
+
+// 화음 청음 훈련 상세 페이지 컴포넌트
+#[function_component(ChordTrainerDetail)]
+pub fn chord_trainer_detail() -> Html {
+    html! {
+        <div class="detail-page">
+            <div class="back-link">
+                <Link<Route> to={Route::Home}>{"🏠 메인화면으로 돌아가기"}</Link<Route>>
+            </div>
+            <div class="content full-width">
+                <h2>{"화음 청음 훈련"}</h2>
+                <div class="analyzer-container">
+                    <crate::tools::chord_trainer::ChordTrainer />
+                </div>
+                <div class="description">
+                    <h3>{"화음 청음 훈련 사용법"}</h3>
+                    <p>{"새 문제를 누르면 피아노로 화음이 재생됩니다. 화음의 종류를 듣고 알맞은 답을 고르세요."}</p>
+                    <p>{"난이도를 올리면 감/증3화음과 7화음, 자리바꿈과 오픈 보이싱이 함께 출제됩니다. 출제 화음은 직접 선택할 수도 있습니다."}</p>
+                    <p>{"분산화음으로 재생하면 구성음을 하나씩 들으며 연습할 수 있습니다."}</p>
+                </div>
+            </div>
+        </div>
+    }
+}
+
+// 음 맞추기 게임 상세 페이지 컴포넌트
+#[function_component(PitchMatchDetail)]
+pub fn pitch_match_detail() -> Html {
+    html! {
+        <div class="detail-page">
+            <div class="back-link">
+                <Link<Route> to={Route::Home}>{"🏠 메인화면으로 돌아가기"}</Link<Route>>
+            </div>
+            <div class="content full-width">
+                <h2>{"음 맞추기 게임"}</h2>
+                <div class="analyzer-container">
+                    <crate::tools::pitch_match::PitchMatchGame />
+                </div>
+                <div class="description">
+                    <h3>{"음 맞추기 게임 사용법"}</h3>
+                    <p>{"시작을 누르면 설정한 음역 안에서 무작위로 음이 재생됩니다. 들은 음을 소리 내어 불러 0.5초 동안 유지하면 성공입니다."}</p>
+                    <p>{"빨리 맞출수록, 정확하게 부를수록 높은 점수를 받습니다. 옥타브 차이는 무시하므로 편한 음높이로 부르면 됩니다."}</p>
+                    <p>{"허용 오차를 줄이면 난이도가 올라갑니다. 기준음 드론을 켜면 부르는 동안 목표음이 작게 들립니다."}</p>
+                </div>
+            </div>
+        </div>
+    }
+}
+
+// 연습 통계 상세 페이지 컴포넌트
+#[function_component(StatisticsDetail)]
+pub fn statistics_detail() -> Html {
+    html! {
+        <div class="detail-page">
+            <div class="back-link">
+                <Link<Route> to={Route::Home}>{"🏠 메인화면으로 돌아가기"}</Link<Route>>
+            </div>
+            <div class="content full-width">
+                <h2>{"연습 통계"}</h2>
+                <div class="analyzer-container">
+                    <crate::tools::practice_stats::PracticeStats />
+                </div>
+                <div class="description">
+                    <h3>{"연습 통계 보기"}</h3>
+                    <p>{"녹음, 메트로놈, 스케일 연습, 피아노, 청음 퀴즈, 음 맞추기, 리듬 훈련을 하면 사용 시간과 결과가 이 브라우저에 자동으로 기록됩니다."}</p>
+                    <p>{"일별/주별 연습 시간과 연속 연습 일수를 확인하고, 점수가 있는 연습은 회차별 점수 추이를 볼 수 있습니다."}</p>
+                    <p>{"기록은 브라우저 저장소에 보관되므로 브라우저 데이터를 지우면 함께 삭제됩니다."}</p>
+                </div>
+            </div>
+        </div>
+    }
+}
+
 #[function_component(NotFound)]
 pub fn not_found() -> Html {
     html! {
@@ -417,18 +621,24 @@ pub fn not_found() -> Html {
 // 피치 분석 컨트롤 컴포넌트
 #[function_component(PitchControls)]
 pub fn pitch_controls() -> Html {
-    let sensitivity = use_state(|| 0.01f32);
+    let sensitivity = use_state(|| settings::load_settings().analyzer.sensitivity);
     let show_sensitivity = use_state(|| false);
     let mic_active = use_state(|| false);
     let monitor_active = use_state(|| false);
     let is_playing = use_state(|| false);
     let has_recorded = use_state(|| true);
-    let speaker_gain = use_state(|| 0.02f32);
+    let speaker_gain = use_state(|| settings::load_settings().analyzer.speaker_gain);
+    let count_in_bars = use_state(|| settings::load_settings().analyzer.count_in_bars); // 녹음 전 카운트인 마디 수 (0이면 끔)
     let show_download_format = use_state(|| false); // 다운로드 포맷 드롭다운 표시 상태
-    let selected_format = use_state(|| "webm".to_string()); // 선택된 다운로드 포맷
+    let export_options = use_state(ExportOptions::default); // 선택된 다운로드 포맷과 포맷별 옵션
+    let export_progress = use_state(|| None::<f64>); // 인코딩 진행률 (내보내는 중이 아니면 None)
+    let export_error = use_state(|| None::<String>); // 마지막 내보내기 실패 메시지
+    let selected_region = use_state(|| None::<(f64, f64)>); // 피치 차트에서 선택한 구간
     
     // 버튼 활성화/비활성화 상태 추가 - 로그를 통해 디버깅
     let buttons_disabled = use_state(|| false);
+    // 녹음 중이거나 카운트인 중인지 (분석기의 LiveStatus로 갱신)
+    let recording_busy = use_state(|| false);
     
     // 재생 정보 상태 추가
     let current_time = use_state(|| 0.0f64);        // 현재 재생 시간
@@ -436,72 +646,33 @@ pub fn pitch_controls() -> Html {
     let progress = use_state(|| 0.0f64);            // 진행률 (0~1)
     let is_seeking = use_state(|| false);           // 시크 중인지 여부
 
-    // 재생 완료 이벤트 리스너 추가
+    // 분석기/차트/테이크 보관함에서 오는 앱 이벤트 처리
     {
         let is_playing = is_playing.clone();
         let mic_active = mic_active.clone();
-        
-        use_effect(move || {
-            let window = web_sys::window().expect("window를 찾을 수 없습니다");
-            let document = window.document().expect("document를 찾을 수 없습니다");
-            
-            let is_playing_clone = is_playing.clone();
-            let mic_active_clone = mic_active.clone();
-            
-            // 기존 이벤트 리스너가 있다면 제거 (중복 방지)
-            let dummy_callback = Closure::wrap(Box::new(|_: web_sys::Event| {}) as Box<dyn FnMut(_)>);
-            let _ = document.remove_event_listener_with_callback(
-                "playbackEnded", 
-                dummy_callback.as_ref().unchecked_ref()
-            );
-            drop(dummy_callback);
-            
-            let callback = Closure::wrap(Box::new(move |_e: web_sys::Event| {
-                // 재생이 끝나면 재생 상태 변경 및 마이크 활성화
-                is_playing_clone.set(false);
-                mic_active_clone.set(false);
-            }) as Box<dyn FnMut(_)>);
-            
-            document.add_event_listener_with_callback(
-                "playbackEnded", 
-                callback.as_ref().unchecked_ref()
-            ).expect("이벤트 리스너 추가 실패");
-            
-            // 클린업 함수 - 컴포넌트가 언마운트될 때 이벤트 리스너 제거
-            move || {
-                let _ = document.remove_event_listener_with_callback(
-                    "playbackEnded",
-                    callback.as_ref().unchecked_ref()
-                );
-                drop(callback);
-            }
-        });
-    }
-    
-    // 컨트롤 상태 초기화 이벤트 리스너 추가
-    {
-        let mic_active = mic_active.clone();
         let monitor_active = monitor_active.clone();
-        let is_playing = is_playing.clone();
         let has_recorded = has_recorded.clone();
         let current_time = current_time.clone();
         let duration = duration.clone();
         let progress = progress.clone();
         let is_seeking = is_seeking.clone();
-        
-        use_effect(move || {
-            let window = web_sys::window().expect("window를 찾을 수 없습니다");
-            let document = window.document().expect("document를 찾을 수 없습니다");
-            
-            // 기존 이벤트 리스너가 있다면 제거 (중복 방지)
-            let dummy_callback = Closure::wrap(Box::new(|_: web_sys::Event| {}) as Box<dyn FnMut(_)>);
-            let _ = document.remove_event_listener_with_callback(
-                "resetPitchAnalyzer", 
-                dummy_callback.as_ref().unchecked_ref()
-            );
-            drop(dummy_callback);
-            
-            let callback = Closure::wrap(Box::new(move |_e: web_sys::Event| {
+        let buttons_disabled = buttons_disabled.clone();
+        let recording_busy = recording_busy.clone();
+        let sensitivity = sensitivity.clone();
+        let speaker_gain = speaker_gain.clone();
+        let count_in_bars = count_in_bars.clone();
+        let selected_region = selected_region.clone();
+        let export_options = export_options.clone();
+        let export_progress = export_progress.clone();
+        let export_error = export_error.clone();
+
+        use_app_event(move |event| match event {
+            AppEvent::PlaybackEnded => {
+                // 재생이 끝나면 재생 상태 변경 및 마이크 활성화
+                is_playing.set(false);
+                mic_active.set(false);
+            }
+            AppEvent::ResetPitchAnalyzer => {
                 // 컨트롤 상태 초기화 (PitchAnalyzer가 초기화될 때 함께 초기화)
                 mic_active.set(false);
                 monitor_active.set(false);
@@ -511,100 +682,43 @@ pub fn pitch_controls() -> Html {
                 duration.set(0.0);
                 progress.set(0.0);
                 is_seeking.set(false);
-                
+
                 console_log!("컨트롤 상태가 초기화되었습니다");
-            }) as Box<dyn FnMut(_)>);
-            
-            document.add_event_listener_with_callback(
-                "resetPitchAnalyzer", 
-                callback.as_ref().unchecked_ref()
-            ).expect("이벤트 리스너 추가 실패");
-            
-            // 클린업 함수 - 컴포넌트가 언마운트될 때 이벤트 리스너 제거
-            move || {
-                let _ = document.remove_event_listener_with_callback(
-                    "resetPitchAnalyzer",
-                    callback.as_ref().unchecked_ref()
-                );
-                drop(callback);
             }
-        });
-    }
-    
-    // 버튼 비활성화 이벤트 처리
-    {
-        let buttons_disabled = buttons_disabled.clone();
-    
-        use_effect(move || {
-            let window = web_sys::window().expect("window를 찾을 수 없습니다");
-            let document = window.document().expect("document를 찾을 수 없습니다");
-            
-            // 기존 이벤트 리스너가 있다면 제거 (중복 방지)
-            let dummy_callback = Closure::wrap(Box::new(|_: web_sys::Event| {}) as Box<dyn FnMut(_)>);
-            let _ = document.remove_event_listener_with_callback(
-                "disableControlButtons", 
-                dummy_callback.as_ref().unchecked_ref()
-            );
-            drop(dummy_callback);
-            
-            let callback = Closure::wrap(Box::new(move |_e: web_sys::Event| {
-                // 컨트롤 상태 초기화 (PitchAnalyzer가 초기화될 때 함께 초기화)
-                buttons_disabled.set(true);
-                
-                console_log!("컨트롤 버튼이 비활성화되었습니다 (이벤트 핸들러)");
-            }) as Box<dyn FnMut(_)>);
-            
-            document.add_event_listener_with_callback(
-                "disableControlButtons", 
-                callback.as_ref().unchecked_ref()
-            ).expect("이벤트 리스너 추가 실패");
-            
-            // 클린업 함수 - 컴포넌트가 언마운트될 때 이벤트 리스너 제거
-            move || {
-                let _ = document.remove_event_listener_with_callback(
-                    "disableControlButtons",
-                    callback.as_ref().unchecked_ref()
-                );
-                drop(callback);
+            // 녹음 마무리 동안 버튼 비활성화
+            AppEvent::ControlsEnabled { enabled } => {
+                buttons_disabled.set(!*enabled);
+                console_log!("컨트롤 버튼 {} (이벤트 핸들러)", if *enabled { "활성화" } else { "비활성화" });
             }
-        });
-    }
-
-    {
-        let buttons_disabled = buttons_disabled.clone();
-
-        use_effect(move || {
-            let window = web_sys::window().expect("window를 찾을 수 없습니다");
-            let document = window.document().expect("document를 찾을 수 없습니다");
-            
-            // 기존 이벤트 리스너가 있다면 제거 (중복 방지)
-            let dummy_callback = Closure::wrap(Box::new(|_: web_sys::Event| {}) as Box<dyn FnMut(_)>);
-            let _ = document.remove_event_listener_with_callback(
-                "enableControlButtons", 
-                dummy_callback.as_ref().unchecked_ref()
-            );
-            drop(dummy_callback);
-            
-            let callback = Closure::wrap(Box::new(move |_e: web_sys::Event| {
-                // 컨트롤 상태 초기화 (PitchAnalyzer가 초기화될 때 함께 초기화)
-                buttons_disabled.set(false);
-                
-                console_log!("컨트롤 버튼이 활성화되었습니다 (이벤트 핸들러)");
-            }) as Box<dyn FnMut(_)>);
-            
-            document.add_event_listener_with_callback(
-                "enableControlButtons", 
-                callback.as_ref().unchecked_ref()
-            ).expect("이벤트 리스너 추가 실패");
-            
-            // 클린업 함수 - 컴포넌트가 언마운트될 때 이벤트 리스너 제거
-            move || {
-                let _ = document.remove_event_listener_with_callback(
-                    "enableControlButtons",
-                    callback.as_ref().unchecked_ref()
-                );
-                drop(callback);
+            AppEvent::LiveStatus(status) => recording_busy.set(status.recording || status.counting_in),
+            // 설정 초기화 시 감도/게인/카운트인을 기본값으로 되돌리고 분석기에 알림
+            AppEvent::SettingsReset => {
+                let defaults = AnalyzerSettings::default();
+                sensitivity.set(defaults.sensitivity);
+                speaker_gain.set(defaults.speaker_gain);
+                count_in_bars.set(defaults.count_in_bars);
+                emit(AppEvent::UpdateSensitivity { value: defaults.sensitivity });
+                emit(AppEvent::UpdateSpeakerVolume { value: defaults.speaker_gain });
             }
+            // 테이크가 모두 삭제되면 재생/다운로드 비활성화
+            AppEvent::TakesChanged { count } => has_recorded.set(*count > 0),
+            // 피치 차트 구간 선택 추적 (구간만 내보내기가 켜져 있으면 새 구간으로 갱신)
+            AppEvent::RegionSelected { region } => {
+                selected_region.set(*region);
+                if export_options.region.is_some() {
+                    export_options.set(ExportOptions { region: *region, ..*export_options });
+                }
+            }
+            // 인코더 워커의 내보내기 진행률 표시
+            AppEvent::ExportProgress { progress } => {
+                if progress.is_some() {
+                    export_error.set(None);
+                }
+                export_progress.set(progress.map(|p| p.min(1.0)));
+            }
+            // 내보내기 실패는 다운로드 버튼 아래에 표시 (클릭하면 닫힘)
+            AppEvent::ExportFailed { message } => export_error.set(Some(message.clone())),
+            _ => {}
         });
     }
 
@@ -618,21 +732,10 @@ pub fn pitch_controls() -> Html {
                 .unwrap();
             let value = input.value().parse::<f32>().unwrap_or(0.01);
             sensitivity.set(value);
+            settings::update_settings(|s| s.analyzer.sensitivity = value);
 
             // 감도 변경 이벤트 발생
-            let event = CustomEvent::new_with_event_init_dict(
-                "updateSensitivity",
-                CustomEventInit::new()
-                    .bubbles(true)
-                    .detail(&JsValue::from_f64(value as f64)),
-            )
-            .unwrap();
-            web_sys::window()
-                .unwrap()
-                .document()
-                .unwrap()
-                .dispatch_event(&event)
-                .unwrap();
+            emit(AppEvent::UpdateSensitivity { value });
         })
     };
 
@@ -648,19 +751,7 @@ pub fn pitch_controls() -> Html {
             sensitivity.set(value);
 
             // 감도 변경 이벤트 발생
-            let event = CustomEvent::new_with_event_init_dict(
-                "updateSensitivity",
-                CustomEventInit::new()
-                    .bubbles(true)
-                    .detail(&JsValue::from_f64(value as f64)),
-            )
-            .unwrap();
-            web_sys::window()
-                .unwrap()
-                .document()
-                .unwrap()
-                .dispatch_event(&event)
-                .unwrap();
+            emit(AppEvent::UpdateSensitivity { value });
         })
     };
 
@@ -689,19 +780,7 @@ pub fn pitch_controls() -> Html {
             }
 
             // 토글 이벤트 발생
-            let event = CustomEvent::new_with_event_init_dict(
-                "toggleAudio",
-                CustomEventInit::new()
-                    .bubbles(true)
-                    .detail(&JsValue::from_bool(new_state)),
-            )
-            .unwrap();
-            web_sys::window()
-                .unwrap()
-                .document()
-                .unwrap()
-                .dispatch_event(&event)
-                .unwrap();
+            emit(AppEvent::ToggleAudio);
         })
     };
 
@@ -719,19 +798,7 @@ pub fn pitch_controls() -> Html {
             monitor_active.set(new_state);
 
             // 모니터링 토글 이벤트 발생
-            let event = CustomEvent::new_with_event_init_dict(
-                "toggleMonitor",
-                CustomEventInit::new()
-                    .bubbles(true)
-                    .detail(&JsValue::from_bool(new_state)),
-            )
-            .unwrap();
-            web_sys::window()
-                .unwrap()
-                .document()
-                .unwrap()
-                .dispatch_event(&event)
-                .unwrap();
+            emit(AppEvent::ToggleMonitor);
         })
     };
     
@@ -752,38 +819,49 @@ pub fn pitch_controls() -> Html {
                 mic_active.set(false);
             }
             
-            let event = CustomEvent::new_with_event_init_dict(
-                "togglePlayback",
-                CustomEventInit::new()
-                    .bubbles(true)
-                    .detail(&JsValue::from_bool(new_state)),
-            )
-            .unwrap();
-            web_sys::window()
-                .unwrap()
-                .document()
-                .unwrap()
-                .dispatch_event(&event)
-                .unwrap();
+            emit(AppEvent::TogglePlayback { playing: new_state });
         })
     };
 
-    // 스피커 게인 슬라이더
-    let on_speaker_gain_change = {
+    // 스피커 게인 슬라이더 - 움직이는 동안 바로 반영 (녹음/재생 중에도 램프로 부드럽게 바뀜), 놓으면 저장
+    let on_speaker_gain_input = {
         let speaker_gain = speaker_gain.clone();
-        Callback::from(move |e: web_sys::Event| {
-            let input = e.target().unwrap().dyn_into::<web_sys::HtmlInputElement>().unwrap();
+        Callback::from(move |e: web_sys::InputEvent| {
+            let input = e.target_unchecked_into::<web_sys::HtmlInputElement>();
             let value = input.value().parse::<f32>().unwrap_or(0.02);
             speaker_gain.set(value);
 
             // 스피커 게인 변경 이벤트 발생
-            let event = CustomEvent::new_with_event_init_dict(
-                "updateSpeakerVolume",
-                CustomEventInit::new()
-                    .bubbles(true)
-                    .detail(&JsValue::from_f64(value as f64)),
-            ).unwrap();
-            web_sys::window().unwrap().document().unwrap().dispatch_event(&event).unwrap();
+            emit(AppEvent::UpdateSpeakerVolume { value });
+        })
+    };
+
+    let on_speaker_gain_change = Callback::from(move |e: web_sys::Event| {
+        let input = e.target_unchecked_into::<web_sys::HtmlInputElement>();
+        let value = input.value().parse::<f32>().unwrap_or(0.02);
+        settings::update_settings(|s| s.analyzer.speaker_gain = value);
+    });
+
+    // 백그라운드 세션 켜기/끄기 (분석기를 다시 만들므로 마이크가 꺼져 있고 녹음/카운트인/마무리 중이 아닐 때만)
+    let background_session = use_context::<BackgroundSession>();
+    let on_background_change = {
+        let background_session = background_session.clone();
+        Callback::from(move |e: web_sys::Event| {
+            let input = e.target_unchecked_into::<web_sys::HtmlInputElement>();
+            if let Some(session) = &background_session {
+                session.set_enabled.emit(input.checked());
+            }
+        })
+    };
+
+    // 카운트인 마디 수 변경 (다음 녹음부터 적용)
+    let on_count_in_change = {
+        let count_in_bars = count_in_bars.clone();
+        Callback::from(move |e: web_sys::Event| {
+            let select = e.target_unchecked_into::<web_sys::HtmlSelectElement>();
+            let bars = select.value().parse::<u32>().unwrap_or(0).min(settings::MAX_COUNT_IN_BARS);
+            count_in_bars.set(bars);
+            settings::update_settings(|s| s.analyzer.count_in_bars = bars);
         })
     };
 
@@ -810,17 +888,8 @@ pub fn pitch_controls() -> Html {
                     
                     // 3. Seek 이벤트 발생 (전역 이벤트)
                     let window = web_sys::window().unwrap();
-                    let document = window.document().unwrap();
-                    
-                    let custom_event = CustomEvent::new_with_event_init_dict(
-                        "seekPlayback",
-                        CustomEventInit::new()
-                            .bubbles(true)
-                            .detail(&JsValue::from_f64(value)),
-                    ).unwrap();
                     
-                    // 4. 이벤트 발생 (main.rs에서 SeekPlayback 메시지 처리)
-                    let _ = document.dispatch_event(&custom_event);
+                    emit(AppEvent::SeekPlayback { progress: value });
                     
                     // 5. 약간의 지연 후 강제로 DOM 업데이트 (closure 사용)
                     let input_clone = input.clone();
@@ -861,19 +930,7 @@ pub fn pitch_controls() -> Html {
                         current_time.set(seek_time);
                     }
                     
-                    // 3. Seek 이벤트 발생 (전역 이벤트)
-                    let window = web_sys::window().unwrap();
-                    let document = window.document().unwrap();
-                    
-                    let custom_event = CustomEvent::new_with_event_init_dict(
-                        "seekPlayback",
-                        CustomEventInit::new()
-                            .bubbles(true)
-                            .detail(&JsValue::from_f64(value)),
-                    ).unwrap();
-                    
-                    // 4. 이벤트 발생 (main.rs에서 SeekPlayback 메시지 처리)
-                    let _ = document.dispatch_event(&custom_event);
+                    emit(AppEvent::SeekPlayback { progress: value });
                 }
             }
         })
@@ -915,7 +972,6 @@ pub fn pitch_controls() -> Html {
                     
                     // 4. 비동기적으로 UI를 강제로 업데이트하는 이벤트 발생
                     let window = web_sys::window().unwrap();
-                    let document = window.document().unwrap();
                     
                     // 입력 이벤트 발생
                     let input_event = web_sys::InputEvent::new("input").unwrap();
@@ -926,15 +982,7 @@ pub fn pitch_controls() -> Html {
                     let _ = input.dispatch_event(&change_event);
                     
                     // 5. Seek 이벤트 발생 (전역 이벤트)
-                    let custom_event = CustomEvent::new_with_event_init_dict(
-                        "seekPlayback",
-                        CustomEventInit::new()
-                            .bubbles(true)
-                            .detail(&JsValue::from_f64(value)),
-                    ).unwrap();
-                    
-                    // 이벤트 발생 (main.rs에서 SeekPlayback 메시지 처리)
-                    let _ = document.dispatch_event(&custom_event);
+                    emit(AppEvent::SeekPlayback { progress: value });
                     
                     // 6. 약간의 지연 후 강제로 DOM 업데이트 (closure 사용)
                     let input_clone = input.clone();
@@ -1016,7 +1064,6 @@ pub fn pitch_controls() -> Html {
                         
                         // 3. 비동기적으로 UI를 강제로 업데이트하는 이벤트 발생
                         let window = web_sys::window().unwrap();
-                        let document = window.document().unwrap();
                         
                         // 입력 이벤트 발생
                         let input_event = web_sys::InputEvent::new("input").unwrap();
@@ -1033,15 +1080,7 @@ pub fn pitch_controls() -> Html {
                         }
                         
                         // 5. Seek 이벤트 발생 (전역 이벤트)
-                        let custom_event = CustomEvent::new_with_event_init_dict(
-                            "seekPlayback",
-                            CustomEventInit::new()
-                                .bubbles(true)
-                                .detail(&JsValue::from_f64(value)),
-                        ).unwrap();
-                        
-                        // 이벤트 발생 (main.rs에서 SeekPlayback 메시지 처리)
-                        let _ = document.dispatch_event(&custom_event);
+                        emit(AppEvent::SeekPlayback { progress: value });
                         
                         // 7. 약간의 지연 후 강제로 DOM 업데이트 (closure 사용)
                         let input_clone = input.clone();
@@ -1067,7 +1106,7 @@ pub fn pitch_controls() -> Html {
         })
     };
 
-    // 재생 시간 업데이트 이벤트 리스너 추가
+    // 재생 시간/재생 상태/마이크 상태 이벤트 처리
     {
         let current_time = current_time.clone();
         let duration = duration.clone();
@@ -1076,144 +1115,53 @@ pub fn pitch_controls() -> Html {
         let is_playing = is_playing.clone();
         let has_recorded = has_recorded.clone();
         let mic_active = mic_active.clone();
-        
-        use_effect(move || {
-            let window = web_sys::window().expect("window를 찾을 수 없습니다");
-            let document = window.document().expect("document를 찾을 수 없습니다");
-            
-            // 재생 시간 업데이트 이벤트 리스너
-            let playback_time_callback = Closure::wrap(Box::new(move |e: web_sys::CustomEvent| {
-                // 드래그 중에도 시간 정보는 업데이트 (단, 슬라이더 위치는 고정)
-                let detail = e.detail();
-                let data = js_sys::Object::from(detail);
-                
-                // 녹음 상태 확인 (녹음 중인지 여부)
-                let is_recording = if let Ok(is_rec) = js_sys::Reflect::get(&data, &JsValue::from_str("isRecording")) {
-                    if let Some(rec_state) = is_rec.as_bool() {
-                        rec_state
-                    } else {
-                        false
-                    }
-                } else {
-                    false
-                };
-                
-                if is_recording {
+
+        use_app_event(move |event| match event {
+            // 드래그 중에도 시간 정보는 업데이트 (단, 슬라이더 위치는 고정)
+            AppEvent::PlaybackTimeUpdate { current_time: time, duration: total, is_recording } => {
+                if *is_recording {
                     // 녹음 중일 때는 진행률을 0으로 고정하고, 현재 시간을 0으로 고정
                     progress.set(0.0);
                     current_time.set(0.0);
-                    
+
                     // 녹음 중에는 마이크가 활성화되어 있어야 함
                     mic_active.set(true);
-                    
+
                     // 전체 녹음 시간만 업데이트
-                    if let Ok(total) = js_sys::Reflect::get(&data, &JsValue::from_str("duration")) {
-                        if let Some(d) = total.as_f64() {
-                            duration.set(d);
-                        }
-                    }
+                    duration.set(*total);
                 } else {
                     // 일반 재생 모드에서는 정상적으로 시간 정보 업데이트
-                    if let Ok(current) = js_sys::Reflect::get(&data, &JsValue::from_str("currentTime")) {
-                        if let Some(time) = current.as_f64() {
-                            current_time.set(time);
-                        }
-                    }
-                    
-                    if let Ok(total) = js_sys::Reflect::get(&data, &JsValue::from_str("duration")) {
-                        if let Some(d) = total.as_f64() {
-                            duration.set(d);
-                            
-                            // 시크 중이 아닐 때만 진행률 계산 및 업데이트
-                            if !*is_seeking && d > 0.0 {
-                                let prog = *current_time / d;
-                                progress.set(prog);
-                            }
-                        }
-                    }
-                }
-            }) as Box<dyn FnMut(_)>);
-            
-            document.add_event_listener_with_callback(
-                "playbackTimeUpdate", 
-                playback_time_callback.as_ref().unchecked_ref()
-            ).expect("이벤트 리스너 추가 실패");
-            
-            // 재생 상태 업데이트 이벤트 리스너
-            let state_callback = Closure::wrap(Box::new(move |e: web_sys::CustomEvent| {
-                let detail = e.detail();
-                
-                if let Some(state) = detail.as_bool() {
-                    is_playing.set(state);
-                    
-                    if state {
-                        // 재생이 시작되면 has_recorded를 true로 설정
-                        has_recorded.set(true);
+                    current_time.set(*time);
+                    duration.set(*total);
+
+                    // 시크 중이 아닐 때만 진행률 계산 및 업데이트
+                    if !*is_seeking && *total > 0.0 {
+                        progress.set(time / total);
                     }
                 }
-            }) as Box<dyn FnMut(_)>);
-            
-            document.add_event_listener_with_callback(
-                "playbackStateChange", 
-                state_callback.as_ref().unchecked_ref()
-            ).expect("이벤트 리스너 추가 실패");
-            
-            // 클린업 함수 - 컴포넌트가 언마운트될 때 이벤트 리스너 제거
-            move || {
-                let _ = document.remove_event_listener_with_callback(
-                    "playbackTimeUpdate",
-                    playback_time_callback.as_ref().unchecked_ref()
-                );
-                let _ = document.remove_event_listener_with_callback(
-                    "playbackStateChange",
-                    state_callback.as_ref().unchecked_ref()
-                );
-                drop(playback_time_callback);
-                drop(state_callback);
             }
-        });
-    }
+            AppEvent::PlaybackStateChange { playing } => {
+                is_playing.set(*playing);
 
-    // 마이크 토글 이벤트 리스너
-    {
-        let mic_active = mic_active.clone();
-        let is_playing = is_playing.clone();
-        let has_recorded = has_recorded.clone();
-        
-        use_effect(move || {
-            let window = web_sys::window().expect("window를 찾을 수 없습니다");
-            let document = window.document().expect("document를 찾을 수 없습니다");
-            
-            // 서버에서 보내는 toggleAudio 이벤트 처리
-            let callback = Closure::wrap(Box::new(move |e: web_sys::CustomEvent| {
+                if *playing {
+                    // 재생이 시작되면 has_recorded를 true로 설정
+                    has_recorded.set(true);
+                }
+            }
+            // 분석기가 스스로 마이크를 끈 경우 (녹음 종료, 최대 녹음 시간 도달)
+            AppEvent::MicStateChanged { active } => {
                 if *is_playing {
                     return;
                 }
-                
-                // detail에 지정된 상태 가져오기
-                let new_state = e.detail().as_bool().unwrap_or(!*mic_active);
-                mic_active.set(new_state);
-                
-                if new_state {
+
+                mic_active.set(*active);
+                if *active {
                     has_recorded.set(true);
                 }
-                
-                console_log!("서버에서 보낸 toggleAudio 이벤트 처리: new_state={}", new_state);
-            }) as Box<dyn FnMut(_)>);
-            
-            document.add_event_listener_with_callback(
-                "toggleAudio", 
-                callback.as_ref().unchecked_ref()
-            ).expect("이벤트 리스너 추가 실패");
-            
-            // 클린업 함수 - 컴포넌트가 언마운트될 때 이벤트 리스너 제거
-            move || {
-                let _ = document.remove_event_listener_with_callback(
-                    "toggleAudio",
-                    callback.as_ref().unchecked_ref()
-                );
-                drop(callback);
+
+                console_log!("분석기의 마이크 상태 변경 처리: active={}", active);
             }
+            _ => {}
         });
     }
 
@@ -1260,22 +1208,13 @@ pub fn pitch_controls() -> Html {
                     
                     // 4. 비동기적으로 UI를 강제로 업데이트하는 이벤트 발생
                     let window = web_sys::window().unwrap();
-                    let document = window.document().unwrap();
                     
                     // 입력 이벤트 발생
                     let input_event = web_sys::InputEvent::new("input").unwrap();
                     let _ = input.dispatch_event(&input_event);
                     
                     // 5. Seek 이벤트 발생 (전역 이벤트)
-                    let custom_event = CustomEvent::new_with_event_init_dict(
-                        "seekPlayback",
-                        CustomEventInit::new()
-                            .bubbles(true)
-                            .detail(&JsValue::from_f64(value)),
-                    ).unwrap();
-                    
-                    // 이벤트 발생 (main.rs에서 SeekPlayback 메시지 처리)
-                    let _ = document.dispatch_event(&custom_event);
+                    emit(AppEvent::SeekPlayback { progress: value });
                     
                     // 6. 약간의 지연 후 강제로 DOM 업데이트 (closure 사용)
                     let input_clone = input.clone();
@@ -1303,40 +1242,144 @@ pub fn pitch_controls() -> Html {
         })
     };
 
-    // 다운로드 포맷 선택 콜백
+    // 다운로드 포맷 선택 콜백 (구간 내보내기 중에는 자를 수 없는 WebM 제외)
     let select_download_format = {
-        let selected_format = selected_format.clone();
-        Callback::from(move |format: String| {
-            selected_format.set(format);
+        let export_options = export_options.clone();
+        Callback::from(move |format: ExportFormat| {
+            if format == ExportFormat::WebM && export_options.region.is_some() {
+                return;
+            }
+            export_options.set(export_options.with_format(format));
+        })
+    };
+
+    // 선택 구간만 내보내기 토글 (WebM이면 WAV로 전환)
+    let on_region_toggle = {
+        let export_options = export_options.clone();
+        let selected_region = selected_region.clone();
+        Callback::from(move |e: Event| {
+            let input = e.target_unchecked_into::<web_sys::HtmlInputElement>();
+            let region = if input.checked() { *selected_region } else { None };
+            let options = if region.is_some() && export_options.format == ExportFormat::WebM {
+                export_options.with_format(ExportFormat::Wav)
+            } else {
+                *export_options
+            };
+            export_options.set(ExportOptions { region, ..options });
+        })
+    };
+
+    // WAV 비트 깊이 선택 콜백
+    let on_wav_bit_depth_change = {
+        let export_options = export_options.clone();
+        Callback::from(move |e: Event| {
+            let select = e.target_unchecked_into::<web_sys::HtmlSelectElement>();
+            if let Some(wav_format) = WavSampleFormat::from_value(&select.value()) {
+                export_options.set(ExportOptions { wav_format, ..*export_options });
+            }
+        })
+    };
+
+    // FLAC 비트 깊이 선택 콜백
+    let on_flac_bit_depth_change = {
+        let export_options = export_options.clone();
+        Callback::from(move |e: Event| {
+            let select = e.target_unchecked_into::<web_sys::HtmlSelectElement>();
+            if let Ok(flac_bits) = select.value().parse::<u32>() {
+                export_options.set(ExportOptions { flac_bits, ..*export_options });
+            }
+        })
+    };
+
+    // MP3 비트레이트 선택 콜백
+    let on_mp3_bitrate_change = {
+        let export_options = export_options.clone();
+        Callback::from(move |e: Event| {
+            let select = e.target_unchecked_into::<web_sys::HtmlSelectElement>();
+            if let Ok(mp3_bitrate) = select.value().parse::<u32>() {
+                export_options.set(ExportOptions { mp3_bitrate, ..*export_options });
+            }
+        })
+    };
+
+    // 샘플레이트 선택 콜백
+    let on_sample_rate_change = {
+        let export_options = export_options.clone();
+        Callback::from(move |e: Event| {
+            let select = e.target_unchecked_into::<web_sys::HtmlSelectElement>();
+            if let Ok(sample_rate) = select.value().parse::<u32>() {
+                export_options.set(ExportOptions { sample_rate, ..*export_options });
+            }
+        })
+    };
+
+    // 채널 선택 콜백
+    let on_channels_change = {
+        let export_options = export_options.clone();
+        Callback::from(move |e: Event| {
+            let select = e.target_unchecked_into::<web_sys::HtmlSelectElement>();
+            if let Ok(channels) = select.value().parse::<u16>() {
+                export_options.set(ExportOptions { channels, ..*export_options });
+            }
         })
     };
 
     // 다운로드 실행 콜백
     let execute_download = {
-        let selected_format = selected_format.clone();
+        let export_options = export_options.clone();
         let show_download_format = show_download_format.clone();
         Callback::from(move |_| {
-            // 다운로드 이벤트 발생 (선택된 포맷 포함)
-            let event = CustomEvent::new_with_event_init_dict(
-                "downloadRecording",
-                CustomEventInit::new()
-                    .bubbles(true)
-                    .detail(&JsValue::from_str(&selected_format)),
-            ).unwrap();
-            web_sys::window()
-                .unwrap()
-                .document()
-                .unwrap()
-                .dispatch_event(&event)
-                .unwrap();
+            // 다운로드 이벤트 발생 (포맷, 포맷별 옵션)
+            emit(AppEvent::DownloadRecording(*export_options));
             
             // 드롭다운 닫기
             show_download_format.set(false);
             
-            console_log!("다운로드 이벤트 발행됨 (포맷: {})", *selected_format);
+            console_log!("다운로드 이벤트 발행됨 (포맷: {})", export_options.format.value());
         })
     };
 
+    // 포맷별 옵션 (WebM은 원본 그대로라 옵션 없음)
+    let format_options = match export_options.format {
+        ExportFormat::WebM => html! {},
+        ExportFormat::Wav => html! {
+            <label class="export-option">
+                <span>{"비트 깊이"}</span>
+                <select onchange={on_wav_bit_depth_change}>
+                    { for WavSampleFormat::ALL.iter().map(|format| html! {
+                        <option value={format.value()} selected={*format == export_options.wav_format}>
+                            { format.label() }
+                        </option>
+                    }) }
+                </select>
+            </label>
+        },
+        ExportFormat::Flac => html! {
+            <label class="export-option">
+                <span>{"비트 깊이"}</span>
+                <select onchange={on_flac_bit_depth_change}>
+                    { for FLAC_BIT_DEPTHS.iter().map(|bits| html! {
+                        <option value={bits.to_string()} selected={*bits == export_options.flac_bits}>
+                            { format!("{}-bit", bits) }
+                        </option>
+                    }) }
+                </select>
+            </label>
+        },
+        ExportFormat::Mp3 => html! {
+            <label class="export-option">
+                <span>{"비트레이트"}</span>
+                <select onchange={on_mp3_bitrate_change}>
+                    { for MP3_BITRATES.iter().map(|bitrate| html! {
+                        <option value={bitrate.to_string()} selected={*bitrate == export_options.mp3_bitrate}>
+                            { format!("{} kbps", bitrate) }
+                        </option>
+                    }) }
+                </select>
+            </label>
+        },
+    };
+
     let buttons_disabled = buttons_disabled.clone();
     html! {
         <div class="pitch-controls navbar-item">
@@ -1370,42 +1413,81 @@ pub fn pitch_controls() -> Html {
                 // 다운로드 버튼과 드롭다운 수정
                 <div class="download-dropdown">
                     <button
-                        class="icon-button download-button"
+                        class={classes!("icon-button", "download-button", export_progress.is_some().then_some("exporting"))}
                         onclick={toggle_download_format}
-                        title="녹음 파일 다운로드"
-                        disabled={*mic_active || !*has_recorded || *buttons_disabled}
+                        title={if export_progress.is_some() { "녹음 파일 인코딩 중" } else { "녹음 파일 다운로드" }}
+                        disabled={*mic_active || !*has_recorded || *buttons_disabled || export_progress.is_some()}
                     >
-                        { "💾" }
+                        {
+                            match *export_progress {
+                                Some(progress) => format!("⏳ {}%", (progress * 100.0).round() as u32),
+                                None => "💾".to_string(),
+                            }
+                        }
                     </button>
                     {
                         if *show_download_format {
                             html! {
                                 <div class="download-dropdown-content">
-                                    <div class="format-option" onclick={let f = "webm".to_string(); select_download_format.clone().reform(move |_| f.clone())}>
-                                        <span class={classes!("format-text", if *selected_format == "webm" { "selected" } else { "" })}>
-                                            {"WebM"}
-                                        </span>
-                                    </div>
-                                    <div class="format-option" onclick={let f = "mp3".to_string(); select_download_format.clone().reform(move |_| f.clone())}>
-                                        <span class={classes!("format-text", if *selected_format == "mp3" { "selected" } else { "" })}>
-                                            {"MP3"}
-                                        </span>
-                                    </div>
-                                    <div class="format-option" onclick={let f = "wav".to_string(); select_download_format.clone().reform(move |_| f.clone())}>
-                                        <span class={classes!("format-text", if *selected_format == "wav" { "selected" } else { "" })}>
-                                            {"WAV"}
-                                        </span>
-                                    </div>
-                                    <div class="format-option" onclick={let f = "ogg".to_string(); select_download_format.clone().reform(move |_| f.clone())}>
-                                        <span class={classes!("format-text", if *selected_format == "ogg" { "selected" } else { "" })}>
-                                            {"OGG"}
-                                        </span>
-                                    </div>
-                                    <div class="format-option" onclick={let f = "m4a".to_string(); select_download_format.clone().reform(move |_| f.clone())}>
-                                        <span class={classes!("format-text", if *selected_format == "m4a" { "selected" } else { "" })}>
-                                            {"M4A"}
-                                        </span>
-                                    </div>
+                                    { for ExportFormat::ALL.iter().map(|format| {
+                                        let format = *format;
+                                        let unavailable = format == ExportFormat::WebM && export_options.region.is_some();
+                                        html! {
+                                            <div
+                                                class={classes!("format-option", unavailable.then_some("disabled"))}
+                                                title={if unavailable { "원본 WebM은 구간으로 자를 수 없습니다" } else { "" }}
+                                                onclick={select_download_format.reform(move |_| format)}
+                                            >
+                                                <span class={classes!("format-text", if export_options.format == format { "selected" } else { "" })}>
+                                                    { format.label() }
+                                                </span>
+                                            </div>
+                                        }
+                                    }) }
+                                    {
+                                        if export_options.format != ExportFormat::WebM {
+                                            html! {
+                                                <div class="export-options">
+                                                    { format_options }
+                                                    <label class="export-option">
+                                                        <span>{"샘플레이트"}</span>
+                                                        <select onchange={on_sample_rate_change}>
+                                                            { for export_options.format.sample_rates().iter().map(|rate| html! {
+                                                                <option value={rate.to_string()} selected={*rate == export_options.sample_rate}>
+                                                                    { format!("{} Hz", rate) }
+                                                                </option>
+                                                            }) }
+                                                        </select>
+                                                    </label>
+                                                    <label class="export-option">
+                                                        <span>{"채널"}</span>
+                                                        <select onchange={on_channels_change}>
+                                                            <option value="1" selected={export_options.channels == 1}>{"모노"}</option>
+                                                            <option value="2" selected={export_options.channels == 2}>{"스테레오"}</option>
+                                                        </select>
+                                                    </label>
+                                                </div>
+                                            }
+                                        } else {
+                                            html! {}
+                                        }
+                                    }
+                                    {
+                                        if let Some((start, end)) = *selected_region {
+                                            html! {
+                                                <label class="export-region-option">
+                                                    <input
+                                                        type="checkbox"
+                                                        checked={export_options.region.is_some()}
+                                                        onchange={on_region_toggle}
+                                                    />
+                                                    { format!("✂️ 선택 구간만 ({:.1}–{:.1}초)", start, end) }
+                                                </label>
+                                            }
+                                        } else {
+                                            html! {}
+                                        }
+                                    }
                                     <div class="download-separator"></div>
                                     <div class="format-option save-option" onclick={execute_download}>
                                         {"저장하기"}
@@ -1416,6 +1498,22 @@ pub fn pitch_controls() -> Html {
                             html! {}
                         }
                     }
+                    {
+                        if let Some(message) = &*export_error {
+                            let export_error = export_error.clone();
+                            html! {
+                                <div
+                                    class="export-error"
+                                    title="클릭하여 닫기"
+                                    onclick={Callback::from(move |_: MouseEvent| export_error.set(None))}
+                                >
+                                    { format!("⚠️ {}", message) }
+                                </div>
+                            }
+                        } else {
+                            html! {}
+                        }
+                    }
                 </div>
                 
                 // 재생 게이지 바 추가
@@ -1463,8 +1561,8 @@ pub fn pitch_controls() -> Html {
                                             max="1.0"
                                             step="0.01"
                                             value={(*speaker_gain).to_string()}
-                                            onchange={on_speaker_gain_change.clone()}
-                                            disabled={*buttons_disabled}
+                                            oninput={on_speaker_gain_input}
+                                            onchange={on_speaker_gain_change}
                                         />
                                         <span>{ format!("{:.2}", *speaker_gain) }</span>
                                     </div>
@@ -1482,6 +1580,35 @@ pub fn pitch_controls() -> Html {
                                         />
                                         <span>{ format!("{:.3}", *sensitivity) }</span>
                                     </div>
+                                    <div class="sensitivity-slider">
+                                        <label for="count-in">{"카운트인"}</label>
+                                        <select
+                                            id="count-in"
+                                            class="count-in-select"
+                                            onchange={on_count_in_change}
+                                            disabled={*mic_active}
+                                            title="녹음 전에 메트로놈 BPM/박자로 울릴 마디 수"
+                                        >
+                                            { for (0..=settings::MAX_COUNT_IN_BARS).map(|bars| html! {
+                                                <option value={bars.to_string()} selected={*count_in_bars == bars}>
+                                                    { if bars == 0 { "끔".to_string() } else { format!("{}마디", bars) } }
+                                                </option>
+                                            }) }
+                                        </select>
+                                    </div>
+                                    if let Some(session) = &background_session {
+                                        <div class="sensitivity-slider">
+                                            <label for="background-session">{"백그라운드 세션"}</label>
+                                            <input
+                                                type="checkbox"
+                                                id="background-session"
+                                                checked={session.enabled}
+                                                onchange={on_background_change}
+                                                disabled={*mic_active || *recording_busy || *buttons_disabled}
+                                                title="켜 두면 다른 도구 페이지로 옮겨도 마이크, 녹음, 피치 기록이 이어집니다"
+                                            />
+                                        </div>
+                                    }
                                 </div>
                             }
                         } else {
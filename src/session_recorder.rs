@@ -0,0 +1,337 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use gloo::timers::callback::Interval;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{Blob, MediaRecorder, MediaStream};
+use yew::prelude::*;
+
+use crate::audio_engine::{AudioEngine, RecordSource};
+use crate::tools::take_library::{self, Stem, Take};
+
+// 조건부 로그 매크로 정의
+#[cfg(debug_assertions)]
+macro_rules! console_log {
+    ($($arg:tt)*) => {
+        web_sys::console::log_1(&format!($($arg)*).into());
+    };
+}
+
+#[cfg(not(debug_assertions))]
+macro_rules! console_log {
+    ($($arg:tt)*) => {};
+}
+
+#[cfg(debug_assertions)]
+macro_rules! console_error {
+    ($($arg:tt)*) => {
+        web_sys::console::error_1(&format!($($arg)*).into());
+    };
+}
+
+#[cfg(not(debug_assertions))]
+macro_rules! console_error {
+    ($($arg:tt)*) => {};
+}
+
+// 엔진의 녹음 버스를 MediaRecorder로 저장
+// - StemRecorders: 소리별 스템 (피치 분석기 녹음과 함께 사용)
+// - SessionRecording: 분석기가 없는 페이지에서 헤더 버튼으로 하는 믹스 + 스템 녹음
+
+// 선호 순서대로 시도하는 녹음 형식 (모두 지원하지 않으면 브라우저 기본 형식)
+const PREFERRED_MIME_TYPES: [&str; 4] = ["audio/webm;codecs=opus", "audio/webm", "audio/ogg;codecs=opus", "audio/mp4"];
+
+// Blob 형식을 알 수 없을 때 사용
+const FALLBACK_BLOB_TYPE: &str = "audio/webm";
+
+// 스트림 하나를 녹음해 청크를 모으는 레코더
+struct TrackRecorder {
+    recorder: MediaRecorder,
+    chunks: Rc<RefCell<Vec<Blob>>>,
+    _ondataavailable: Closure<dyn FnMut(web_sys::BlobEvent)>, // 레코더와 함께 해제
+}
+
+impl TrackRecorder {
+    fn start(stream: &MediaStream) -> Result<Self, JsValue> {
+        let options = web_sys::MediaRecorderOptions::new();
+        options.set_audio_bits_per_second(128000);
+        match PREFERRED_MIME_TYPES.into_iter().find(|mime| MediaRecorder::is_type_supported(mime)) {
+            Some(mime) => options.set_mime_type(mime),
+            None => {
+                console_log!("선호하는 녹음 형식을 지원하지 않아 브라우저 기본 형식 사용");
+            }
+        }
+        let recorder = MediaRecorder::new_with_media_stream_and_media_recorder_options(stream, &options)?;
+
+        let chunks = Rc::new(RefCell::new(Vec::new()));
+        let chunks_clone = chunks.clone();
+        let ondataavailable = Closure::<dyn FnMut(web_sys::BlobEvent)>::new(move |event: web_sys::BlobEvent| {
+            if let Some(blob) = event.data().filter(|blob| blob.size() > 0.0) {
+                chunks_clone.borrow_mut().push(blob);
+            }
+        });
+        recorder.set_ondataavailable(Some(ondataavailable.as_ref().unchecked_ref()));
+
+        recorder.start_with_time_slice(250)?;
+        Ok(Self {
+            recorder,
+            chunks,
+            _ondataavailable: ondataavailable,
+        })
+    }
+
+    // 녹음을 멈추고 남은 청크까지 모아 하나의 Blob으로
+    async fn finish(self) -> Option<Blob> {
+        if self.recorder.state() != web_sys::RecordingState::Inactive {
+            let recorder = self.recorder.clone();
+            let stopped = js_sys::Promise::new(&mut |resolve, _reject| {
+                let onstop = Closure::once_into_js(move |_: web_sys::Event| {
+                    let _ = resolve.call0(&JsValue::NULL);
+                });
+                recorder.set_onstop(Some(onstop.unchecked_ref()));
+                if let Err(err) = recorder.stop() {
+                    console_error!("레코더 중지 실패: {:?}", err);
+                }
+            });
+            let _ = JsFuture::from(stopped).await;
+        }
+        self.recorder.set_ondataavailable(None);
+        self.recorder.set_onstop(None);
+
+        let chunks = self.chunks.borrow();
+        if chunks.is_empty() {
+            return None;
+        }
+        let parts = js_sys::Array::new();
+        for chunk in chunks.iter() {
+            parts.push(chunk);
+        }
+        // 실제로 사용된 형식으로 저장 (mimeType을 지정하지 않았으면 브라우저가 고른 값)
+        let mime_type = self.recorder.mime_type();
+        let blob_options = web_sys::BlobPropertyBag::new();
+        blob_options.set_type(if mime_type.is_empty() { FALLBACK_BLOB_TYPE } else { &mime_type });
+        Blob::new_with_blob_sequence_and_options(&parts, &blob_options).ok()
+    }
+
+    // 결과 없이 중지
+    fn cancel(self) {
+        self.recorder.set_ondataavailable(None);
+        if self.recorder.state() != web_sys::RecordingState::Inactive {
+            let _ = self.recorder.stop();
+        }
+    }
+}
+
+// 선택된 소리마다 따로 녹음하는 스템 레코더 묶음
+pub struct StemRecorders {
+    tracks: Vec<(RecordSource, TrackRecorder)>,
+}
+
+impl StemRecorders {
+    pub fn start(engine: &AudioEngine, sources: &[RecordSource]) -> Result<Self, JsValue> {
+        let mut tracks = Vec::new();
+        for source in sources {
+            tracks.push((*source, TrackRecorder::start(&engine.stem_stream(*source)?)?));
+        }
+        console_log!("스템 녹음 시작: {}개", tracks.len());
+        Ok(Self { tracks })
+    }
+
+    pub async fn finish(self) -> Vec<Stem> {
+        let mut stems = Vec::new();
+        for (source, track) in self.tracks {
+            if let Some(blob) = track.finish().await {
+                stems.push(Stem { name: source.label().to_string(), blob });
+            }
+        }
+        stems
+    }
+
+    pub fn cancel(self) {
+        self.tracks.into_iter().for_each(|(_, track)| track.cancel());
+    }
+}
+
+// 헤더 버튼으로 시작한 녹음 (페이지를 옮겨도 계속됨)
+struct SessionRecording {
+    engine: AudioEngine,
+    mix: TrackRecorder,
+    stems: Option<StemRecorders>,
+    uses_mic: bool,
+    latency: f64,    // 녹음 버스의 정렬 지연 (마이크를 녹음할 때의 왕복 지연, 초)
+    started_at: f64, // 녹음 시작 시각 (ms)
+}
+
+thread_local! {
+    static ACTIVE_SESSION: RefCell<Option<SessionRecording>> = const { RefCell::new(None) };
+}
+
+pub fn is_session_recording() -> bool {
+    ACTIVE_SESSION.with(|session| session.borrow().is_some())
+}
+
+fn session_started_at() -> Option<f64> {
+    ACTIVE_SESSION.with(|session| session.borrow().as_ref().map(|session| session.started_at))
+}
+
+// 녹음 설정대로 믹스(와 스템) 녹음 시작 (마이크가 포함되면 마이크도 염)
+async fn start_session(engine: AudioEngine) -> Result<(), JsValue> {
+    let recording = engine.apply_recording_settings()?;
+    let sources = RecordSource::enabled(&recording);
+    if sources.is_empty() {
+        return Err(JsValue::from_str("녹음할 소리가 선택되지 않았습니다"));
+    }
+
+    let uses_mic = sources.contains(&RecordSource::Mic);
+    if uses_mic {
+        engine.acquire_mic().await?;
+    }
+
+    let started = TrackRecorder::start(&engine.recording_stream()?).and_then(|mix| {
+        let stems = if recording.stems { Some(StemRecorders::start(&engine, &sources)?) } else { None };
+        Ok((mix, stems))
+    });
+    let (mix, stems) = match started {
+        Ok(recorders) => recorders,
+        Err(err) => {
+            if uses_mic {
+                engine.release_mic();
+            }
+            return Err(err);
+        }
+    };
+
+    let latency = engine.record_delay();
+    ACTIVE_SESSION.with(|session| {
+        *session.borrow_mut() = Some(SessionRecording {
+            engine,
+            mix,
+            stems,
+            uses_mic,
+            latency,
+            started_at: js_sys::Date::now(),
+        })
+    });
+    console_log!("세션 녹음 시작");
+    Ok(())
+}
+
+// 녹음을 마치고 테이크 목록에 추가 (피치 이력은 없음)
+async fn finish_session() {
+    let Some(session) = ACTIVE_SESSION.with(|session| session.borrow_mut().take()) else {
+        return;
+    };
+    let duration = (js_sys::Date::now() - session.started_at) / 1000.0;
+
+    let audio_blob = session.mix.finish().await;
+    let stems = match session.stems {
+        Some(stems) => stems.finish().await,
+        None => Vec::new(),
+    };
+    if session.uses_mic {
+        session.engine.release_mic();
+    }
+
+    let Some(audio_blob) = audio_blob else {
+        console_error!("세션 녹음 데이터가 없습니다");
+        return;
+    };
+    let audio_url = match web_sys::Url::create_object_url_with_blob(&audio_blob) {
+        Ok(url) => url,
+        Err(err) => {
+            console_error!("URL 생성 실패: {:?}", err);
+            return;
+        }
+    };
+
+    take_library::add_take(Take {
+        id: 0,
+        name: String::new(),
+        starred: false,
+        created_at: session.started_at,
+        duration,
+        audio_blob,
+        audio_url,
+        history: Default::default(),
+        amplitude_history: Default::default(),
+        stems,
+        beat_grid: None,
+        latency: session.latency,
+    });
+    console_log!("세션 녹음 저장: {:.1}초", duration);
+}
+
+fn format_elapsed(secs: f64) -> String {
+    let secs = secs.max(0.0) as u64;
+    format!("{}:{:02}", secs / 60, secs % 60)
+}
+
+// 피치 분석기가 없는 페이지에서 쓰는 헤더 녹음 버튼
+#[function_component(SessionRecordButton)]
+pub fn session_record_button() -> Html {
+    let engine = use_context::<AudioEngine>().unwrap_or_default();
+    let is_recording = use_state(is_session_recording);
+    let is_busy = use_state(|| false); // 마이크 권한 대기, 저장 중
+    let elapsed = use_state(|| 0.0f64);
+
+    // 녹음 중에는 경과 시간 표시
+    {
+        let elapsed = elapsed.clone();
+        use_effect_with(*is_recording, move |is_recording| {
+            let interval = is_recording.then(|| {
+                Interval::new(500, move || {
+                    if let Some(started_at) = session_started_at() {
+                        elapsed.set((js_sys::Date::now() - started_at) / 1000.0);
+                    }
+                })
+            });
+            move || drop(interval)
+        });
+    }
+
+    let on_click = {
+        let is_recording = is_recording.clone();
+        let is_busy = is_busy.clone();
+        let elapsed = elapsed.clone();
+        Callback::from(move |_: MouseEvent| {
+            let engine = engine.clone();
+            let is_recording = is_recording.clone();
+            let is_busy = is_busy.clone();
+            let elapsed = elapsed.clone();
+            is_busy.set(true);
+            wasm_bindgen_futures::spawn_local(async move {
+                if is_session_recording() {
+                    finish_session().await;
+                } else if let Err(err) = start_session(engine).await {
+                    console_error!("세션 녹음 시작 실패: {:?}", err);
+                    if let Some(window) = web_sys::window() {
+                        let _ = window.alert_with_message(
+                            &err.as_string().unwrap_or_else(|| "녹음을 시작할 수 없습니다.".to_string()),
+                        );
+                    }
+                }
+                elapsed.set(0.0);
+                is_recording.set(is_session_recording());
+                is_busy.set(false);
+            });
+        })
+    };
+
+    html! {
+        <button
+            class={classes!("session-record-button", (*is_recording).then_some("recording"))}
+            onclick={on_click}
+            disabled={*is_busy}
+            title={if *is_recording { "녹음 중지 (테이크로 저장)" } else { "믹서에서 고른 소리를 녹음" }}
+        >
+            {
+                if *is_recording {
+                    format!("⏹ {}", format_elapsed(*elapsed))
+                } else {
+                    "⏺ 녹음".to_string()
+                }
+            }
+        </button>
+    }
+}
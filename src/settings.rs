@@ -0,0 +1,454 @@
+use std::collections::HashMap;
+
+use gloo::storage::{LocalStorage, Storage};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::event_bus::{emit, AppEvent};
+use crate::monitor_effects::{MonitorEffects, MonitorPreset};
+use crate::tools::metronome::{NoteUnit, TimeSignature};
+use crate::tools::scale_generator::PlayDirection;
+
+// 조건부 로그 매크로 정의
+#[cfg(debug_assertions)]
+macro_rules! console_log {
+    ($($arg:tt)*) => {
+        web_sys::console::log_1(&format!($($arg)*).into());
+    };
+}
+
+#[cfg(not(debug_assertions))]
+macro_rules! console_log {
+    ($($arg:tt)*) => {};
+}
+
+#[cfg(debug_assertions)]
+macro_rules! console_error {
+    ($($arg:tt)*) => {
+        web_sys::console::error_1(&format!($($arg)*).into());
+    };
+}
+
+#[cfg(not(debug_assertions))]
+macro_rules! console_error {
+    ($($arg:tt)*) => {};
+}
+
+const STORAGE_KEY: &str = "musicalmind.settings";
+
+// 카운트인으로 고를 수 있는 최대 마디 수
+pub const MAX_COUNT_IN_BARS: u32 = 4;
+
+// 지연 보정값으로 받아들이는 최대 왕복 지연 (ms)
+pub const MAX_LATENCY_MS: f64 = 500.0;
+
+// 장치 ID를 알 수 없을 때 보정값을 저장하는 키 (Chrome의 기본 장치 ID와 같음)
+const DEFAULT_DEVICE_KEY: &str = "default";
+
+// 저장 형식 버전 - 필드 구조가 바뀌면 올리고 MIGRATIONS에 변환 단계를 추가
+pub const SETTINGS_VERSION: u32 = 1;
+
+// MIGRATIONS[n]은 버전 n + 1의 JSON을 버전 n + 2로 변환 (v1이 첫 저장 형식)
+const MIGRATIONS: [fn(&mut Value); SETTINGS_VERSION as usize - 1] = [];
+
+// 전체 설정
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    pub version: u32,
+    pub analyzer: AnalyzerSettings,
+    pub metronome: MetronomeSettings,
+    pub scale_generator: ScaleGeneratorSettings,
+    pub piano: PianoSettings,
+    pub recording: RecordingSettings,
+    pub input: InputSettings,
+    pub monitor: MonitorSettings,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            version: SETTINGS_VERSION,
+            analyzer: AnalyzerSettings::default(),
+            metronome: MetronomeSettings::default(),
+            scale_generator: ScaleGeneratorSettings::default(),
+            piano: PianoSettings::default(),
+            recording: RecordingSettings::default(),
+            input: InputSettings::default(),
+            monitor: MonitorSettings::default(),
+        }
+    }
+}
+
+// 피치 컨트롤 (마이크 감도, 스피커 게인, 녹음 재생 속도, 녹음 전 카운트인 마디 수)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AnalyzerSettings {
+    pub sensitivity: f32,
+    pub speaker_gain: f32,
+    pub playback_rate: f64,
+    pub count_in_bars: u32,
+    pub background_session: bool, // 다른 페이지로 가도 마이크/녹음 유지
+}
+
+impl Default for AnalyzerSettings {
+    fn default() -> Self {
+        Self {
+            sensitivity: 0.01,
+            speaker_gain: 0.02,
+            playback_rate: 1.0,
+            count_in_bars: 0,
+            background_session: false,
+        }
+    }
+}
+
+// 메트로놈
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MetronomeSettings {
+    pub bpm: u32,
+    pub time_signature: TimeSignature,
+    pub note_unit: NoteUnit,
+    pub accent: bool,
+}
+
+impl Default for MetronomeSettings {
+    fn default() -> Self {
+        Self {
+            bpm: 120,
+            time_signature: TimeSignature::FourFour,
+            note_unit: NoteUnit::Quarter,
+            accent: true,
+        }
+    }
+}
+
+// 옥타브를 포함한 음 (스케일 범위 저장용)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NoteSetting {
+    pub name: String,
+    pub octave: i32,
+}
+
+// 스케일 생성기
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ScaleGeneratorSettings {
+    pub start_note: NoteSetting,
+    pub end_note: NoteSetting,
+    pub bpm: u32,
+    pub intervals: Vec<String>,
+    pub direction: PlayDirection,
+}
+
+impl Default for ScaleGeneratorSettings {
+    fn default() -> Self {
+        Self {
+            start_note: NoteSetting { name: "C".to_string(), octave: 4 },
+            end_note: NoteSetting { name: "C".to_string(), octave: 5 },
+            bpm: 120,
+            intervals: vec!["1".to_string()],
+            direction: PlayDirection::Ascending,
+        }
+    }
+}
+
+// 피아노 건반
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PianoSettings {
+    pub left_hand_octave: i32,
+    pub right_hand_octave: i32,
+    pub left_hand_start_note_idx: usize,
+    pub right_hand_start_note_idx: usize,
+}
+
+impl Default for PianoSettings {
+    fn default() -> Self {
+        Self {
+            left_hand_octave: 2,
+            right_hand_octave: 4,
+            left_hand_start_note_idx: 0,
+            right_hand_start_note_idx: 0,
+        }
+    }
+}
+
+// 녹음에 섞을 소리와 소리별 스템 저장 여부
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RecordingSettings {
+    pub mic: bool,
+    pub metronome: bool,
+    pub scale_generator: bool,
+    pub piano: bool,
+    pub stems: bool,
+}
+
+impl Default for RecordingSettings {
+    fn default() -> Self {
+        Self {
+            mic: true,
+            metronome: true,
+            scale_generator: true,
+            piano: true,
+            stems: false,
+        }
+    }
+}
+
+// 마이크 입력 장치와 브라우저 음성 처리
+// 에코 제거/잡음 억제/자동 게인은 음성 통화용이라 피치 분석을 망치므로 기본값은 끔
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct InputSettings {
+    pub device_id: Option<String>, // None이면 브라우저 기본 장치
+    pub echo_cancellation: bool,
+    pub noise_suppression: bool,
+    pub auto_gain_control: bool,
+    pub latency_ms: HashMap<String, f64>, // 장치별 왕복 지연 보정값
+}
+
+impl InputSettings {
+    // 장치의 왕복 지연 (보정하지 않았으면 0)
+    pub fn latency_ms_for(&self, device_id: Option<&str>) -> f64 {
+        self.latency_ms.get(device_id.unwrap_or(DEFAULT_DEVICE_KEY)).copied().unwrap_or(0.0)
+    }
+
+    pub fn set_latency_ms(&mut self, device_id: Option<&str>, latency_ms: f64) {
+        self.latency_ms
+            .insert(device_id.unwrap_or(DEFAULT_DEVICE_KEY).to_string(), latency_ms.clamp(0.0, MAX_LATENCY_MS));
+    }
+}
+
+// 모니터 이펙트 체인과 사용자 프리셋
+// 녹음은 기본적으로 드라이 (record_wet을 켜면 모니터링 중 이펙트가 걸린 소리를 녹음)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MonitorSettings {
+    pub effects: MonitorEffects,
+    pub presets: Vec<MonitorPreset>,
+    pub record_wet: bool,
+}
+
+impl Settings {
+    // 저장소에서 읽은 값이 허용 범위를 벗어나면 보정
+    fn sanitize(mut self) -> Self {
+        self.version = SETTINGS_VERSION;
+        self.analyzer.sensitivity = self.analyzer.sensitivity.clamp(0.001, 0.1);
+        self.analyzer.speaker_gain = self.analyzer.speaker_gain.clamp(0.0, 1.0);
+        self.analyzer.count_in_bars = self.analyzer.count_in_bars.min(MAX_COUNT_IN_BARS);
+        self.metronome.bpm = self.metronome.bpm.clamp(30, 300);
+        self.scale_generator.bpm = self.scale_generator.bpm.clamp(30, 300);
+        for note in [&mut self.scale_generator.start_note, &mut self.scale_generator.end_note] {
+            note.octave = note.octave.clamp(0, 8);
+        }
+        if self.scale_generator.intervals.is_empty() {
+            self.scale_generator.intervals = ScaleGeneratorSettings::default().intervals;
+        }
+        self.piano.left_hand_octave = self.piano.left_hand_octave.clamp(0, 7);
+        self.piano.right_hand_octave = self.piano.right_hand_octave.clamp(0, 7);
+        self.piano.left_hand_start_note_idx %= 12;
+        self.piano.right_hand_start_note_idx %= 12;
+        self.input.latency_ms.retain(|_, latency| latency.is_finite());
+        for latency in self.input.latency_ms.values_mut() {
+            *latency = latency.clamp(0.0, MAX_LATENCY_MS);
+        }
+        self.monitor.effects = self.monitor.effects.sanitized();
+        self.monitor.presets.retain(|preset| !preset.name.trim().is_empty());
+        for preset in &mut self.monitor.presets {
+            preset.effects = std::mem::take(&mut preset.effects).sanitized();
+        }
+        self
+    }
+}
+
+// 저장된 설정 읽기 (없거나 손상되었으면 기본값, 이전 버전이면 변환)
+// 더 새 버전(나중 빌드)이 저장한 값이면 아는 필드만 읽고, 저장할 때 모르는 필드는 그대로 둠
+pub fn load_settings() -> Settings {
+    let value: Value = match LocalStorage::get(STORAGE_KEY) {
+        Ok(value) => value,
+        Err(_) => return Settings::default(),
+    };
+
+    let stored_version = stored_version(&value);
+    if stored_version < SETTINGS_VERSION {
+        console_log!("설정 형식 변환: v{} -> v{}", stored_version, SETTINGS_VERSION);
+    } else if stored_version > SETTINGS_VERSION {
+        console_log!("더 새 버전의 설정(v{})을 v{}로 읽음 - 모르는 필드는 유지", stored_version, SETTINGS_VERSION);
+    }
+
+    match settings_from_value(value) {
+        Ok(settings) => settings,
+        Err(err) => {
+            console_error!("설정 읽기 실패, 기본값 사용: {:?}", err);
+            Settings::default()
+        }
+    }
+}
+
+// 설정 일부를 바꾸고 저장
+pub fn update_settings(update: impl FnOnce(&mut Settings)) {
+    let mut settings = load_settings();
+    update(&mut settings);
+    store_settings(&settings);
+}
+
+// 사용자 환경설정을 기본값으로 되돌리고 열려 있는 도구에 SettingsReset 이벤트로 알림
+// 장치별 지연 보정값과 사용자 모니터 프리셋은 측정/저장한 데이터이므로 유지
+pub fn reset_settings() {
+    let current = load_settings();
+    let mut settings = Settings::default();
+    settings.input.latency_ms = current.input.latency_ms;
+    settings.monitor.presets = current.monitor.presets;
+    store_settings(&settings);
+    console_log!("설정 초기화");
+    emit(AppEvent::SettingsReset);
+}
+
+// 설정 저장 - 저장된 값이 더 새 버전이면 그 위에 아는 필드만 덮어써서 모르는 필드와 버전을 유지
+fn store_settings(settings: &Settings) {
+    let result = serde_json::to_value(settings).map_err(|err| format!("{:?}", err)).and_then(|value| {
+        let value = match LocalStorage::get::<Value>(STORAGE_KEY) {
+            Ok(stored) if stored_version(&stored) > SETTINGS_VERSION => overlay_newer(stored, value),
+            _ => value,
+        };
+        LocalStorage::set(STORAGE_KEY, &value).map_err(|err| format!("{:?}", err))
+    });
+    if let Err(err) = result {
+        console_error!("설정 저장 실패: {}", err);
+    }
+}
+
+// 저장된 JSON의 형식 버전 (버전 필드가 없으면 첫 형식인 v1)
+fn stored_version(value: &Value) -> u32 {
+    value.get("version").and_then(Value::as_u64).unwrap_or(1).max(1) as u32
+}
+
+// 이전 버전 JSON을 현재 형식으로 변환한 뒤 읽기
+fn settings_from_value(mut value: Value) -> Result<Settings, serde_json::Error> {
+    migrate(&mut value, &MIGRATIONS);
+    serde_json::from_value::<Settings>(value).map(Settings::sanitize)
+}
+
+// migrations[n]은 버전 n + 1을 n + 2로 변환하므로 마지막 단계 뒤가 현재 버전
+fn migrate(value: &mut Value, migrations: &[fn(&mut Value)]) {
+    let current = migrations.len() as u32 + 1;
+    let stored = stored_version(value);
+    if stored >= current {
+        return;
+    }
+    for migration in &migrations[stored as usize - 1..] {
+        migration(value);
+    }
+    if let Some(object) = value.as_object_mut() {
+        object.insert("version".to_string(), Value::from(current));
+    }
+}
+
+// 더 새 버전의 저장 값에 현재 빌드의 값을 덮어씀 (객체는 필드별로 합치고 버전은 저장된 값 유지)
+fn overlay_newer(stored: Value, current: Value) -> Value {
+    let version = stored.get("version").cloned();
+    let mut merged = overlay(stored, current);
+    if let (Some(object), Some(version)) = (merged.as_object_mut(), version) {
+        object.insert("version".to_string(), version);
+    }
+    merged
+}
+
+fn overlay(base: Value, update: Value) -> Value {
+    match (base, update) {
+        (Value::Object(mut base), Value::Object(update)) => {
+            for (key, value) in update {
+                let merged = match base.remove(&key) {
+                    Some(existing) => overlay(existing, value),
+                    None => value,
+                };
+                base.insert(key, merged);
+            }
+            Value::Object(base)
+        }
+        (_, update) => update,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    // 테스트용 v1 -> v2: 메트로놈 tempo 필드를 bpm으로 이름 변경
+    fn rename_tempo(value: &mut Value) {
+        if let Some(metronome) = value.get_mut("metronome").and_then(Value::as_object_mut) {
+            if let Some(tempo) = metronome.remove("tempo") {
+                metronome.insert("bpm".to_string(), tempo);
+            }
+        }
+    }
+
+    // 테스트용 v2 -> v3: 최상위 sensitivity를 analyzer 아래로 이동
+    fn nest_sensitivity(value: &mut Value) {
+        if let Some(object) = value.as_object_mut() {
+            if let Some(sensitivity) = object.remove("sensitivity") {
+                object.insert("analyzer".to_string(), json!({ "sensitivity": sensitivity }));
+            }
+        }
+    }
+
+    #[test]
+    fn older_blob_runs_every_remaining_migration() {
+        let migrations: [fn(&mut Value); 2] = [rename_tempo, nest_sensitivity];
+
+        let mut v1 = json!({ "metronome": { "tempo": 90 }, "sensitivity": 0.05 });
+        migrate(&mut v1, &migrations);
+        assert_eq!(v1, json!({ "version": 3, "metronome": { "bpm": 90 }, "analyzer": { "sensitivity": 0.05 } }));
+
+        // v2부터는 두 번째 단계만 적용
+        let mut v2 = json!({ "version": 2, "metronome": { "tempo": 90 }, "sensitivity": 0.05 });
+        migrate(&mut v2, &migrations);
+        assert_eq!(v2["metronome"], json!({ "tempo": 90 }));
+        assert_eq!(v2["analyzer"], json!({ "sensitivity": 0.05 }));
+        assert_eq!(v2["version"], json!(3));
+
+        let settings: Settings = serde_json::from_value(v1).unwrap();
+        assert_eq!(settings.metronome.bpm, 90);
+        assert_eq!(settings.analyzer.sensitivity, 0.05);
+    }
+
+    #[test]
+    fn current_blob_is_not_migrated() {
+        let migrations: [fn(&mut Value); 1] = [rename_tempo];
+        let mut value = json!({ "version": 2, "metronome": { "tempo": 90 } });
+        let before = value.clone();
+        migrate(&mut value, &migrations);
+        assert_eq!(value, before);
+    }
+
+    #[test]
+    fn partial_blob_fills_defaults_and_sanitizes() {
+        let settings = settings_from_value(json!({ "metronome": { "bpm": 1000 }, "piano": { "left_hand_octave": 3 } })).unwrap();
+        assert_eq!(settings.version, SETTINGS_VERSION);
+        assert_eq!(settings.metronome.bpm, 300);
+        assert_eq!(settings.piano.left_hand_octave, 3);
+        assert_eq!(settings.analyzer, AnalyzerSettings::default());
+    }
+
+    #[test]
+    fn newer_blob_reads_known_fields_and_keeps_unknown_ones() {
+        let newer = json!({
+            "version": SETTINGS_VERSION + 1,
+            "metronome": { "bpm": 100, "swing": 0.6 },
+            "future_tool": { "enabled": true },
+        });
+        let mut settings = settings_from_value(newer.clone()).unwrap();
+        assert_eq!(settings.metronome.bpm, 100);
+
+        settings.metronome.bpm = 140;
+        let stored = overlay_newer(newer, serde_json::to_value(&settings).unwrap());
+        assert_eq!(stored["version"], json!(SETTINGS_VERSION + 1));
+        assert_eq!(stored["metronome"]["bpm"], json!(140));
+        assert_eq!(stored["metronome"]["swing"], json!(0.6));
+        assert_eq!(stored["future_tool"], json!({ "enabled": true }));
+    }
+}
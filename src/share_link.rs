@@ -0,0 +1,111 @@
+use gloo::history::query::ToQuery;
+use gloo_timers::callback::Timeout;
+use serde::{de::DeserializeOwned, Serialize};
+use wasm_bindgen_futures::JsFuture;
+use yew::html::Scope;
+use yew::prelude::*;
+use yew_router::prelude::*;
+
+use crate::routes::Route;
+
+// 조건부 로그 매크로 정의
+#[cfg(debug_assertions)]
+macro_rules! console_error {
+    ($($arg:tt)*) => {
+        web_sys::console::error_1(&format!($($arg)*).into());
+    };
+}
+
+#[cfg(not(debug_assertions))]
+macro_rules! console_error {
+    ($($arg:tt)*) => {};
+}
+
+const COPIED_FEEDBACK_MS: u32 = 2000; // "복사됨" 표시 유지 시간
+
+// 현재 라우트가 해당 도구 페이지이면 라우터 위치의 쿼리 문자열을 도구 설정으로 읽기
+// (대시보드처럼 여러 도구가 함께 있는 페이지나, 쿼리가 없거나 형식이 틀리면 None)
+pub fn read_query<Q: DeserializeOwned>(link: &Scope<impl Component>, route: &Route) -> Option<Q> {
+    if link.route::<Route>().as_ref() != Some(route) {
+        return None;
+    }
+
+    let location = link.location()?;
+    if location.query_str().trim_start_matches('?').is_empty() {
+        return None;
+    }
+
+    match location.query::<Q>() {
+        Ok(parsed) => Some(parsed),
+        Err(err) => {
+            console_error!("공유 링크 쿼리 해석 실패: {:?}", err);
+            None
+        }
+    }
+}
+
+// 라우트와 도구 설정으로 공유 링크 생성 (예: https://.../metronome?bpm=140&sig=7%2F8)
+pub fn share_url<Q: Serialize>(route: &Route, query: &Q) -> Option<String> {
+    let origin = web_sys::window()?.location().origin().ok()?;
+    let query = query.to_query().ok()?;
+    if query.is_empty() {
+        Some(format!("{}{}", origin, route.to_path()))
+    } else {
+        Some(format!("{}{}?{}", origin, route.to_path(), query))
+    }
+}
+
+#[derive(Properties, PartialEq)]
+pub struct CopyLinkButtonProps {
+    pub url: Option<AttrValue>,
+}
+
+// 공유 링크를 클립보드에 복사하는 버튼
+#[function_component(CopyLinkButton)]
+pub fn copy_link_button(props: &CopyLinkButtonProps) -> Html {
+    let copied = use_state(|| false);
+    let feedback_timeout = use_mut_ref(|| None::<Timeout>);
+
+    let onclick = {
+        let url = props.url.clone();
+        let copied = copied.clone();
+        Callback::from(move |_: MouseEvent| {
+            let url = match &url {
+                Some(url) => url.to_string(),
+                None => return,
+            };
+            let clipboard = match web_sys::window() {
+                Some(window) => window.navigator().clipboard(),
+                None => return,
+            };
+
+            let copied = copied.clone();
+            let feedback_timeout = feedback_timeout.clone();
+            wasm_bindgen_futures::spawn_local(async move {
+                match JsFuture::from(clipboard.write_text(&url)).await {
+                    Ok(_) => {
+                        copied.set(true);
+                        let copied = copied.clone();
+                        *feedback_timeout.borrow_mut() = Some(Timeout::new(COPIED_FEEDBACK_MS, move || {
+                            copied.set(false);
+                        }));
+                    }
+                    Err(err) => {
+                        console_error!("링크 복사 실패: {:?}", err);
+                    }
+                }
+            });
+        })
+    };
+
+    html! {
+        <button
+            class="copy-link-button"
+            onclick={onclick}
+            disabled={props.url.is_none()}
+            title="현재 설정이 담긴 링크를 클립보드에 복사"
+        >
+            {if *copied { "✅ 링크 복사됨" } else { "🔗 링크 복사" }}
+        </button>
+    }
+}
@@ -0,0 +1,283 @@
+use std::cell::RefCell;
+
+use js_sys::{Array, Float32Array, Float64Array, Object, Promise, Reflect, Uint32Array};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{IdbDatabase, IdbOpenDbRequest, IdbRequest, IdbTransaction, IdbTransactionMode};
+
+use crate::tools::take_library::{AmplitudeHistory, BeatGrid, PitchHistory, Stem, Take};
+
+// 조건부 로그 매크로 정의
+#[cfg(debug_assertions)]
+macro_rules! console_log {
+    ($($arg:tt)*) => {
+        web_sys::console::log_1(&format!($($arg)*).into());
+    };
+}
+
+#[cfg(not(debug_assertions))]
+macro_rules! console_log {
+    ($($arg:tt)*) => {};
+}
+
+// 녹음 테이크를 IndexedDB에 저장 (오디오 Blob + 피치/진폭 이력)
+// 진폭 이력은 JSON 대신 타입 배열로 저장해 크기를 줄임
+const DB_NAME: &str = "musicalmind";
+const DB_VERSION: u32 = 1;
+const TAKE_STORE: &str = "takes";
+
+// 한 번 연 연결을 계속 사용 (다른 탭이 DB 버전을 올리면 닫고 다음 요청 때 다시 엶)
+thread_local! {
+    static DATABASE: RefCell<Option<IdbDatabase>> = const { RefCell::new(None) };
+}
+
+// 저장 공간 사용량 (바이트)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StorageUsage {
+    pub usage: f64,
+    pub quota: f64,
+}
+
+// IdbRequest 완료를 기다리는 Future (성공 시 result)
+async fn wait_request(request: &IdbRequest) -> Result<JsValue, JsValue> {
+    let request = request.clone();
+    let promise = Promise::new(&mut |resolve, reject| {
+        let success_request = request.clone();
+        let onsuccess = Closure::once_into_js(move |_: web_sys::Event| {
+            let _ = resolve.call1(&JsValue::NULL, &success_request.result().unwrap_or(JsValue::UNDEFINED));
+        });
+        let error_request = request.clone();
+        let onerror = Closure::once_into_js(move |_: web_sys::Event| {
+            let error = error_request.error().ok().flatten().map(JsValue::from).unwrap_or(JsValue::NULL);
+            let _ = reject.call1(&JsValue::NULL, &error);
+        });
+        request.set_onsuccess(Some(onsuccess.unchecked_ref()));
+        request.set_onerror(Some(onerror.unchecked_ref()));
+    });
+    JsFuture::from(promise).await
+}
+
+// 트랜잭션 커밋을 기다리는 Future (요청 성공만으로는 디스크에 반영되었다고 볼 수 없음)
+async fn wait_transaction(transaction: &IdbTransaction) -> Result<(), JsValue> {
+    let transaction = transaction.clone();
+    let promise = Promise::new(&mut |resolve, reject| {
+        let oncomplete = Closure::once_into_js(move |_: web_sys::Event| {
+            let _ = resolve.call0(&JsValue::NULL);
+        });
+        // 오류와 중단은 둘 중 먼저 오는 쪽만 처리
+        let error_transaction = transaction.clone();
+        let onfailure = Closure::<dyn FnMut(web_sys::Event)>::new(move |_: web_sys::Event| {
+            let error = error_transaction.error().map(JsValue::from).unwrap_or_else(|| JsValue::from_str("트랜잭션 중단"));
+            let _ = reject.call1(&JsValue::NULL, &error);
+        })
+        .into_js_value();
+        transaction.set_oncomplete(Some(oncomplete.unchecked_ref()));
+        transaction.set_onerror(Some(onfailure.unchecked_ref()));
+        transaction.set_onabort(Some(onfailure.unchecked_ref()));
+    });
+    JsFuture::from(promise).await.map(|_| ())
+}
+
+async fn database() -> Result<IdbDatabase, JsValue> {
+    if let Some(db) = DATABASE.with(|db| db.borrow().clone()) {
+        return Ok(db);
+    }
+
+    let db = open_database().await?;
+    let versionchange_db = db.clone();
+    let onversionchange = Closure::once_into_js(move |_: web_sys::Event| {
+        versionchange_db.close();
+        DATABASE.with(|db| *db.borrow_mut() = None);
+    });
+    db.set_onversionchange(Some(onversionchange.unchecked_ref()));
+
+    // 여는 동안 다른 요청이 먼저 연결을 저장했으면 그쪽을 쓰고 새 연결은 닫음
+    Ok(DATABASE.with(|cached| match &*cached.borrow() {
+        Some(existing) => {
+            db.close();
+            existing.clone()
+        }
+        None => {
+            *cached.borrow_mut() = Some(db.clone());
+            db
+        }
+    }))
+}
+
+async fn open_database() -> Result<IdbDatabase, JsValue> {
+    let factory = web_sys::window()
+        .ok_or_else(|| JsValue::from_str("window 없음"))?
+        .indexed_db()?
+        .ok_or_else(|| JsValue::from_str("IndexedDB를 사용할 수 없습니다"))?;
+    let request: IdbOpenDbRequest = factory.open_with_u32(DB_NAME, DB_VERSION)?;
+
+    // 처음 열거나 버전이 올라가면 object store 생성
+    let upgrade_request = request.clone();
+    let onupgradeneeded = Closure::once_into_js(move |_: web_sys::Event| {
+        if let Ok(db) = upgrade_request.result().and_then(|db| db.dyn_into::<IdbDatabase>()) {
+            if !db.object_store_names().contains(TAKE_STORE) {
+                let params = web_sys::IdbObjectStoreParameters::new();
+                params.set_key_path(&JsValue::from_str("id"));
+                let _ = db.create_object_store_with_optional_parameters(TAKE_STORE, &params);
+            }
+        }
+    });
+    request.set_onupgradeneeded(Some(onupgradeneeded.unchecked_ref()));
+
+    wait_request(&request).await?.dyn_into()
+}
+
+async fn take_transaction(mode: IdbTransactionMode) -> Result<IdbTransaction, JsValue> {
+    database().await?.transaction_with_str_and_mode(TAKE_STORE, mode)
+}
+
+fn set(record: &Object, key: &str, value: &JsValue) {
+    let _ = Reflect::set(record, &JsValue::from_str(key), value);
+}
+
+fn get(record: &JsValue, key: &str) -> Option<JsValue> {
+    Reflect::get(record, &JsValue::from_str(key)).ok().filter(|v| !v.is_undefined())
+}
+
+// Take -> 저장용 객체
+fn take_to_record(take: &Take) -> Result<Object, JsValue> {
+    let record = Object::new();
+    set(&record, "id", &JsValue::from_f64(take.id as f64));
+    set(&record, "name", &JsValue::from_str(&take.name));
+    set(&record, "starred", &JsValue::from_bool(take.starred));
+    set(&record, "createdAt", &JsValue::from_f64(take.created_at));
+    set(&record, "duration", &JsValue::from_f64(take.duration));
+    set(&record, "audio", &take.audio_blob);
+
+    let pitch_json = serde_json::to_string(&take.history).map_err(|e| JsValue::from_str(&e.to_string()))?;
+    set(&record, "pitchHistory", &JsValue::from_str(&pitch_json));
+
+    // 진폭 이력: 프레임 시간, 프레임 길이, 모든 샘플을 이어붙인 배열
+    let times: Vec<f64> = take.amplitude_history.iter().map(|(t, _)| *t).collect();
+    let lengths: Vec<u32> = take.amplitude_history.iter().map(|(_, frame)| frame.len() as u32).collect();
+    let samples: Vec<f32> = take.amplitude_history.iter().flat_map(|(_, frame)| frame.iter().copied()).collect();
+    set(&record, "amplitudeTimes", &Float64Array::from(times.as_slice()));
+    set(&record, "amplitudeLengths", &Uint32Array::from(lengths.as_slice()));
+    set(&record, "amplitudeSamples", &Float32Array::from(samples.as_slice()));
+
+    let stems = Array::new();
+    for stem in &take.stems {
+        let stem_record = Object::new();
+        set(&stem_record, "name", &JsValue::from_str(&stem.name));
+        set(&stem_record, "audio", &stem.blob);
+        stems.push(&stem_record);
+    }
+    set(&record, "stems", &stems);
+
+    if let Some(grid) = &take.beat_grid {
+        let grid_record = Object::new();
+        set(&grid_record, "bpm", &JsValue::from_f64(grid.bpm));
+        set(&grid_record, "beatsPerBar", &JsValue::from_f64(grid.beats_per_bar as f64));
+        set(&record, "beatGrid", &grid_record);
+    }
+    set(&record, "latency", &JsValue::from_f64(take.latency));
+
+    Ok(record)
+}
+
+// 저장된 객체 -> Take (오디오는 새 object URL로)
+fn record_to_take(record: &JsValue) -> Option<Take> {
+    let id = get(record, "id")?.as_f64()? as u64;
+    let audio_blob: web_sys::Blob = get(record, "audio")?.dyn_into().ok()?;
+    let audio_url = web_sys::Url::create_object_url_with_blob(&audio_blob).ok()?;
+
+    let history: PitchHistory = get(record, "pitchHistory")
+        .and_then(|v| v.as_string())
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default();
+
+    let mut amplitude_history = AmplitudeHistory::new();
+    if let (Some(times), Some(lengths), Some(samples)) = (
+        get(record, "amplitudeTimes").and_then(|v| v.dyn_into::<Float64Array>().ok()),
+        get(record, "amplitudeLengths").and_then(|v| v.dyn_into::<Uint32Array>().ok()),
+        get(record, "amplitudeSamples").and_then(|v| v.dyn_into::<Float32Array>().ok()),
+    ) {
+        let samples = samples.to_vec();
+        let mut offset = 0;
+        for (time, length) in times.to_vec().into_iter().zip(lengths.to_vec()) {
+            let end = (offset + length as usize).min(samples.len());
+            amplitude_history.push_back((time, samples[offset..end].to_vec()));
+            offset = end;
+        }
+    }
+
+    // 스템이 없던 이전 기록은 빈 목록
+    let stems = get(record, "stems")
+        .and_then(|v| v.dyn_into::<Array>().ok())
+        .map(|stems| {
+            stems
+                .iter()
+                .filter_map(|stem| {
+                    Some(Stem {
+                        name: get(&stem, "name")?.as_string()?,
+                        blob: get(&stem, "audio")?.dyn_into().ok()?,
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let beat_grid = get(record, "beatGrid").and_then(|grid| {
+        Some(BeatGrid {
+            bpm: get(&grid, "bpm")?.as_f64()?,
+            beats_per_bar: get(&grid, "beatsPerBar")?.as_f64()? as u32,
+        })
+    });
+
+    Some(Take {
+        id,
+        name: get(record, "name").and_then(|v| v.as_string()).unwrap_or_else(|| format!("테이크 {}", id)),
+        starred: get(record, "starred").and_then(|v| v.as_bool()).unwrap_or(false),
+        created_at: get(record, "createdAt").and_then(|v| v.as_f64()).unwrap_or(id as f64),
+        duration: get(record, "duration").and_then(|v| v.as_f64()).unwrap_or(0.0),
+        audio_blob,
+        audio_url,
+        history,
+        amplitude_history,
+        stems,
+        beat_grid,
+        latency: get(record, "latency").and_then(|v| v.as_f64()).unwrap_or(0.0),
+    })
+}
+
+// 테이크 저장 (같은 id면 덮어씀)
+pub async fn save_take(take: &Take) -> Result<(), JsValue> {
+    let record = take_to_record(take)?;
+    let transaction = take_transaction(IdbTransactionMode::Readwrite).await?;
+    transaction.object_store(TAKE_STORE)?.put(&record)?;
+    wait_transaction(&transaction).await?;
+    console_log!("테이크 저장됨: {} ({})", take.name, take.id);
+    Ok(())
+}
+
+// 저장된 모든 테이크 불러오기 (녹음 순)
+pub async fn load_takes() -> Result<Vec<Take>, JsValue> {
+    let store = take_transaction(IdbTransactionMode::Readonly).await?.object_store(TAKE_STORE)?;
+    let records: Array = wait_request(&store.get_all()?).await?.dyn_into()?;
+    let mut takes: Vec<Take> = records.iter().filter_map(|record| record_to_take(&record)).collect();
+    takes.sort_by_key(|take| take.id);
+    console_log!("저장된 테이크 {}개 불러옴", takes.len());
+    Ok(takes)
+}
+
+pub async fn delete_take(id: u64) -> Result<(), JsValue> {
+    let transaction = take_transaction(IdbTransactionMode::Readwrite).await?;
+    transaction.object_store(TAKE_STORE)?.delete(&JsValue::from_f64(id as f64))?;
+    wait_transaction(&transaction).await
+}
+
+// 브라우저가 보고하는 이 사이트의 저장 공간 사용량
+pub async fn storage_usage() -> Option<StorageUsage> {
+    let storage = web_sys::window()?.navigator().storage();
+    // StorageEstimate는 딕셔너리라 instanceof 검사 없이 변환
+    let estimate: web_sys::StorageEstimate = JsFuture::from(storage.estimate().ok()?).await.ok()?.unchecked_into();
+    Some(StorageUsage {
+        usage: estimate.get_usage().unwrap_or(0.0),
+        quota: estimate.get_quota().unwrap_or(0.0),
+    })
+}
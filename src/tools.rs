@@ -2,4 +2,13 @@ pub mod pitch_plot;
 pub mod amplitude_visualizer;
 pub mod metronome;
 pub mod scale_generator;
-pub mod piano;
\ No newline at end of file
+pub mod piano;
+pub mod target_melody;
+pub mod piano_sampler;
+pub mod quiz;
+pub mod interval_trainer;
+pub mod chord_trainer;
+pub mod pitch_match;
+pub mod practice_stats;
+pub mod take_library;
+pub mod overdub;
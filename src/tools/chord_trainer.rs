@@ -0,0 +1,602 @@
+use gloo_timers::callback::Timeout;
+use serde::{Deserialize, Serialize};
+use yew::prelude::*;
+
+use crate::audio_engine::{engine_from_scope, AudioEngine, Channel};
+use crate::tools::piano_sampler::{play_piano_sample, PianoSample};
+use crate::tools::scale_generator::ScaleGenerator;
+use crate::tools::target_melody::note_name_from_midi;
+use crate::practice_log::PracticeTool;
+use crate::tools::quiz::{random_index, QuizState};
+use crate::routes::Route;
+use crate::share_link::{self, CopyLinkButton};
+
+// 조건부 로그 매크로 정의
+#[cfg(debug_assertions)]
+macro_rules! console_log {
+    ($($arg:tt)*) => {
+        web_sys::console::log_1(&format!($($arg)*).into());
+    };
+}
+
+#[cfg(not(debug_assertions))]
+macro_rules! console_log {
+    ($($arg:tt)*) => {};
+}
+
+// 화음 종류 (코드 기호, 표시 이름, 스케일 생성기 음정 표기로 된 구성음)
+struct ChordType {
+    symbol: &'static str,
+    name: &'static str,
+    degrees: &'static [&'static str],
+}
+
+const CHORD_TYPES: [ChordType; 9] = [
+    ChordType { symbol: "maj", name: "장3화음", degrees: &["1", "3", "5"] },
+    ChordType { symbol: "m", name: "단3화음", degrees: &["1", "b3", "5"] },
+    ChordType { symbol: "dim", name: "감3화음", degrees: &["1", "b3", "b5"] },
+    ChordType { symbol: "aug", name: "증3화음", degrees: &["1", "3", "#5"] },
+    ChordType { symbol: "7", name: "속7화음", degrees: &["1", "3", "5", "b7"] },
+    ChordType { symbol: "m7", name: "단7화음", degrees: &["1", "b3", "5", "b7"] },
+    ChordType { symbol: "maj7", name: "장7화음", degrees: &["1", "3", "5", "7"] },
+    ChordType { symbol: "m7b5", name: "반감7화음", degrees: &["1", "b3", "b5", "b7"] },
+    ChordType { symbol: "dim7", name: "감7화음", degrees: &["1", "b3", "b5", "6"] }, // 감7도 = 장6도와 같은 음
+];
+
+const NOTE_VOLUME: f64 = 0.6;
+const ARPEGGIO_GAP_MS: u32 = 350; // 분산화음 음 사이 간격
+const AUTO_NEXT_MS: u32 = 1500;   // 정답 후 다음 문제까지 대기 시간
+const ROOT_MIN_MIDI: i32 = 48;    // 근음 출제 범위 (C3 ~ B3)
+const ROOT_MAX_MIDI: i32 = 59;
+
+// 난이도 - 출제 화음, 자리바꿈, 보이싱 설정을 한 번에 지정
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ChordDifficulty {
+    Easy,   // 장/단3화음, 기본형
+    Medium, // 3화음 전체 + 속7화음, 자리바꿈
+    Hard,   // 모든 화음, 자리바꿈 + 오픈 보이싱
+}
+
+impl ChordDifficulty {
+    fn enabled_types(&self) -> Vec<bool> {
+        let count = match self {
+            ChordDifficulty::Easy => 2,
+            ChordDifficulty::Medium => 5,
+            ChordDifficulty::Hard => CHORD_TYPES.len(),
+        };
+        (0..CHORD_TYPES.len()).map(|idx| idx < count).collect()
+    }
+}
+
+// 화음 재생 방식
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ChordPlayStyle {
+    Block,    // 모든 음을 동시에
+    Arpeggio, // 아래 음부터 차례로
+}
+
+// 공유 링크 쿼리 (예: /chord-trainer?level=medium&types=maj,m,7&style=arpeggio&inversions=true)
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+struct ChordTrainerQuery {
+    level: Option<String>,      // easy / medium / hard
+    types: Option<String>,      // 코드 기호를 ','로 연결
+    style: Option<String>,      // block / arpeggio
+    inversions: Option<bool>,
+    open: Option<bool>,
+}
+
+// 출제된 문제
+#[derive(Debug, Clone, PartialEq)]
+struct ChordQuestion {
+    chord_idx: usize, // CHORD_TYPES 인덱스
+    root_midi: i32,
+    inversion: usize, // 0: 기본형, 1: 1전위, ...
+    open: bool,       // 오픈 보이싱 (드롭2) 여부
+    notes: Vec<i32>,  // 실제 재생할 음 (낮은 음부터)
+}
+
+pub enum ChordTrainerMsg {
+    SetDifficulty(ChordDifficulty),
+    ToggleChordType(usize),
+    ToggleInversions,
+    ToggleOpenVoicing,
+    SetPlayStyle(ChordPlayStyle),
+    NewQuestion,
+    Replay,
+    PlayNote(i32),
+    Answer(usize),
+    ResetStats,
+}
+
+pub struct ChordTrainer {
+    difficulty: ChordDifficulty,
+    enabled: Vec<bool>,            // 출제할 화음 종류
+    use_inversions: bool,          // 자리바꿈 출제
+    use_open_voicing: bool,        // 오픈 보이싱 출제
+    play_style: ChordPlayStyle,
+    quiz: QuizState,               // 답변과 화음 종류별 통계
+    question: Option<ChordQuestion>,
+    engine: AudioEngine,           // 공유 오디오 엔진 (청음 채널로 출력)
+    audio: Vec<PianoSample>,  // 재생 중인 피아노 음원
+    note_timeouts: Vec<Timeout>,   // 분산화음 재생 타이머
+    next_timeout: Option<Timeout>, // 자동 진행 타이머
+    error: Option<String>,
+}
+
+impl Component for ChordTrainer {
+    type Message = ChordTrainerMsg;
+    type Properties = ();
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let difficulty = ChordDifficulty::Easy;
+        let mut trainer = Self {
+            difficulty,
+            enabled: difficulty.enabled_types(),
+            use_inversions: false,
+            use_open_voicing: false,
+            play_style: ChordPlayStyle::Block,
+            quiz: QuizState::new(CHORD_TYPES.len()),
+            question: None,
+            engine: engine_from_scope(ctx.link()),
+            audio: Vec::new(),
+            note_timeouts: Vec::new(),
+            next_timeout: None,
+            error: None,
+        };
+
+        // 공유 링크로 열었으면 링크의 설정 적용
+        if let Some(query) = share_link::read_query::<ChordTrainerQuery>(ctx.link(), &Route::ChordTrainer) {
+            trainer.apply_query(query);
+        }
+        trainer
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            ChordTrainerMsg::SetDifficulty(difficulty) => {
+                self.difficulty = difficulty;
+                self.enabled = difficulty.enabled_types();
+                self.use_inversions = difficulty != ChordDifficulty::Easy;
+                self.use_open_voicing = difficulty == ChordDifficulty::Hard;
+                true
+            }
+            ChordTrainerMsg::ToggleChordType(idx) => {
+                if let Some(enabled) = self.enabled.get_mut(idx) {
+                    *enabled = !*enabled;
+                }
+                true
+            }
+            ChordTrainerMsg::ToggleInversions => {
+                self.use_inversions = !self.use_inversions;
+                true
+            }
+            ChordTrainerMsg::ToggleOpenVoicing => {
+                self.use_open_voicing = !self.use_open_voicing;
+                true
+            }
+            ChordTrainerMsg::SetPlayStyle(style) => {
+                self.play_style = style;
+                true
+            }
+            ChordTrainerMsg::NewQuestion => {
+                self.next_timeout = None;
+                self.quiz.next_question();
+                self.question = self.generate_question();
+                if self.question.is_some() {
+                    self.error = None;
+                    self.play_question(ctx);
+                } else {
+                    self.error = Some("출제할 화음 종류를 하나 이상 선택하세요.".to_string());
+                }
+                true
+            }
+            ChordTrainerMsg::Replay => {
+                self.play_question(ctx);
+                false
+            }
+            ChordTrainerMsg::PlayNote(midi) => {
+                if let Some(audio) = play_piano_sample(&self.engine, Channel::Trainer, midi, NOTE_VOLUME) {
+                    self.audio.push(audio);
+                }
+                false
+            }
+            ChordTrainerMsg::Answer(idx) => {
+                let expected = match &self.question {
+                    Some(question) => question.chord_idx,
+                    None => return false,
+                };
+                let correct = match self.quiz.answer(idx, expected, js_sys::Date::now()) {
+                    Some(correct) => correct,
+                    None => return false,
+                };
+                console_log!("화음 퀴즈 답변: {} (정답: {})", CHORD_TYPES[idx].name, CHORD_TYPES[expected].name);
+
+                // 정답이면 잠시 후 다음 문제
+                if correct {
+                    let link = ctx.link().clone();
+                    self.next_timeout = Some(Timeout::new(AUTO_NEXT_MS, move || {
+                        link.send_message(ChordTrainerMsg::NewQuestion);
+                    }));
+                }
+                self.save_practice();
+                true
+            }
+            ChordTrainerMsg::ResetStats => {
+                self.quiz.reset();
+                true
+            }
+        }
+    }
+
+    fn destroy(&mut self, _ctx: &Context<Self>) {
+        self.stop_audio();
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let link = ctx.link();
+
+        html! {
+            <div class="chord-trainer">
+                <div class="trainer-settings">
+                    <div class="setting-row">
+                        <span class="setting-label">{"난이도:"}</span>
+                        <select onchange={link.callback(|e: Event| {
+                            let select = e.target_dyn_into::<web_sys::HtmlSelectElement>().unwrap();
+                            let difficulty = match select.value().as_str() {
+                                "medium" => ChordDifficulty::Medium,
+                                "hard" => ChordDifficulty::Hard,
+                                _ => ChordDifficulty::Easy,
+                            };
+                            ChordTrainerMsg::SetDifficulty(difficulty)
+                        })}>
+                            <option value="easy" selected={self.difficulty == ChordDifficulty::Easy}>{"초급 (장/단3화음)"}</option>
+                            <option value="medium" selected={self.difficulty == ChordDifficulty::Medium}>{"중급 (3화음 + 속7, 자리바꿈)"}</option>
+                            <option value="hard" selected={self.difficulty == ChordDifficulty::Hard}>{"고급 (모든 화음, 오픈 보이싱)"}</option>
+                        </select>
+                    </div>
+                    <div class="setting-row">
+                        <span class="setting-label">{"재생 방식:"}</span>
+                        <select onchange={link.callback(|e: Event| {
+                            let select = e.target_dyn_into::<web_sys::HtmlSelectElement>().unwrap();
+                            if select.value() == "arpeggio" {
+                                ChordTrainerMsg::SetPlayStyle(ChordPlayStyle::Arpeggio)
+                            } else {
+                                ChordTrainerMsg::SetPlayStyle(ChordPlayStyle::Block)
+                            }
+                        })}>
+                            <option value="block" selected={self.play_style == ChordPlayStyle::Block}>{"동시에"}</option>
+                            <option value="arpeggio" selected={self.play_style == ChordPlayStyle::Arpeggio}>{"분산화음"}</option>
+                        </select>
+                    </div>
+                    <div class="setting-row">
+                        <label>
+                            <input
+                                type="checkbox"
+                                checked={self.use_inversions}
+                                onchange={link.callback(|_| ChordTrainerMsg::ToggleInversions)}
+                            />
+                            {" 자리바꿈"}
+                        </label>
+                        <label>
+                            <input
+                                type="checkbox"
+                                checked={self.use_open_voicing}
+                                onchange={link.callback(|_| ChordTrainerMsg::ToggleOpenVoicing)}
+                            />
+                            {" 오픈 보이싱"}
+                        </label>
+                    </div>
+                    <div class="setting-row interval-toggles">
+                        {
+                            CHORD_TYPES.iter().enumerate().map(|(idx, chord)| {
+                                html! {
+                                    <label class="interval-toggle">
+                                        <input
+                                            type="checkbox"
+                                            checked={self.enabled[idx]}
+                                            onchange={link.callback(move |_| ChordTrainerMsg::ToggleChordType(idx))}
+                                        />
+                                        {format!(" {}", chord.name)}
+                                    </label>
+                                }
+                            }).collect::<Html>()
+                        }
+                    </div>
+                    <div class="setting-row">
+                        <CopyLinkButton url={self.share_url()} />
+                    </div>
+                </div>
+
+                <div class="trainer-quiz">
+                    <div class="button-group">
+                        <button class="play-button" onclick={link.callback(|_| ChordTrainerMsg::NewQuestion)}>
+                            {"▶ 새 문제"}
+                        </button>
+                        <button
+                            class="replay-button"
+                            disabled={self.question.is_none()}
+                            onclick={link.callback(|_| ChordTrainerMsg::Replay)}
+                        >
+                            {"🔁 다시 듣기"}
+                        </button>
+                    </div>
+                    { self.view_feedback() }
+                    <div class="answer-buttons">
+                        {
+                            CHORD_TYPES.iter().enumerate().filter(|(idx, _)| self.enabled[*idx]).map(|(idx, chord)| {
+                                let state = self.quiz.answer_class(idx, self.question.as_ref().map(|q| q.chord_idx));
+                                html! {
+                                    <button
+                                        class={classes!("answer-button", state)}
+                                        disabled={self.question.is_none() || self.quiz.answered.is_some()}
+                                        onclick={link.callback(move |_| ChordTrainerMsg::Answer(idx))}
+                                    >
+                                        {format!("{} ({})", chord.name, chord.symbol)}
+                                    </button>
+                                }
+                            }).collect::<Html>()
+                        }
+                    </div>
+                    {
+                        if let Some(err) = &self.error {
+                            html! { <div class="trainer-error">{err}</div> }
+                        } else {
+                            html! {}
+                        }
+                    }
+                </div>
+
+                { self.quiz.view_stats("화음", &CHORD_TYPES.map(|chord| chord.name), link.callback(|_| ChordTrainerMsg::ResetStats)) }
+            </div>
+        }
+    }
+}
+
+impl ChordTrainer {
+    // 난이도 프리셋을 먼저 적용하고 나머지 값으로 덮어씀
+    fn apply_query(&mut self, query: ChordTrainerQuery) {
+        let difficulty = match query.level.as_deref() {
+            Some("easy") => Some(ChordDifficulty::Easy),
+            Some("medium") => Some(ChordDifficulty::Medium),
+            Some("hard") => Some(ChordDifficulty::Hard),
+            _ => None,
+        };
+        if let Some(difficulty) = difficulty {
+            self.difficulty = difficulty;
+            self.enabled = difficulty.enabled_types();
+            self.use_inversions = difficulty != ChordDifficulty::Easy;
+            self.use_open_voicing = difficulty == ChordDifficulty::Hard;
+        }
+        if let Some(types) = query.types {
+            let enabled: Vec<bool> = CHORD_TYPES.iter().map(|chord| types.split(',').any(|t| t == chord.symbol)).collect();
+            if enabled.iter().any(|e| *e) {
+                self.enabled = enabled;
+            }
+        }
+        match query.style.as_deref() {
+            Some("block") => self.play_style = ChordPlayStyle::Block,
+            Some("arpeggio") => self.play_style = ChordPlayStyle::Arpeggio,
+            _ => {}
+        }
+        if let Some(inversions) = query.inversions {
+            self.use_inversions = inversions;
+        }
+        if let Some(open) = query.open {
+            self.use_open_voicing = open;
+        }
+    }
+
+    // 현재 설정이 담긴 공유 링크
+    fn share_url(&self) -> Option<AttrValue> {
+        let types: Vec<&str> = CHORD_TYPES
+            .iter()
+            .zip(self.enabled.iter())
+            .filter(|(_, enabled)| **enabled)
+            .map(|(chord, _)| chord.symbol)
+            .collect();
+        let query = ChordTrainerQuery {
+            level: Some(match self.difficulty {
+                ChordDifficulty::Easy => "easy".to_string(),
+                ChordDifficulty::Medium => "medium".to_string(),
+                ChordDifficulty::Hard => "hard".to_string(),
+            }),
+            types: Some(types.join(",")),
+            style: Some(match self.play_style {
+                ChordPlayStyle::Block => "block".to_string(),
+                ChordPlayStyle::Arpeggio => "arpeggio".to_string(),
+            }),
+            inversions: Some(self.use_inversions),
+            open: Some(self.use_open_voicing),
+        };
+        share_link::share_url(&Route::ChordTrainer, &query).map(AttrValue::from)
+    }
+
+    // 이번 세션을 연습 기록에 반영
+    fn save_practice(&self) {
+        let difficulty = match self.difficulty {
+            ChordDifficulty::Easy => "초급",
+            ChordDifficulty::Medium => "중급",
+            ChordDifficulty::Hard => "고급",
+        };
+        self.quiz.save_session(PracticeTool::ChordTrainer, difficulty);
+    }
+
+    // 설정에 맞는 새 문제 생성 (선택된 화음이 없으면 None)
+    fn generate_question(&self) -> Option<ChordQuestion> {
+        let candidates: Vec<usize> = (0..CHORD_TYPES.len()).filter(|idx| self.enabled[*idx]).collect();
+        if candidates.is_empty() {
+            return None;
+        }
+
+        let chord_idx = candidates[random_index(candidates.len())];
+        let root_midi = ROOT_MIN_MIDI + random_index((ROOT_MAX_MIDI - ROOT_MIN_MIDI + 1) as usize) as i32;
+        let inversion = if self.use_inversions {
+            random_index(CHORD_TYPES[chord_idx].degrees.len())
+        } else {
+            0
+        };
+        let open = self.use_open_voicing && js_sys::Math::random() < 0.5;
+
+        Some(ChordQuestion {
+            chord_idx,
+            root_midi,
+            inversion,
+            open,
+            notes: build_voicing(root_midi, &CHORD_TYPES[chord_idx], inversion, open),
+        })
+    }
+
+    // 현재 문제 재생
+    fn play_question(&mut self, ctx: &Context<Self>) {
+        let question = match &self.question {
+            Some(question) => question.clone(),
+            None => return,
+        };
+
+        self.stop_audio();
+        match self.play_style {
+            ChordPlayStyle::Block => {
+                for midi in &question.notes {
+                    if let Some(audio) = play_piano_sample(&self.engine, Channel::Trainer, *midi, NOTE_VOLUME) {
+                        self.audio.push(audio);
+                    }
+                }
+            }
+            ChordPlayStyle::Arpeggio => {
+                for (i, midi) in question.notes.iter().enumerate() {
+                    let link = ctx.link().clone();
+                    let midi = *midi;
+                    self.note_timeouts.push(Timeout::new(ARPEGGIO_GAP_MS * i as u32, move || {
+                        link.send_message(ChordTrainerMsg::PlayNote(midi));
+                    }));
+                }
+            }
+        }
+    }
+
+    fn stop_audio(&mut self) {
+        self.note_timeouts.clear();
+        for audio in self.audio.drain(..) {
+            audio.stop();
+        }
+    }
+
+    // 답변 결과 표시
+    fn view_feedback(&self) -> Html {
+        let question = match &self.question {
+            Some(question) => question,
+            None => return html! { <div class="quiz-prompt">{"▶ 새 문제를 눌러 시작하세요"}</div> },
+        };
+
+        let chord = &CHORD_TYPES[question.chord_idx];
+        let inversion = match question.inversion {
+            0 => "기본형".to_string(),
+            n => format!("{}전위", n),
+        };
+        let voicing = if question.open { "오픈 보이싱" } else { "클로즈 보이싱" };
+        let notes = question.notes.iter().map(|m| note_name_from_midi(*m)).collect::<Vec<_>>().join(" ");
+        let detail = format!(
+            "{}{} {}, {}, {} - {}",
+            note_name_from_midi(question.root_midi),
+            chord.symbol,
+            chord.name,
+            inversion,
+            voicing,
+            notes
+        );
+
+        match self.quiz.answered {
+            None => html! { <div class="quiz-prompt">{"어떤 화음일까요?"}</div> },
+            Some(chosen) if chosen == question.chord_idx => html! {
+                <div class="quiz-feedback correct">{format!("✅ 정답! {}", detail)}</div>
+            },
+            Some(_) => html! {
+                <div class="quiz-feedback wrong">{format!("❌ 오답 - 정답은 {}", detail)}</div>
+            },
+        }
+    }
+}
+
+// 근음, 자리바꿈, 보이싱에 맞는 화음 구성음 (낮은 음부터)
+fn build_voicing(root_midi: i32, chord: &ChordType, inversion: usize, open: bool) -> Vec<i32> {
+    let mut notes: Vec<i32> = chord
+        .degrees
+        .iter()
+        .map(|degree| root_midi + ScaleGenerator::interval_semitones(degree))
+        .collect();
+
+    // 자리바꿈: 가장 낮은 음을 한 옥타브 위로
+    for _ in 0..inversion {
+        let lowest = notes.remove(0);
+        notes.push(lowest + 12);
+    }
+
+    // 드롭2 보이싱: 위에서 두 번째 음을 한 옥타브 아래로
+    if open && notes.len() >= 3 {
+        let idx = notes.len() - 2;
+        notes[idx] -= 12;
+        notes.sort();
+    }
+
+    notes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chord(symbol: &str) -> &'static ChordType {
+        CHORD_TYPES.iter().find(|chord| chord.symbol == symbol).unwrap()
+    }
+
+    // 가장 낮은 음 기준 음정 (반음)
+    fn intervals(notes: &[i32]) -> Vec<i32> {
+        notes.iter().map(|note| note - notes[0]).collect()
+    }
+
+    #[test]
+    fn root_position_stacks_chord_degrees() {
+        assert_eq!(build_voicing(60, chord("maj"), 0, false), vec![60, 64, 67]);
+        assert_eq!(intervals(&build_voicing(60, chord("m"), 0, false)), vec![0, 3, 7]);
+        assert_eq!(intervals(&build_voicing(60, chord("dim"), 0, false)), vec![0, 3, 6]);
+        assert_eq!(intervals(&build_voicing(60, chord("aug"), 0, false)), vec![0, 4, 8]);
+        assert_eq!(intervals(&build_voicing(55, chord("7"), 0, false)), vec![0, 4, 7, 10]);
+        assert_eq!(intervals(&build_voicing(55, chord("maj7"), 0, false)), vec![0, 4, 7, 11]);
+        assert_eq!(intervals(&build_voicing(55, chord("m7b5"), 0, false)), vec![0, 3, 6, 10]);
+        assert_eq!(intervals(&build_voicing(55, chord("dim7"), 0, false)), vec![0, 3, 6, 9]);
+    }
+
+    #[test]
+    fn inversions_move_the_lowest_notes_up_an_octave() {
+        // C장3화음: 1전위 E-G-C, 2전위 G-C-E
+        assert_eq!(build_voicing(60, chord("maj"), 1, false), vec![64, 67, 72]);
+        assert_eq!(build_voicing(60, chord("maj"), 2, false), vec![67, 72, 76]);
+        // G7 3전위는 7음이 베이스
+        assert_eq!(build_voicing(55, chord("7"), 3, false), vec![65, 67, 71, 74]);
+
+        // 자리바꿈해도 구성음(음이름)은 같음
+        for chord in &CHORD_TYPES {
+            let root: Vec<i32> = build_voicing(48, chord, 0, false).iter().map(|n| n % 12).collect();
+            for inversion in 0..chord.degrees.len() {
+                let notes = build_voicing(48, chord, inversion, false);
+                assert!(notes.windows(2).all(|pair| pair[0] < pair[1]), "{} {}전위: {:?}", chord.symbol, inversion, notes);
+                let mut pitch_classes: Vec<i32> = notes.iter().map(|n| n % 12).collect();
+                let mut expected = root.clone();
+                pitch_classes.sort();
+                expected.sort();
+                assert_eq!(pitch_classes, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn open_voicing_drops_the_second_from_top() {
+        // C장7 클로즈 C-E-G-B -> 드롭2 G-C-E-B
+        assert_eq!(build_voicing(60, chord("maj7"), 0, true), vec![55, 60, 64, 71]);
+        // C장3 클로즈 C-E-G -> E가 내려가 E-C-G
+        assert_eq!(build_voicing(60, chord("maj"), 0, true), vec![52, 60, 67]);
+        // 1전위에 드롭2
+        assert_eq!(build_voicing(60, chord("maj"), 1, true), vec![55, 64, 72]);
+    }
+}
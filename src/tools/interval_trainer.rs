@@ -0,0 +1,533 @@
+use gloo_timers::callback::Timeout;
+use serde::{Deserialize, Serialize};
+use yew::prelude::*;
+
+use crate::audio_engine::{engine_from_scope, AudioEngine, Channel};
+use crate::tools::piano_sampler::{play_piano_sample, PianoSample};
+use crate::tools::scale_generator::ScaleGenerator;
+use crate::tools::target_melody::{midi_from_note_name, note_name_from_midi};
+use crate::practice_log::PracticeTool;
+use crate::tools::quiz::QuizState;
+use crate::routes::Route;
+use crate::share_link::{self, CopyLinkButton};
+
+// 조건부 로그 매크로 정의
+#[cfg(debug_assertions)]
+macro_rules! console_log {
+    ($($arg:tt)*) => {
+        web_sys::console::log_1(&format!($($arg)*).into());
+    };
+}
+
+#[cfg(not(debug_assertions))]
+macro_rules! console_log {
+    ($($arg:tt)*) => {};
+}
+
+// 퀴즈에 사용할 음정 목록 (스케일 생성기 음정 표기, 표시 이름)
+const QUIZ_INTERVALS: [(&str, &str); 12] = [
+    ("b2", "단2도"),
+    ("2", "장2도"),
+    ("b3", "단3도"),
+    ("3", "장3도"),
+    ("4", "완전4도"),
+    ("b5", "트라이톤"),
+    ("5", "완전5도"),
+    ("#5", "단6도"),
+    ("6", "장6도"),
+    ("b7", "단7도"),
+    ("7", "장7도"),
+    ("8", "옥타브"),
+];
+
+// 처음 켜져 있는 음정 (단2도, 장2도, 단3도, 장3도, 완전4도, 완전5도, 옥타브)
+const DEFAULT_ENABLED: [bool; 12] = [true, true, true, true, true, false, true, false, false, false, false, true];
+
+const NOTE_VOLUME: f64 = 0.7;
+const MELODIC_GAP_MS: u32 = 700; // 선율 음정의 두 음 사이 간격
+const AUTO_NEXT_MS: u32 = 1200;  // 정답 후 다음 문제까지 대기 시간
+const RANGE_MIN_MIDI: i32 = 36;  // 선택 가능한 최저음 (C2)
+const RANGE_MAX_MIDI: i32 = 84;  // 선택 가능한 최고음 (C6)
+
+// 음정 재생 방식
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PlayStyle {
+    Melodic,  // 두 음을 차례로
+    Harmonic, // 두 음을 동시에
+}
+
+// 선율 음정 방향
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum IntervalDirection {
+    Ascending,
+    Descending,
+    Both,
+}
+
+// 공유 링크 쿼리 (예: /interval-trainer?intervals=b3,3,5&style=harmonic&low=C3&high=C5)
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+struct IntervalTrainerQuery {
+    intervals: Option<String>, // 스케일 생성기 음정 표기를 ','로 연결
+    style: Option<String>,     // melodic / harmonic
+    dir: Option<String>,       // up / down / both
+    low: Option<String>,       // 음역 최저음 (예: C3)
+    high: Option<String>,
+    adaptive: Option<bool>,
+}
+
+// 출제된 문제
+#[derive(Debug, Clone, PartialEq)]
+struct IntervalQuestion {
+    interval_idx: usize, // QUIZ_INTERVALS 인덱스
+    first_midi: i32,     // 먼저 들리는 음 (화성 음정에서는 아래 음)
+    second_midi: i32,
+}
+
+pub enum IntervalTrainerMsg {
+    ToggleInterval(usize),
+    SetPlayStyle(PlayStyle),
+    SetDirection(IntervalDirection),
+    SetLowNote(i32),
+    SetHighNote(i32),
+    ToggleAdaptive,
+    NewQuestion,
+    Replay,
+    PlaySecondNote,
+    Answer(usize),
+    ResetStats,
+}
+
+pub struct IntervalTrainer {
+    enabled: Vec<bool>,              // 출제할 음정
+    play_style: PlayStyle,
+    direction: IntervalDirection,
+    low_midi: i32,                   // 출제 음역 최저음
+    high_midi: i32,                  // 출제 음역 최고음
+    adaptive: bool,                  // 약한 음정 집중 출제
+    quiz: QuizState,                 // 답변과 음정별 통계
+    question: Option<IntervalQuestion>,
+    engine: AudioEngine,             // 공유 오디오 엔진 (청음 채널로 출력)
+    audio: Vec<PianoSample>,    // 재생 중인 피아노 음원
+    timeout: Option<Timeout>,        // 두 번째 음 / 자동 진행 타이머
+    error: Option<String>,
+}
+
+impl Component for IntervalTrainer {
+    type Message = IntervalTrainerMsg;
+    type Properties = ();
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let mut trainer = Self {
+            enabled: DEFAULT_ENABLED.to_vec(),
+            play_style: PlayStyle::Melodic,
+            direction: IntervalDirection::Ascending,
+            low_midi: 48,  // C3
+            high_midi: 72, // C5
+            adaptive: true,
+            quiz: QuizState::new(QUIZ_INTERVALS.len()),
+            question: None,
+            engine: engine_from_scope(ctx.link()),
+            audio: Vec::new(),
+            timeout: None,
+            error: None,
+        };
+
+        // 공유 링크로 열었으면 링크의 설정 적용
+        if let Some(query) = share_link::read_query::<IntervalTrainerQuery>(ctx.link(), &Route::IntervalTrainer) {
+            trainer.apply_query(query);
+        }
+        trainer
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            IntervalTrainerMsg::ToggleInterval(idx) => {
+                if let Some(enabled) = self.enabled.get_mut(idx) {
+                    *enabled = !*enabled;
+                }
+                true
+            }
+            IntervalTrainerMsg::SetPlayStyle(style) => {
+                self.play_style = style;
+                true
+            }
+            IntervalTrainerMsg::SetDirection(direction) => {
+                self.direction = direction;
+                true
+            }
+            IntervalTrainerMsg::SetLowNote(midi) => {
+                self.low_midi = midi.min(self.high_midi - 1);
+                true
+            }
+            IntervalTrainerMsg::SetHighNote(midi) => {
+                self.high_midi = midi.max(self.low_midi + 1);
+                true
+            }
+            IntervalTrainerMsg::ToggleAdaptive => {
+                self.adaptive = !self.adaptive;
+                true
+            }
+            IntervalTrainerMsg::NewQuestion => {
+                self.timeout = None;
+                self.quiz.next_question();
+                self.question = self.generate_question();
+                if self.question.is_some() {
+                    self.error = None;
+                    self.play_question(ctx);
+                } else {
+                    self.error = Some("선택한 음역 안에 출제할 수 있는 음정이 없습니다. 음정이나 음역을 조정하세요.".to_string());
+                }
+                true
+            }
+            IntervalTrainerMsg::Replay => {
+                self.play_question(ctx);
+                false
+            }
+            IntervalTrainerMsg::PlaySecondNote => {
+                if let Some(question) = &self.question {
+                    if let Some(audio) = play_piano_sample(&self.engine, Channel::Trainer, question.second_midi, NOTE_VOLUME) {
+                        self.audio.push(audio);
+                    }
+                }
+                false
+            }
+            IntervalTrainerMsg::Answer(idx) => {
+                let expected = match &self.question {
+                    Some(question) => question.interval_idx,
+                    None => return false,
+                };
+                let correct = match self.quiz.answer(idx, expected, js_sys::Date::now()) {
+                    Some(correct) => correct,
+                    None => return false,
+                };
+                console_log!("음정 퀴즈 답변: {} (정답: {})", QUIZ_INTERVALS[idx].1, QUIZ_INTERVALS[expected].1);
+
+                // 정답이면 잠시 후 다음 문제
+                if correct {
+                    let link = ctx.link().clone();
+                    self.timeout = Some(Timeout::new(AUTO_NEXT_MS, move || {
+                        link.send_message(IntervalTrainerMsg::NewQuestion);
+                    }));
+                }
+                self.save_practice();
+                true
+            }
+            IntervalTrainerMsg::ResetStats => {
+                self.quiz.reset();
+                true
+            }
+        }
+    }
+
+    fn destroy(&mut self, _ctx: &Context<Self>) {
+        self.stop_audio();
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let link = ctx.link();
+
+        html! {
+            <div class="interval-trainer">
+                <div class="trainer-settings">
+                    <div class="setting-row">
+                        <span class="setting-label">{"재생 방식:"}</span>
+                        <select onchange={link.callback(|e: Event| {
+                            let select = e.target_dyn_into::<web_sys::HtmlSelectElement>().unwrap();
+                            if select.value() == "harmonic" {
+                                IntervalTrainerMsg::SetPlayStyle(PlayStyle::Harmonic)
+                            } else {
+                                IntervalTrainerMsg::SetPlayStyle(PlayStyle::Melodic)
+                            }
+                        })}>
+                            <option value="melodic" selected={self.play_style == PlayStyle::Melodic}>{"선율 (차례로)"}</option>
+                            <option value="harmonic" selected={self.play_style == PlayStyle::Harmonic}>{"화성 (동시에)"}</option>
+                        </select>
+                    </div>
+                    <div class="setting-row">
+                        <span class="setting-label">{"방향:"}</span>
+                        <select
+                            disabled={self.play_style == PlayStyle::Harmonic}
+                            onchange={link.callback(|e: Event| {
+                                let select = e.target_dyn_into::<web_sys::HtmlSelectElement>().unwrap();
+                                let direction = match select.value().as_str() {
+                                    "descending" => IntervalDirection::Descending,
+                                    "both" => IntervalDirection::Both,
+                                    _ => IntervalDirection::Ascending,
+                                };
+                                IntervalTrainerMsg::SetDirection(direction)
+                            })}
+                        >
+                            <option value="ascending" selected={self.direction == IntervalDirection::Ascending}>{"상행"}</option>
+                            <option value="descending" selected={self.direction == IntervalDirection::Descending}>{"하행"}</option>
+                            <option value="both" selected={self.direction == IntervalDirection::Both}>{"상행/하행"}</option>
+                        </select>
+                    </div>
+                    <div class="setting-row">
+                        <span class="setting-label">{"음역:"}</span>
+                        { self.view_range_select(ctx, self.low_midi, RANGE_MIN_MIDI..self.high_midi, IntervalTrainerMsg::SetLowNote) }
+                        <span>{" ~ "}</span>
+                        { self.view_range_select(ctx, self.high_midi, (self.low_midi + 1)..(RANGE_MAX_MIDI + 1), IntervalTrainerMsg::SetHighNote) }
+                    </div>
+                    <div class="setting-row">
+                        <label>
+                            <input
+                                type="checkbox"
+                                checked={self.adaptive}
+                                onchange={link.callback(|_| IntervalTrainerMsg::ToggleAdaptive)}
+                            />
+                            {" 틀린 음정 집중 출제"}
+                        </label>
+                    </div>
+                    <div class="setting-row interval-toggles">
+                        {
+                            QUIZ_INTERVALS.iter().enumerate().map(|(idx, (_, name))| {
+                                html! {
+                                    <label class="interval-toggle">
+                                        <input
+                                            type="checkbox"
+                                            checked={self.enabled[idx]}
+                                            onchange={link.callback(move |_| IntervalTrainerMsg::ToggleInterval(idx))}
+                                        />
+                                        {format!(" {}", name)}
+                                    </label>
+                                }
+                            }).collect::<Html>()
+                        }
+                    </div>
+                    <div class="setting-row">
+                        <CopyLinkButton url={self.share_url()} />
+                    </div>
+                </div>
+
+                <div class="trainer-quiz">
+                    <div class="button-group">
+                        <button class="play-button" onclick={link.callback(|_| IntervalTrainerMsg::NewQuestion)}>
+                            {"▶ 새 문제"}
+                        </button>
+                        <button
+                            class="replay-button"
+                            disabled={self.question.is_none()}
+                            onclick={link.callback(|_| IntervalTrainerMsg::Replay)}
+                        >
+                            {"🔁 다시 듣기"}
+                        </button>
+                    </div>
+                    { self.view_feedback() }
+                    <div class="answer-buttons">
+                        {
+                            QUIZ_INTERVALS.iter().enumerate().filter(|(idx, _)| self.enabled[*idx]).map(|(idx, (_, name))| {
+                                let state = self.quiz.answer_class(idx, self.question.as_ref().map(|q| q.interval_idx));
+                                html! {
+                                    <button
+                                        class={classes!("answer-button", state)}
+                                        disabled={self.question.is_none() || self.quiz.answered.is_some()}
+                                        onclick={link.callback(move |_| IntervalTrainerMsg::Answer(idx))}
+                                    >
+                                        {*name}
+                                    </button>
+                                }
+                            }).collect::<Html>()
+                        }
+                    </div>
+                    {
+                        if let Some(err) = &self.error {
+                            html! { <div class="trainer-error">{err}</div> }
+                        } else {
+                            html! {}
+                        }
+                    }
+                </div>
+
+                { self.quiz.view_stats("음정", &QUIZ_INTERVALS.map(|(_, name)| name), link.callback(|_| IntervalTrainerMsg::ResetStats)) }
+            </div>
+        }
+    }
+}
+
+impl IntervalTrainer {
+    fn apply_query(&mut self, query: IntervalTrainerQuery) {
+        if let Some(intervals) = query.intervals {
+            let enabled: Vec<bool> = QUIZ_INTERVALS.iter().map(|(degree, _)| intervals.split(',').any(|i| i == *degree)).collect();
+            if enabled.iter().any(|e| *e) {
+                self.enabled = enabled;
+            }
+        }
+        match query.style.as_deref() {
+            Some("melodic") => self.play_style = PlayStyle::Melodic,
+            Some("harmonic") => self.play_style = PlayStyle::Harmonic,
+            _ => {}
+        }
+        match query.dir.as_deref() {
+            Some("up") => self.direction = IntervalDirection::Ascending,
+            Some("down") => self.direction = IntervalDirection::Descending,
+            Some("both") => self.direction = IntervalDirection::Both,
+            _ => {}
+        }
+        let low = query.low.as_deref().and_then(midi_from_note_name).map(|m| m.clamp(RANGE_MIN_MIDI, RANGE_MAX_MIDI - 1));
+        let high = query.high.as_deref().and_then(midi_from_note_name).map(|m| m.clamp(RANGE_MIN_MIDI + 1, RANGE_MAX_MIDI));
+        let (low, high) = (low.unwrap_or(self.low_midi), high.unwrap_or(self.high_midi));
+        if low < high {
+            self.low_midi = low;
+            self.high_midi = high;
+        }
+        if let Some(adaptive) = query.adaptive {
+            self.adaptive = adaptive;
+        }
+    }
+
+    // 현재 설정이 담긴 공유 링크
+    fn share_url(&self) -> Option<AttrValue> {
+        let intervals: Vec<&str> = QUIZ_INTERVALS
+            .iter()
+            .zip(self.enabled.iter())
+            .filter(|(_, enabled)| **enabled)
+            .map(|((degree, _), _)| *degree)
+            .collect();
+        let query = IntervalTrainerQuery {
+            intervals: Some(intervals.join(",")),
+            style: Some(match self.play_style {
+                PlayStyle::Melodic => "melodic".to_string(),
+                PlayStyle::Harmonic => "harmonic".to_string(),
+            }),
+            dir: Some(match self.direction {
+                IntervalDirection::Ascending => "up".to_string(),
+                IntervalDirection::Descending => "down".to_string(),
+                IntervalDirection::Both => "both".to_string(),
+            }),
+            low: Some(note_name_from_midi(self.low_midi)),
+            high: Some(note_name_from_midi(self.high_midi)),
+            adaptive: Some(self.adaptive),
+        };
+        share_link::share_url(&Route::IntervalTrainer, &query).map(AttrValue::from)
+    }
+
+    // 이번 세션을 연습 기록에 반영
+    fn save_practice(&self) {
+        let style = match self.play_style {
+            PlayStyle::Melodic => "선율 음정",
+            PlayStyle::Harmonic => "화성 음정",
+        };
+        self.quiz.save_session(PracticeTool::IntervalTrainer, style);
+    }
+
+    // 설정에 맞는 새 문제 생성 (출제 가능한 음정이 없으면 None)
+    fn generate_question(&self) -> Option<IntervalQuestion> {
+        let span = self.high_midi - self.low_midi;
+        let candidates: Vec<(usize, f64)> = QUIZ_INTERVALS
+            .iter()
+            .enumerate()
+            .filter(|(idx, (interval, _))| self.enabled[*idx] && ScaleGenerator::interval_semitones(interval) <= span)
+            .map(|(idx, _)| {
+                let weight = if self.adaptive { self.quiz.stats[idx].weight() } else { 1.0 };
+                (idx, weight)
+            })
+            .collect();
+
+        if candidates.is_empty() {
+            return None;
+        }
+
+        // 가중치에 비례해 음정 선택
+        let total: f64 = candidates.iter().map(|(_, w)| w).sum();
+        let mut pick = js_sys::Math::random() * total;
+        let mut interval_idx = candidates[candidates.len() - 1].0;
+        for (idx, weight) in &candidates {
+            if pick < *weight {
+                interval_idx = *idx;
+                break;
+            }
+            pick -= weight;
+        }
+
+        // 두 음이 모두 음역 안에 들어오도록 아래 음 선택
+        let semitones = ScaleGenerator::interval_semitones(QUIZ_INTERVALS[interval_idx].0);
+        let lower = self.low_midi + (js_sys::Math::random() * (span - semitones + 1) as f64) as i32;
+        let upper = lower + semitones;
+
+        let descending = self.play_style == PlayStyle::Melodic
+            && match self.direction {
+                IntervalDirection::Ascending => false,
+                IntervalDirection::Descending => true,
+                IntervalDirection::Both => js_sys::Math::random() < 0.5,
+            };
+
+        let (first_midi, second_midi) = if descending { (upper, lower) } else { (lower, upper) };
+        Some(IntervalQuestion { interval_idx, first_midi, second_midi })
+    }
+
+    // 현재 문제 재생
+    fn play_question(&mut self, ctx: &Context<Self>) {
+        let question = match &self.question {
+            Some(question) => question.clone(),
+            None => return,
+        };
+
+        self.stop_audio();
+        if let Some(audio) = play_piano_sample(&self.engine, Channel::Trainer, question.first_midi, NOTE_VOLUME) {
+            self.audio.push(audio);
+        }
+
+        match self.play_style {
+            PlayStyle::Harmonic => {
+                if let Some(audio) = play_piano_sample(&self.engine, Channel::Trainer, question.second_midi, NOTE_VOLUME) {
+                    self.audio.push(audio);
+                }
+            }
+            PlayStyle::Melodic => {
+                let link = ctx.link().clone();
+                self.timeout = Some(Timeout::new(MELODIC_GAP_MS, move || {
+                    link.send_message(IntervalTrainerMsg::PlaySecondNote);
+                }));
+            }
+        }
+    }
+
+    fn stop_audio(&mut self) {
+        for audio in self.audio.drain(..) {
+            audio.stop();
+        }
+    }
+
+    // 답변 결과 표시
+    fn view_feedback(&self) -> Html {
+        let question = match &self.question {
+            Some(question) => question,
+            None => return html! { <div class="quiz-prompt">{"▶ 새 문제를 눌러 시작하세요"}</div> },
+        };
+
+        let notes = format!("{} → {}", note_name_from_midi(question.first_midi), note_name_from_midi(question.second_midi));
+        let answer_name = QUIZ_INTERVALS[question.interval_idx].1;
+        match self.quiz.answered {
+            None => html! { <div class="quiz-prompt">{"어떤 음정일까요?"}</div> },
+            Some(chosen) if chosen == question.interval_idx => html! {
+                <div class="quiz-feedback correct">{format!("✅ 정답! {} ({})", answer_name, notes)}</div>
+            },
+            Some(_) => html! {
+                <div class="quiz-feedback wrong">{format!("❌ 오답 - 정답은 {} ({})", answer_name, notes)}</div>
+            },
+        }
+    }
+
+    // 음역 선택 드롭다운
+    fn view_range_select(
+        &self,
+        ctx: &Context<Self>,
+        selected: i32,
+        range: std::ops::Range<i32>,
+        to_msg: fn(i32) -> IntervalTrainerMsg,
+    ) -> Html {
+        html! {
+            <select onchange={ctx.link().callback(move |e: Event| {
+                let select = e.target_dyn_into::<web_sys::HtmlSelectElement>().unwrap();
+                to_msg(select.value().parse().unwrap_or(selected))
+            })}>
+                {
+                    range.map(|midi| html! {
+                        <option value={midi.to_string()} selected={midi == selected}>{note_name_from_midi(midi)}</option>
+                    }).collect::<Html>()
+                }
+            </select>
+        }
+    }
+}
@@ -1,9 +1,22 @@
 use wasm_bindgen::prelude::*;
-use web_sys::{AudioContext, AudioNode, HtmlCanvasElement};
+use std::cell::Cell;
+use std::rc::Rc;
+use web_sys::HtmlCanvasElement;
 use wasm_bindgen::JsCast;
 use yew::prelude::*;
 use gloo_timers::callback::Interval;
+use gloo::events::{EventListener, EventListenerOptions};
 use js_sys::Date;
+use serde::{Deserialize, Serialize};
+
+use crate::audio_engine::{engine_from_scope, AudioEngine, Channel};
+use crate::event_bus::{subscribe, AppEvent, Subscription};
+use crate::pitch_input::{self, MicInput};
+use crate::practice_log::{save_practice_entry, PracticeEntry, PracticeTool};
+use crate::settings::{self, MetronomeSettings};
+use crate::routes::Route;
+use crate::share_link::{self, CopyLinkButton};
+use crate::tools::take_library::BeatGrid;
 
 // 조건부 로그 매크로 정의
 #[cfg(debug_assertions)]
@@ -44,13 +57,23 @@ macro_rules! console_warn {
 
 // 인라인 스타일 제거
 
+// 리듬 훈련 설정
+const RHYTHM_HISTOGRAM_RANGE_MS: f64 = 100.0; // 히스토그램 표시 범위 (±ms)
+const RHYTHM_HISTOGRAM_BIN_MS: f64 = 10.0;    // 히스토그램 구간 폭
+const RHYTHM_ON_TIME_MS: f64 = 20.0;          // 이 범위 안이면 정확한 타이밍으로 간주
+const ONSET_POLL_MS: u32 = 5;                 // 마이크 온셋 검출 간격
+const ONSET_THRESHOLD: f32 = 0.2;             // 온셋으로 인정하는 최소 진폭
+const ONSET_WINDOW_SAMPLES: usize = 256;      // 온셋 검출에 사용할 최근 샘플 수
+const ONSET_REFRACTORY_MS: f64 = 120.0;       // 온셋 후 다음 온셋을 무시하는 시간
+
 // 박자 정보를 나타내는 열거형
-#[derive(Debug, Clone, PartialEq, Copy)]
+#[derive(Debug, Clone, PartialEq, Copy, Serialize, Deserialize)]
 pub enum TimeSignature {
     FourFour,    // 4/4
     ThreeFour,   // 3/4
     TwoFour,     // 2/4
     SixEight,    // 6/8
+    SevenEight,  // 7/8
     NineEight,   // 9/8
     TwelveEight, // 12/8
 }
@@ -63,6 +86,7 @@ impl TimeSignature {
             TimeSignature::ThreeFour => 3,
             TimeSignature::TwoFour => 2,
             TimeSignature::SixEight => 6,
+            TimeSignature::SevenEight => 7,
             TimeSignature::NineEight => 9,
             TimeSignature::TwelveEight => 12,
         }
@@ -72,7 +96,7 @@ impl TimeSignature {
     fn beat_unit(&self) -> u8 {
         match self {
             TimeSignature::FourFour | TimeSignature::ThreeFour | TimeSignature::TwoFour => 4,
-            TimeSignature::SixEight | TimeSignature::NineEight | TimeSignature::TwelveEight => 8,
+            TimeSignature::SixEight | TimeSignature::SevenEight | TimeSignature::NineEight | TimeSignature::TwelveEight => 8,
         }
     }
     
@@ -83,14 +107,30 @@ impl TimeSignature {
             TimeSignature::ThreeFour => "3/4".to_string(),
             TimeSignature::TwoFour => "2/4".to_string(),
             TimeSignature::SixEight => "6/8".to_string(),
+            TimeSignature::SevenEight => "7/8".to_string(),
             TimeSignature::NineEight => "9/8".to_string(),
             TimeSignature::TwelveEight => "12/8".to_string(),
         }
     }
+    
+    // 표시 문자열("7/8" 등)로부터 박자 찾기
+    fn from_display_str(value: &str) -> Option<Self> {
+        [
+            TimeSignature::FourFour,
+            TimeSignature::ThreeFour,
+            TimeSignature::TwoFour,
+            TimeSignature::SixEight,
+            TimeSignature::SevenEight,
+            TimeSignature::NineEight,
+            TimeSignature::TwelveEight,
+        ]
+        .into_iter()
+        .find(|signature| signature.display_str() == value)
+    }
 }
 
 // 음표 단위를 나타내는 열거형
-#[derive(Debug, Clone, PartialEq, Copy)]
+#[derive(Debug, Clone, PartialEq, Copy, Serialize, Deserialize)]
 pub enum NoteUnit {
     Quarter,     // 4분 음표
     Eighth,      // 8분 음표
@@ -118,6 +158,32 @@ impl NoteUnit {
             NoteUnit::Sixteenth => "Sixteenth Note (1/16)".to_string(),
         }
     }
+    
+    // 선택 상자와 공유 링크에서 쓰는 값
+    fn value(&self) -> &'static str {
+        match self {
+            NoteUnit::Quarter => "quarter",
+            NoteUnit::Eighth => "eighth",
+            NoteUnit::Triplet => "triplet",
+            NoteUnit::Sixteenth => "sixteenth",
+        }
+    }
+    
+    fn from_value(value: &str) -> Option<Self> {
+        [NoteUnit::Quarter, NoteUnit::Eighth, NoteUnit::Triplet, NoteUnit::Sixteenth]
+            .into_iter()
+            .find(|unit| unit.value() == value)
+    }
+}
+
+// 공유 링크 쿼리 (예: /metronome?bpm=140&sig=7%2F8&unit=eighth)
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+struct MetronomeQuery {
+    bpm: Option<u32>,
+    sig: Option<String>,
+    unit: Option<String>,
+    accent: Option<bool>,
 }
 
 // 메트로놈 컴포넌트의 메시지 정의
@@ -132,6 +198,78 @@ pub enum MetronomeMsg {
     UpdateCanvas,
     TapTempo,
     ToggleAccent,
+    ToggleRhythmTraining,                     // 리듬 훈련 모드 켜기/끄기
+    RhythmTap,                                // 스페이스바/터치 탭 입력
+    ToggleRhythmMic,                          // 마이크 온셋 입력 켜기/끄기
+    RhythmMicReady(Result<MicInput, String>), // 리듬 훈련용 마이크 준비 완료
+    RhythmMicPoll,                            // 마이크 온셋 검출 (주기적)
+    ResetRhythmStats,                         // 리듬 훈련 기록 초기화
+    SettingsReset,                            // 저장된 설정이 기본값으로 초기화됨
+}
+
+// 리듬 훈련 세션 통계
+struct RhythmStats {
+    count: usize,
+    mean_ms: f64,                  // 평균 편차 (+: 늦음, -: 빠름)
+    std_dev_ms: f64,               // 표준편차 (일관성)
+    mean_abs_ms: f64,              // 평균 절대 편차
+    on_time_ratio: f64,            // 정확한 타이밍 비율
+    drift_ms_per_min: Option<f64>, // 시간에 따른 편차 변화 (+: 점점 늦어짐)
+}
+
+impl RhythmStats {
+    // (세션 시작 후 초, 편차 ms) 목록으로 통계 계산
+    fn from_hits(hits: &[(f64, f64)]) -> Option<Self> {
+        if hits.is_empty() {
+            return None;
+        }
+
+        let count = hits.len();
+        let n = count as f64;
+        let mean_ms = hits.iter().map(|(_, d)| d).sum::<f64>() / n;
+        let variance = hits.iter().map(|(_, d)| (d - mean_ms).powi(2)).sum::<f64>() / n;
+        let mean_abs_ms = hits.iter().map(|(_, d)| d.abs()).sum::<f64>() / n;
+        let on_time = hits.iter().filter(|(_, d)| d.abs() <= RHYTHM_ON_TIME_MS).count();
+
+        // 선형 회귀 기울기 (ms/초 -> ms/분)
+        let drift_ms_per_min = if count >= 4 {
+            let mean_t = hits.iter().map(|(t, _)| t).sum::<f64>() / n;
+            let cov: f64 = hits.iter().map(|(t, d)| (t - mean_t) * (d - mean_ms)).sum();
+            let var_t: f64 = hits.iter().map(|(t, _)| (t - mean_t).powi(2)).sum();
+            if var_t > 0.0 { Some(cov / var_t * 60.0) } else { None }
+        } else {
+            None
+        };
+
+        Some(Self {
+            count,
+            mean_ms,
+            std_dev_ms: variance.sqrt(),
+            mean_abs_ms,
+            on_time_ratio: on_time as f64 / n,
+            drift_ms_per_min,
+        })
+    }
+}
+
+// 입력 시각과 직전 클릭 시각(AudioContext 초)으로 가까운 클릭 기준 편차 (ms, +: 늦음, -: 빠름)
+// 두 클릭의 정가운데면 직전 클릭에 늦은 것으로 봄
+fn click_deviation_ms(time: f64, last_click: f64, click_ms: f64) -> f64 {
+    let late = (time - last_click) * 1000.0;
+    let early = late - click_ms;
+    if late.abs() <= early.abs() { late } else { early }
+}
+
+// 편차 히스토그램의 구간별 개수 (범위 밖은 양 끝 구간에 포함)
+fn rhythm_histogram(hits: &[(f64, f64)]) -> Vec<usize> {
+    let bin_count = (RHYTHM_HISTOGRAM_RANGE_MS * 2.0 / RHYTHM_HISTOGRAM_BIN_MS) as usize;
+    let mut bins = vec![0usize; bin_count];
+    for (_, deviation) in hits {
+        let idx = ((deviation + RHYTHM_HISTOGRAM_RANGE_MS) / RHYTHM_HISTOGRAM_BIN_MS).floor();
+        let idx = idx.clamp(0.0, (bin_count - 1) as f64) as usize;
+        bins[idx] += 1;
+    }
+    bins
 }
 
 // 메트로놈 컴포넌트의 상태 정의
@@ -146,22 +284,65 @@ pub struct Metronome {
     interval: Option<Interval>,
     canvas_ref: NodeRef,
     sound_enabled: bool,
-    audio_ctx: Option<AudioContext>,
+    engine: AudioEngine, // 공유 오디오 엔진 (클릭은 메트로놈 채널로 출력)
     last_update_time: f64,
     total_clicks: u32,
     tap_times: Vec<f64>,
     accent_enabled: bool,
+    
+    // 리듬 훈련 관련 필드
+    rhythm_training: bool,                      // 리듬 훈련 모드
+    last_click_time: Option<f64>,               // 마지막 클릭을 예약한 AudioContext 시각 (초)
+    rhythm_start_time: f64,                     // 첫 입력의 AudioContext 시각 (드리프트 그래프 기준)
+    rhythm_hits: Vec<(f64, f64)>,               // (세션 시작 후 초, 편차 ms)
+    rhythm_key_listener: Option<EventListener>, // 스페이스바 입력 리스너
+    rhythm_mic: Option<MicInput>,               // 박수 검출용 마이크
+    rhythm_mic_interval: Option<Interval>,      // 온셋 검출 타이머
+    rhythm_mic_error: Option<String>,
+    last_onset_time: f64,                       // 마지막 온셋 시각
+    prev_onset_level: f32,                      // 직전 검출 진폭 (상승 에지 판별용)
+    histogram_canvas_ref: NodeRef,
+    drift_canvas_ref: NodeRef,
+    
+    // 연습 기록 관련 필드
+    play_started_at: f64,                       // 재생 시작 시각 (Date::now())
+    play_start_hit_count: usize,                // 재생 시작 시점의 리듬 입력 수
+    _settings_subscription: Subscription,       // 설정 초기화 이벤트 구독
 }
 
 impl Component for Metronome {
     type Message = MetronomeMsg;
     type Properties = ();
 
-    fn create(_ctx: &Context<Self>) -> Self {
+    fn create(ctx: &Context<Self>) -> Self {
+        // 저장된 설정 불러오기 - 공유 링크로 열었으면 링크의 값이 우선
+        let mut saved = settings::load_settings().metronome;
+        if let Some(query) = share_link::read_query::<MetronomeQuery>(ctx.link(), &Route::Metronome) {
+            if let Some(bpm) = query.bpm {
+                saved.bpm = bpm.clamp(30, 300);
+            }
+            if let Some(signature) = query.sig.as_deref().and_then(TimeSignature::from_display_str) {
+                saved.time_signature = signature;
+            }
+            if let Some(unit) = query.unit.as_deref().and_then(NoteUnit::from_value) {
+                saved.note_unit = unit;
+            }
+            if let Some(accent) = query.accent {
+                saved.accent = accent;
+            }
+        }
+        
+        let link = ctx.link().clone();
+        let settings_subscription = subscribe(move |event| {
+            if let AppEvent::SettingsReset = event {
+                link.send_message(MetronomeMsg::SettingsReset);
+            }
+        });
+        
         Self {
-            bpm: 120,
-            time_signature: TimeSignature::FourFour,
-            note_unit: NoteUnit::Quarter,
+            bpm: saved.bpm,
+            time_signature: saved.time_signature,
+            note_unit: saved.note_unit,
             is_playing: false,
             current_beat: 0,
             current_click: 0,
@@ -169,11 +350,26 @@ impl Component for Metronome {
             interval: None,
             canvas_ref: NodeRef::default(),
             sound_enabled: true,
-            audio_ctx: None,
+            engine: engine_from_scope(ctx.link()),
             last_update_time: 0.0,
             total_clicks: 0,
             tap_times: Vec::new(),
-            accent_enabled: true,
+            accent_enabled: saved.accent,
+            rhythm_training: false,
+            last_click_time: None,
+            rhythm_start_time: 0.0,
+            rhythm_hits: Vec::new(),
+            rhythm_key_listener: None,
+            rhythm_mic: None,
+            rhythm_mic_interval: None,
+            rhythm_mic_error: None,
+            last_onset_time: 0.0,
+            prev_onset_level: 0.0,
+            histogram_canvas_ref: NodeRef::default(),
+            drift_canvas_ref: NodeRef::default(),
+            play_started_at: 0.0,
+            play_start_hit_count: 0,
+            _settings_subscription: settings_subscription,
         }
     }
 
@@ -189,26 +385,10 @@ impl Component for Metronome {
                 self.current_click = 0;
                 self.total_clicks = 0;
                 
-                // 오디오 컨텍스트 초기화
+                // 공유 오디오 컨텍스트 준비 (사용자 입력 시점에 재개)
                 if self.sound_enabled {
-                    if self.audio_ctx.is_none() {
-                        // 오디오 컨텍스트가 없으면 새로 생성
-                        match AudioContext::new() {
-                            Ok(context) => {
-                                self.audio_ctx = Some(context);
-                                console_log!("오디오 컨텍스트 생성 성공");
-                            },
-                            Err(err) => {
-                                console_error!("오디오 컨텍스트 생성 실패: {:?}", err);
-                            }
-                        }
-                    } else if let Some(context) = &self.audio_ctx {
-                        // 이미 컨텍스트가 있는 경우 재개
-                        if let Err(err) = context.resume() {
-                            console_error!("오디오 컨텍스트 재개 실패: {:?}", err);
-                        } else {
-                            console_log!("오디오 컨텍스트 재개됨");
-                        }
+                    if let Err(err) = self.engine.context() {
+                        console_error!("오디오 컨텍스트 준비 실패: {:?}", err);
                     }
                 }
                 
@@ -220,11 +400,11 @@ impl Component for Metronome {
                 
                 // 초기 시간 설정
                 self.last_update_time = Date::now();
+                self.play_started_at = self.last_update_time;
+                self.play_start_hit_count = self.rhythm_hits.len();
                 
                 // 첫 박자 소리 즉시 재생 (첫 번째 박자이므로 true)
-                if self.sound_enabled {
-                    self.play_click(true);
-                }
+                self.play_click(true);
                 
                 // 메트로놈 틱 인터벌 설정
                 let link = ctx.link().clone();
@@ -253,11 +433,7 @@ impl Component for Metronome {
                 
                 self.is_playing = false;
                 self.interval = None;
-                
-                // 오디오 컨텍스트 일시 중지
-                if let Some(context) = &self.audio_ctx {
-                    let _ = context.suspend();
-                }
+                self.save_practice();
                 
                 true
             },
@@ -269,6 +445,7 @@ impl Component for Metronome {
                 
                 // BPM 값 업데이트
                 self.bpm = bpm;
+                self.save_settings();
                 
                 // 재생 중인 경우 인터벌 재설정
                 if self.is_playing {
@@ -292,15 +469,10 @@ impl Component for Metronome {
                     
                     self.interval = Some(interval);
                     
-                    // 오디오 컨텍스트가 없으면 생성
-                    if self.sound_enabled && self.audio_ctx.is_none() {
-                        match AudioContext::new() {
-                            Ok(context) => {
-                                self.audio_ctx = Some(context);
-                            },
-                            Err(err) => {
-                                console_error!("오디오 컨텍스트 생성 실패: {:?}", err);
-                            }
+                    // 공유 오디오 컨텍스트 준비
+                    if self.sound_enabled {
+                        if let Err(err) = self.engine.context() {
+                            console_error!("오디오 컨텍스트 준비 실패: {:?}", err);
                         }
                     }
                 }
@@ -311,6 +483,7 @@ impl Component for Metronome {
             MetronomeMsg::SetTimeSignature(signature) => {
                 // 박자 설정 업데이트
                 self.time_signature = signature;
+                self.save_settings();
                 
                 // 비트 카운터 초기화
                 self.current_beat = 0;
@@ -344,6 +517,7 @@ impl Component for Metronome {
             MetronomeMsg::SetNoteUnit(unit) => {
                 // 음표 단위 업데이트
                 self.note_unit = unit;
+                self.save_settings();
                 
                 // 클릭 카운터 초기화
                 self.current_click = 0;
@@ -396,11 +570,9 @@ impl Component for Metronome {
                 // 총 클릭 수 증가 (애니메이션용)
                 self.total_clicks += 1;
                 
-                // 소리 재생
-                if self.sound_enabled {
-                    let is_primary_beat = self.current_beat == 0 && self.current_click == 0;
-                    self.play_click(is_primary_beat);
-                }
+                // 소리 재생 (리듬 훈련 기준 시각도 함께 갱신)
+                let is_primary_beat = self.current_beat == 0 && self.current_click == 0;
+                self.play_click(is_primary_beat);
                 
                 true
             },
@@ -408,25 +580,10 @@ impl Component for Metronome {
             MetronomeMsg::ToggleSound => {
                 self.sound_enabled = !self.sound_enabled;
                 
-                if !self.sound_enabled {
-                    // 소리 비활성화 시 오디오 컨텍스트 중지
-                    if let Some(context) = &self.audio_ctx {
-                        let _ = context.suspend();
-                    }
-                } else if self.is_playing {
-                    // 소리 활성화 및 재생 중이면 오디오 컨텍스트 재개
-                    if let Some(context) = &self.audio_ctx {
-                        let _ = context.resume();
-                    } else {
-                        // 없으면 새로 생성
-                        match AudioContext::new() {
-                            Ok(context) => {
-                                self.audio_ctx = Some(context);
-                            },
-                            Err(err) => {
-                                console_error!("오디오 컨텍스트 생성 실패: {:?}", err);
-                            }
-                        }
+                // 소리를 끄면 play_click이 클릭을 만들지 않음 (공유 컨텍스트는 다른 도구가 쓰므로 유지)
+                if self.sound_enabled && self.is_playing {
+                    if let Err(err) = self.engine.context() {
+                        console_error!("오디오 컨텍스트 준비 실패: {:?}", err);
                     }
                 }
                 
@@ -473,6 +630,7 @@ impl Component for Metronome {
                     // 허용 범위(30-300) 내에 있는 경우만 적용
                     if new_bpm >= 30 && new_bpm <= 300 {
                         self.bpm = new_bpm;
+                        self.save_settings();
                         
                         // 재생 중인 경우 인터벌 재설정
                         if self.is_playing {
@@ -506,7 +664,117 @@ impl Component for Metronome {
             
             MetronomeMsg::ToggleAccent => {
                 self.accent_enabled = !self.accent_enabled;
+                self.save_settings();
+                
+                true
+            },
+            
+            MetronomeMsg::ToggleRhythmTraining => {
+                self.rhythm_training = !self.rhythm_training;
+                
+                if self.rhythm_training {
+                    // 스페이스바로 탭 입력 (입력창에 포커스가 있으면 무시)
+                    let document = web_sys::window().unwrap().document().unwrap();
+                    let link = ctx.link().clone();
+                    let options = EventListenerOptions::enable_prevent_default();
+                    self.rhythm_key_listener = Some(EventListener::new_with_options(&document, "keydown", options, move |event| {
+                        let event = event.dyn_ref::<web_sys::KeyboardEvent>().unwrap();
+                        if event.key() != " " || event.repeat() {
+                            return;
+                        }
+                        let in_form = event
+                            .target()
+                            .and_then(|t| t.dyn_into::<web_sys::Element>().ok())
+                            .map(|el| matches!(el.tag_name().as_str(), "INPUT" | "SELECT" | "TEXTAREA"))
+                            .unwrap_or(false);
+                        if !in_form {
+                            event.prevent_default();
+                            link.send_message(MetronomeMsg::RhythmTap);
+                        }
+                    }));
+                } else {
+                    self.rhythm_key_listener = None;
+                    self.stop_rhythm_mic();
+                }
+                
+                true
+            },
+            
+            MetronomeMsg::RhythmTap => {
+                // 탭은 스피커로 나온 클릭을 듣고 누르므로 출력 지연만큼 늦음
+                let output_latency = self.engine.output_latency();
+                match self.audio_time() {
+                    Some(now) => self.record_rhythm_hit(now - output_latency),
+                    None => false,
+                }
+            },
+            
+            MetronomeMsg::ToggleRhythmMic => {
+                if self.rhythm_mic.is_some() {
+                    self.stop_rhythm_mic();
+                } else {
+                    pitch_input::open_microphone(&self.engine, ctx.link().callback(MetronomeMsg::RhythmMicReady));
+                }
+                true
+            },
+            
+            MetronomeMsg::RhythmMicReady(result) => {
+                match result {
+                    Ok(mic) => {
+                        self.rhythm_mic = Some(mic);
+                        self.rhythm_mic_error = None;
+                        self.prev_onset_level = 0.0;
+                        
+                        let link = ctx.link().clone();
+                        self.rhythm_mic_interval = Some(Interval::new(ONSET_POLL_MS, move || {
+                            link.send_message(MetronomeMsg::RhythmMicPoll);
+                        }));
+                    },
+                    Err(err) => {
+                        console_error!("리듬 훈련 마이크 준비 실패: {}", err);
+                        self.rhythm_mic_error = Some(err);
+                    }
+                }
+                true
+            },
+            
+            MetronomeMsg::RhythmMicPoll => {
+                let level = match &self.rhythm_mic {
+                    Some(mic) => mic.peak_level(ONSET_WINDOW_SAMPLES),
+                    None => return false,
+                };
+                
+                // 진폭이 임계값을 넘어서는 순간을 온셋으로 판단
+                let now = Date::now();
+                let is_onset = level >= ONSET_THRESHOLD
+                    && self.prev_onset_level < ONSET_THRESHOLD * 0.5
+                    && now - self.last_onset_time > ONSET_REFRACTORY_MS;
+                self.prev_onset_level = level;
                 
+                if is_onset {
+                    self.last_onset_time = now;
+                    // 박수는 클릭이 스피커로 나가 마이크로 돌아오는 왕복 지연(출력 + 입력)만큼 늦게 잡히므로 보정
+                    let round_trip = self.engine.round_trip_latency();
+                    if let Some(onset) = self.audio_time() {
+                        return self.record_rhythm_hit(onset - round_trip);
+                    }
+                }
+                false
+            },
+            
+            MetronomeMsg::ResetRhythmStats => {
+                self.rhythm_hits.clear();
+                self.play_start_hit_count = 0;
+                true
+            }
+            
+            MetronomeMsg::SettingsReset => {
+                // 재생 중이면 인터벌도 다시 설정되도록 기존 메시지로 적용
+                let defaults = MetronomeSettings::default();
+                self.accent_enabled = defaults.accent;
+                ctx.link().send_message(MetronomeMsg::SetBpm(defaults.bpm));
+                ctx.link().send_message(MetronomeMsg::SetTimeSignature(defaults.time_signature));
+                ctx.link().send_message(MetronomeMsg::SetNoteUnit(defaults.note_unit));
                 true
             }
         }
@@ -606,15 +874,9 @@ impl Component for Metronome {
                                 <select style="width: 100%;" onchange={ctx.link().callback(|e: Event| {
                                     let select = e.target_dyn_into::<web_sys::HtmlSelectElement>();
                                     if let Some(select) = select {
-                                        match select.value().as_str() {
-                                            "4/4" => MetronomeMsg::SetTimeSignature(TimeSignature::FourFour),
-                                            "3/4" => MetronomeMsg::SetTimeSignature(TimeSignature::ThreeFour),
-                                            "2/4" => MetronomeMsg::SetTimeSignature(TimeSignature::TwoFour),
-                                            "6/8" => MetronomeMsg::SetTimeSignature(TimeSignature::SixEight),
-                                            "9/8" => MetronomeMsg::SetTimeSignature(TimeSignature::NineEight),
-                                            "12/8" => MetronomeMsg::SetTimeSignature(TimeSignature::TwelveEight),
-                                            _ => MetronomeMsg::SetTimeSignature(TimeSignature::FourFour),
-                                        }
+                                        MetronomeMsg::SetTimeSignature(
+                                            TimeSignature::from_display_str(&select.value()).unwrap_or(TimeSignature::FourFour)
+                                        )
                                     } else {
                                         MetronomeMsg::SetTimeSignature(TimeSignature::FourFour)
                                     }
@@ -623,6 +885,7 @@ impl Component for Metronome {
                                     <option value="3/4" selected={time_signature == TimeSignature::ThreeFour}>{"3/4"}</option>
                                     <option value="2/4" selected={time_signature == TimeSignature::TwoFour}>{"2/4"}</option>
                                     <option value="6/8" selected={time_signature == TimeSignature::SixEight}>{"6/8"}</option>
+                                    <option value="7/8" selected={time_signature == TimeSignature::SevenEight}>{"7/8"}</option>
                                     <option value="9/8" selected={time_signature == TimeSignature::NineEight}>{"9/8"}</option>
                                     <option value="12/8" selected={time_signature == TimeSignature::TwelveEight}>{"12/8"}</option>
                                 </select>
@@ -655,19 +918,21 @@ impl Component for Metronome {
                                         html! {"="} 
                                     }}
                                 </button>
+                                
+                                <button 
+                                    class={if self.rhythm_training { "play-btn rhythm on" } else { "play-btn rhythm" }}
+                                    title="리듬 훈련"
+                                    onclick={ctx.link().callback(|_| MetronomeMsg::ToggleRhythmTraining)}
+                                >
+                                    {"👏"}
+                                </button>
                             </div>
                             
                             <div class="note-unit-controls">
                                 <select style="width: 100%;" onchange={ctx.link().callback(|e: Event| {
                                     let select = e.target_dyn_into::<web_sys::HtmlSelectElement>();
                                     if let Some(select) = select {
-                                        match select.value().as_str() {
-                                            "quarter" => MetronomeMsg::SetNoteUnit(NoteUnit::Quarter),
-                                            "eighth" => MetronomeMsg::SetNoteUnit(NoteUnit::Eighth),
-                                            "triplet" => MetronomeMsg::SetNoteUnit(NoteUnit::Triplet),
-                                            "sixteenth" => MetronomeMsg::SetNoteUnit(NoteUnit::Sixteenth),
-                                            _ => MetronomeMsg::SetNoteUnit(NoteUnit::Quarter),
-                                        }
+                                        MetronomeMsg::SetNoteUnit(NoteUnit::from_value(&select.value()).unwrap_or(NoteUnit::Quarter))
                                     } else {
                                         MetronomeMsg::SetNoteUnit(NoteUnit::Quarter)
                                     }
@@ -681,11 +946,21 @@ impl Component for Metronome {
                         </div>
                     </div>
                 </div>
+                
+                <div class="share-link-row">
+                    <CopyLinkButton url={self.share_url()} />
+                </div>
+                
+                { self.view_rhythm_trainer(ctx) }
             </div>
         }
     }
 
     fn rendered(&mut self, ctx: &Context<Self>, first_render: bool) {
+        if self.rhythm_training {
+            self.draw_rhythm_charts();
+        }
+        
         if first_render {
             // 캔버스 초기화
             self.draw_metronome();
@@ -735,15 +1010,282 @@ impl Component for Metronome {
     fn destroy(&mut self, _ctx: &Context<Self>) {
         // 컴포넌트 제거 시 타이머 및 오디오 리소스 정리
         self.interval = None;
-        
-        if let Some(context) = &self.audio_ctx {
-            let _ = context.close();
-            self.audio_ctx = None;
+        self.stop_rhythm_mic();
+        if self.is_playing {
+            self.is_playing = false;
+            self.save_practice();
         }
     }
 }
 
 impl Metronome {
+    // 공유 AudioContext의 현재 시각 (클릭 예약과 같은 시계)
+    fn audio_time(&self) -> Option<f64> {
+        self.engine.context().ok().map(|audio_ctx| audio_ctx.current_time())
+    }
+
+    // 지연 보정을 마친 입력 시각(AudioContext 초)을 직전 클릭과 다음 예정 클릭 중 가까운 쪽과 비교해 기록
+    fn record_rhythm_hit(&mut self, time: f64) -> bool {
+        let last_click = match self.last_click_time {
+            Some(last_click) if self.rhythm_training && self.is_playing => last_click,
+            _ => return false,
+        };
+
+        let deviation = click_deviation_ms(time, last_click, self.click_time_ms());
+
+        if self.rhythm_hits.is_empty() {
+            self.rhythm_start_time = time;
+        }
+        let elapsed = time - self.rhythm_start_time;
+        self.rhythm_hits.push((elapsed, deviation));
+        console_log!("리듬 입력 편차: {:.1}ms", deviation);
+        true
+    }
+
+    // 클릭 간격 (밀리초, 타이머 인터벌과 같은 계산)
+    fn click_time_ms(&self) -> f64 {
+        let beat_time_ms = 60000 / self.bpm;
+        (beat_time_ms / self.note_unit.clicks_per_beat() as u32) as f64
+    }
+    
+    // 현재 설정이 담긴 공유 링크
+    fn share_url(&self) -> Option<AttrValue> {
+        let query = MetronomeQuery {
+            bpm: Some(self.bpm),
+            sig: Some(self.time_signature.display_str()),
+            unit: Some(self.note_unit.value().to_string()),
+            accent: Some(self.accent_enabled),
+        };
+        share_link::share_url(&Route::Metronome, &query).map(AttrValue::from)
+    }
+    
+    // 현재 설정을 저장소에 기록
+    fn save_settings(&self) {
+        let current = MetronomeSettings {
+            bpm: self.bpm,
+            time_signature: self.time_signature,
+            note_unit: self.note_unit,
+            accent: self.accent_enabled,
+        };
+        settings::update_settings(|s| s.metronome = current);
+    }
+    
+    // 이번 재생 구간을 연습 기록에 저장 (리듬 입력이 있었으면 리듬 훈련 결과도 저장)
+    fn save_practice(&self) {
+        let exercise = format!("{} {}", self.time_signature.display_str(), self.note_unit.display_str());
+        save_practice_entry(
+            PracticeEntry::new(PracticeTool::Metronome, self.play_started_at)
+                .finish_now()
+                .with_bpm(self.bpm)
+                .with_exercise(exercise.clone()),
+        );
+        
+        let session_hits = &self.rhythm_hits[self.play_start_hit_count.min(self.rhythm_hits.len())..];
+        if let Some(stats) = RhythmStats::from_hits(session_hits) {
+            save_practice_entry(
+                PracticeEntry::new(PracticeTool::RhythmTrainer, self.play_started_at)
+                    .finish_now()
+                    .with_bpm(self.bpm)
+                    .with_exercise(exercise)
+                    .with_score(stats.on_time_ratio * 100.0)
+                    .with_details(format!("{}회, 평균 {:+.0} ms, 표준편차 {:.0} ms", stats.count, stats.mean_ms, stats.std_dev_ms)),
+            );
+        }
+    }
+    
+    // 리듬 훈련용 마이크 해제
+    fn stop_rhythm_mic(&mut self) {
+        self.rhythm_mic_interval = None;
+        if let Some(mic) = self.rhythm_mic.take() {
+            mic.close();
+        }
+    }
+    
+    // 리듬 훈련 패널
+    fn view_rhythm_trainer(&self, ctx: &Context<Self>) -> Html {
+        if !self.rhythm_training {
+            return html! {};
+        }
+        
+        let last_hit = match self.rhythm_hits.last() {
+            Some((_, deviation)) if deviation.abs() <= RHYTHM_ON_TIME_MS => format!("{:+.0} ms 👍", deviation),
+            Some((_, deviation)) if *deviation > 0.0 => format!("{:+.0} ms (늦음)", deviation),
+            Some((_, deviation)) => format!("{:+.0} ms (빠름)", deviation),
+            None => "-".to_string(),
+        };
+        let stats = RhythmStats::from_hits(&self.rhythm_hits);
+        
+        html! {
+            <div class="rhythm-trainer">
+                <div class="rhythm-inputs">
+                    <div
+                        class="rhythm-tap-pad"
+                        onpointerdown={ctx.link().callback(|_: PointerEvent| MetronomeMsg::RhythmTap)}
+                    >
+                        {if self.is_playing { "👏 클릭에 맞춰 탭하거나 스페이스바를 누르세요" } else { "▶ 메트로놈을 시작하세요" }}
+                    </div>
+                    <button onclick={ctx.link().callback(|_| MetronomeMsg::ToggleRhythmMic)}>
+                        {if self.rhythm_mic.is_some() { "🎤 박수 감지 끄기" } else { "🎤 박수 감지 (이어폰 권장)" }}
+                    </button>
+                    <button onclick={ctx.link().callback(|_| MetronomeMsg::ResetRhythmStats)}>
+                        {"초기화"}
+                    </button>
+                </div>
+                {
+                    if let Some(err) = &self.rhythm_mic_error {
+                        html! { <div class="rhythm-error">{err}</div> }
+                    } else {
+                        html! {}
+                    }
+                }
+                <div class="rhythm-last-hit">{format!("마지막 입력: {}", last_hit)}</div>
+                <div class="rhythm-charts">
+                    <canvas ref={self.histogram_canvas_ref.clone()} width="400" height="150" style="width: 100%; height: auto;"></canvas>
+                    <canvas ref={self.drift_canvas_ref.clone()} width="400" height="150" style="width: 100%; height: auto;"></canvas>
+                </div>
+                {
+                    if let Some(stats) = stats {
+                        html! {
+                            <div class="rhythm-stats">
+                                <span>{format!("입력 {}회", stats.count)}</span>
+                                <span>{format!("평균 {:+.1} ms", stats.mean_ms)}</span>
+                                <span>{format!("평균 절대 편차 {:.1} ms", stats.mean_abs_ms)}</span>
+                                <span>{format!("일관성(표준편차) {:.1} ms", stats.std_dev_ms)}</span>
+                                <span>{format!("±{:.0} ms 이내 {:.0}%", RHYTHM_ON_TIME_MS, stats.on_time_ratio * 100.0)}</span>
+                                <span>{
+                                    match stats.drift_ms_per_min {
+                                        Some(drift) => format!("드리프트 {:+.1} ms/분", drift),
+                                        None => "드리프트 -".to_string(),
+                                    }
+                                }</span>
+                            </div>
+                        }
+                    } else {
+                        html! {}
+                    }
+                }
+            </div>
+        }
+    }
+    
+    // 리듬 훈련 히스토그램 및 드리프트 그래프 그리기
+    fn draw_rhythm_charts(&self) {
+        let background = "#0f1419";
+        let grid_color = "#3a3f4e";
+        let bar_color = "#8b9aff";
+        let text_color = "#a0a8c0";
+        
+        // 편차 분포 히스토그램
+        if let Some(canvas) = self.histogram_canvas_ref.cast::<HtmlCanvasElement>() {
+            let context = canvas
+                .get_context("2d")
+                .unwrap()
+                .unwrap()
+                .dyn_into::<web_sys::CanvasRenderingContext2d>()
+                .unwrap();
+            let width = canvas.width() as f64;
+            let height = canvas.height() as f64;
+            let plot_height = height - 20.0;
+            
+            context.set_fill_style_str(background);
+            context.fill_rect(0.0, 0.0, width, height);
+            
+            let bins = rhythm_histogram(&self.rhythm_hits);
+            let bin_count = bins.len();
+            let max_count = bins.iter().copied().max().unwrap_or(0).max(1) as f64;
+            
+            let bin_width = width / bin_count as f64;
+            for (i, count) in bins.iter().enumerate() {
+                let bar_height = *count as f64 / max_count * (plot_height - 10.0);
+                let center_ms = -RHYTHM_HISTOGRAM_RANGE_MS + (i as f64 + 0.5) * RHYTHM_HISTOGRAM_BIN_MS;
+                context.set_global_alpha(if center_ms.abs() <= RHYTHM_ON_TIME_MS { 1.0 } else { 0.6 });
+                context.set_fill_style_str(bar_color);
+                context.fill_rect(i as f64 * bin_width + 1.0, plot_height - bar_height, bin_width - 2.0, bar_height);
+            }
+            context.set_global_alpha(1.0);
+            
+            // 0ms 기준선과 축 라벨
+            context.set_stroke_style_str(grid_color);
+            context.begin_path();
+            context.move_to(width / 2.0, 0.0);
+            context.line_to(width / 2.0, plot_height);
+            context.stroke();
+            
+            context.set_fill_style_str(text_color);
+            context.set_font("11px sans-serif");
+            let _ = context.fill_text(&format!("-{:.0}ms 빠름", RHYTHM_HISTOGRAM_RANGE_MS), 2.0, height - 5.0);
+            let _ = context.fill_text("0", width / 2.0 - 3.0, height - 5.0);
+            let _ = context.fill_text(&format!("늦음 +{:.0}ms", RHYTHM_HISTOGRAM_RANGE_MS), width - 80.0, height - 5.0);
+        }
+        
+        // 입력 순서에 따른 편차 변화 (드리프트)
+        if let Some(canvas) = self.drift_canvas_ref.cast::<HtmlCanvasElement>() {
+            let context = canvas
+                .get_context("2d")
+                .unwrap()
+                .unwrap()
+                .dyn_into::<web_sys::CanvasRenderingContext2d>()
+                .unwrap();
+            let width = canvas.width() as f64;
+            let height = canvas.height() as f64;
+            let center_y = height / 2.0;
+            let scale = (height / 2.0 - 10.0) / RHYTHM_HISTOGRAM_RANGE_MS;
+            
+            context.set_fill_style_str(background);
+            context.fill_rect(0.0, 0.0, width, height);
+            
+            // 정확한 타이밍 범위 표시
+            context.set_fill_style_str(grid_color);
+            context.set_global_alpha(0.4);
+            context.fill_rect(0.0, center_y - RHYTHM_ON_TIME_MS * scale, width, RHYTHM_ON_TIME_MS * 2.0 * scale);
+            context.set_global_alpha(1.0);
+            
+            context.set_stroke_style_str(grid_color);
+            context.begin_path();
+            context.move_to(0.0, center_y);
+            context.line_to(width, center_y);
+            context.stroke();
+            
+            let y_of = |deviation: f64| {
+                let clamped = deviation.clamp(-RHYTHM_HISTOGRAM_RANGE_MS, RHYTHM_HISTOGRAM_RANGE_MS);
+                center_y + clamped * scale
+            };
+            
+            if self.rhythm_hits.len() > 1 {
+                let step = width / (self.rhythm_hits.len() - 1) as f64;
+                
+                // 각 입력의 편차
+                context.set_fill_style_str(bar_color);
+                for (i, (_, deviation)) in self.rhythm_hits.iter().enumerate() {
+                    context.begin_path();
+                    context.arc(i as f64 * step, y_of(*deviation), 2.5, 0.0, std::f64::consts::PI * 2.0).unwrap();
+                    context.fill();
+                }
+                
+                // 최근 4회 이동 평균 (흐름)
+                context.set_stroke_style_str("#40e0d0");
+                context.set_line_width(2.0);
+                context.begin_path();
+                for i in 0..self.rhythm_hits.len() {
+                    let window = &self.rhythm_hits[i.saturating_sub(3)..=i];
+                    let average = window.iter().map(|(_, d)| d).sum::<f64>() / window.len() as f64;
+                    if i == 0 {
+                        context.move_to(0.0, y_of(average));
+                    } else {
+                        context.line_to(i as f64 * step, y_of(average));
+                    }
+                }
+                context.stroke();
+                context.set_line_width(1.0);
+            }
+            
+            context.set_fill_style_str(text_color);
+            context.set_font("11px sans-serif");
+            let _ = context.fill_text("늦음 ↓", 2.0, height - 5.0);
+            let _ = context.fill_text("빠름 ↑", 2.0, 12.0);
+        }
+    }
+    
     // 메트로놈 시각화 그리기
     fn draw_metronome(&self) {
         if let Some(canvas) = self.canvas_ref.cast::<HtmlCanvasElement>() {
@@ -916,58 +1458,198 @@ impl Metronome {
         }
     }
     
-    // 클릭 소리 재생
-    fn play_click(&self, is_primary: bool) {
-        // 오디오 컨텍스트가 없으면 재생하지 않음
-        if let Some(audio_ctx) = &self.audio_ctx {
-            // 오실레이터 노드 생성
-            if let Ok(oscillator) = audio_ctx.create_oscillator() {
-                // 주 박자와 나머지 박자의 주파수 다르게 설정
-                if is_primary && self.accent_enabled {
-                    oscillator.frequency().set_value(1200.0); // 1200Hz (첫 박자용 더 높은 소리)
-                } else {
-                    oscillator.frequency().set_value(800.0);  // 800Hz (일반 박자용)
-                }
-                
-                // 게인 노드 생성 (볼륨 제어)
-                if let Ok(gain) = audio_ctx.create_gain() {
-                    // 오실레이터를 게인 노드에 연결
-                    oscillator.connect_with_audio_node(&gain).unwrap();
-                    
-                    // 게인 노드를 출력에 연결
-                    gain.connect_with_audio_node(&audio_ctx.destination()).unwrap();
-                    
-                    // 볼륨 설정 (첫 박자는 조금 더 크게)
-                    if is_primary && self.accent_enabled {
-                        gain.gain().set_value(0.3); // 첫 박자는 더 크게
-                    } else {
-                        gain.gain().set_value(0.2); // 일반 박자는 약간 작게
-                    }
-                    
-                    // 현재 시간 가져오기
-                    let current_time = audio_ctx.current_time();
-                    
-                    // 소리 길이 설정 (첫 박자는 조금 더 길게)
-                    let duration = if is_primary && self.accent_enabled {
-                        0.05 // 첫 박자는 50ms로 길게
-                    } else {
-                        0.03 // 일반 박자는 30ms
-                    };
-                    
-                    // 게인 엔벨로프 설정 (빠른 어택, 빠른 릴리즈)
-                    gain.gain().set_value_at_time(0.0, current_time).unwrap();
-                    gain.gain().linear_ramp_to_value_at_time(if is_primary && self.accent_enabled { 0.3 } else { 0.2 }, current_time + 0.005).unwrap();
-                    gain.gain().exponential_ramp_to_value_at_time(0.001, current_time + duration).unwrap();
-                    
-                    // 오실레이터 시작 및 중지 스케줄링
-                    oscillator.start().unwrap();
-                    oscillator.stop_with_when(current_time + duration).unwrap();
-                }
+    // 클릭 소리 재생 - 예약한 AudioContext 시각을 리듬 훈련 기준으로 기록 (소리를 꺼도 기준은 유지)
+    fn play_click(&mut self, is_primary: bool) {
+        // 공유 컨텍스트의 메트로놈 채널로 출력
+        if let Ok(audio_ctx) = self.engine.context() {
+            let when = audio_ctx.current_time();
+            self.last_click_time = Some(when);
+            if !self.sound_enabled {
+                return;
+            }
+            if let Err(err) = schedule_click(&self.engine, when, is_primary && self.accent_enabled) {
+                console_error!("클릭 재생 실패: {:?}", err);
             }
-        } else if self.sound_enabled && self.is_playing {
-            // 오디오 컨텍스트가 없지만 소리가 활성화되어 있고 재생 중이라면
-            // 오디오 컨텍스트 생성을 시도하는 대신 경고 메시지만 출력
+        } else if self.is_playing {
+            // 오디오 엔진을 사용할 수 없으면 경고 메시지만 출력
             console_warn!("오디오 컨텍스트가 없어 소리를 재생할 수 없습니다.");
         }
     }
-} 
\ No newline at end of file
+}
+
+// 클릭 한 번을 when(AudioContext 시간)에 메트로놈 채널로 예약 (강박은 높고 길게)
+pub fn schedule_click(engine: &AudioEngine, when: f64, accented: bool) -> Result<web_sys::OscillatorNode, JsValue> {
+    let audio_ctx = engine.context()?;
+    let output = engine.channel_input(Channel::Metronome)?;
+    schedule_click_on(&audio_ctx, &output, when, accented)
+}
+
+// 클릭 한 번을 when에 지정한 노드로 예약 (채널/마스터 게인을 거치지 않을 때 사용)
+pub fn schedule_click_on(
+    audio_ctx: &web_sys::AudioContext,
+    output: &web_sys::AudioNode,
+    when: f64,
+    accented: bool,
+) -> Result<web_sys::OscillatorNode, JsValue> {
+    let oscillator = audio_ctx.create_oscillator()?;
+    oscillator.frequency().set_value(if accented { 1200.0 } else { 800.0 });
+
+    let gain = audio_ctx.create_gain()?;
+    oscillator.connect_with_audio_node(&gain)?;
+    gain.connect_with_audio_node(output)?;
+
+    // 게인 엔벨로프 (빠른 어택, 빠른 릴리즈)
+    let (volume, duration) = if accented { (0.3, 0.05) } else { (0.2, 0.03) };
+    gain.gain().set_value_at_time(0.0, when)?;
+    gain.gain().linear_ramp_to_value_at_time(volume, when + 0.005)?;
+    gain.gain().exponential_ramp_to_value_at_time(0.001, when + duration)?;
+
+    oscillator.start_with_when(when)?;
+    oscillator.stop_with_when(when + duration)?;
+    Ok(oscillator)
+}
+
+// 녹음 전 카운트인 - 예약된 클릭과 녹음을 시작할 다운비트 시각
+pub struct CountIn {
+    pub downbeat: f64, // AudioContext 시간
+    pub grid: BeatGrid,
+    clicks: Vec<web_sys::OscillatorNode>,
+    cancelled: Rc<Cell<bool>>,
+}
+
+impl CountIn {
+    // 기다리는 쪽에서 취소 여부를 확인하는 플래그
+    pub fn cancelled_flag(&self) -> Rc<Cell<bool>> {
+        self.cancelled.clone()
+    }
+
+    // 아직 울리지 않은 클릭을 멈추고 녹음 시작을 취소
+    pub fn cancel(&self) {
+        self.cancelled.set(true);
+        for click in &self.clicks {
+            let _ = click.stop();
+        }
+    }
+}
+
+// 저장된 메트로놈 BPM/박자로 bars 마디 카운트인 예약 (첫 박은 강박)
+pub fn schedule_count_in(engine: &AudioEngine, metronome: &MetronomeSettings, bars: u32) -> Result<CountIn, JsValue> {
+    let grid = BeatGrid {
+        bpm: metronome.bpm as f64,
+        beats_per_bar: metronome.time_signature.beats_per_measure() as u32,
+    };
+    // 예약이 밀리지 않도록 약간 뒤에서 시작
+    let start = engine.context()?.current_time() + 0.1;
+    let clicks = (0..bars * grid.beats_per_bar)
+        .map(|beat| {
+            let accented = metronome.accent && beat % grid.beats_per_bar == 0;
+            schedule_click(engine, start + beat as f64 * grid.beat_secs(), accented)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    console_log!("카운트인 {}마디 ({} BPM, {}박)", bars, grid.bpm, grid.beats_per_bar);
+    Ok(CountIn {
+        downbeat: start + (bars * grid.beats_per_bar) as f64 * grid.beat_secs(),
+        grid,
+        clicks,
+        cancelled: Rc::new(Cell::new(false)),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CLICK_MS: f64 = 500.0; // 120 BPM 4분음표
+
+    // 클릭이 0초부터 CLICK_MS 간격으로 울릴 때 각 입력 시각의 (세션 시간, 편차) 목록
+    fn hits_at(times: &[f64]) -> Vec<(f64, f64)> {
+        times
+            .iter()
+            .map(|time| {
+                let last_click = (time * 1000.0 / CLICK_MS).floor() * CLICK_MS / 1000.0;
+                (*time, click_deviation_ms(*time, last_click, CLICK_MS))
+            })
+            .collect()
+    }
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!((actual - expected).abs() < 1e-6, "{} != {}", actual, expected);
+    }
+
+    #[test]
+    fn deviation_is_measured_against_the_nearest_click() {
+        assert_close(click_deviation_ms(1.015, 1.0, CLICK_MS), 15.0);
+        // 다음 클릭 30ms 전이면 직전 클릭에 470ms 늦은 것이 아니라 30ms 빠름
+        assert_close(click_deviation_ms(1.470, 1.0, CLICK_MS), -30.0);
+        assert_close(click_deviation_ms(1.0, 1.0, CLICK_MS), 0.0);
+    }
+
+    #[test]
+    fn tap_halfway_between_clicks_counts_as_late() {
+        assert_close(click_deviation_ms(1.25, 1.0, CLICK_MS), 250.0);
+        // 정가운데를 조금만 넘어도 다음 클릭에 빠른 것
+        assert_close(click_deviation_ms(1.251, 1.0, CLICK_MS), -249.0);
+    }
+
+    #[test]
+    fn stats_report_early_and_late_tendency() {
+        let late = RhythmStats::from_hits(&hits_at(&[0.010, 0.510, 1.010, 1.510])).unwrap();
+        assert_eq!(late.count, 4);
+        assert_close(late.mean_ms, 10.0);
+        assert_close(late.mean_abs_ms, 10.0);
+
+        let early = RhythmStats::from_hits(&hits_at(&[0.480, 0.980, 1.480])).unwrap();
+        assert_close(early.mean_ms, -20.0);
+        assert_close(early.mean_abs_ms, 20.0);
+
+        // 빠름과 늦음이 섞이면 평균은 상쇄되지만 절대 편차는 남음
+        let mixed = RhythmStats::from_hits(&hits_at(&[0.030, 0.470])).unwrap();
+        assert_close(mixed.mean_ms, 0.0);
+        assert_close(mixed.mean_abs_ms, 30.0);
+        assert!(RhythmStats::from_hits(&[]).is_none());
+    }
+
+    #[test]
+    fn stats_report_consistency_and_on_time_ratio() {
+        // 항상 같은 만큼 늦으면 편차는 커도 일관성은 완벽
+        let steady = RhythmStats::from_hits(&hits_at(&[0.040, 0.540, 1.040, 1.540])).unwrap();
+        assert_close(steady.std_dev_ms, 0.0);
+        assert_close(steady.on_time_ratio, 0.0);
+
+        // 편차 -10, +10, -30, +30 -> 표준편차 sqrt((100+100+900+900)/4)
+        let loose = RhythmStats::from_hits(&hits_at(&[0.490, 0.510, 0.970, 1.530])).unwrap();
+        assert_close(loose.std_dev_ms, 500f64.sqrt());
+        assert_close(loose.on_time_ratio, 0.5);
+
+        // 경계값(±RHYTHM_ON_TIME_MS)은 정확한 타이밍에 포함
+        let edge = RhythmStats::from_hits(&[(0.0, RHYTHM_ON_TIME_MS), (1.0, -RHYTHM_ON_TIME_MS)]).unwrap();
+        assert_close(edge.on_time_ratio, 1.0);
+    }
+
+    #[test]
+    fn drift_needs_four_hits() {
+        let few = RhythmStats::from_hits(&hits_at(&[0.000, 0.505, 1.010])).unwrap();
+        assert!(few.drift_ms_per_min.is_none());
+
+        // 1초에 10ms씩 늦어짐 -> 분당 600ms
+        let dragging = RhythmStats::from_hits(&[(0.0, 0.0), (1.0, 10.0), (2.0, 20.0), (3.0, 30.0)]).unwrap();
+        assert_close(dragging.drift_ms_per_min.unwrap(), 600.0);
+    }
+
+    #[test]
+    fn histogram_bins_deviations_and_clamps_outliers() {
+        let bins = rhythm_histogram(&hits_at(&[0.003, 0.495, 1.012, 1.25, 1.751]));
+        let bin_count = (RHYTHM_HISTOGRAM_RANGE_MS * 2.0 / RHYTHM_HISTOGRAM_BIN_MS) as usize;
+        assert_eq!(bins.len(), bin_count);
+        assert_eq!(bins.iter().sum::<usize>(), 5);
+
+        let bin_of = |ms: f64| ((ms + RHYTHM_HISTOGRAM_RANGE_MS) / RHYTHM_HISTOGRAM_BIN_MS) as usize;
+        assert_eq!(bins[bin_of(3.0)], 1);  // +3ms -> 0~10ms 구간
+        assert_eq!(bins[bin_of(-5.0)], 1); // -5ms -> -10~0ms 구간
+        assert_eq!(bins[bin_of(12.0)], 1); // +12ms -> 10~20ms 구간
+        // 정가운데(+250ms)와 -249ms는 범위 밖이라 양 끝 구간
+        assert_eq!(bins[bin_count - 1], 1);
+        assert_eq!(bins[0], 1);
+    }
+}
@@ -0,0 +1,269 @@
+use std::rc::Rc;
+
+use wasm_bindgen::prelude::*;
+use web_sys::{GainNode, HtmlAudioElement, HtmlInputElement, HtmlSelectElement};
+use yew::prelude::*;
+
+use crate::audio_engine::{ramp_gain, AudioEngine, Channel};
+use crate::tools::take_library::{PitchHistory, Take, TakeSummary};
+
+// 겹쳐 녹음(오버더빙) - 이전 테이크를 반주 트랙으로 들으며 새 파트를 녹음
+// 트랙은 모두 0초에서 시작하므로 현재 테이크와 같은 시간축을 공유함
+// 테이크 오디오는 녹음 당시 왕복 지연만큼 늦게 담겨 있으므로 (오디오 시각 - 지연)을 공통 시간축으로 씀
+// 피치 기록은 저장할 때 이미 지연을 빼 두었으므로 그대로 공통 시간축임
+
+// 트랙별 피치 선 색 (현재 테이크는 피치 차트의 기본 색)
+pub const CURRENT_TRACK_COLOR: (u8, u8, u8) = (186, 85, 211); // #ba55d3
+pub const TRACK_COLORS: [(u8, u8, u8); 4] = [
+    (255, 159, 67), // #ff9f43 주황
+    (46, 204, 113), // #2ecc71 초록
+    (52, 152, 219), // #3498db 파랑
+    (241, 196, 15), // #f1c40f 노랑
+];
+
+// 재생 중 이보다 어긋나면 현재 테이크 위치로 다시 맞춤 (초)
+const MAX_DRIFT_SECS: f64 = 0.05;
+
+// 트랙 구분 (현재 테이크 / 반주로 겹친 테이크)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrackKey {
+    Current,
+    Overdub(u64),
+}
+
+// 트랙 볼륨/뮤트/솔로
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TrackMix {
+    pub gain: f32,
+    pub muted: bool,
+    pub solo: bool,
+}
+
+impl Default for TrackMix {
+    fn default() -> Self {
+        Self { gain: 1.0, muted: false, solo: false }
+    }
+}
+
+impl TrackMix {
+    // 솔로 트랙이 하나라도 있으면 솔로가 아닌 트랙은 무음
+    pub fn effective_gain(&self, any_solo: bool) -> f32 {
+        if self.muted || (any_solo && !self.solo) {
+            0.0
+        } else {
+            self.gain
+        }
+    }
+}
+
+// 피치 차트에 겹쳐 그릴 트랙의 피치 이력
+#[derive(Clone, PartialEq)]
+pub struct PitchTrace {
+    pub history: Rc<PitchHistory>,
+    pub color: (u8, u8, u8),
+}
+
+// 반주로 재생하는 이전 테이크
+pub struct OverdubTrack {
+    pub take_id: u64,
+    pub name: String,
+    pub color: (u8, u8, u8),
+    pub mix: TrackMix,
+    pub history: Rc<PitchHistory>,
+    latency: f64, // 이 테이크 녹음 파일의 정렬 지연 (초)
+    audio: HtmlAudioElement,
+    gain_node: GainNode,
+}
+
+impl OverdubTrack {
+    // 테이크 오디오를 녹음 재생 채널에 트랙 게인을 거쳐 연결
+    pub fn open(engine: &AudioEngine, take: &Take, color: (u8, u8, u8)) -> Result<Self, JsValue> {
+        let audio = HtmlAudioElement::new_with_src(&take.audio_url)?;
+        audio.set_preload("auto");
+        let gain_node = engine.connect_media_element_with_gain(Channel::Playback, &audio)?;
+
+        Ok(Self {
+            take_id: take.id,
+            name: take.name.clone(),
+            color,
+            mix: TrackMix::default(),
+            history: Rc::new(take.history.clone()),
+            latency: take.latency,
+            audio,
+            gain_node,
+        })
+    }
+
+    pub fn audio(&self) -> &HtmlAudioElement {
+        &self.audio
+    }
+
+    // 공통 시간축의 time 위치부터 재생 (트랙이 더 짧으면 끝난 상태로 둠)
+    pub fn play_from(&self, time: f64) {
+        let time = (time + self.latency).max(0.0);
+        let duration = self.audio.duration();
+        if duration.is_finite() && time >= duration {
+            let _ = self.audio.pause();
+            return;
+        }
+        self.audio.set_current_time(time);
+        let _ = self.audio.play();
+    }
+
+    pub fn pause(&self) {
+        let _ = self.audio.pause();
+    }
+
+    // 재생 중 현재 테이크와 어긋났으면 맞춤 (time은 공통 시간축)
+    pub fn resync(&self, time: f64) {
+        let time = (time + self.latency).max(0.0);
+        if !self.audio.paused() && (self.audio.current_time() - time).abs() > MAX_DRIFT_SECS {
+            self.audio.set_current_time(time);
+        }
+    }
+
+    pub fn apply_gain(&self, any_solo: bool) {
+        ramp_gain(&self.gain_node, self.mix.effective_gain(any_solo));
+    }
+
+    pub fn trace(&self) -> PitchTrace {
+        PitchTrace { history: self.history.clone(), color: self.color }
+    }
+
+    // 트랙 제거 시 재생을 멈추고 그래프에서 분리
+    pub fn close(&self) {
+        let _ = self.audio.pause();
+        let _ = self.gain_node.disconnect();
+        self.audio.set_src("");
+    }
+}
+
+// 아직 쓰이지 않은 트랙 색
+pub fn next_track_color(tracks: &[OverdubTrack]) -> (u8, u8, u8) {
+    TRACK_COLORS
+        .into_iter()
+        .find(|color| tracks.iter().all(|track| track.color != *color))
+        .unwrap_or(TRACK_COLORS[tracks.len() % TRACK_COLORS.len()])
+}
+
+// 믹서 패널 표시용 트랙 정보
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrackRow {
+    pub key: TrackKey,
+    pub name: String,
+    pub color: (u8, u8, u8),
+    pub mix: TrackMix,
+}
+
+#[derive(Properties, PartialEq)]
+pub struct OverdubPanelProps {
+    pub rows: Vec<TrackRow>,          // 첫 행은 현재 테이크 (있을 때)
+    pub candidates: Vec<TakeSummary>, // 반주 트랙으로 추가할 수 있는 테이크
+    pub can_add: bool,                // 트랙 수 제한 전인지
+    pub is_recording: bool,
+    pub on_add: Callback<u64>,
+    pub on_remove: Callback<u64>,
+    pub on_gain: Callback<(TrackKey, f32)>,
+    pub on_toggle_mute: Callback<TrackKey>,
+    pub on_toggle_solo: Callback<TrackKey>,
+}
+
+// 겹쳐 녹음 트랙 목록 (트랙별 볼륨/뮤트/솔로)
+#[function_component(OverdubPanel)]
+pub fn overdub_panel(props: &OverdubPanelProps) -> Html {
+    let on_add_change = {
+        let on_add = props.on_add.clone();
+        Callback::from(move |e: Event| {
+            let select: HtmlSelectElement = e.target_unchecked_into();
+            if let Ok(id) = select.value().parse::<u64>() {
+                on_add.emit(id);
+            }
+            select.set_value("");
+        })
+    };
+
+    let rows = props
+        .rows
+        .iter()
+        .map(|row| {
+            let key = row.key;
+            let (r, g, b) = row.color;
+            let on_gain_input = {
+                let on_gain = props.on_gain.clone();
+                Callback::from(move |e: InputEvent| {
+                    let input: HtmlInputElement = e.target_unchecked_into();
+                    on_gain.emit((key, input.value().parse::<f32>().unwrap_or(100.0) / 100.0));
+                })
+            };
+            let on_mute = props.on_toggle_mute.reform(move |_: MouseEvent| key);
+            let on_solo = props.on_toggle_solo.reform(move |_: MouseEvent| key);
+
+            html! {
+                <li class={classes!("overdub-track", row.mix.muted.then_some("muted"))}>
+                    <span class="overdub-color" style={format!("background-color: rgb({}, {}, {});", r, g, b)}></span>
+                    <span class="overdub-name">{ &row.name }</span>
+                    <input
+                        type="range"
+                        min="0"
+                        max="100"
+                        value={((row.mix.gain * 100.0).round() as u32).to_string()}
+                        oninput={on_gain_input}
+                    />
+                    <button
+                        class={classes!("overdub-toggle", row.mix.muted.then_some("active"))}
+                        onclick={on_mute}
+                        title="뮤트"
+                    >
+                        { "M" }
+                    </button>
+                    <button
+                        class={classes!("overdub-toggle", "solo", row.mix.solo.then_some("active"))}
+                        onclick={on_solo}
+                        title="솔로"
+                    >
+                        { "S" }
+                    </button>
+                    {
+                        match key {
+                            TrackKey::Overdub(id) => html! {
+                                <button
+                                    class="overdub-remove"
+                                    onclick={props.on_remove.reform(move |_: MouseEvent| id)}
+                                    disabled={props.is_recording}
+                                    title="트랙 빼기"
+                                >
+                                    { "✕" }
+                                </button>
+                            },
+                            TrackKey::Current => html! { <span class="overdub-remove-placeholder"></span> },
+                        }
+                    }
+                </li>
+            }
+        })
+        .collect::<Html>();
+
+    html! {
+        <div class="overdub-panel">
+            <div class="overdub-header">
+                <span class="overdub-title">{ "🎧 겹쳐 녹음" }</span>
+                <select
+                    class="overdub-add"
+                    onchange={on_add_change}
+                    disabled={props.is_recording || !props.can_add || props.candidates.is_empty()}
+                >
+                    <option value="" selected=true>{ "+ 반주 트랙 추가" }</option>
+                    { for props.candidates.iter().map(|take| html! {
+                        <option value={take.id.to_string()}>{ &take.name }</option>
+                    }) }
+                </select>
+            </div>
+            if props.rows.len() > 1 {
+                <ul class="overdub-tracks">{ rows }</ul>
+                <p class="overdub-hint">{ "녹음하면 반주 트랙이 0초부터 함께 재생됩니다. 이어폰을 쓰면 반주가 마이크에 섞이지 않습니다." }</p>
+            } else {
+                <p class="overdub-hint">{ "이전 테이크를 반주 트랙으로 추가하면 들으면서 다음 파트를 녹음할 수 있습니다." }</p>
+            }
+        </div>
+    }
+}
@@ -1,14 +1,16 @@
-use wasm_bindgen::prelude::*;
 use wasm_bindgen::JsCast;
-use web_sys::{AudioContext, AudioNode, AudioParam, GainNode, HtmlAudioElement, KeyboardEvent, Document, HtmlElement, MouseEvent, TouchEvent};
+use web_sys::{GainNode, HtmlAudioElement, KeyboardEvent};
 use yew::prelude::*;
 use std::collections::HashMap;
-use std::rc::Rc;
 use gloo_timers::callback::Timeout;
 use wasm_bindgen::closure::Closure;
-use web_sys::console;
 use js_sys;
 
+use crate::audio_engine::{engine_from_scope, ramp_gain, ramp_param, AudioEngine, Channel, GAIN_RAMP_SECONDS};
+use crate::event_bus::{subscribe, AppEvent, Subscription};
+use crate::practice_log::{save_practice_entry, PracticeEntry, PracticeTool};
+use crate::settings::{self, PianoSettings};
+
 // 조건부 로그 매크로 정의
 #[cfg(debug_assertions)]
 macro_rules! console_log {
@@ -91,6 +93,55 @@ struct KeyMapping {
 // 노트 이름 인덱스 (C = 0, C# = 1, ... B = 11)
 const NOTE_NAMES: [&str; 12] = ["C", "C#", "D", "D#", "E", "F", "F#", "G", "G#", "A", "A#", "B"];
 
+// 이 시간 이상 연주하지 않으면 새 연습 세션으로 기록
+const PRACTICE_IDLE_GAP_MS: f64 = 5.0 * 60.0 * 1000.0;
+
+// 이전 소리를 줄여 없애는 시간 (초)
+const FADE_OUT_SECONDS: f64 = 0.25;
+
+// 재생 중인 건반 소리 - 오디오 요소와 피아노 채널로 가는 전용 게인
+// 볼륨은 게인 램프로만 바꾸고, 멈출 때도 짧게 줄인 뒤 정지해 딸깍 소리를 없앰
+pub struct ActiveSound {
+    audio: HtmlAudioElement,
+    gain: Option<GainNode>, // 채널 연결에 실패하면 None (요소 볼륨으로만 재생)
+}
+
+impl ActiveSound {
+    fn connect(engine: &AudioEngine, audio: &HtmlAudioElement) -> Self {
+        let gain = match engine.connect_media_element_with_gain(Channel::Piano, audio) {
+            Ok(gain) => Some(gain),
+            Err(err) => {
+                console_error!("피아노 채널 연결 실패: {:?}", err);
+                None
+            }
+        };
+        Self { audio: audio.clone(), gain }
+    }
+
+    fn fade_out(&self) {
+        if let Some(gain) = &self.gain {
+            ramp_param(&gain.context(), &gain.gain(), 0.0, FADE_OUT_SECONDS);
+        }
+    }
+
+    fn stop(self) {
+        let Self { audio, gain } = self;
+        let Some(gain) = gain else {
+            let _ = audio.pause();
+            audio.set_current_time(0.0);
+            return;
+        };
+
+        ramp_gain(&gain, 0.0);
+        let timeout = Timeout::new((GAIN_RAMP_SECONDS * 1000.0) as u32 + 10, move || {
+            let _ = audio.pause();
+            audio.set_current_time(0.0);
+            let _ = gain.disconnect();
+        });
+        timeout.forget();
+    }
+}
+
 // 피아노 컴포넌트 메시지에 키보드 이벤트 추가
 pub enum PianoMsg {
     KeyPressed(usize),              // 키가 눌렸을 때
@@ -121,18 +172,19 @@ pub enum PianoMsg {
     RemoveSetSound(usize, usize),   // 특정 세트의 특정 키 소리 제거
     StopSetSoundsIfReleased(usize),  // 세트의 모든 키가 눌려있지 않고 서스테인이 꺼져 있을 때만 소리 정지
     StopSetKeySound(usize, usize),   // 특정 세트의 특정 키 소리 정지
-    AddActiveSound(String, HtmlAudioElement), // 활성 소리 추가
+    AddActiveSound(String, ActiveSound), // 활성 소리 추가
     RemoveActiveSound(String),        // 활성 소리 제거
-    FadeOutSound(String, f64),      // 특정 소리를 서서히 페이드아웃 (소리 이름, 현재 볼륨)
+    FadeOutSound(String),           // 특정 소리를 서서히 페이드아웃
+    SettingsReset,                  // 저장된 설정이 기본값으로 초기화됨
 }
 
 // 피아노 컴포넌트
 pub struct PianoKeyboard {
     keys: Vec<PianoKey>,            // 모든 피아노 키
-    active_sounds: HashMap<String, HtmlAudioElement>, // 현재 재생 중인 소리
+    active_sounds: HashMap<String, ActiveSound>, // 현재 재생 중인 소리
     sustain: bool,                  // 서스테인 상태
     start_octave: i32,              // 표시할 시작 옥타브
-    audio_ctx: Option<AudioContext>, // 오디오 컨텍스트
+    engine: AudioEngine, // 공유 오디오 엔진 (피아노 채널로 출력)
     key_mappings: Vec<KeyMapping>,  // 키보드 매핑 정보
     left_hand_octave: i32,          // 왼손 옥타브 (기본 C2-C3)
     right_hand_octave: i32,         // 오른손 옥타브 (기본 C4-C5)
@@ -152,13 +204,20 @@ pub struct PianoKeyboard {
     set_edit_mode: bool,            // 세트 수정 모드 활성화 여부
     current_edit_set: Option<usize>, // 현재 수정 중인 세트 인덱스
     active_set: Option<usize>,      // 현재 활성화된 세트 인덱스
+    practice_started_at: f64,       // 현재 연습 세션 시작 시각 (0이면 세션 없음)
+    last_played_at: f64,            // 마지막으로 건반을 누른 시각
+    notes_played: u32,              // 현재 세션에서 누른 건반 수
+    _settings_subscription: Subscription, // 설정 초기화 이벤트 구독
 }
 
 impl Component for PianoKeyboard {
     type Message = PianoMsg;
     type Properties = ();
 
-    fn create(_ctx: &Context<Self>) -> Self {
+    fn create(ctx: &Context<Self>) -> Self {
+        // 저장된 손 위치 설정 불러오기
+        let saved = settings::load_settings().piano;
+        
         // 모든 88개 키 생성 (A0-C8)
         let mut keys = Vec::new();
         let note_names = ["C", "C#", "D", "D#", "E", "F", "F#", "G", "G#", "A", "A#", "B"];
@@ -194,9 +253,9 @@ impl Component for PianoKeyboard {
         // 오른손 키 매핑 (C4-C5 기본 옥타브)
         let right_hand_keys = ["m", ",", ".", "/", "j", "k", "l", ";", "u", "i", "o", "p", "["];
         
-        // 초기 매핑 생성
-        let left_hand_start_note_idx = 0; // C로 시작
-        let right_hand_start_note_idx = 0; // C로 시작
+        // 초기 매핑 생성 (기본값은 양손 모두 C로 시작)
+        let left_hand_start_note_idx = saved.left_hand_start_note_idx;
+        let right_hand_start_note_idx = saved.right_hand_start_note_idx;
         
         Self::create_key_mappings(
             &mut key_mappings, 
@@ -236,6 +295,14 @@ impl Component for PianoKeyboard {
         pressed_keyboard_keys.insert("0".to_string(), false); // 10번 세트
         pressed_keyboard_keys.insert("`".to_string(), false); // 수정 모드 토글
 
+        // 설정 초기화 이벤트 리스너
+        let link = ctx.link().clone();
+        let settings_subscription = subscribe(move |event| {
+            if let AppEvent::SettingsReset = event {
+                link.send_message(PianoMsg::SettingsReset);
+            }
+        });
+
         // 피아노 세트 초기화 (10개의 빈 세트)
         let mut piano_sets = Vec::new();
         for _ in 0..10 {
@@ -247,10 +314,10 @@ impl Component for PianoKeyboard {
             active_sounds: HashMap::new(),
             sustain: false,
             start_octave: 2, // 기본 시작 옥타브는 2
-            audio_ctx: None,
+            engine: engine_from_scope(ctx.link()),
             key_mappings,
-            left_hand_octave: 2,
-            right_hand_octave: 4,
+            left_hand_octave: saved.left_hand_octave,
+            right_hand_octave: saved.right_hand_octave,
             left_hand_start_note_idx,
             right_hand_start_note_idx,
             pressed_keyboard_keys,
@@ -260,6 +327,10 @@ impl Component for PianoKeyboard {
             set_edit_mode: false,
             current_edit_set: None,
             active_set: None,
+            practice_started_at: 0.0,
+            last_played_at: 0.0,
+            notes_played: 0,
+            _settings_subscription: settings_subscription,
         }
     }
 
@@ -268,6 +339,7 @@ impl Component for PianoKeyboard {
             PianoMsg::KeyPressed(index) => {
                 if index < self.keys.len() {
                     self.keys[index].is_pressed = true;
+                    self.track_practice();
                     
                     // 동일한 키에 대한 이전 소리 제거 (연타 방지를 위함)
                     let key_base_name = self.keys[index].full_name();
@@ -279,14 +351,13 @@ impl Component for PianoKeyboard {
                     
                     // 기존 소리를 중지하지 않고 페이드아웃하도록 변경
                     for key_name in existing_sounds {
-                        if let Some(audio) = self.active_sounds.get(&key_name) {
-                            // 현재 볼륨 값을 가져와 페이드아웃 시작
-                            let current_volume = audio.volume();
+                        if self.active_sounds.contains_key(&key_name) {
+                            // 페이드아웃 시작
                             let key_name_clone = key_name.clone();
                             let link = ctx.link().clone();
                             
                             // 페이드아웃 메시지 전송
-                            link.send_message(PianoMsg::FadeOutSound(key_name_clone, current_volume));
+                            link.send_message(PianoMsg::FadeOutSound(key_name_clone));
                         }
                     }
                     
@@ -294,6 +365,7 @@ impl Component for PianoKeyboard {
                     let audio_path = self.keys[index].audio_path();
                     let key_full_name = self.keys[index].full_name();
                     let link = ctx.link().clone();
+                    let engine = self.engine.clone();
                     
                     // 10ms 지연 후 새 오디오 생성 및 재생
                     let timeout = Timeout::new(10, move || {
@@ -301,6 +373,7 @@ impl Component for PianoKeyboard {
                         if let Ok(audio) = HtmlAudioElement::new_with_src(&audio_path) {
                             // 볼륨 설정
                             audio.set_volume(0.7);
+                            let sound = ActiveSound::connect(&engine, &audio);
                             
                             // 시작 위치 리셋
                             audio.set_current_time(0.0);
@@ -314,10 +387,9 @@ impl Component for PianoKeyboard {
                             // 먼저 재생하려면 타임스탬프 지연이 중요함
                             let play_link = link.clone();
                             let key_name_clone = key_name.clone();
-                            let audio_clone = audio.clone();
                             
                             // active_sounds에 추가
-                            let msg = PianoMsg::AddActiveSound(key_name_clone, audio_clone);
+                            let msg = PianoMsg::AddActiveSound(key_name_clone, sound);
                             play_link.send_message(msg);
                             
                             // 약간의 지연 후 재생 시작
@@ -420,10 +492,8 @@ impl Component for PianoKeyboard {
             },
             PianoMsg::StopSound(key_name) => {
                 // 소리를 먼저 제거하고 나중에 일시 중지 - 재생 중단 오류 방지
-                if let Some(audio) = self.active_sounds.remove(&key_name) {
-                    // 맵에서 먼저 제거한 후 pause 호출
-                    let _ = audio.set_current_time(0.0);
-                    let _ = audio.pause();
+                if let Some(sound) = self.active_sounds.remove(&key_name) {
+                    sound.stop();
                 }
                 false
             },
@@ -592,6 +662,7 @@ impl Component for PianoKeyboard {
                     
                     // 영역이 바뀌면 이전 영역에 눌려있던 키들 해제
                     self.release_keys_in_octave(ctx, old_octave, true);
+                    self.save_settings();
                     
                     true
                 } else {
@@ -606,6 +677,7 @@ impl Component for PianoKeyboard {
                     
                     // 영역이 바뀌면 이전 영역에 눌려있던 키들 해제
                     self.release_keys_in_octave(ctx, old_octave, false);
+                    self.save_settings();
                     
                     true
                 } else {
@@ -635,6 +707,7 @@ impl Component for PianoKeyboard {
                 
                 // 키 매핑 재생성
                 self.recreate_key_mappings();
+                self.save_settings();
                 true
             },
             PianoMsg::ChangeLeftHandStartNote(delta) => {
@@ -666,6 +739,7 @@ impl Component for PianoKeyboard {
                 
                 // 키 매핑 재생성
                 self.recreate_key_mappings();
+                self.save_settings();
                 true
             },
             PianoMsg::ChangeRightHandStartNote(delta) => {
@@ -697,6 +771,7 @@ impl Component for PianoKeyboard {
                 
                 // 키 매핑 재생성
                 self.recreate_key_mappings();
+                self.save_settings();
                 true
             },
             PianoMsg::ResetAllKeys => {
@@ -716,10 +791,8 @@ impl Component for PianoKeyboard {
                 // 모든 소리 중지
                 let sounds_to_stop: Vec<String> = self.active_sounds.keys().cloned().collect();
                 for key_name in sounds_to_stop {
-                    if let Some(audio) = self.active_sounds.get(&key_name) {
-                        let _ = audio.pause();
-                        let _ = audio.set_current_time(0.0);
-                        self.active_sounds.remove(&key_name);
+                    if let Some(sound) = self.active_sounds.remove(&key_name) {
+                        sound.stop();
                     }
                 }
                 
@@ -819,7 +892,7 @@ impl Component for PianoKeyboard {
                             
                             // 소리가 최소 500ms 재생되도록 타임스탬프 확인
                             for key_name in existing_sounds {
-                                if let Some(audio) = self.active_sounds.get(&key_name) {
+                                if self.active_sounds.contains_key(&key_name) {
                                     // 키 이름에서 타임스탬프 추출
                                     if let Some(pos) = key_name.rfind('_') {
                                         if let Ok(timestamp) = key_name[pos+1..].parse::<f64>() {
@@ -828,15 +901,14 @@ impl Component for PianoKeyboard {
                                             
                                             // 500ms 미만인 경우 페이드아웃 사용, 그렇지 않은 경우 기존 로직 사용
                                             if elapsed < 500.0 {
-                                                // 현재 볼륨 값을 가져와 페이드아웃 시작
-                                                let current_volume = audio.volume();
+                                                // 페이드아웃 시작
                                                 let key_name_clone = key_name.clone();
                                                 let link = ctx.link().clone();
                                                 
                                                 // 페이드아웃 메시지 전송 (500ms - elapsed 시간 후에 소리 정지)
                                                 let remaining = (500.0 - elapsed).max(100.0) as u32;
                                                 let timeout = Timeout::new(remaining, move || {
-                                                    link.send_message(PianoMsg::FadeOutSound(key_name_clone, current_volume));
+                                                    link.send_message(PianoMsg::FadeOutSound(key_name_clone));
                                                 });
                                                 timeout.forget();
                                                 continue;
@@ -844,13 +916,12 @@ impl Component for PianoKeyboard {
                                         }
                                     }
                                     
-                                    // 기본 동작: 현재 볼륨 값을 가져와 페이드아웃 시작
-                                    let current_volume = audio.volume();
+                                    // 기본 동작: 페이드아웃 시작
                                     let key_name_clone = key_name.clone();
                                     let link = ctx.link().clone();
                                     
                                     // 페이드아웃 메시지 전송
-                                    link.send_message(PianoMsg::FadeOutSound(key_name_clone, current_volume));
+                                    link.send_message(PianoMsg::FadeOutSound(key_name_clone));
                                 }
                             }
                             
@@ -859,6 +930,7 @@ impl Component for PianoKeyboard {
                             let key_full_name = self.keys[key_idx].full_name();
                             let set_idx_copy = set_idx;
                             let link = ctx.link().clone();
+                            let engine = self.engine.clone();
                             
                             // 10ms 지연 후 새 오디오 생성 및 재생
                             let timeout = Timeout::new(10, move || {
@@ -866,6 +938,7 @@ impl Component for PianoKeyboard {
                                 if let Ok(audio) = HtmlAudioElement::new_with_src(&audio_path) {
                                     // 볼륨 설정
                                     audio.set_volume(0.7);
+                                    let sound = ActiveSound::connect(&engine, &audio);
                                     
                                     // 시작 위치 리셋
                                     audio.set_current_time(0.0);
@@ -879,10 +952,9 @@ impl Component for PianoKeyboard {
                                     // 먼저 재생하려면 타임스탬프 지연이 중요함
                                     let play_link = link.clone();
                                     let key_name_clone = key_name.clone();
-                                    let audio_clone = audio.clone();
                                     
                                     // active_sounds에 추가
-                                    let msg = PianoMsg::AddActiveSound(key_name_clone, audio_clone);
+                                    let msg = PianoMsg::AddActiveSound(key_name_clone, sound);
                                     play_link.send_message(msg);
                                     
                                     // 약간의 지연 후 재생 시작
@@ -1035,9 +1107,8 @@ impl Component for PianoKeyboard {
                     
                     for key_name in sounds_to_stop {
                         // 맵에서 먼저 제거
-                        if let Some(audio) = self.active_sounds.remove(&key_name) {
-                            let _ = audio.set_current_time(0.0);
-                            let _ = audio.pause();
+                        if let Some(sound) = self.active_sounds.remove(&key_name) {
+                            sound.stop();
                             console_log!("세트 {} 키 {} 소리 제거", set_idx, key_idx);
                         }
                     }
@@ -1067,9 +1138,8 @@ impl Component for PianoKeyboard {
                             
                             for key_name in sounds_to_stop {
                                 // 맵에서 먼저 제거
-                                if let Some(audio) = self.active_sounds.remove(&key_name) {
-                                    let _ = audio.set_current_time(0.0);
-                                    let _ = audio.pause();
+                                if let Some(sound) = self.active_sounds.remove(&key_name) {
+                                    sound.stop();
                                     console_log!("세트 키 {} 소리 정지", key_base_name);
                                 }
                             }
@@ -1097,9 +1167,8 @@ impl Component for PianoKeyboard {
                         
                         for key_name in sounds_to_stop {
                             // 맵에서 먼저 제거
-                            if let Some(audio) = self.active_sounds.remove(&key_name) {
-                                let _ = audio.set_current_time(0.0);
-                                let _ = audio.pause();
+                            if let Some(sound) = self.active_sounds.remove(&key_name) {
+                                sound.stop();
                                 console_log!("세트 키 {} 소리 정지", key_base_name);
                             }
                         }
@@ -1109,9 +1178,9 @@ impl Component for PianoKeyboard {
                 }
                 false
             },
-            PianoMsg::AddActiveSound(key_name, audio) => {
+            PianoMsg::AddActiveSound(key_name, sound) => {
                 // active_sounds에 오디오 요소 추가
-                self.active_sounds.insert(key_name, audio);
+                self.active_sounds.insert(key_name, sound);
                 false
             },
             PianoMsg::RemoveActiveSound(key_name) => {
@@ -1119,29 +1188,30 @@ impl Component for PianoKeyboard {
                 self.active_sounds.remove(&key_name);
                 false
             },
-            PianoMsg::FadeOutSound(key_name, current_volume) => {
-                if let Some(audio) = self.active_sounds.get(&key_name) {
-                    // 볼륨 단계적으로 줄이기 (페이드아웃 속도 더 빠르게 조정)
-                    let new_volume = (current_volume - 0.1).max(0.0);
-                    audio.set_volume(new_volume);
-                    
-                    // 볼륨이 0에 도달하지 않았으면 계속 페이드아웃
-                    if new_volume > 0.0 {
-                        let key_name_clone = key_name.clone();
-                        let link = ctx.link().clone();
-                        
-                        // 페이드아웃 간격 더 짧게 조정 (30ms)
-                        let timeout = Timeout::new(30, move || {
-                            link.send_message(PianoMsg::FadeOutSound(key_name_clone, new_volume));
-                        });
-                        timeout.forget();
-                    } else {
-                        // 볼륨이 0에 도달하면 소리 정지
-                        ctx.link().send_message(PianoMsg::StopSound(key_name));
-                    }
+            PianoMsg::FadeOutSound(key_name) => {
+                if let Some(sound) = self.active_sounds.get(&key_name) {
+                    // 게인을 램프로 내리고 다 줄어들면 소리 정지
+                    sound.fade_out();
+                    let link = ctx.link().clone();
+                    let timeout = Timeout::new((FADE_OUT_SECONDS * 1000.0) as u32, move || {
+                        link.send_message(PianoMsg::StopSound(key_name));
+                    });
+                    timeout.forget();
                 }
                 false
             },
+            PianoMsg::SettingsReset => {
+                // 눌려 있던 키를 모두 해제한 뒤 기본 손 위치로
+                let _ = yew::Component::update(self, ctx, PianoMsg::ResetAllKeys);
+                
+                let defaults = PianoSettings::default();
+                self.left_hand_octave = defaults.left_hand_octave;
+                self.right_hand_octave = defaults.right_hand_octave;
+                self.left_hand_start_note_idx = defaults.left_hand_start_note_idx;
+                self.right_hand_start_note_idx = defaults.right_hand_start_note_idx;
+                self.recreate_key_mappings();
+                true
+            },
         }
     }
 
@@ -1182,10 +1252,12 @@ impl Component for PianoKeyboard {
         }
         
         // 모든 활성 소리 정지
-        for (_, audio) in &self.active_sounds {
-            let _ = audio.pause();
+        for (_, sound) in self.active_sounds.drain() {
+            sound.stop();
         }
-        self.active_sounds.clear();
+        
+        // 진행 중인 연습 세션 기록
+        self.save_practice();
     }
 
     fn view(&self, ctx: &Context<Self>) -> Html {
@@ -1668,6 +1740,33 @@ impl Component for PianoKeyboard {
 }
 
 impl PianoKeyboard {
+    // 건반 입력으로 연습 세션 갱신 - 오래 쉬었다면 이전 세션을 기록하고 새로 시작
+    fn track_practice(&mut self) {
+        let now = js_sys::Date::now();
+        if self.practice_started_at > 0.0 && now - self.last_played_at > PRACTICE_IDLE_GAP_MS {
+            self.save_practice();
+        }
+        if self.practice_started_at <= 0.0 {
+            self.practice_started_at = now;
+            self.notes_played = 0;
+        }
+        self.last_played_at = now;
+        self.notes_played += 1;
+    }
+    
+    // 현재 연습 세션 저장 (마지막 입력까지를 연습 시간으로)
+    fn save_practice(&mut self) {
+        if self.practice_started_at <= 0.0 {
+            return;
+        }
+        
+        let mut entry = PracticeEntry::new(PracticeTool::Piano, self.practice_started_at)
+            .with_details(format!("{}음 연주", self.notes_played));
+        entry.duration_secs = (self.last_played_at - self.practice_started_at) / 1000.0;
+        save_practice_entry(entry);
+        self.practice_started_at = 0.0;
+    }
+    
     // 검은 건반의 위치 계산
     fn get_black_key_position(&self, key: &PianoKey) -> String {
         // 각 검은 건반의 상대적 위치를 계산
@@ -1692,9 +1791,8 @@ impl PianoKeyboard {
     fn play_sound(&mut self, ctx: &Context<Self>, key_idx: usize) {
         // 이미 재생 중인 소리가 있으면 중지
         let key_name = self.keys[key_idx].full_name();
-        if let Some(audio) = self.active_sounds.remove(&key_name) {
-            let _ = audio.pause();
-            let _ = audio.set_current_time(0.0);
+        if let Some(sound) = self.active_sounds.remove(&key_name) {
+            sound.stop();
         }
         
         let key = &self.keys[key_idx];
@@ -1714,6 +1812,7 @@ impl PianoKeyboard {
                     
                     // 볼륨 설정
                     audio_element.set_volume(0.7);
+                    let sound = ActiveSound::connect(&self.engine, &audio_element);
                     
                     // 오디오 요소 미리 로드
                     let _ = audio_element.load();
@@ -1728,7 +1827,7 @@ impl PianoKeyboard {
                         console_log!("피아노 노트 재생: {}", key.full_name());
                         
                         // 재생 중인 소리 목록에 추가
-                        self.active_sounds.insert(key.full_name(), audio_element);
+                        self.active_sounds.insert(key.full_name(), sound);
                     }
                 }
             }
@@ -1738,9 +1837,8 @@ impl PianoKeyboard {
     // 건반에 해당하는 소리 중지
     fn stop_sound(&mut self, key_idx: usize) {
         let key_name = self.keys[key_idx].full_name();
-        if let Some(audio) = self.active_sounds.remove(&key_name) {
-            let _ = audio.pause();
-            let _ = audio.set_src("");  // 리소스 해제
+        if let Some(sound) = self.active_sounds.remove(&key_name) {
+            sound.stop();
             
             console_log!("피아노 노트 중지: {}", key_name);
         }
@@ -1817,6 +1915,17 @@ impl PianoKeyboard {
     }
     
     // 키 매핑 재생성
+    // 현재 손 위치 설정을 저장소에 기록
+    fn save_settings(&self) {
+        let current = PianoSettings {
+            left_hand_octave: self.left_hand_octave,
+            right_hand_octave: self.right_hand_octave,
+            left_hand_start_note_idx: self.left_hand_start_note_idx,
+            right_hand_start_note_idx: self.right_hand_start_note_idx,
+        };
+        settings::update_settings(|s| s.piano = current);
+    }
+    
     fn recreate_key_mappings(&mut self) {
         let left_hand_keys = ["z", "x", "c", "v", "a", "s", "d", "f", "w", "e", "r", "t", "y"];
         let right_hand_keys = ["m", ",", ".", "/", "j", "k", "l", ";", "u", "i", "o", "p", "["];
@@ -1938,9 +2047,8 @@ impl PianoKeyboard {
                     
                     for key_name in sounds_to_stop {
                         // 맵에서 먼저 제거
-                        if let Some(audio) = self.active_sounds.remove(&key_name) {
-                            let _ = audio.set_current_time(0.0);
-                            let _ = audio.pause();
+                        if let Some(sound) = self.active_sounds.remove(&key_name) {
+                            sound.stop();
                         }
                     }
                 }
@@ -0,0 +1,105 @@
+use gloo::events::EventListener;
+use gloo_timers::callback::Timeout;
+use web_sys::{GainNode, HtmlAudioElement, MediaElementAudioSourceNode};
+
+use crate::audio_engine::{ramp_gain, AudioEngine, Channel, GAIN_RAMP_SECONDS};
+
+// 조건부 로그 매크로 정의
+#[cfg(debug_assertions)]
+macro_rules! console_error {
+    ($($arg:tt)*) => {
+        web_sys::console::error_1(&format!($($arg)*).into());
+    };
+}
+
+#[cfg(not(debug_assertions))]
+macro_rules! console_error {
+    ($($arg:tt)*) => {};
+}
+
+// 피아노 음원 범위 (A0 ~ C8)
+pub const LOWEST_PIANO_MIDI: i32 = 21;
+pub const HIGHEST_PIANO_MIDI: i32 = 108;
+
+// 피아노 음원 파일명에 쓰이는 음 이름 (샵 대신 플랫 표기)
+const SAMPLE_NOTE_NAMES: [&str; 12] = ["C", "Db", "D", "Eb", "E", "F", "Gb", "G", "Ab", "A", "Bb", "B"];
+
+// MIDI 번호에 해당하는 피아노 음원 경로 (범위를 벗어나면 가장 가까운 음)
+pub fn piano_sample_path(midi: i32) -> String {
+    let midi = midi.clamp(LOWEST_PIANO_MIDI, HIGHEST_PIANO_MIDI);
+    let name = SAMPLE_NOTE_NAMES[midi.rem_euclid(12) as usize];
+    let octave = midi.div_euclid(12) - 1;
+    format!("/static/piano/Piano.ff.{}{}.mp3", name, octave)
+}
+
+// 채널에 연결된 피아노 음원 - 끝까지 재생되거나 stop()으로 멈추면 소스 노드 연결을 끊음
+//   <audio> -> 소스 노드 -> 페이드용 게인 -> 채널
+pub struct PianoSample {
+    audio: HtmlAudioElement,
+    source: Option<(MediaElementAudioSourceNode, GainNode)>, // 채널 연결에 실패하면 None (요소 볼륨으로만 재생)
+    _ended_listener: Option<EventListener>,
+}
+
+impl PianoSample {
+    pub fn connect(engine: &AudioEngine, channel: Channel, audio: &HtmlAudioElement) -> Self {
+        let source = match engine.connect_media_element(channel, audio) {
+            Ok(source) => Some(source),
+            Err(err) => {
+                console_error!("피아노 음원 채널 연결 실패: {:?}", err);
+                None
+            }
+        };
+        let ended_listener = source.clone().map(|(source, gain)| {
+            EventListener::once(audio, "ended", move |_| {
+                let _ = source.disconnect();
+                let _ = gain.disconnect();
+            })
+        });
+        Self {
+            audio: audio.clone(),
+            source,
+            _ended_listener: ended_listener,
+        }
+    }
+
+    // 짧게 줄인 뒤 정지 (마이크로 판정하기 전에 제시음을 끌 때 - 바로 끊으면 딸깍 소리가 남)
+    pub fn fade_out(self) {
+        match &self.source {
+            Some((_, gain)) => {
+                ramp_gain(gain, 0.0);
+                Timeout::new((GAIN_RAMP_SECONDS * 1000.0) as u32 + 10, move || self.stop()).forget();
+            }
+            None => self.stop(),
+        }
+    }
+
+    // 재생 중인 피아노 음원 정지 및 리소스 해제
+    pub fn stop(self) {
+        let _ = self.audio.pause();
+        self.audio.set_src("");
+        if let Some((source, gain)) = &self.source {
+            let _ = source.disconnect();
+            let _ = gain.disconnect();
+        }
+    }
+}
+
+// 피아노 음원을 엔진의 채널로 재생 - 나중에 멈출 수 있도록 연결된 음원 반환
+pub fn play_piano_sample(engine: &AudioEngine, channel: Channel, midi: i32, volume: f64) -> Option<PianoSample> {
+    let audio = match HtmlAudioElement::new_with_src(&piano_sample_path(midi)) {
+        Ok(audio) => audio,
+        Err(err) => {
+            console_error!("피아노 음원 로드 실패: {:?}", err);
+            return None;
+        }
+    };
+
+    audio.set_volume(volume);
+    let sample = PianoSample::connect(engine, channel, &audio);
+    if let Err(err) = audio.play() {
+        console_error!("피아노 음원 재생 실패: {:?}", err);
+        sample.stop();
+        return None;
+    }
+    Some(sample)
+}
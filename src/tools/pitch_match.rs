@@ -0,0 +1,746 @@
+use gloo_timers::callback::{Interval, Timeout};
+use wasm_bindgen::JsValue;
+use web_sys::{GainNode, OscillatorNode};
+use serde::{Deserialize, Serialize};
+use yew::prelude::*;
+
+use crate::audio_engine::{engine_from_scope, ramp_gain, AudioEngine, Channel, GAIN_RAMP_SECONDS};
+use crate::pitch_input::{self, cents_ignoring_octave, midi_to_frequency, MicInput};
+use crate::tools::piano_sampler::{play_piano_sample, PianoSample};
+use crate::tools::target_melody::{midi_from_note_name, note_name_from_midi};
+use crate::practice_log::{save_practice_entry, PracticeEntry, PracticeTool};
+use crate::routes::Route;
+use crate::share_link::{self, CopyLinkButton};
+
+// 조건부 로그 매크로 정의
+#[cfg(debug_assertions)]
+macro_rules! console_log {
+    ($($arg:tt)*) => {
+        web_sys::console::log_1(&format!($($arg)*).into());
+    };
+}
+
+#[cfg(not(debug_assertions))]
+macro_rules! console_log {
+    ($($arg:tt)*) => {};
+}
+
+#[cfg(debug_assertions)]
+macro_rules! console_error {
+    ($($arg:tt)*) => {
+        web_sys::console::error_1(&format!($($arg)*).into());
+    };
+}
+
+#[cfg(not(debug_assertions))]
+macro_rules! console_error {
+    ($($arg:tt)*) => {};
+}
+
+const NOTE_VOLUME: f64 = 0.7;
+const LISTEN_MS: u32 = 1200;          // 제시음을 듣는 시간
+const POLL_MS: u32 = 50;              // 마이크 피치 검출 간격
+const HOLD_MS: f64 = 500.0;           // 이 시간 동안 음을 유지하면 성공
+const ROUND_TIMEOUT_MS: f64 = 8000.0; // 이 시간 안에 맞추지 못하면 실패
+const RESULT_MS: u32 = 1500;          // 라운드 결과를 보여주는 시간
+const DRONE_GAIN: f32 = 0.05;         // 드론 음량 (마이크에 잡히지 않도록 작게)
+const DRONE_INTERVAL: i32 = 5;        // 드론은 목표음의 완전4도 아래 (배음이 목표음과 같은 음이름에 오지 않음)
+const RANGE_MIN_MIDI: i32 = 36;       // 선택 가능한 최저음 (C2)
+const RANGE_MAX_MIDI: i32 = 84;       // 선택 가능한 최고음 (C6)
+
+// 게임 진행 단계
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MatchPhase {
+    Idle,      // 시작 전
+    Listening, // 제시음 듣는 중
+    Singing,   // 부르는 중 (판정 중)
+    Result,    // 라운드 결과 표시
+    Finished,  // 세션 종료 (요약 표시)
+}
+
+// 공유 링크 쿼리 (예: /pitch-match?tolerance=25&low=C3&high=G4&rounds=20&drone=true)
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+struct PitchMatchQuery {
+    tolerance: Option<f64>,
+    low: Option<String>,
+    high: Option<String>,
+    rounds: Option<usize>,
+    drone: Option<bool>,
+}
+
+// 단계별로 들려도 되는 소리
+// 부르는 동안 제시음이 울리면 마이크가 스피커 소리를 목표음으로 판정하므로 제시음은 듣는 단계에서만 허용
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct PhaseSounds {
+    reference: bool, // 제시음 (피아노)
+    drone: bool,     // 기준 드론
+}
+
+impl PhaseSounds {
+    fn for_phase(phase: MatchPhase, use_drone: bool) -> Self {
+        match phase {
+            MatchPhase::Listening => Self { reference: true, drone: false },
+            MatchPhase::Singing => Self { reference: false, drone: use_drone },
+            MatchPhase::Idle | MatchPhase::Result | MatchPhase::Finished => Self { reference: false, drone: false },
+        }
+    }
+}
+
+// 라운드 결과
+#[derive(Debug, Clone, PartialEq)]
+struct RoundResult {
+    midi: i32,
+    matched: bool,
+    reaction_ms: Option<f64>,    // 부르기 시작부터 음을 맞춘 시점까지
+    mean_abs_cents: Option<f64>, // 음을 유지한 구간의 평균 오차
+    score: u32,                  // 0 ~ 100
+}
+
+pub enum PitchMatchMsg {
+    SetTolerance(f64),
+    SetLowNote(i32),
+    SetHighNote(i32),
+    SetRounds(usize),
+    ToggleDrone,
+    Start,
+    Stop,
+    MicReady(Result<MicInput, String>),
+    NextRound,
+    BeginSinging,
+    Poll,
+}
+
+pub struct PitchMatchGame {
+    tolerance_cents: f64,
+    low_midi: i32,
+    high_midi: i32,
+    rounds: usize,                   // 세션당 라운드 수
+    use_drone: bool,                 // 부르는 동안 기준 드론 재생
+    phase: MatchPhase,
+    engine: AudioEngine, // 공유 오디오 엔진 (제시음/드론은 청음 채널로 출력)
+    mic: Option<MicInput>,
+    poll_interval: Option<Interval>,
+    timeout: Option<Timeout>,
+    target_midi: Option<i32>,
+    audio: Option<PianoSample>, // 제시음
+    drone: Option<(OscillatorNode, GainNode)>,
+    sing_start_ms: f64,              // 부르기 시작 시각
+    hold_start_ms: Option<f64>,      // 허용 오차 안에 들어온 시각
+    hold_cents: Vec<f64>,            // 유지 구간의 센트 오차
+    current_cents: Option<f64>,      // 현재 부르는 음과 목표음의 차이
+    results: Vec<RoundResult>,
+    error: Option<String>,
+    session_started_at: f64,         // 세션 시작 시각 (연습 기록용)
+}
+
+impl Component for PitchMatchGame {
+    type Message = PitchMatchMsg;
+    type Properties = ();
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let mut game = Self {
+            tolerance_cents: 50.0,
+            low_midi: 48,  // C3
+            high_midi: 67, // G4
+            rounds: 10,
+            use_drone: false,
+            phase: MatchPhase::Idle,
+            engine: engine_from_scope(ctx.link()),
+            mic: None,
+            poll_interval: None,
+            timeout: None,
+            target_midi: None,
+            audio: None,
+            drone: None,
+            sing_start_ms: 0.0,
+            hold_start_ms: None,
+            hold_cents: Vec::new(),
+            current_cents: None,
+            results: Vec::new(),
+            error: None,
+            session_started_at: 0.0,
+        };
+
+        // 공유 링크로 열었으면 링크의 설정 적용
+        if let Some(query) = share_link::read_query::<PitchMatchQuery>(ctx.link(), &Route::PitchMatch) {
+            game.apply_query(query);
+        }
+        game
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            PitchMatchMsg::SetTolerance(cents) => {
+                self.tolerance_cents = cents;
+                true
+            }
+            PitchMatchMsg::SetLowNote(midi) => {
+                self.low_midi = midi.min(self.high_midi);
+                true
+            }
+            PitchMatchMsg::SetHighNote(midi) => {
+                self.high_midi = midi.max(self.low_midi);
+                true
+            }
+            PitchMatchMsg::SetRounds(rounds) => {
+                self.rounds = rounds;
+                true
+            }
+            PitchMatchMsg::ToggleDrone => {
+                self.use_drone = !self.use_drone;
+                true
+            }
+            PitchMatchMsg::Start => {
+                if self.is_running() {
+                    return false;
+                }
+
+                // 마이크가 준비된 뒤에 시작
+                if self.mic.is_none() {
+                    pitch_input::open_microphone(&self.engine, ctx.link().callback(PitchMatchMsg::MicReady));
+                    return false;
+                }
+
+                self.results.clear();
+                self.error = None;
+                self.session_started_at = js_sys::Date::now();
+                ctx.link().send_message(PitchMatchMsg::NextRound);
+                false
+            }
+            PitchMatchMsg::Stop => {
+                self.stop_session();
+                self.phase = if self.results.is_empty() { MatchPhase::Idle } else { MatchPhase::Finished };
+                true
+            }
+            PitchMatchMsg::MicReady(result) => {
+                match result {
+                    Ok(mic) => {
+                        self.mic = Some(mic);
+                        let link = ctx.link().clone();
+                        self.poll_interval = Some(Interval::new(POLL_MS, move || {
+                            link.send_message(PitchMatchMsg::Poll);
+                        }));
+                        ctx.link().send_message(PitchMatchMsg::Start);
+                    }
+                    Err(err) => {
+                        console_error!("음 맞추기 마이크 준비 실패: {}", err);
+                        self.error = Some(err);
+                    }
+                }
+                true
+            }
+            PitchMatchMsg::NextRound => {
+                if self.results.len() >= self.rounds {
+                    console_log!("음 맞추기 세션 종료: {}라운드", self.results.len());
+                    self.stop_session();
+                    self.phase = MatchPhase::Finished;
+                    return true;
+                }
+
+                // 직전 음과 다른 음 선택 (음역이 한 음이면 그대로)
+                let span = self.high_midi - self.low_midi + 1;
+                let previous = self.results.last().map(|r| r.midi);
+                let mut midi = self.low_midi + (js_sys::Math::random() * span as f64) as i32;
+                if span > 1 && Some(midi) == previous {
+                    midi = self.low_midi + (midi - self.low_midi + 1) % span;
+                }
+
+                self.target_midi = Some(midi);
+                self.current_cents = None;
+                self.phase = MatchPhase::Listening;
+                self.stop_reference();
+                self.audio = play_piano_sample(&self.engine, Channel::Trainer, midi, NOTE_VOLUME);
+
+                let link = ctx.link().clone();
+                self.timeout = Some(Timeout::new(LISTEN_MS, move || {
+                    link.send_message(PitchMatchMsg::BeginSinging);
+                }));
+                true
+            }
+            PitchMatchMsg::BeginSinging => {
+                if self.phase != MatchPhase::Listening {
+                    return false;
+                }
+                self.phase = MatchPhase::Singing;
+                self.sing_start_ms = js_sys::Date::now();
+                self.hold_start_ms = None;
+                self.hold_cents.clear();
+                self.apply_phase_sounds();
+                true
+            }
+            PitchMatchMsg::Poll => {
+                let (mic, target) = match (&self.mic, self.target_midi) {
+                    (Some(mic), Some(target)) if self.phase == MatchPhase::Singing => (mic, target),
+                    _ => return false,
+                };
+
+                let now = js_sys::Date::now();
+                self.current_cents = mic
+                    .detect_pitch(pitch_input::DEFAULT_SENSITIVITY)
+                    .map(|freq| match_cents(freq, target));
+
+                match self.current_cents {
+                    Some(cents) if cents.abs() <= self.tolerance_cents => {
+                        let hold_start = *self.hold_start_ms.get_or_insert(now);
+                        self.hold_cents.push(cents.abs());
+                        if now - hold_start >= HOLD_MS {
+                            self.finish_round(ctx, true, now);
+                        }
+                    }
+                    _ => {
+                        self.hold_start_ms = None;
+                        self.hold_cents.clear();
+                    }
+                }
+
+                if self.phase == MatchPhase::Singing && now - self.sing_start_ms >= ROUND_TIMEOUT_MS {
+                    self.finish_round(ctx, false, now);
+                }
+                true
+            }
+        }
+    }
+
+    fn destroy(&mut self, _ctx: &Context<Self>) {
+        self.stop_session();
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let link = ctx.link();
+        let running = self.is_running();
+
+        html! {
+            <div class="pitch-match-game">
+                <div class="trainer-settings">
+                    <div class="setting-row">
+                        <span class="setting-label">{"허용 오차:"}</span>
+                        <select disabled={running} onchange={link.callback(|e: Event| {
+                            let select = e.target_dyn_into::<web_sys::HtmlSelectElement>().unwrap();
+                            PitchMatchMsg::SetTolerance(select.value().parse().unwrap_or(50.0))
+                        })}>
+                            { for [(10.0, "±10 cents (어려움)"), (25.0, "±25 cents (보통)"), (50.0, "±50 cents (쉬움)")].iter().map(|(value, label)| html! {
+                                <option value={value.to_string()} selected={self.tolerance_cents == *value}>{*label}</option>
+                            }) }
+                        </select>
+                    </div>
+                    <div class="setting-row">
+                        <span class="setting-label">{"음역:"}</span>
+                        { self.view_range_select(ctx, self.low_midi, RANGE_MIN_MIDI..(self.high_midi + 1), PitchMatchMsg::SetLowNote) }
+                        <span>{" ~ "}</span>
+                        { self.view_range_select(ctx, self.high_midi, self.low_midi..(RANGE_MAX_MIDI + 1), PitchMatchMsg::SetHighNote) }
+                    </div>
+                    <div class="setting-row">
+                        <span class="setting-label">{"라운드:"}</span>
+                        <select disabled={running} onchange={link.callback(|e: Event| {
+                            let select = e.target_dyn_into::<web_sys::HtmlSelectElement>().unwrap();
+                            PitchMatchMsg::SetRounds(select.value().parse().unwrap_or(10))
+                        })}>
+                            { for [5usize, 10, 20].iter().map(|rounds| html! {
+                                <option value={rounds.to_string()} selected={self.rounds == *rounds}>{format!("{}회", rounds)}</option>
+                            }) }
+                        </select>
+                    </div>
+                    <div class="setting-row">
+                        <label>
+                            <input
+                                type="checkbox"
+                                checked={self.use_drone}
+                                disabled={running}
+                                onchange={link.callback(|_| PitchMatchMsg::ToggleDrone)}
+                            />
+                            {" 부르는 동안 기준음 드론 재생 (이어폰 권장)"}
+                        </label>
+                    </div>
+                    <div class="setting-row">
+                        <CopyLinkButton url={self.share_url()} />
+                    </div>
+                </div>
+
+                <div class="trainer-quiz">
+                    <div class="button-group">
+                        {
+                            if running {
+                                html! {
+                                    <button class="stop-button" onclick={link.callback(|_| PitchMatchMsg::Stop)}>
+                                        {"⏹ 그만하기"}
+                                    </button>
+                                }
+                            } else {
+                                html! {
+                                    <button class="play-button" onclick={link.callback(|_| PitchMatchMsg::Start)}>
+                                        {"▶ 시작"}
+                                    </button>
+                                }
+                            }
+                        }
+                    </div>
+                    { self.view_round() }
+                    {
+                        if let Some(err) = &self.error {
+                            html! { <div class="trainer-error">{err}</div> }
+                        } else {
+                            html! {}
+                        }
+                    }
+                </div>
+
+                { self.view_summary() }
+            </div>
+        }
+    }
+}
+
+impl PitchMatchGame {
+    fn apply_query(&mut self, query: PitchMatchQuery) {
+        if let Some(tolerance) = query.tolerance.filter(|t| [10.0, 25.0, 50.0].contains(t)) {
+            self.tolerance_cents = tolerance;
+        }
+        let low = query.low.as_deref().and_then(midi_from_note_name).map(|m| m.clamp(RANGE_MIN_MIDI, RANGE_MAX_MIDI));
+        let high = query.high.as_deref().and_then(midi_from_note_name).map(|m| m.clamp(RANGE_MIN_MIDI, RANGE_MAX_MIDI));
+        let (low, high) = (low.unwrap_or(self.low_midi), high.unwrap_or(self.high_midi));
+        if low <= high {
+            self.low_midi = low;
+            self.high_midi = high;
+        }
+        if let Some(rounds) = query.rounds.filter(|r| [5, 10, 20].contains(r)) {
+            self.rounds = rounds;
+        }
+        if let Some(drone) = query.drone {
+            self.use_drone = drone;
+        }
+    }
+
+    // 현재 설정이 담긴 공유 링크
+    fn share_url(&self) -> Option<AttrValue> {
+        let query = PitchMatchQuery {
+            tolerance: Some(self.tolerance_cents),
+            low: Some(note_name_from_midi(self.low_midi)),
+            high: Some(note_name_from_midi(self.high_midi)),
+            rounds: Some(self.rounds),
+            drone: Some(self.use_drone),
+        };
+        share_link::share_url(&Route::PitchMatch, &query).map(AttrValue::from)
+    }
+
+    fn is_running(&self) -> bool {
+        matches!(self.phase, MatchPhase::Listening | MatchPhase::Singing | MatchPhase::Result)
+    }
+
+    // 라운드 종료 - 점수 계산 후 잠시 결과 표시
+    fn finish_round(&mut self, ctx: &Context<Self>, matched: bool, now: f64) {
+        let midi = match self.target_midi {
+            Some(midi) => midi,
+            None => return,
+        };
+
+        let (reaction_ms, mean_abs_cents) = if matched {
+            let reaction = self.hold_start_ms.unwrap_or(now) - self.sing_start_ms;
+            let mean = self.hold_cents.iter().sum::<f64>() / self.hold_cents.len().max(1) as f64;
+            (Some(reaction), Some(mean))
+        } else {
+            (None, None)
+        };
+
+        // 정확도 50점 + 속도 50점
+        let score = match (reaction_ms, mean_abs_cents) {
+            (Some(reaction), Some(cents)) => {
+                let accuracy = (1.0 - cents / self.tolerance_cents).clamp(0.0, 1.0);
+                let speed = (1.0 - reaction / ROUND_TIMEOUT_MS).clamp(0.0, 1.0);
+                (50.0 * accuracy + 50.0 * speed).round() as u32
+            }
+            _ => 0,
+        };
+
+        console_log!("음 맞추기 라운드: {} {} ({}점)", note_name_from_midi(midi), if matched { "성공" } else { "실패" }, score);
+        self.results.push(RoundResult { midi, matched, reaction_ms, mean_abs_cents, score });
+        self.phase = MatchPhase::Result;
+        self.apply_phase_sounds();
+
+        let link = ctx.link().clone();
+        self.timeout = Some(Timeout::new(RESULT_MS, move || {
+            link.send_message(PitchMatchMsg::NextRound);
+        }));
+    }
+
+    // 현재 단계에서 들리면 안 되는 소리는 끄고, 필요한 드론은 켬
+    fn apply_phase_sounds(&mut self) {
+        let sounds = PhaseSounds::for_phase(self.phase, self.use_drone);
+        if !sounds.reference {
+            if let Some(audio) = self.audio.take() {
+                audio.fade_out();
+            }
+        }
+        if sounds.drone && self.drone.is_none() {
+            self.start_drone();
+        } else if !sounds.drone {
+            self.stop_drone();
+        }
+    }
+
+    // 목표음 완전4도 아래로 작은 사인파 드론 재생
+    fn start_drone(&mut self) {
+        let midi = match (&self.mic, self.target_midi) {
+            (Some(_), Some(midi)) => midi,
+            _ => return,
+        };
+
+        match create_drone(&self.engine, drone_frequency(midi)) {
+            Ok(drone) => self.drone = Some(drone),
+            Err(err) => {
+                console_error!("드론 재생 실패: {:?}", err);
+            }
+        }
+    }
+
+    // 드론을 짧게 줄인 뒤 정지 (바로 끊으면 딸깍 소리가 남)
+    fn stop_drone(&mut self) {
+        if let Some((oscillator, gain)) = self.drone.take() {
+            ramp_gain(&gain, 0.0);
+            let _ = oscillator.stop_with_when(gain.context().current_time() + GAIN_RAMP_SECONDS);
+            Timeout::new((GAIN_RAMP_SECONDS * 1000.0) as u32 + 10, move || {
+                let _ = gain.disconnect();
+            })
+            .forget();
+        }
+    }
+
+    fn stop_reference(&mut self) {
+        if let Some(audio) = self.audio.take() {
+            audio.stop();
+        }
+    }
+
+    // 세션 정리 - 타이머, 소리, 마이크 해제
+    fn stop_session(&mut self) {
+        if self.is_running() {
+            self.save_practice();
+        }
+        self.timeout = None;
+        self.poll_interval = None;
+        self.target_midi = None;
+        self.current_cents = None;
+        self.stop_reference();
+        self.stop_drone();
+        if let Some(mic) = self.mic.take() {
+            mic.close();
+        }
+    }
+
+    // 진행한 라운드를 연습 기록에 저장 (평균 라운드 점수)
+    fn save_practice(&self) {
+        if self.results.is_empty() {
+            return;
+        }
+
+        let matched = self.results.iter().filter(|r| r.matched).count();
+        let average = self.results.iter().map(|r| r.score as f64).sum::<f64>() / self.results.len() as f64;
+        save_practice_entry(
+            PracticeEntry::new(PracticeTool::PitchMatch, self.session_started_at)
+                .finish_now()
+                .with_exercise(format!("±{:.0} cents", self.tolerance_cents))
+                .with_score(average)
+                .with_details(format!("성공 {}/{}", matched, self.results.len())),
+        );
+    }
+
+    // 현재 라운드 표시
+    fn view_round(&self) -> Html {
+        let round_label = format!("라운드 {}/{}", (self.results.len() + 1).min(self.rounds), self.rounds);
+        match self.phase {
+            MatchPhase::Idle | MatchPhase::Finished => html! {
+                <div class="quiz-prompt">{"▶ 시작을 누르면 음이 재생됩니다. 들은 음을 소리 내어 불러 보세요."}</div>
+            },
+            MatchPhase::Listening => html! {
+                <div class="quiz-prompt">{format!("{} - 🎹 잘 들어 보세요", round_label)}</div>
+            },
+            MatchPhase::Singing => {
+                let hint = match self.current_cents {
+                    Some(cents) if cents.abs() <= self.tolerance_cents => format!("✅ 좋아요! ({:+.0} cents)", cents),
+                    Some(cents) if cents > 0.0 => format!("⬇ 조금 낮게 ({:+.0} cents)", cents),
+                    Some(cents) => format!("⬆ 조금 높게 ({:+.0} cents)", cents),
+                    None => "🎤 불러 보세요".to_string(),
+                };
+                html! {
+                    <div class="quiz-prompt">
+                        <div>{round_label}</div>
+                        <div class="match-hint">{hint}</div>
+                    </div>
+                }
+            }
+            MatchPhase::Result => match self.results.last() {
+                Some(result) if result.matched => html! {
+                    <div class="quiz-feedback correct">
+                        {format!("✅ {} 성공! {:.1}초, 평균 {:.0} cents - {}점",
+                            note_name_from_midi(result.midi),
+                            result.reaction_ms.unwrap_or(0.0) / 1000.0,
+                            result.mean_abs_cents.unwrap_or(0.0),
+                            result.score)}
+                    </div>
+                },
+                Some(result) => html! {
+                    <div class="quiz-feedback wrong">{format!("❌ 시간 초과 - 목표음은 {}였습니다", note_name_from_midi(result.midi))}</div>
+                },
+                None => html! {},
+            },
+        }
+    }
+
+    // 세션 요약
+    fn view_summary(&self) -> Html {
+        if self.results.is_empty() {
+            return html! {};
+        }
+
+        let matched: Vec<&RoundResult> = self.results.iter().filter(|r| r.matched).collect();
+        let total_score: u32 = self.results.iter().map(|r| r.score).sum();
+        let average = |values: Vec<f64>| {
+            if values.is_empty() {
+                None
+            } else {
+                Some(values.iter().sum::<f64>() / values.len() as f64)
+            }
+        };
+        let mean_reaction = average(matched.iter().filter_map(|r| r.reaction_ms).collect());
+        let mean_cents = average(matched.iter().filter_map(|r| r.mean_abs_cents).collect());
+        let missed: Vec<String> = self.results.iter().filter(|r| !r.matched).map(|r| note_name_from_midi(r.midi)).collect();
+
+        html! {
+            <div class="trainer-stats">
+                <div class="stats-summary">
+                    <span>{if self.phase == MatchPhase::Finished { "세션 요약" } else { "진행 상황" }}</span>
+                    <span>{format!("성공 {}/{}", matched.len(), self.results.len())}</span>
+                    <span>{format!("총점 {}", total_score)}</span>
+                    <span>{format!("평균 반응 {}", mean_reaction.map(|ms| format!("{:.1}초", ms / 1000.0)).unwrap_or_else(|| "-".to_string()))}</span>
+                    <span>{format!("평균 오차 {}", mean_cents.map(|c| format!("{:.0} cents", c)).unwrap_or_else(|| "-".to_string()))}</span>
+                </div>
+                {
+                    if !missed.is_empty() {
+                        html! { <div class="missed-notes">{format!("놓친 음: {}", missed.join(", "))}</div> }
+                    } else {
+                        html! {}
+                    }
+                }
+                <table class="stats-table">
+                    <thead>
+                        <tr>
+                            <th>{"#"}</th>
+                            <th>{"목표음"}</th>
+                            <th>{"반응 시간"}</th>
+                            <th>{"평균 오차"}</th>
+                            <th>{"점수"}</th>
+                        </tr>
+                    </thead>
+                    <tbody>
+                        {
+                            self.results.iter().enumerate().map(|(i, result)| html! {
+                                <tr class={classes!(if result.matched { "" } else { "weak" })}>
+                                    <td>{i + 1}</td>
+                                    <td>{note_name_from_midi(result.midi)}</td>
+                                    <td>{result.reaction_ms.map(|ms| format!("{:.1}초", ms / 1000.0)).unwrap_or_else(|| "-".to_string())}</td>
+                                    <td>{result.mean_abs_cents.map(|c| format!("{:.0} cents", c)).unwrap_or_else(|| "-".to_string())}</td>
+                                    <td>{result.score}</td>
+                                </tr>
+                            }).collect::<Html>()
+                        }
+                    </tbody>
+                </table>
+            </div>
+        }
+    }
+
+    // 음역 선택 드롭다운
+    fn view_range_select(
+        &self,
+        ctx: &Context<Self>,
+        selected: i32,
+        range: std::ops::Range<i32>,
+        to_msg: fn(i32) -> PitchMatchMsg,
+    ) -> Html {
+        html! {
+            <select disabled={self.is_running()} onchange={ctx.link().callback(move |e: Event| {
+                let select = e.target_dyn_into::<web_sys::HtmlSelectElement>().unwrap();
+                to_msg(select.value().parse().unwrap_or(selected))
+            })}>
+                {
+                    range.map(|midi| html! {
+                        <option value={midi.to_string()} selected={midi == selected}>{note_name_from_midi(midi)}</option>
+                    }).collect::<Html>()
+                }
+            </select>
+        }
+    }
+}
+
+// 드론 주파수 (목표음 완전4도 아래)
+// 목표음과 음이름이 달라 스피커 소리가 마이크에 잡혀도 옥타브를 무시한 판정에서 목표음으로 인정되지 않음
+// (완전5도 아래로 두면 3배음이 목표음 한 옥타브 위에 걸림)
+fn drone_frequency(target_midi: i32) -> f64 {
+    midi_to_frequency(target_midi - DRONE_INTERVAL)
+}
+
+// 검출한 피치와 목표음의 센트 차이 (남녀 음역 차이를 고려해 옥타브 차이는 무시)
+fn match_cents(freq: f64, target_midi: i32) -> f64 {
+    cents_ignoring_octave(freq, midi_to_frequency(target_midi))
+}
+
+// 사인파 오실레이터 -> 게인 -> 청음 채널 연결
+fn create_drone(engine: &AudioEngine, freq: f64) -> Result<(OscillatorNode, GainNode), JsValue> {
+    let audio_ctx = engine.context()?;
+    let oscillator = audio_ctx.create_oscillator()?;
+    let gain = audio_ctx.create_gain()?;
+    oscillator.frequency().set_value(freq as f32);
+    gain.gain().set_value(0.0);
+    oscillator.connect_with_audio_node(&gain)?;
+    let output = engine.channel_input(Channel::Trainer)?;
+    gain.connect_with_audio_node(&output)?;
+    oscillator.start()?;
+    ramp_gain(&gain, DRONE_GAIN);
+    Ok((oscillator, gain))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TOLERANCES: [f64; 3] = [10.0, 25.0, 50.0];
+
+    #[test]
+    fn sung_note_matches_in_any_octave() {
+        let target = 60; // C4
+        assert!(match_cents(midi_to_frequency(target), target).abs() < 1e-6);
+        // 남성 음역처럼 한 옥타브 아래로 불러도 인정
+        assert!(match_cents(midi_to_frequency(target - 12), target).abs() < 1e-6);
+        let octave_up = match_cents(midi_to_frequency(target + 12) * 1.01, target);
+        assert!((octave_up - 1200.0 * 1.01f64.log2()).abs() < 1e-6);
+    }
+
+    #[test]
+    fn drone_bleed_is_never_a_match() {
+        // 드론 기음과 배음이 스피커에서 마이크로 들어와도 가장 넓은 허용 오차 밖
+        for target in RANGE_MIN_MIDI..=RANGE_MAX_MIDI {
+            for harmonic in 1..=8 {
+                let cents = match_cents(drone_frequency(target) * harmonic as f64, target);
+                assert!(cents.abs() > TOLERANCES[2], "목표 {} 드론 {}배음: {:.1} cents", target, harmonic, cents);
+            }
+        }
+    }
+
+    #[test]
+    fn reference_note_stops_when_singing_starts() {
+        for use_drone in [false, true] {
+            let listening = PhaseSounds::for_phase(MatchPhase::Listening, use_drone);
+            assert!(listening.reference);
+            assert!(!listening.drone);
+
+            // 부르기 시작하면 제시음은 꺼지고 드론만 설정대로
+            let singing = PhaseSounds::for_phase(MatchPhase::Singing, use_drone);
+            assert!(!singing.reference);
+            assert_eq!(singing.drone, use_drone);
+
+            for phase in [MatchPhase::Idle, MatchPhase::Result, MatchPhase::Finished] {
+                assert_eq!(PhaseSounds::for_phase(phase, use_drone), PhaseSounds { reference: false, drone: false });
+            }
+        }
+    }
+}
@@ -3,12 +3,15 @@ use plotters::prelude::*;
 use plotters_canvas::CanvasBackend;
 use std::collections::BTreeMap;
 use std::collections::VecDeque;
-use wasm_bindgen::prelude::*;
-use wasm_bindgen::JsCast;
 use web_sys::{HtmlCanvasElement, MouseEvent};
 use yew::prelude::*;
-use gloo::events::EventListener;
-use gloo::utils::window;
+use std::rc::Rc;
+
+use crate::event_bus::{emit, use_app_event, AppEvent};
+use crate::pitch_input::midi_to_frequency;
+use crate::tools::target_melody::TargetNote;
+use crate::tools::overdub::PitchTrace;
+use crate::tools::take_library::BeatGrid;
 
 // 조건부 로그 매크로 정의
 #[cfg(debug_assertions)]
@@ -31,6 +34,22 @@ pub struct PitchPlotProps {
     pub is_playing: bool, // 재생 중인지 여부
     pub is_recording: bool, // 녹음 중인지 여부 추가
     pub is_frozen: bool, // 녹음 종료 후 화면 고정 여부
+    #[prop_or_default]
+    pub target_notes: Vec<TargetNote>, // 피아노 롤로 표시할 목표 멜로디 (오프셋 적용됨)
+    #[prop_or_default]
+    pub region: Option<(f64, f64)>, // 선택된 구간 (시작, 끝 초)
+    #[prop_or_default]
+    pub beat_grid: Option<BeatGrid>, // 카운트인으로 녹음한 테이크의 마디/박 격자
+    #[prop_or_default]
+    pub traces: Vec<PitchTrace>, // 겹쳐 녹음할 때 함께 그리는 반주 트랙의 피치
+}
+
+// 이보다 짧게 드래그하면 구간 선택 해제로 처리 (초)
+const MIN_REGION_SECS: f64 = 0.1;
+
+// 구간 선택 변경을 RegionSelected 이벤트로 알림
+pub fn dispatch_region_selected(region: Option<(f64, f64)>) {
+    emit(AppEvent::RegionSelected { region });
 }
 
 #[function_component(PitchPlot)]
@@ -69,6 +88,11 @@ pub fn pitch_plot(props: &PitchPlotProps) -> Html {
     
     // 차트 렌더링 코드 내에서 현재 표시 범위 저장
     let current_x_range = use_state(|| None::<(f64, f64)>); // 현재 차트에 표시되는 x축 범위
+    
+    // 구간 선택 (Shift+드래그) 관련 상태
+    let plot_pixel_range = use_mut_ref(|| None::<(i32, i32)>); // 플롯 영역의 x 픽셀 범위 (캔버스 좌표)
+    let region_anchor = use_state(|| None::<f64>); // 드래그를 시작한 시간
+    let draft_region = use_state(|| None::<(f64, f64)>); // 드래그 중인 구간
 
     // 화면 고정 상태 감지 및 저장
     {
@@ -79,12 +103,15 @@ pub fn pitch_plot(props: &PitchPlotProps) -> Html {
         let current_freq = props.current_freq;
         let last_playback_time = last_playback_time.clone();
         
+        // 고정된 상태에서 다른 테이크를 불러오면 히스토리가 바뀌므로 길이와 끝 시간도 감지
+        let history_key = (history.len(), history.back().map(|(t, _)| t.to_bits()));
+
         use_effect_with(
-            (props.is_frozen, props.is_recording, props.is_playing),
-            move |(is_frozen, is_recording, is_playing)| {
+            (props.is_frozen, props.is_recording, props.is_playing, history_key),
+            move |(is_frozen, is_recording, is_playing, _)| {
                 // 녹음이 중지되고 화면이 고정되어야 할 때
                 if *is_frozen && !*is_recording && !*is_playing {
-                    if frozen_history.is_none() {
+                    if frozen_history.as_ref() != Some(&history) {
                         // 현재 상태를 고정된 상태로 저장
                         frozen_history.set(Some(history.clone()));
                         frozen_current_freq.set(current_freq);
@@ -115,86 +142,53 @@ pub fn pitch_plot(props: &PitchPlotProps) -> Html {
         );
     }
 
-    // playbackReset 이벤트 리스너 추가
+    // 재생선 초기화, 녹음 시간 업데이트 이벤트 구독
     {
         let last_playback_time = last_playback_time.clone();
-        
-        use_effect_with(
-            (),  // 의존성 없음 (컴포넌트 마운트시 한 번만 실행)
-            move |_| {
-                // playbackReset 이벤트 핸들러 생성
-                let handler = move |e: &web_sys::Event| {
-                    // playback 선 초기화 (0초로)
-                    last_playback_time.set(Some(0.0));
-                    console_log!("[PitchPlot] playbackReset 이벤트 수신: 재생 위치를 0초로 초기화");
-                };
-                
-                // 이벤트 핸들러 등록
-                let document = web_sys::window().unwrap().document().unwrap();
-                let listener = EventListener::new(&document, "playbackReset", handler);
-                
-                // cleanup 함수 반환
-                move || {
-                    drop(listener); // 이벤트 리스너 제거
-                }
-            },
-        );
-    }
-
-    // 녹음 시간 업데이트 이벤트 리스너 추가
-    {
         let current_recording_time = current_recording_time.clone();
         let auto_follow = auto_follow.clone();
         let fixed_time_range = fixed_time_range.clone(); // 고정 시간 범위 상태 추가
-        
-        use_effect_with(
-            (),  // 의존성 없음 (컴포넌트 마운트시 한 번만 실행)
-            move |_| {
-                // playbackTimeUpdate 이벤트 핸들러 생성
-                let handler = move |e: &web_sys::Event| {
-                    // CustomEvent로 변환
-                    if let Some(custom_event) = e.dyn_ref::<web_sys::CustomEvent>() {
-                        let detail = custom_event.detail();
-                        let data = js_sys::Object::from(detail);
-                        
-                        // 녹음 중인지 확인
-                        if let Ok(is_rec) = js_sys::Reflect::get(&data, &JsValue::from_str("isRecording")) {
-                            if let Some(rec_state) = is_rec.as_bool() {
-                                if rec_state {
-                                    // 녹음 중일 때 현재 시간 업데이트
-                                    if let Ok(current) = js_sys::Reflect::get(&data, &JsValue::from_str("currentTime")) {
-                                        if let Some(time) = current.as_f64() {
-                                            current_recording_time.set(time);
-                                            
-                                            // auto_follow가 켜져 있을 때만 로그 출력
-                                            if *auto_follow {
-                                                // 고정 시간 범위가 없는 경우에만 자동 따라가기 적용
-                                                if fixed_time_range.is_none() {
-                                                    console_log!("녹음 시간 업데이트 (auto_follow 모드): {:.2}s", time);
-                                                } else {
-                                                    console_log!("녹음 시간 업데이트 (고정 시간 범위 모드): {:.2}s", time);
-                                                }
-                                            }
-                                        }
-                                    }
-                                }
-                            }
-                        }
+
+        use_app_event(move |event| match event {
+            AppEvent::PlaybackReset => {
+                // playback 선 초기화 (0초로)
+                last_playback_time.set(Some(0.0));
+                console_log!("[PitchPlot] PlaybackReset 이벤트 수신: 재생 위치를 0초로 초기화");
+            }
+            // 녹음 중일 때 현재 시간 업데이트
+            AppEvent::PlaybackTimeUpdate { current_time, is_recording: true, .. } => {
+                current_recording_time.set(*current_time);
+
+                // auto_follow가 켜져 있을 때만 로그 출력
+                if *auto_follow {
+                    // 고정 시간 범위가 없는 경우에만 자동 따라가기 적용
+                    if fixed_time_range.is_none() {
+                        console_log!("녹음 시간 업데이트 (auto_follow 모드): {:.2}s", current_time);
+                    } else {
+                        console_log!("녹음 시간 업데이트 (고정 시간 범위 모드): {:.2}s", current_time);
                     }
-                };
-                
-                // 이벤트 핸들러 등록
-                let document = web_sys::window().unwrap().document().unwrap();
-                let listener = EventListener::new(&document, "playbackTimeUpdate", handler);
-                
-                // cleanup 함수 반환
-                move || {
-                    drop(listener); // 이벤트 리스너 제거
                 }
-            },
-        );
+            }
+            _ => {}
+        });
     }
 
+    // 마우스 위치를 차트의 시간으로 변환 (캔버스가 CSS로 늘어나도 맞도록 비율 보정)
+    let to_time = {
+        let canvas_ref = canvas_ref.clone();
+        let current_x_range = current_x_range.clone();
+        let plot_pixel_range = plot_pixel_range.clone();
+        Rc::new(move |e: &MouseEvent| -> Option<f64> {
+            let canvas = canvas_ref.cast::<HtmlCanvasElement>()?;
+            let (x_min, x_max) = (*current_x_range)?;
+            let (left, right) = (*plot_pixel_range.borrow())?;
+            let scale = canvas.width() as f64 / (canvas.client_width().max(1)) as f64;
+            let x = e.offset_x() as f64 * scale;
+            let ratio = ((x - left as f64) / (right - left).max(1) as f64).clamp(0.0, 1.0);
+            Some(x_min + ratio * (x_max - x_min))
+        })
+    };
+
     // 마우스 이벤트 핸들러
     let on_mouse_down = {
         let is_dragging = is_dragging.clone();
@@ -209,8 +203,22 @@ pub fn pitch_plot(props: &PitchPlotProps) -> Html {
         let is_playing = props.is_playing;
         let current_x_range = current_x_range.clone(); // 현재 차트 범위 추가
 
+        let region_anchor = region_anchor.clone();
+        let draft_region = draft_region.clone();
+        let to_time = to_time.clone();
+
         Callback::from(move |e: MouseEvent| {
             e.prevent_default();
+
+            // Shift+드래그: 화면 이동 대신 구간 선택 (녹음 중 제외)
+            if e.shift_key() && !is_recording {
+                if let Some(time) = to_time(&e) {
+                    region_anchor.set(Some(time));
+                    draft_region.set(Some((time, time)));
+                }
+                return;
+            }
+
             is_dragging.set(true);
             drag_start_x.set(e.client_x());
             drag_start_y.set(e.client_y());
@@ -282,8 +290,18 @@ pub fn pitch_plot(props: &PitchPlotProps) -> Html {
         let last_center_freq = last_center_freq.clone();
         let is_recording = props.is_recording;
         let is_playing = props.is_playing;
+        let region_anchor = region_anchor.clone();
+        let draft_region = draft_region.clone();
+        let to_time = to_time.clone();
 
         Callback::from(move |e: MouseEvent| {
+            if let Some(anchor) = *region_anchor {
+                if let Some(time) = to_time(&e) {
+                    draft_region.set(Some((anchor.min(time), anchor.max(time))));
+                }
+                return;
+            }
+
             if !*is_dragging {
                 return;
             }
@@ -373,10 +391,24 @@ pub fn pitch_plot(props: &PitchPlotProps) -> Html {
 
     let on_mouse_up = {
         let is_dragging = is_dragging.clone();
+        let region_anchor = region_anchor.clone();
+        let draft_region = draft_region.clone();
+        let history_end = props.history.back().map(|(t, _)| *t).unwrap_or(0.0);
 
         Callback::from(move |e: MouseEvent| {
             e.prevent_default();
             is_dragging.set(false);
+
+            // 구간 선택 완료 (녹음 길이 안으로 제한, 너무 짧으면 선택 해제)
+            if region_anchor.is_some() {
+                let region = (*draft_region)
+                    .map(|(start, end)| (start.clamp(0.0, history_end), end.clamp(0.0, history_end)))
+                    .filter(|(start, end)| end - start >= MIN_REGION_SECS);
+                region_anchor.set(None);
+                draft_region.set(None);
+                dispatch_region_selected(region);
+                console_log!("[PitchPlot] 구간 선택: {:?}", region);
+            }
         })
     };
 
@@ -445,6 +477,12 @@ pub fn pitch_plot(props: &PitchPlotProps) -> Html {
         let last_playback_time = last_playback_time.clone();
         let current_recording_time = current_recording_time.clone();
         let current_x_range = current_x_range.clone(); // 현재 x 범위 상태 추가
+        let target_notes = props.target_notes.clone();
+        let plot_pixel_range = plot_pixel_range.clone();
+        // 드래그 중인 구간이 있으면 그것을, 아니면 선택된 구간 표시
+        let region = (*draft_region).or(props.region);
+        let beat_grid = props.beat_grid;
+        let traces = props.traces.clone();
 
         use_effect_with(
             (
@@ -459,6 +497,7 @@ pub fn pitch_plot(props: &PitchPlotProps) -> Html {
                 is_recording, // 상태 변경 감지 위해 추가
                 props.is_frozen, // 화면 고정 상태 감지
                 *current_recording_time, // 녹음 시간 변경 감지 위해 추가
+                (target_notes.clone(), region, beat_grid, traces.clone()), // 목표 멜로디, 선택 구간, 박자 격자, 반주 트랙 변경 감지
             ),
             move |_| {
                 // 현재 시간 얻기 (초 단위)
@@ -658,6 +697,59 @@ pub fn pitch_plot(props: &PitchPlotProps) -> Html {
                         .build_cartesian_2d(x_min..x_max, min_log..max_log) // 로그 스케일 범위 사용
                         .unwrap();
 
+                    // 마우스 좌표 -> 시간 변환용 플롯 영역 저장
+                    let (pixel_x, _) = chart.plotting_area().get_pixel_range();
+                    *plot_pixel_range.borrow_mut() = Some((pixel_x.start, pixel_x.end));
+
+                    // 선택 구간 음영 표시
+                    if let Some((start, end)) = region {
+                        let start = start.max(x_min);
+                        let end = end.min(x_max);
+                        if end > start {
+                            chart
+                                .draw_series(std::iter::once(Rectangle::new(
+                                    [(start, min_log), (end, max_log)],
+                                    RGBColor(102, 126, 234).mix(0.18).filled(), // #667eea
+                                )))
+                                .unwrap();
+                            for edge in [start, end] {
+                                chart
+                                    .draw_series(std::iter::once(PathElement::new(
+                                        vec![(edge, min_log), (edge, max_log)],
+                                        ShapeStyle::from(&RGBColor(102, 126, 234)).stroke_width(1),
+                                    )))
+                                    .unwrap();
+                            }
+                        }
+                    }
+
+                    // 마디/박 세로선 (마디 첫 박은 진하게, 마디 번호 표시)
+                    if let Some(grid) = beat_grid {
+                        let bar_label_style = TextStyle::from(("Lexend", 12).into_font()).color(&RGBColor(139, 146, 165)); // #8b92a5
+                        for (time, bar, is_bar_start) in grid.beats_between(x_min, x_max) {
+                            let (color, width) = if is_bar_start {
+                                (RGBColor(90, 98, 120), 2) // #5a6278
+                            } else {
+                                (RGBColor(45, 50, 64), 1) // #2d3240
+                            };
+                            chart
+                                .draw_series(std::iter::once(PathElement::new(
+                                    vec![(time, min_log), (time, max_log)],
+                                    ShapeStyle::from(&color).stroke_width(width),
+                                )))
+                                .unwrap();
+                            if is_bar_start {
+                                chart
+                                    .draw_series(std::iter::once(Text::new(
+                                        bar.to_string(),
+                                        (time, max_log),
+                                        &bar_label_style,
+                                    )))
+                                    .unwrap();
+                            }
+                        }
+                    }
+
                     // 라벨과 보조선 위치 설정
                     let mut y_labels: Vec<(f64, String, bool)> = Vec::new();
                     let mut grid_lines: Vec<f64> = Vec::new();
@@ -811,6 +903,48 @@ pub fn pitch_plot(props: &PitchPlotProps) -> Html {
                         .unwrap();
                     }
 
+                    // 목표 멜로디 피아노 롤 레인 그리기 (실시간 피치 점보다 뒤에 위치)
+                    let lane_time = if is_recording {
+                        *current_recording_time
+                    } else {
+                        playback_time.or(*last_playback_time).unwrap_or(-1.0)
+                    };
+                    for note in target_notes.iter() {
+                        if note.end() < x_min || note.start > x_max {
+                            continue;
+                        }
+
+                        // 반음 폭의 레인 (목표 음 ±50 cents)
+                        let center_log = midi_to_frequency(note.midi).log10();
+                        let half_semitone_log = 2f64.log10() / 24.0;
+                        let lane_low = center_log - half_semitone_log;
+                        let lane_high = center_log + half_semitone_log;
+                        if lane_high < min_log || lane_low > max_log {
+                            continue;
+                        }
+                        let lane_low = lane_low.max(min_log);
+                        let lane_high = lane_high.min(max_log);
+                        let start = note.start.max(x_min);
+                        let end = note.end().min(x_max);
+
+                        // 현재 시간에 해당하는 목표 음은 밝게 강조
+                        let is_active = lane_time >= note.start && lane_time <= note.end();
+                        let opacity = if is_active { 0.45 } else { 0.2 };
+
+                        chart
+                            .draw_series(std::iter::once(Rectangle::new(
+                                [(start, lane_low), (end, lane_high)],
+                                RGBColor(64, 224, 208).mix(opacity).filled(), // #40e0d0 청록색
+                            )))
+                            .unwrap();
+                        chart
+                            .draw_series(std::iter::once(Rectangle::new(
+                                [(start, lane_low), (end, lane_high)],
+                                ShapeStyle::from(&RGBColor(64, 224, 208)).stroke_width(1),
+                            )))
+                            .unwrap();
+                    }
+
                     // 모든 시간대에 대해 점 그리기 및 각 시간대의 최대 진폭 찾기
                     let mut time_grouped_points: BTreeMap<i64, Vec<(f64, f32)>> = BTreeMap::new();
                     
@@ -909,6 +1043,19 @@ pub fn pitch_plot(props: &PitchPlotProps) -> Html {
                             .unwrap();
                     }
                     
+                    // 반주 트랙 피치 (프레임마다 가장 강한 주파수를 트랙 색으로)
+                    for trace in &traces {
+                        let (r, g, b) = trace.color;
+                        let points = trace
+                            .history
+                            .iter()
+                            .filter(|(t, _)| *t >= x_min && *t <= x_max)
+                            .filter_map(|(t, freqs)| freqs.first().map(|(freq, _)| (*t, freq.log10())))
+                            .filter(|(_, log_freq)| *log_freq >= min_log && *log_freq <= max_log)
+                            .map(|point| Circle::new(point, 2, RGBColor(r, g, b).filled()));
+                        chart.draw_series(points).unwrap();
+                    }
+
                     // 전체 히스토리에서 마지막 시간대의 시간 키를 찾는다 (화면에 보이는 영역이 아닌 전체 데이터 기준)
                     let absolute_latest_time = history.back().map(|(t, _)| (*t * 1000.0) as i64);
                     
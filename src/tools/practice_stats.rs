@@ -0,0 +1,414 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use plotters::prelude::*;
+use plotters_canvas::CanvasBackend;
+use web_sys::HtmlCanvasElement;
+use yew::prelude::*;
+
+use crate::event_bus::{subscribe, AppEvent, Subscription};
+use crate::practice_log::{
+    clear_practice_log, daily_minutes, day_label, load_practice_log, local_day_index, streaks, week_index, PracticeEntry,
+    PracticeTool,
+};
+
+// 조건부 로그 매크로 정의
+#[cfg(debug_assertions)]
+macro_rules! console_error {
+    ($($arg:tt)*) => {
+        web_sys::console::error_1(&format!($($arg)*).into());
+    };
+}
+
+#[cfg(not(debug_assertions))]
+macro_rules! console_error {
+    ($($arg:tt)*) => {};
+}
+
+const DAILY_CHART_DAYS: i64 = 14;  // 일별 차트에 표시할 일 수
+const WEEKLY_CHART_WEEKS: i64 = 8; // 주별 차트에 표시할 주 수
+const RECENT_ENTRIES: usize = 20;  // 최근 기록 표에 표시할 개수
+
+// 차트 색상 (피치 플롯과 같은 다크 테마)
+const CHART_BACKGROUND: RGBColor = RGBColor(15, 20, 25);
+const CHART_BAR: RGBColor = RGBColor(139, 154, 255);
+const CHART_LINE: RGBColor = RGBColor(64, 224, 208);
+const CHART_TEXT: RGBColor = RGBColor(160, 168, 192);
+const CHART_GRID: RGBColor = RGBColor(58, 63, 78);
+
+// 점수 추이를 묶는 단위 (도구, 연습 내용)
+type ExerciseKey = (PracticeTool, Option<String>);
+
+pub enum PracticeStatsMsg {
+    LogUpdated,
+    SelectTool(PracticeTool),
+    SelectExercise(usize), // 선택한 도구의 연습 목록 내 위치
+    ClearLog,
+}
+
+pub struct PracticeStats {
+    entries: Vec<PracticeEntry>,
+    selected: Option<ExerciseKey>,       // 점수 추이 차트에 표시할 연습
+    daily_canvas_ref: NodeRef,
+    weekly_canvas_ref: NodeRef,
+    progress_canvas_ref: NodeRef,
+    _log_subscription: Subscription,     // 다른 도구에서 기록이 추가되면 갱신
+}
+
+impl Component for PracticeStats {
+    type Message = PracticeStatsMsg;
+    type Properties = ();
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let link = ctx.link().clone();
+        let subscription = subscribe(move |event| {
+            if let AppEvent::PracticeLogUpdated = event {
+                link.send_message(PracticeStatsMsg::LogUpdated);
+            }
+        });
+
+        let entries = load_practice_log();
+        let selected = scored_exercises(&entries).into_iter().next();
+        Self {
+            entries,
+            selected,
+            daily_canvas_ref: NodeRef::default(),
+            weekly_canvas_ref: NodeRef::default(),
+            progress_canvas_ref: NodeRef::default(),
+            _log_subscription: subscription,
+        }
+    }
+
+    fn update(&mut self, _ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            PracticeStatsMsg::LogUpdated => {
+                self.entries = load_practice_log();
+                let exercises = scored_exercises(&self.entries);
+                if !self.selected.as_ref().is_some_and(|key| exercises.contains(key)) {
+                    self.selected = exercises.into_iter().next();
+                }
+                true
+            }
+            PracticeStatsMsg::SelectTool(tool) => {
+                // 도구를 바꾸면 그 도구의 첫 연습 선택
+                self.selected = scored_exercises(&self.entries).into_iter().find(|(t, _)| *t == tool);
+                true
+            }
+            PracticeStatsMsg::SelectExercise(index) => {
+                let tool = match &self.selected {
+                    Some((tool, _)) => *tool,
+                    None => return false,
+                };
+                if let Some(key) = scored_exercises(&self.entries).into_iter().filter(|(t, _)| *t == tool).nth(index) {
+                    self.selected = Some(key);
+                }
+                true
+            }
+            PracticeStatsMsg::ClearLog => {
+                let confirmed = web_sys::window()
+                    .and_then(|w| w.confirm_with_message("모든 연습 기록을 삭제할까요?").ok())
+                    .unwrap_or(false);
+                if confirmed {
+                    clear_practice_log();
+                    self.selected = None;
+                }
+                false
+            }
+        }
+    }
+
+    fn rendered(&mut self, _ctx: &Context<Self>, _first_render: bool) {
+        self.draw_charts();
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let link = ctx.link();
+        let today = local_day_index(js_sys::Date::now());
+        let daily = daily_minutes(&self.entries, local_day_index);
+        let today_minutes = daily.get(&today).copied().unwrap_or(0.0);
+        let week_minutes: f64 = daily.iter().filter(|(day, _)| week_index(**day) == week_index(today)).map(|(_, m)| m).sum();
+        let (current_streak, longest_streak) = streaks(&daily, today);
+        let exercises = scored_exercises(&self.entries);
+        let tools: BTreeSet<PracticeTool> = exercises.iter().map(|(tool, _)| *tool).collect();
+        let selected_tool = self.selected.as_ref().map(|(tool, _)| *tool);
+        let tool_exercises: Vec<ExerciseKey> =
+            exercises.iter().filter(|(tool, _)| Some(*tool) == selected_tool).cloned().collect();
+
+        html! {
+            <div class="practice-stats">
+                <div class="stats-summary">
+                    <span>{format!("오늘 {:.0}분", today_minutes)}</span>
+                    <span>{format!("이번 주 {:.0}분", week_minutes)}</span>
+                    <span>{format!("연속 {}일 🔥", current_streak)}</span>
+                    <span>{format!("최장 연속 {}일", longest_streak)}</span>
+                    <span>{format!("총 {}회 연습", self.entries.len())}</span>
+                </div>
+
+                <div class="stats-charts">
+                    <div>
+                        <h4>{format!("최근 {}일 연습 시간 (분)", DAILY_CHART_DAYS)}</h4>
+                        <canvas ref={self.daily_canvas_ref.clone()} width="500" height="220" style="width: 100%; height: auto;"></canvas>
+                    </div>
+                    <div>
+                        <h4>{format!("최근 {}주 연습 시간 (분)", WEEKLY_CHART_WEEKS)}</h4>
+                        <canvas ref={self.weekly_canvas_ref.clone()} width="500" height="220" style="width: 100%; height: auto;"></canvas>
+                    </div>
+                </div>
+
+                <div class="stats-progress">
+                    <h4>{"연습별 점수 추이"}</h4>
+                    {
+                        if tools.is_empty() {
+                            html! { <p>{"아직 점수가 기록된 연습이 없습니다. 청음 퀴즈, 음 맞추기, 리듬 훈련 등을 해 보세요."}</p> }
+                        } else {
+                            html! {
+                                <>
+                                    <div class="button-group">
+                                        {
+                                            tools.iter().map(|tool| {
+                                                let tool = *tool;
+                                                html! {
+                                                    <button
+                                                        class={classes!("tool-select", if selected_tool == Some(tool) { "active" } else { "" })}
+                                                        onclick={link.callback(move |_| PracticeStatsMsg::SelectTool(tool))}
+                                                    >
+                                                        {tool.label()}
+                                                    </button>
+                                                }
+                                            }).collect::<Html>()
+                                        }
+                                    </div>
+                                    <div class="setting-row">
+                                        <span class="setting-label">{"연습:"}</span>
+                                        <select onchange={link.callback(|e: Event| {
+                                            let select = e.target_dyn_into::<web_sys::HtmlSelectElement>().unwrap();
+                                            PracticeStatsMsg::SelectExercise(select.value().parse().unwrap_or(0))
+                                        })}>
+                                            {
+                                                tool_exercises.iter().enumerate().map(|(index, key)| html! {
+                                                    <option value={index.to_string()} selected={self.selected.as_ref() == Some(key)}>
+                                                        {exercise_label(key)}
+                                                    </option>
+                                                }).collect::<Html>()
+                                            }
+                                        </select>
+                                    </div>
+                                    <canvas ref={self.progress_canvas_ref.clone()} width="1000" height="240" style="width: 100%; height: auto;"></canvas>
+                                </>
+                            }
+                        }
+                    }
+                </div>
+
+                <div class="stats-recent">
+                    <h4>{"최근 기록"}</h4>
+                    <table class="stats-table">
+                        <thead>
+                            <tr>
+                                <th>{"날짜"}</th>
+                                <th>{"도구"}</th>
+                                <th>{"시간"}</th>
+                                <th>{"BPM"}</th>
+                                <th>{"내용"}</th>
+                                <th>{"점수"}</th>
+                            </tr>
+                        </thead>
+                        <tbody>
+                            {
+                                self.entries.iter().rev().take(RECENT_ENTRIES).map(|entry| html! {
+                                    <tr>
+                                        <td>{format_timestamp(entry.started_at)}</td>
+                                        <td>{entry.tool.label()}</td>
+                                        <td>{format_duration(entry.duration_secs)}</td>
+                                        <td>{entry.bpm.map(|b| b.to_string()).unwrap_or_else(|| "-".to_string())}</td>
+                                        <td>
+                                            {entry.exercise.clone().unwrap_or_default()}
+                                            {entry.details.as_ref().map(|d| format!(" ({})", d)).unwrap_or_default()}
+                                        </td>
+                                        <td>{entry.score.map(|s| format!("{:.0}", s)).unwrap_or_else(|| "-".to_string())}</td>
+                                    </tr>
+                                }).collect::<Html>()
+                            }
+                        </tbody>
+                    </table>
+                    <button class="reset-button" onclick={link.callback(|_| PracticeStatsMsg::ClearLog)}>
+                        {"기록 전체 삭제"}
+                    </button>
+                </div>
+            </div>
+        }
+    }
+}
+
+impl PracticeStats {
+    fn draw_charts(&self) {
+        let today = local_day_index(js_sys::Date::now());
+        let daily = daily_minutes(&self.entries, local_day_index);
+
+        // 일별 연습 시간
+        let daily_bars: Vec<(String, f64)> = (0..DAILY_CHART_DAYS)
+            .rev()
+            .map(|offset| {
+                let day = today - offset;
+                (day_label(day), daily.get(&day).copied().unwrap_or(0.0))
+            })
+            .collect();
+        draw_bar_chart(&self.daily_canvas_ref, &daily_bars);
+
+        // 주별 연습 시간 (월요일 시작)
+        let mut weekly: BTreeMap<i64, f64> = BTreeMap::new();
+        for (day, minutes) in &daily {
+            *weekly.entry(week_index(*day)).or_insert(0.0) += minutes;
+        }
+        let this_week = week_index(today);
+        let weekly_bars: Vec<(String, f64)> = (0..WEEKLY_CHART_WEEKS)
+            .rev()
+            .map(|offset| {
+                let week = this_week - offset;
+                // 주의 첫날(월요일) 날짜로 라벨 표시
+                (day_label(week * 7 - 3), weekly.get(&week).copied().unwrap_or(0.0))
+            })
+            .collect();
+        draw_bar_chart(&self.weekly_canvas_ref, &weekly_bars);
+
+        // 선택한 연습의 점수 추이
+        if let Some((tool, exercise)) = &self.selected {
+            let scores: Vec<f64> = self
+                .entries
+                .iter()
+                .filter(|e| e.tool == *tool && e.exercise == *exercise)
+                .filter_map(|e| e.score)
+                .collect();
+            draw_progress_chart(&self.progress_canvas_ref, &scores);
+        }
+    }
+}
+
+// 점수가 기록된 (도구, 연습 내용) 목록
+fn scored_exercises(entries: &[PracticeEntry]) -> Vec<ExerciseKey> {
+    let exercises: BTreeSet<ExerciseKey> =
+        entries.iter().filter(|e| e.score.is_some()).map(|e| (e.tool, e.exercise.clone())).collect();
+    exercises.into_iter().collect()
+}
+
+fn exercise_label((_, exercise): &ExerciseKey) -> String {
+    exercise.clone().unwrap_or_else(|| "기타".to_string())
+}
+
+fn format_timestamp(timestamp_ms: f64) -> String {
+    let date = js_sys::Date::new(&wasm_bindgen::JsValue::from_f64(timestamp_ms));
+    format!(
+        "{}/{} {:02}:{:02}",
+        date.get_month() + 1,
+        date.get_date(),
+        date.get_hours(),
+        date.get_minutes()
+    )
+}
+
+fn format_duration(secs: f64) -> String {
+    let secs = secs.round() as u64;
+    format!("{}:{:02}", secs / 60, secs % 60)
+}
+
+// 라벨이 붙은 막대 차트
+fn draw_bar_chart(canvas_ref: &NodeRef, bars: &[(String, f64)]) {
+    let canvas = match canvas_ref.cast::<HtmlCanvasElement>() {
+        Some(canvas) => canvas,
+        None => return,
+    };
+    let backend = match CanvasBackend::with_canvas_object(canvas) {
+        Some(backend) => backend,
+        None => return,
+    };
+    let root = backend.into_drawing_area();
+    if root.fill(&CHART_BACKGROUND).is_err() {
+        return;
+    }
+
+    let max_value = bars.iter().map(|(_, v)| *v).fold(0.0, f64::max).max(10.0) * 1.1;
+    let count = bars.len();
+    let result = (|| -> Result<(), Box<dyn std::error::Error>> {
+        let mut chart = ChartBuilder::on(&root)
+            .margin(10)
+            .set_label_area_size(LabelAreaPosition::Left, 40)
+            .set_label_area_size(LabelAreaPosition::Bottom, 30)
+            .build_cartesian_2d(-0.5..(count as f64 - 0.5), 0.0..max_value)?;
+
+        chart
+            .configure_mesh()
+            .disable_x_mesh()
+            .x_labels(count)
+            .x_label_formatter(&|x| {
+                let idx = x.round();
+                if (x - idx).abs() < 0.01 && idx >= 0.0 && (idx as usize) < count {
+                    bars[idx as usize].0.clone()
+                } else {
+                    String::new()
+                }
+            })
+            .y_label_formatter(&|y| format!("{:.0}", y))
+            .label_style(("sans-serif", 11).into_font().color(&CHART_TEXT))
+            .axis_style(CHART_GRID)
+            .light_line_style(CHART_GRID.mix(0.3))
+            .bold_line_style(CHART_GRID.mix(0.6))
+            .draw()?;
+
+        chart.draw_series(bars.iter().enumerate().map(|(i, (_, value))| {
+            let x = i as f64;
+            Rectangle::new([(x - 0.35, 0.0), (x + 0.35, *value)], CHART_BAR.filled())
+        }))?;
+        Ok(())
+    })();
+
+    if let Err(err) = result {
+        console_error!("연습 통계 차트 그리기 실패: {:?}", err);
+    }
+    let _ = root.present();
+}
+
+// 회차별 점수 추이 (0 ~ 100)
+fn draw_progress_chart(canvas_ref: &NodeRef, scores: &[f64]) {
+    let canvas = match canvas_ref.cast::<HtmlCanvasElement>() {
+        Some(canvas) => canvas,
+        None => return,
+    };
+    let backend = match CanvasBackend::with_canvas_object(canvas) {
+        Some(backend) => backend,
+        None => return,
+    };
+    let root = backend.into_drawing_area();
+    if root.fill(&CHART_BACKGROUND).is_err() {
+        return;
+    }
+
+    let result = (|| -> Result<(), Box<dyn std::error::Error>> {
+        let mut chart = ChartBuilder::on(&root)
+            .margin(10)
+            .set_label_area_size(LabelAreaPosition::Left, 40)
+            .set_label_area_size(LabelAreaPosition::Bottom, 30)
+            .build_cartesian_2d(1.0..(scores.len().max(2) as f64), 0.0..100.0)?;
+
+        chart
+            .configure_mesh()
+            .x_desc("회차")
+            .y_desc("점수")
+            .x_label_formatter(&|x| format!("{:.0}", x))
+            .y_label_formatter(&|y| format!("{:.0}", y))
+            .label_style(("sans-serif", 11).into_font().color(&CHART_TEXT))
+            .axis_desc_style(("sans-serif", 12).into_font().color(&CHART_TEXT))
+            .axis_style(CHART_GRID)
+            .light_line_style(CHART_GRID.mix(0.3))
+            .bold_line_style(CHART_GRID.mix(0.6))
+            .draw()?;
+
+        let points: Vec<(f64, f64)> = scores.iter().enumerate().map(|(i, s)| ((i + 1) as f64, *s)).collect();
+        chart.draw_series(LineSeries::new(points.clone(), CHART_LINE.stroke_width(2)))?;
+        chart.draw_series(points.into_iter().map(|p| Circle::new(p, 3, CHART_BAR.filled())))?;
+        Ok(())
+    })();
+
+    if let Err(err) = result {
+        console_error!("점수 추이 차트 그리기 실패: {:?}", err);
+    }
+    let _ = root.present();
+}
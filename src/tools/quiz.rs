@@ -0,0 +1,223 @@
+use yew::prelude::*;
+
+use crate::practice_log::{save_practice_entry, PracticeEntry, PracticeTool};
+
+// 청음 퀴즈(음정, 화음)가 함께 쓰는 문제 상태와 채점
+
+const WEAK_ACCURACY: f64 = 0.6; // 이보다 정확도가 낮으면 약점으로 표시
+const WEAK_MIN_ATTEMPTS: u32 = 3;
+
+// 문제 유형별 정답 통계
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct QuizStats {
+    pub attempts: u32,
+    pub correct: u32,
+}
+
+impl QuizStats {
+    pub fn accuracy(&self) -> Option<f64> {
+        if self.attempts == 0 {
+            None
+        } else {
+            Some(self.correct as f64 / self.attempts as f64)
+        }
+    }
+
+    pub fn is_weak(&self) -> bool {
+        self.attempts >= WEAK_MIN_ATTEMPTS && self.accuracy().unwrap_or(1.0) < WEAK_ACCURACY
+    }
+
+    // 출제 가중치 - 오답률이 높을수록 자주 출제 (시도가 적으면 50% 오답률로 간주)
+    pub fn weight(&self) -> f64 {
+        let misses = self.attempts - self.correct;
+        let smoothed_error = (misses + 1) as f64 / (self.attempts + 2) as f64;
+        1.0 + 4.0 * smoothed_error
+    }
+
+    fn record(&mut self, correct: bool) {
+        self.attempts += 1;
+        if correct {
+            self.correct += 1;
+        }
+    }
+}
+
+// 출제 중인 문제에 대한 답변과 누적 통계 (문제 유형은 각 퀴즈의 목록 인덱스)
+pub struct QuizState {
+    pub stats: Vec<QuizStats>, // 문제 유형별 통계
+    pub answered: Option<usize>, // 현재 문제에 대해 고른 답
+    pub streak: u32,           // 연속 정답 수
+    session_started_at: f64,   // 이번 연습 세션 시작 시각 (연습 기록용)
+    session_stats: QuizStats,  // 이번 세션 전체 정답 통계
+}
+
+impl QuizState {
+    pub fn new(kinds: usize) -> Self {
+        Self {
+            stats: vec![QuizStats::default(); kinds],
+            answered: None,
+            streak: 0,
+            session_started_at: 0.0,
+            session_stats: QuizStats::default(),
+        }
+    }
+
+    // 새 문제 출제 - 이전 답변 지움
+    pub fn next_question(&mut self) {
+        self.answered = None;
+    }
+
+    // 답변 채점 (이미 답한 문제면 None, 아니면 정답 여부)
+    pub fn answer(&mut self, chosen: usize, expected: usize, now: f64) -> Option<bool> {
+        if self.answered.is_some() {
+            return None;
+        }
+
+        let correct = chosen == expected;
+        self.stats[expected].record(correct);
+        self.streak = if correct { self.streak + 1 } else { 0 };
+
+        if self.session_stats.attempts == 0 {
+            self.session_started_at = now;
+        }
+        self.session_stats.record(correct);
+        self.answered = Some(chosen);
+        Some(correct)
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self::new(self.stats.len());
+    }
+
+    // 전체 문제 유형을 합친 통계
+    pub fn totals(&self) -> QuizStats {
+        self.stats.iter().fold(QuizStats::default(), |total, stats| QuizStats {
+            attempts: total.attempts + stats.attempts,
+            correct: total.correct + stats.correct,
+        })
+    }
+
+    // 답변 버튼 상태 (답한 뒤 정답은 correct, 고른 오답은 wrong)
+    pub fn answer_class(&self, idx: usize, expected: Option<usize>) -> &'static str {
+        match (expected, self.answered) {
+            (Some(expected), Some(_)) if idx == expected => "correct",
+            (Some(_), Some(chosen)) if idx == chosen => "wrong",
+            _ => "",
+        }
+    }
+
+    // 이번 세션을 연습 기록에 반영 (같은 세션은 한 건으로 갱신)
+    pub fn save_session(&self, tool: PracticeTool, exercise: &str) {
+        save_practice_entry(
+            PracticeEntry::new(tool, self.session_started_at)
+                .finish_now()
+                .with_exercise(exercise)
+                .with_score(self.session_stats.accuracy().unwrap_or(0.0) * 100.0)
+                .with_details(format!("정답 {}/{}", self.session_stats.correct, self.session_stats.attempts)),
+        );
+    }
+
+    // 정답 합계, 연속 정답, 유형별 통계 표
+    pub fn view_stats(&self, heading: &str, names: &[&str], on_reset: Callback<MouseEvent>) -> Html {
+        let totals = self.totals();
+        html! {
+            <div class="trainer-stats">
+                <div class="stats-summary">
+                    <span>{format!("정답 {}/{}", totals.correct, totals.attempts)}</span>
+                    <span>{format!("연속 정답 {}", self.streak)}</span>
+                    <button class="reset-button" onclick={on_reset}>
+                        {"통계 초기화"}
+                    </button>
+                </div>
+                <table class="stats-table">
+                    <thead>
+                        <tr>
+                            <th>{heading}</th>
+                            <th>{"정답/시도"}</th>
+                            <th>{"정확도"}</th>
+                        </tr>
+                    </thead>
+                    <tbody>
+                        {
+                            names.iter().zip(self.stats.iter()).filter(|(_, stats)| stats.attempts > 0).map(|(name, stats)| {
+                                let accuracy = stats.accuracy().map(|a| format!("{:.0}%", a * 100.0)).unwrap_or_else(|| "-".to_string());
+                                html! {
+                                    <tr class={classes!(if stats.is_weak() { "weak" } else { "" })}>
+                                        <td>{*name}{ if stats.is_weak() { " ⚠️" } else { "" } }</td>
+                                        <td>{format!("{}/{}", stats.correct, stats.attempts)}</td>
+                                        <td>{accuracy}</td>
+                                    </tr>
+                                }
+                            }).collect::<Html>()
+                        }
+                    </tbody>
+                </table>
+            </div>
+        }
+    }
+}
+
+// 0 ~ len-1 범위의 무작위 인덱스
+pub fn random_index(len: usize) -> usize {
+    ((js_sys::Math::random() * len as f64) as usize).min(len - 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn answers_update_stats_and_streak() {
+        let mut quiz = QuizState::new(3);
+        assert_eq!(quiz.answer(1, 1, 1000.0), Some(true));
+        quiz.next_question();
+        assert_eq!(quiz.answer(2, 2, 2000.0), Some(true));
+        assert_eq!(quiz.streak, 2);
+
+        // 오답은 정답 유형의 통계에 기록되고 연속 정답이 끊김
+        quiz.next_question();
+        assert_eq!(quiz.answer(0, 1, 3000.0), Some(false));
+        assert_eq!(quiz.streak, 0);
+        assert_eq!(quiz.stats[1], QuizStats { attempts: 2, correct: 1 });
+        assert_eq!(quiz.stats[0], QuizStats::default());
+        assert_eq!(quiz.totals(), QuizStats { attempts: 3, correct: 2 });
+        assert_eq!(quiz.session_started_at, 1000.0);
+        assert_eq!(quiz.session_stats, QuizStats { attempts: 3, correct: 2 });
+    }
+
+    #[test]
+    fn a_question_is_scored_only_once() {
+        let mut quiz = QuizState::new(2);
+        assert_eq!(quiz.answer(0, 1, 0.0), Some(false));
+        assert_eq!(quiz.answer(1, 1, 0.0), None);
+        assert_eq!(quiz.totals(), QuizStats { attempts: 1, correct: 0 });
+
+        assert_eq!(quiz.answer_class(1, Some(1)), "correct");
+        assert_eq!(quiz.answer_class(0, Some(1)), "wrong");
+        quiz.next_question();
+        assert_eq!(quiz.answer_class(1, Some(1)), "");
+    }
+
+    #[test]
+    fn reset_clears_stats_and_session() {
+        let mut quiz = QuizState::new(2);
+        quiz.answer(1, 1, 500.0);
+        quiz.reset();
+        assert_eq!(quiz.stats.len(), 2);
+        assert_eq!(quiz.totals(), QuizStats::default());
+        assert_eq!(quiz.streak, 0);
+        assert_eq!(quiz.answered, None);
+
+        // 초기화 후 첫 답변이 새 세션의 시작
+        quiz.answer(0, 0, 900.0);
+        assert_eq!(quiz.session_started_at, 900.0);
+    }
+
+    #[test]
+    fn weak_types_need_enough_attempts() {
+        assert!(!QuizStats { attempts: 2, correct: 0 }.is_weak());
+        assert!(QuizStats { attempts: 3, correct: 1 }.is_weak());
+        assert!(!QuizStats { attempts: 5, correct: 3 }.is_weak());
+        assert!(QuizStats { attempts: 4, correct: 0 }.weight() > QuizStats { attempts: 4, correct: 4 }.weight());
+    }
+}
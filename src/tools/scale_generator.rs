@@ -1,11 +1,62 @@
-use wasm_bindgen::prelude::*;
-use web_sys::{AudioContext, OscillatorNode, GainNode, HtmlAudioElement, AudioNode};
+use web_sys::HtmlAudioElement;
 use yew::prelude::*;
-use std::collections::HashMap;
-use gloo_timers::callback::Timeout;
+use gloo_timers::callback::{Interval, Timeout};
+use serde::{Deserialize, Serialize};
 use wasm_bindgen::closure::Closure;
 use wasm_bindgen::JsCast;
 
+use crate::audio_engine::{engine_from_scope, AudioEngine, Channel};
+use crate::event_bus::{subscribe, AppEvent, Subscription};
+use crate::pitch_input::{self, cents_ignoring_octave, frequency_to_midi, midi_to_frequency, MicInput};
+use crate::tools::piano_sampler::PianoSample;
+use crate::tools::target_melody::{self, note_name_from_midi, TargetMelody, TargetNote};
+use crate::practice_log::{save_practice_entry, PracticeEntry, PracticeTool};
+use crate::settings::{self, NoteSetting, ScaleGeneratorSettings};
+use crate::routes::Route;
+use crate::share_link::{self, CopyLinkButton};
+
+// 따라 부르기 연습 판정 기준
+const EXERCISE_TOLERANCE_CENTS: f64 = 50.0; // 허용 음정 오차 (센트)
+const HIT_HOLD_MS: u32 = 300;               // 성공으로 인정되는 누적 정확 음정 시간
+const PITCH_POLL_MS: u32 = 50;              // 마이크 피치 검출 간격
+
+// 피치 검출 한 번(PITCH_POLL_MS)만큼 정확 음정 유지 시간 누적 (옥타브 차이는 무시)
+fn accumulate_in_tune(in_tune_ms: u32, sung_freq: f64, target_midi: i32) -> u32 {
+    let cents = cents_ignoring_octave(sung_freq, midi_to_frequency(target_midi));
+    if cents.abs() <= EXERCISE_TOLERANCE_CENTS {
+        in_tune_ms + PITCH_POLL_MS
+    } else {
+        in_tune_ms
+    }
+}
+
+// 판정 구간 동안 충분히 오래 정확한 음정을 유지했는지
+fn is_hit(in_tune_ms: u32) -> bool {
+    in_tune_ms >= HIT_HOLD_MS
+}
+
+// 한 음의 판정이 끝난 뒤 다음 진행
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum JudgingStep {
+    NextNote,  // 셋 안의 다음 음
+    SetPassed, // 셋 통과 - 다음 근음으로
+    AllPassed, // 마지막 셋까지 통과
+    RetrySet,  // 셋 실패 - 같은 근음으로 다시
+}
+
+// 지금까지의 셋 판정 결과로 다음 진행 결정 - 셋의 모든 음이 통과해야만 다음 근음으로 넘어감
+fn next_judging_step(set_hits: &[bool], is_set_end: bool, is_last_note: bool) -> JudgingStep {
+    if !is_set_end {
+        JudgingStep::NextNote
+    } else if !set_hits.iter().all(|hit| *hit) {
+        JudgingStep::RetrySet
+    } else if is_last_note {
+        JudgingStep::AllPassed
+    } else {
+        JudgingStep::SetPassed
+    }
+}
+
 // 조건부 로그 매크로 정의
 #[cfg(debug_assertions)]
 macro_rules! console_log {
@@ -79,6 +130,26 @@ impl Note {
         format!("/static/piano/Piano.ff.{}{}.mp3", file_name, octave)
     }
 
+    // MIDI 노트 번호 (C4 = 60)
+    fn midi(&self) -> i32 {
+        let semitones = match self.name.as_str() {
+            "C" => 0,
+            "C#" | "Db" => 1,
+            "D" => 2,
+            "D#" | "Eb" => 3,
+            "E" => 4,
+            "F" => 5,
+            "F#" | "Gb" => 6,
+            "G" => 7,
+            "G#" | "Ab" => 8,
+            "A" => 9,
+            "A#" | "Bb" => 10,
+            "B" => 11,
+            _ => 0,
+        };
+        (self.octave + 1) * 12 + semitones
+    }
+
     // 주파수 계산 (A4 = 440Hz 기준)
     fn frequency(&self) -> f32 {
         // 모든 음 이름을 반음 단위로 변환
@@ -128,7 +199,7 @@ pub enum ScaleType {
 }
 
 // 재생 방향 열거형
-#[derive(Debug, Clone, PartialEq, Copy)]
+#[derive(Debug, Clone, PartialEq, Copy, Serialize, Deserialize)]
 pub enum PlayDirection {
     Ascending,      // 상행
     Descending,     // 하행
@@ -136,6 +207,48 @@ pub enum PlayDirection {
     BothDescendingFirst,  // 하행 후 상행
 }
 
+impl PlayDirection {
+    // 공유 링크에서 쓰는 값
+    fn query_value(&self) -> &'static str {
+        match self {
+            PlayDirection::Ascending => "up",
+            PlayDirection::Descending => "down",
+            PlayDirection::Both => "both",
+            PlayDirection::BothDescendingFirst => "both-down",
+        }
+    }
+    
+    fn from_query_value(value: &str) -> Option<Self> {
+        [PlayDirection::Ascending, PlayDirection::Descending, PlayDirection::Both, PlayDirection::BothDescendingFirst]
+            .into_iter()
+            .find(|direction| direction.query_value() == value)
+    }
+}
+
+// 공유 링크 쿼리 (예: /scale-generator?from=C3&to=G4&intervals=1-2-3-4-5-4-3-2-1&bpm=100&dir=both)
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+struct ScaleGeneratorQuery {
+    from: Option<String>,
+    to: Option<String>,
+    intervals: Option<String>, // 음정을 '-'로 연결
+    bpm: Option<u32>,
+    dir: Option<String>,
+}
+
+// "C3", "F#4" 같은 음 이름을 (음 이름, 옥타브)로 분리
+fn parse_note_setting(value: &str) -> Option<NoteSetting> {
+    const NAMES: [&str; 12] = ["C", "C#", "D", "D#", "E", "F", "F#", "G", "G#", "A", "A#", "B"];
+    let split = value.find(|c: char| c.is_ascii_digit())?;
+    let (name, octave) = value.split_at(split);
+    let octave = octave.parse::<i32>().ok()?;
+    if NAMES.contains(&name) && (0..=8).contains(&octave) {
+        Some(NoteSetting { name: name.to_string(), octave })
+    } else {
+        None
+    }
+}
+
 // 현재 재생 상태 열거형
 #[derive(Debug, Clone, PartialEq, Copy)]
 pub enum PlaybackState {
@@ -144,6 +257,22 @@ pub enum PlaybackState {
     Paused,         // 일시 정지
 }
 
+// 따라 부르기 연습 모드
+#[derive(Debug, Clone, PartialEq, Copy)]
+pub enum ExerciseMode {
+    Off,             // 연습 모드 끔 (일반 재생)
+    CallAndResponse, // 음을 들은 뒤 따라 부르기
+    Simultaneous,    // 음과 동시에 부르기
+}
+
+// 연습 중 현재 단계
+#[derive(Debug, Clone, PartialEq, Copy)]
+pub enum ExercisePhase {
+    Idle,      // 대기
+    Listening, // 음 듣는 중
+    Singing,   // 부르는 중 (판정 중)
+}
+
 // 스케일 생성기 메시지 열거형
 pub enum ScaleGeneratorMsg {
     SetStartNote(String, i32),  // 시작 근음 설정 (음 이름, 옥타브)
@@ -159,6 +288,14 @@ pub enum ScaleGeneratorMsg {
     PlayNextNote,               // 다음 음 재생
     InitAudioContext,           // 오디오 컨텍스트 초기화
     ClearIntervals,             // 인터벌 초기화 (근음만 남김)
+    SendToTargetMelody,         // 현재 설정을 피치 분석기의 목표 멜로디로 보내기
+    SetExerciseMode(ExerciseMode), // 따라 부르기 연습 모드 설정
+    ToggleHeadphones,           // 이어폰 사용 여부 토글 (함께 부르기 판정에 필요)
+    MicReady(Result<MicInput, String>), // 연습용 마이크 준비 완료
+    ExercisePitch,              // 마이크 피치 검출 (주기적)
+    StartResponse(u32),         // 따라 부르기 구간 시작 (구간 길이 ms)
+    FinishJudging,              // 현재 음 판정 종료
+    SettingsReset,              // 저장된 설정이 기본값으로 초기화됨
 }
 
 // 스케일 생성기 컴포넌트
@@ -172,33 +309,104 @@ pub struct ScaleGenerator {
     current_note_idx: usize,    // 현재 재생 중인 음 인덱스
     current_root_note: Option<Note>, // 현재 재생 중인 근음
     current_playing_note: Option<Note>, // 현재 재생 중인 음
-    audio_ctx: Option<AudioContext>, // 오디오 컨텍스트
+    engine: AudioEngine,        // 공유 오디오 엔진 (음은 스케일 채널로 출력)
     notes_to_play: Vec<Note>,   // 재생할 음 목록
     play_timeout: Option<Timeout>, // 재생 타이머
     is_ascending: bool,         // 현재 상행 중인지 여부
-    audio_element: Option<HtmlAudioElement>, // 오디오 요소
+    audio_element: Option<PianoSample>, // 재생 중인 피아노 음원 (채널 연결 포함)
+    
+    // 따라 부르기 연습 관련 필드
+    exercise_mode: ExerciseMode,      // 연습 모드
+    exercise_phase: ExercisePhase,    // 현재 연습 단계
+    headphones: bool,                 // 이어폰 사용 중 (스피커 소리가 마이크에 섞이지 않음)
+    mic: Option<MicInput>,            // 연습용 마이크 입력
+    pitch_interval: Option<Interval>, // 피치 검출 타이머
+    judging_note: Option<Note>,       // 판정 중인 음
+    in_tune_ms: u32,                  // 판정 구간에서 정확한 음정을 유지한 누적 시간
+    sung_freq: Option<f64>,           // 현재 부르고 있는 주파수
+    set_start_idx: usize,             // 현재 스케일 셋의 시작 인덱스 (재시도 시 되돌아갈 위치)
+    set_results: Vec<(Note, bool)>,   // 현재 셋 시도의 음별 판정 결과
+    set_attempts: u32,                // 현재 셋의 실패 횟수
+    passed_sets: u32,                 // 통과한 셋 수
+    failed_sets: u32,                 // 이번 연습의 전체 실패 횟수
+    exercise_status: Option<String>,  // 연습 상태 메시지
+    exercise_error: Option<String>,   // 마이크 오류 메시지
+    play_started_at: f64,             // 재생 시작 시각 (연습 기록용)
+    _settings_subscription: Subscription, // 설정 초기화 이벤트 구독
 }
 
 impl Component for ScaleGenerator {
     type Message = ScaleGeneratorMsg;
     type Properties = ();
 
-    fn create(_ctx: &Context<Self>) -> Self {
+    fn create(ctx: &Context<Self>) -> Self {
+        // 저장된 설정 불러오기 (처음에는 C4 - C5, 120 BPM, 근음만, 상행) - 공유 링크로 열었으면 링크의 값이 우선
+        let mut saved = settings::load_settings().scale_generator;
+        if let Some(query) = share_link::read_query::<ScaleGeneratorQuery>(ctx.link(), &Route::ScaleGenerator) {
+            if let Some(note) = query.from.as_deref().and_then(parse_note_setting) {
+                saved.start_note = note;
+            }
+            if let Some(note) = query.to.as_deref().and_then(parse_note_setting) {
+                saved.end_note = note;
+            }
+            if let Some(intervals) = query.intervals {
+                // 알 수 없는 음정은 버림 (근음 "1"만 0 반음)
+                let parsed: Vec<String> = intervals
+                    .split('-')
+                    .filter(|i| *i == "1" || Self::interval_semitones(i) != 0)
+                    .map(str::to_string)
+                    .collect();
+                if !parsed.is_empty() {
+                    saved.intervals = parsed;
+                }
+            }
+            if let Some(bpm) = query.bpm {
+                saved.bpm = bpm.clamp(30, 300);
+            }
+            if let Some(direction) = query.dir.as_deref().and_then(PlayDirection::from_query_value) {
+                saved.direction = direction;
+            }
+        }
+        
+        let link = ctx.link().clone();
+        let settings_subscription = subscribe(move |event| {
+            if let AppEvent::SettingsReset = event {
+                link.send_message(ScaleGeneratorMsg::SettingsReset);
+            }
+        });
+        
         Self {
-            start_note: Note::new("C", 4),  // 기본값 C4
-            end_note: Note::new("C", 5),    // 기본값 C5
-            bpm: 120,                       // 기본값 120 BPM
-            intervals: vec!["1".to_string()], // 기본값 근음(1도)
-            play_direction: PlayDirection::Ascending, // 기본값 상행
+            start_note: Note::new(&saved.start_note.name, saved.start_note.octave),
+            end_note: Note::new(&saved.end_note.name, saved.end_note.octave),
+            bpm: saved.bpm,
+            intervals: saved.intervals,
+            play_direction: saved.direction,
             playback_state: PlaybackState::Stopped, // 기본값 정지
             current_note_idx: 0,
             current_root_note: None,
             current_playing_note: None,
-            audio_ctx: None,
+            engine: engine_from_scope(ctx.link()),
             notes_to_play: Vec::new(),
             play_timeout: None,
             is_ascending: true,
             audio_element: None,
+            exercise_mode: ExerciseMode::Off,
+            exercise_phase: ExercisePhase::Idle,
+            headphones: false,
+            mic: None,
+            pitch_interval: None,
+            judging_note: None,
+            in_tune_ms: 0,
+            sung_freq: None,
+            set_start_idx: 0,
+            set_results: Vec::new(),
+            set_attempts: 0,
+            passed_sets: 0,
+            failed_sets: 0,
+            exercise_status: None,
+            exercise_error: None,
+            play_started_at: 0.0,
+            _settings_subscription: settings_subscription,
         }
     }
 
@@ -215,6 +423,7 @@ impl Component for ScaleGenerator {
                 };
                 
                 self.start_note = Note::new(&name, adjusted_octave);
+                self.save_settings();
                 true
             }
             ScaleGeneratorMsg::SetEndNote(name, octave) => {
@@ -228,21 +437,25 @@ impl Component for ScaleGenerator {
                 };
                 
                 self.end_note = Note::new(&name, adjusted_octave);
+                self.save_settings();
                 true
             }
             ScaleGeneratorMsg::SetBpm(bpm) => {
                 self.bpm = bpm;
+                self.save_settings();
                 true
             }
             ScaleGeneratorMsg::AddInterval => {
                 // 기본값 "1"(근음)으로 새 인터벌 추가
                 self.intervals.push("1".to_string());
+                self.save_settings();
                 true
             }
             ScaleGeneratorMsg::RemoveInterval(index) => {
                 // 최소 1개의 인터벌은 남겨둬야 함
                 if self.intervals.len() > 1 && index < self.intervals.len() {
                     self.intervals.remove(index);
+                    self.save_settings();
                     true
                 } else {
                     false
@@ -251,6 +464,7 @@ impl Component for ScaleGenerator {
             ScaleGeneratorMsg::SetIntervalValue(index, value) => {
                 if index < self.intervals.len() {
                     self.intervals[index] = value;
+                    self.save_settings();
                     true
                 } else {
                     false
@@ -258,6 +472,7 @@ impl Component for ScaleGenerator {
             }
             ScaleGeneratorMsg::SetPlayDirection(direction) => {
                 self.play_direction = direction;
+                self.save_settings();
                 true
             }
             ScaleGeneratorMsg::Play => {
@@ -266,23 +481,36 @@ impl Component for ScaleGenerator {
                     return false;
                 }
                 
-                // 오디오 컨텍스트 초기화 여부 확인
-                if self.audio_ctx.is_none() {
-                    // 오디오 컨텍스트 초기화
-                    match AudioContext::new() {
-                        Ok(ctx) => {
-                            console_log!("오디오 컨텍스트 초기화 성공");
-                            self.audio_ctx = Some(ctx);
-                        }
-                        Err(err) => {
-                            console_error!("오디오 컨텍스트 초기화 실패: {:?}", err);
-                            return false;
-                        }
+                // 함께 부르기는 들려주는 음이 판정 구간 내내 울리므로 스피커로 들으면 그 소리만으로 통과됨
+                if self.exercise_mode == ExerciseMode::Simultaneous && !self.headphones {
+                    self.exercise_error = Some("함께 부르기는 이어폰을 연결한 뒤 '이어폰 사용 중'을 체크해야 판정할 수 있습니다.".to_string());
+                    return true;
+                }
+                
+                // 연습 모드에서는 마이크가 준비된 뒤에 재생 시작
+                if self.exercise_mode != ExerciseMode::Off {
+                    if self.mic.is_none() {
+                        pitch_input::open_microphone(&self.engine, ctx.link().callback(ScaleGeneratorMsg::MicReady));
+                        return false;
                     }
+                    
+                    // 연습 진행 상황 초기화
+                    self.set_results.clear();
+                    self.set_attempts = 0;
+                    self.passed_sets = 0;
+                    self.failed_sets = 0;
+                    self.exercise_status = None;
+                }
+                
+                // 공유 오디오 컨텍스트 준비 (사용자 입력 시점에 재개)
+                if let Err(err) = self.engine.context() {
+                    console_error!("오디오 컨텍스트 초기화 실패: {:?}", err);
+                    return false;
                 }
                 
                 // 상태 업데이트
                 self.playback_state = PlaybackState::Playing;
+                self.play_started_at = js_sys::Date::now();
                 
                 // 재생할 노트 목록 생성
                 self.generate_notes_to_play();
@@ -312,18 +540,22 @@ impl Component for ScaleGenerator {
                 self.play_timeout = None;
                 
                 // 현재 재생 중인 오디오 중지 및 리소스 해제
-                if let Some(audio) = &self.audio_element {
-                    let _ = audio.pause();
-                    let _ = audio.set_src(""); // 리소스 해제
-                    self.audio_element = None;
+                if let Some(audio) = self.audio_element.take() {
+                    audio.stop();
                 }
                 
                 // 상태 업데이트
                 self.playback_state = PlaybackState::Stopped;
+                self.save_practice();
                 self.current_note_idx = 0;
                 self.current_root_note = None;
                 self.current_playing_note = None;
                 
+                // 연습 판정 중지 및 마이크 해제
+                self.judging_note = None;
+                self.exercise_phase = ExercisePhase::Idle;
+                self.close_microphone();
+                
                 true
             }
             ScaleGeneratorMsg::TogglePlayback => {
@@ -378,6 +610,10 @@ impl Component for ScaleGenerator {
                             self.notes_to_play[self.current_note_idx - 1].name == "SET_INTERVAL" && 
                             self.notes_to_play[self.current_note_idx - 1].octave == -1) {
                             self.current_root_note = Some(current_note.clone());
+                            
+                            // 새 셋 시도 시작 - 재시도 위치 기록
+                            self.set_start_idx = self.current_note_idx;
+                            self.set_results.clear();
                         }
                         
                         // 피아노 음원으로 노트 재생
@@ -387,7 +623,21 @@ impl Component for ScaleGenerator {
                         self.current_note_idx = next_idx;
                         
                         // 다음 노트를 위한 타이머 설정
-                        if !is_last_note {
+                        if self.exercise_mode != ExerciseMode::Off {
+                            // 연습 모드: 판정이 끝난 뒤 FinishJudging에서 다음 진행 결정
+                            self.judging_note = Some(current_note.clone());
+                            if self.exercise_mode == ExerciseMode::Simultaneous {
+                                self.begin_judging(ctx, note_duration);
+                            } else {
+                                // 음을 끝까지 들은 뒤 같은 길이만큼 따라 부르기
+                                self.exercise_phase = ExercisePhase::Listening;
+                                let link = ctx.link().clone();
+                                let timeout = Timeout::new(note_duration, move || {
+                                    link.send_message(ScaleGeneratorMsg::StartResponse(note_duration));
+                                });
+                                self.play_timeout = Some(timeout);
+                            }
+                        } else if !is_last_note {
                             let link = ctx.link().clone();
                             let timeout = Timeout::new(note_duration, move || {
                                 link.send_message(ScaleGeneratorMsg::PlayNextNote);
@@ -419,24 +669,178 @@ impl Component for ScaleGenerator {
                 true
             }
             ScaleGeneratorMsg::InitAudioContext => {
-                if self.audio_ctx.is_none() {
-                    match AudioContext::new() {
-                        Ok(ctx) => {
-                            console_log!("오디오 컨텍스트 초기화 성공");
-                            self.audio_ctx = Some(ctx);
-                        }
-                        Err(err) => {
-                            console_error!("오디오 컨텍스트 초기화 실패: {:?}", err);
-                        }
-                    }
+                if let Err(err) = self.engine.context() {
+                    console_error!("오디오 컨텍스트 초기화 실패: {:?}", err);
                 }
                 false
             }
             ScaleGeneratorMsg::ClearIntervals => {
                 self.intervals.clear();
                 self.intervals.push("1".to_string());
+                self.save_settings();
+                true
+            }
+            ScaleGeneratorMsg::SetExerciseMode(mode) => {
+                // 재생 중에는 모드 변경 불가
+                if self.playback_state == PlaybackState::Playing {
+                    return false;
+                }
+                
+                self.exercise_mode = mode;
+                self.set_results.clear();
+                self.exercise_status = None;
+                self.exercise_error = None;
+                if mode == ExerciseMode::Off {
+                    self.close_microphone();
+                }
+                true
+            }
+            ScaleGeneratorMsg::ToggleHeadphones => {
+                self.headphones = !self.headphones;
+                self.exercise_error = None;
+                true
+            }
+            ScaleGeneratorMsg::MicReady(result) => {
+                match result {
+                    Ok(mic) => {
+                        self.mic = Some(mic);
+                        self.exercise_error = None;
+                        
+                        // 주기적으로 부르는 음 검출
+                        let link = ctx.link().clone();
+                        self.pitch_interval = Some(Interval::new(PITCH_POLL_MS, move || {
+                            link.send_message(ScaleGeneratorMsg::ExercisePitch);
+                        }));
+                        
+                        // 마이크가 준비되었으니 재생 시작
+                        ctx.link().send_message(ScaleGeneratorMsg::Play);
+                    }
+                    Err(err) => {
+                        console_error!("연습용 마이크 준비 실패: {}", err);
+                        self.exercise_error = Some(err);
+                    }
+                }
+                true
+            }
+            ScaleGeneratorMsg::ExercisePitch => {
+                let mic = match &self.mic {
+                    Some(mic) => mic,
+                    None => return false,
+                };
+                
+                self.sung_freq = mic.detect_pitch(pitch_input::DEFAULT_SENSITIVITY);
+                
+                // 판정 구간이면 정확한 음정 유지 시간 누적 (옥타브 차이는 무시)
+                if self.exercise_phase == ExercisePhase::Singing {
+                    if let (Some(note), Some(freq)) = (&self.judging_note, self.sung_freq) {
+                        self.in_tune_ms = accumulate_in_tune(self.in_tune_ms, freq, note.midi());
+                    }
+                }
+                true
+            }
+            ScaleGeneratorMsg::StartResponse(duration) => {
+                if self.playback_state != PlaybackState::Playing {
+                    return false;
+                }
+                // 들려준 음이 판정 구간까지 마이크에 섞이지 않도록 페이드 아웃
+                if let Some(audio) = self.audio_element.take() {
+                    audio.fade_out();
+                }
+                self.begin_judging(ctx, duration);
+                true
+            }
+            ScaleGeneratorMsg::FinishJudging => {
+                if self.playback_state != PlaybackState::Playing {
+                    return false;
+                }
+                let note = match self.judging_note.take() {
+                    Some(note) => note,
+                    None => return false,
+                };
+                
+                let hit = is_hit(self.in_tune_ms);
+                console_log!("따라 부르기 판정: {} - {} ({}ms)", note.full_name(), if hit { "성공" } else { "실패" }, self.in_tune_ms);
+                self.set_results.push((note, hit));
+                self.exercise_phase = ExercisePhase::Idle;
+                
+                let beat_time_ms = 60000 / self.bpm;
+                let is_last_note = self.current_note_idx >= self.notes_to_play.len();
+                let is_set_end = is_last_note || 
+                    (self.notes_to_play[self.current_note_idx].name == "SET_INTERVAL" && 
+                     self.notes_to_play[self.current_note_idx].octave == -1);
+                
+                let root_name = self.current_root_note.as_ref().map(|n| n.full_name()).unwrap_or_default();
+                let link = ctx.link().clone();
+                
+                let hits: Vec<bool> = self.set_results.iter().map(|(_, hit)| *hit).collect();
+                match next_judging_step(&hits, is_set_end, is_last_note) {
+                    JudgingStep::NextNote => {
+                        // 셋 안에서는 바로 다음 음으로 진행
+                        link.send_message(ScaleGeneratorMsg::PlayNextNote);
+                    }
+                    JudgingStep::SetPassed => {
+                        // 셋 통과 - 다음 근음으로 진행
+                        self.passed_sets += 1;
+                        self.set_attempts = 0;
+                        self.exercise_status = Some(format!("✅ {} 통과! 다음 근음으로 이동합니다", root_name));
+                        let timeout = Timeout::new(beat_time_ms, move || {
+                            link.send_message(ScaleGeneratorMsg::PlayNextNote);
+                        });
+                        self.play_timeout = Some(timeout);
+                    }
+                    JudgingStep::AllPassed => {
+                        self.passed_sets += 1;
+                        self.set_attempts = 0;
+                        self.exercise_status = Some(format!("🎉 모든 셋 통과! ({}개)", self.passed_sets));
+                        link.send_message(ScaleGeneratorMsg::Stop);
+                    }
+                    JudgingStep::RetrySet => {
+                        // 셋 실패 - 같은 근음으로 다시 시도
+                        self.set_attempts += 1;
+                        self.failed_sets += 1;
+                        self.current_note_idx = self.set_start_idx;
+                        self.exercise_status = Some(format!("❌ {} 다시 시도합니다 ({}회 실패)", root_name, self.set_attempts));
+                        let timeout = Timeout::new(beat_time_ms * 2, move || {
+                            link.send_message(ScaleGeneratorMsg::PlayNextNote);
+                        });
+                        self.play_timeout = Some(timeout);
+                    }
+                }
                 true
             }
+            ScaleGeneratorMsg::SendToTargetMelody => {
+                // 재생 중에는 notes_to_play를 건드리지 않음
+                if self.playback_state == PlaybackState::Playing {
+                    return false;
+                }
+                
+                self.generate_notes_to_play();
+                let melody = self.build_target_melody();
+                console_log!("목표 멜로디로 보내기: {}개 음", melody.notes.len());
+                target_melody::set_target_melody(Some(melody));
+                false
+            }
+            ScaleGeneratorMsg::SettingsReset => {
+                if self.playback_state == PlaybackState::Playing {
+                    ctx.link().send_message(ScaleGeneratorMsg::Stop);
+                }
+                
+                let defaults = ScaleGeneratorSettings::default();
+                self.start_note = Note::new(&defaults.start_note.name, defaults.start_note.octave);
+                self.end_note = Note::new(&defaults.end_note.name, defaults.end_note.octave);
+                self.bpm = defaults.bpm;
+                self.intervals = defaults.intervals;
+                self.play_direction = defaults.direction;
+                true
+            }
+        }
+    }
+
+    fn destroy(&mut self, _ctx: &Context<Self>) {
+        // 페이지를 떠날 때 연습용 마이크 해제
+        self.close_microphone();
+        if self.playback_state == PlaybackState::Playing {
+            self.save_practice();
         }
     }
 
@@ -659,6 +1063,43 @@ impl Component for ScaleGenerator {
                                     </div>
                                 </div>
                             </div>
+                            
+                            <div class="direction-settings exercise-settings">
+                                <div class="direction-label">{"따라 부르기 연습:"}</div>
+                                <select
+                                    disabled={self.playback_state == PlaybackState::Playing}
+                                    onchange={ctx.link().callback(|e: Event| {
+                                        let select = e.target_dyn_into::<web_sys::HtmlSelectElement>().unwrap();
+                                        let mode = match select.value().as_str() {
+                                            "call-and-response" => ExerciseMode::CallAndResponse,
+                                            "simultaneous" => ExerciseMode::Simultaneous,
+                                            _ => ExerciseMode::Off,
+                                        };
+                                        ScaleGeneratorMsg::SetExerciseMode(mode)
+                                    })}
+                                >
+                                    <option value="off" selected={self.exercise_mode == ExerciseMode::Off}>{"끄기"}</option>
+                                    <option value="call-and-response" selected={self.exercise_mode == ExerciseMode::CallAndResponse}>{"듣고 따라 부르기"}</option>
+                                    <option value="simultaneous" selected={self.exercise_mode == ExerciseMode::Simultaneous}>{"함께 부르기 (이어폰 필요)"}</option>
+                                </select>
+                                {
+                                    if self.exercise_mode == ExerciseMode::Simultaneous {
+                                        html! {
+                                            <label>
+                                                <input
+                                                    type="checkbox"
+                                                    checked={self.headphones}
+                                                    disabled={self.playback_state == PlaybackState::Playing}
+                                                    onchange={ctx.link().callback(|_| ScaleGeneratorMsg::ToggleHeadphones)}
+                                                />
+                                                {" 이어폰 사용 중"}
+                                            </label>
+                                        }
+                                    } else {
+                                        html! {}
+                                    }
+                                }
+                            </div>
                         </div>
                         
                         <div class="right-column">
@@ -847,7 +1288,18 @@ impl Component for ScaleGenerator {
                             </div>
                         </div>
                     
+                        { self.view_exercise_status() }
+                    
                         <div class="button-group">
+                            <button
+                                class="target-button"
+                                title="현재 스케일을 피치 분석기의 목표 멜로디로 보내기"
+                                disabled={self.playback_state == PlaybackState::Playing}
+                                onclick={ctx.link().callback(|_| ScaleGeneratorMsg::SendToTargetMelody)}
+                            >
+                                {"🎯 목표로 보내기"}
+                            </button>
+                            <CopyLinkButton url={self.share_url()} />
                             <button
                                 class={if self.playback_state == PlaybackState::Playing { "play-button playing" } else { "play-button" }}
                                 onclick={ctx.link().callback(|_| ScaleGeneratorMsg::TogglePlayback)}
@@ -869,6 +1321,132 @@ impl Component for ScaleGenerator {
 }
 
 impl ScaleGenerator {
+    // 판정 구간 시작 - duration 후 FinishJudging
+    fn begin_judging(&mut self, ctx: &Context<Self>, duration: u32) {
+        self.exercise_phase = ExercisePhase::Singing;
+        self.in_tune_ms = 0;
+        
+        let link = ctx.link().clone();
+        let timeout = Timeout::new(duration, move || {
+            link.send_message(ScaleGeneratorMsg::FinishJudging);
+        });
+        self.play_timeout = Some(timeout);
+    }
+    
+    // 현재 설정이 담긴 공유 링크
+    fn share_url(&self) -> Option<AttrValue> {
+        let query = ScaleGeneratorQuery {
+            from: Some(self.start_note.full_name()),
+            to: Some(self.end_note.full_name()),
+            intervals: Some(self.intervals.join("-")),
+            bpm: Some(self.bpm),
+            dir: Some(self.play_direction.query_value().to_string()),
+        };
+        share_link::share_url(&Route::ScaleGenerator, &query).map(AttrValue::from)
+    }
+    
+    // 현재 설정을 저장소에 기록
+    fn save_settings(&self) {
+        let current = ScaleGeneratorSettings {
+            start_note: NoteSetting { name: self.start_note.name.clone(), octave: self.start_note.octave },
+            end_note: NoteSetting { name: self.end_note.name.clone(), octave: self.end_note.octave },
+            bpm: self.bpm,
+            intervals: self.intervals.clone(),
+            direction: self.play_direction,
+        };
+        settings::update_settings(|s| s.scale_generator = current);
+    }
+    
+    // 이번 재생을 연습 기록에 저장 (따라 부르기 모드면 셋 통과율을 점수로)
+    fn save_practice(&self) {
+        let mut entry = PracticeEntry::new(PracticeTool::ScaleGenerator, self.play_started_at)
+            .finish_now()
+            .with_bpm(self.bpm)
+            .with_exercise(format!("스케일 [{}]", self.intervals.join(", ")));
+        
+        let judged_sets = self.passed_sets + self.failed_sets;
+        if self.exercise_mode != ExerciseMode::Off && judged_sets > 0 {
+            entry = entry
+                .with_score(self.passed_sets as f64 / judged_sets as f64 * 100.0)
+                .with_details(format!("통과 {}셋, 실패 {}회", self.passed_sets, self.failed_sets));
+        }
+        save_practice_entry(entry);
+    }
+    
+    // 연습용 마이크 해제
+    fn close_microphone(&mut self) {
+        self.pitch_interval = None;
+        self.sung_freq = None;
+        if let Some(mic) = self.mic.take() {
+            mic.close();
+        }
+    }
+    
+    // 따라 부르기 연습 상태 표시
+    fn view_exercise_status(&self) -> Html {
+        if self.exercise_mode == ExerciseMode::Off {
+            return html! {};
+        }
+        
+        let phase_label = match self.exercise_phase {
+            ExercisePhase::Idle => "대기 중",
+            ExercisePhase::Listening => "🎹 듣기",
+            ExercisePhase::Singing => "🎤 부르기",
+        };
+        
+        // 부르고 있는 음과 목표 음과의 차이
+        let sung_label = match (self.sung_freq, &self.judging_note) {
+            (Some(freq), Some(note)) if self.exercise_phase == ExercisePhase::Singing => {
+                let cents = cents_ignoring_octave(freq, midi_to_frequency(note.midi()));
+                format!("{} ({:+.0} cents)", note_name_from_midi(frequency_to_midi(freq).round() as i32), cents)
+            }
+            (Some(freq), _) => note_name_from_midi(frequency_to_midi(freq).round() as i32),
+            (None, _) => "-".to_string(),
+        };
+        
+        html! {
+            <div class="exercise-status">
+                <div class="note-display-item">
+                    <span class="note-label">{"단계:"}</span>
+                    <span class="note-value">{phase_label}</span>
+                </div>
+                <div class="note-display-item">
+                    <span class="note-label">{"부른 음:"}</span>
+                    <span class="note-value">{sung_label}</span>
+                </div>
+                <div class="note-display-item">
+                    <span class="note-label">{"통과한 셋:"}</span>
+                    <span class="note-value">{self.passed_sets}</span>
+                </div>
+                <div class="exercise-results">
+                    {
+                        self.set_results.iter().map(|(note, hit)| {
+                            html! {
+                                <span class={classes!("exercise-result", if *hit { "hit" } else { "miss" })}>
+                                    {format!("{} {}", note.full_name(), if *hit { "✓" } else { "✗" })}
+                                </span>
+                            }
+                        }).collect::<Html>()
+                    }
+                </div>
+                {
+                    if let Some(status) = &self.exercise_status {
+                        html! { <div class="exercise-message">{status}</div> }
+                    } else {
+                        html! {}
+                    }
+                }
+                {
+                    if let Some(err) = &self.exercise_error {
+                        html! { <div class="exercise-error">{err}</div> }
+                    } else {
+                        html! {}
+                    }
+                }
+            </div>
+        }
+    }
+    
     // 피아노 음원으로 노트 재생
     fn play_piano_note(&mut self, ctx: &Context<Self>, note: &Note) {
         // 문서 객체 모델에서 window 객체 가져오기
@@ -897,9 +1475,9 @@ impl ScaleGenerator {
         
         // 볼륨 설정
         audio_element.set_volume(0.7);
-        
-        // 오디오 요소 저장
-        self.audio_element = Some(audio_element.clone());
+
+        // 스케일 채널로 출력 (끝까지 재생되면 소스 연결 해제)
+        self.audio_element = Some(PianoSample::connect(&self.engine, Channel::ScaleGenerator, &audio_element));
         
         // 오디오 요소를 미리 로드
         let _ = audio_element.load();
@@ -919,8 +1497,7 @@ impl ScaleGenerator {
                 // 0.1초 후에 이전 오디오 중지
                 let window_clone = window.clone();
                 let closure = Closure::once_into_js(move || {
-                    let _ = prev.pause();
-                    let _ = prev.set_src("");  // 리소스 해제
+                    prev.stop();
                 });
                 
                 let _ = window_clone.set_timeout_with_callback_and_timeout_and_arguments_0(
@@ -1019,6 +1596,38 @@ impl ScaleGenerator {
         }
     }
     
+    // notes_to_play를 재생 타이밍 그대로 목표 멜로디로 변환
+    // PlayNextNote와 동일하게 한 박자씩 진행하고, 셋의 마지막 음은 4박자 유지
+    fn build_target_melody(&self) -> TargetMelody {
+        let beat_time = (60000 / self.bpm) as f64 / 1000.0;
+        let mut notes = Vec::new();
+        let mut time = 0.0;
+        
+        for (idx, note) in self.notes_to_play.iter().enumerate() {
+            if note.name == "SET_INTERVAL" && note.octave == -1 {
+                continue;
+            }
+            
+            let next_is_set_end = self.notes_to_play.get(idx + 1)
+                .map(|next| next.name == "SET_INTERVAL" && next.octave == -1)
+                .unwrap_or(true);
+            let duration = if next_is_set_end { beat_time * 4.0 } else { beat_time };
+            
+            notes.push(TargetNote {
+                midi: note.midi(),
+                start: time,
+                duration,
+            });
+            time += duration;
+        }
+        
+        TargetMelody {
+            name: format!("{} - {} 스케일 ({} BPM)", self.start_note.full_name(), self.end_note.full_name(), self.bpm),
+            notes,
+            offset: 0.0,
+        }
+    }
+    
     // 노트 범위에 대해 스케일 생성
     fn generate_scale_for_range(&mut self, notes: &[Note]) {
         if notes.is_empty() {
@@ -1073,8 +1682,8 @@ impl ScaleGenerator {
         }
     }
     
-    // 인터벌 문자열을 반음 개수로 변환
-    fn interval_semitones(&self, interval: &str) -> i32 {
+    // 인터벌 문자열을 반음 개수로 변환 (청음 퀴즈에서도 사용)
+    pub(crate) fn interval_semitones(interval: &str) -> i32 {
         match interval {
             "1" => 0,     // 근음 (완전1도)
             "b2" => 1,    // 단2도
@@ -1111,7 +1720,7 @@ impl ScaleGenerator {
     // 근음과 음정으로 새 노트 계산
     fn compute_note_from_interval(&self, root: &Note, interval: &str) -> Option<Note> {
         // 인터벌의 반음 수 계산
-        let semitones = self.interval_semitones(interval);
+        let semitones = Self::interval_semitones(interval);
         
         // 근음의 MIDI 노트 번호 계산
         let root_midi = (root.octave + 1) * 12 + self.semitones_from_c(&root.name);
@@ -1140,4 +1749,54 @@ impl ScaleGenerator {
         
         Some(Note::new(note_name, octave))
     }
-} 
\ No newline at end of file
+} 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 판정 구간 내내 같은 주파수를 부른 경우의 누적 정확 음정 시간
+    fn hold(sung_freq: f64, target_midi: i32, duration_ms: u32) -> u32 {
+        (0..duration_ms / PITCH_POLL_MS).fold(0, |ms, _| accumulate_in_tune(ms, sung_freq, target_midi))
+    }
+
+    #[test]
+    fn holding_the_target_is_a_hit() {
+        let in_tune = hold(midi_to_frequency(60), 60, 500);
+        assert_eq!(in_tune, 500);
+        assert!(is_hit(in_tune));
+
+        // 허용 오차 안에서 살짝 벗어난 음도 성공
+        let slightly_sharp = midi_to_frequency(60) * 2f64.powf(30.0 / 1200.0);
+        assert!(is_hit(hold(slightly_sharp, 60, 500)));
+    }
+
+    #[test]
+    fn wrong_or_short_notes_are_a_miss() {
+        assert!(!is_hit(hold(midi_to_frequency(61), 60, 500)));
+        assert!(!is_hit(hold(midi_to_frequency(67), 60, 500)));
+        // 맞는 음이라도 유지 시간이 부족하면 실패
+        assert!(!is_hit(hold(midi_to_frequency(60), 60, HIT_HOLD_MS - PITCH_POLL_MS)));
+    }
+
+    #[test]
+    fn octave_shifted_singing_is_a_hit() {
+        assert!(is_hit(hold(midi_to_frequency(48), 60, 500)));
+        assert!(is_hit(hold(midi_to_frequency(72), 60, 500)));
+    }
+
+    #[test]
+    fn set_advances_only_after_every_note_passes() {
+        // 셋 중간에는 판정 결과와 상관없이 다음 음으로
+        assert_eq!(next_judging_step(&[true], false, false), JudgingStep::NextNote);
+        assert_eq!(next_judging_step(&[false], false, false), JudgingStep::NextNote);
+
+        // 셋 끝에서 하나라도 실패하면 같은 근음으로 다시
+        assert_eq!(next_judging_step(&[true, false, true], true, false), JudgingStep::RetrySet);
+        assert_eq!(next_judging_step(&[true, true, false], true, true), JudgingStep::RetrySet);
+
+        // 모두 통과해야 다음 근음 (마지막 셋이면 종료)
+        assert_eq!(next_judging_step(&[true, true, true], true, false), JudgingStep::SetPassed);
+        assert_eq!(next_judging_step(&[true, true, true], true, true), JudgingStep::AllPassed);
+    }
+}
@@ -0,0 +1,535 @@
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use web_sys::HtmlInputElement;
+use yew::prelude::*;
+
+use crate::audio_export::download_blob;
+use crate::event_bus::{emit, AppEvent};
+use crate::pitch_input::frequency_to_midi;
+use crate::take_store::{self, StorageUsage};
+use crate::tools::target_melody::note_name_from_midi;
+
+#[cfg(debug_assertions)]
+macro_rules! console_error {
+    ($($arg:tt)*) => {
+        web_sys::console::error_1(&format!($($arg)*).into());
+    };
+}
+
+#[cfg(not(debug_assertions))]
+macro_rules! console_error {
+    ($($arg:tt)*) => {};
+}
+
+// 피치 이력 - (timestamp, [(frequency, amplitude)])
+pub type PitchHistory = VecDeque<(f64, Vec<(f64, f32)>)>;
+// 진폭 이력 - (timestamp, 진폭 데이터 배열)
+pub type AmplitudeHistory = VecDeque<(f64, Vec<f32>)>;
+
+// 카운트인으로 녹음한 테이크의 마디/박 격자 (0초가 첫 마디 첫 박)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BeatGrid {
+    pub bpm: f64,
+    pub beats_per_bar: u32,
+}
+
+impl BeatGrid {
+    pub fn beat_secs(&self) -> f64 {
+        60.0 / self.bpm
+    }
+
+    // start..end 사이의 박 (시간, 마디 번호(1부터), 마디 첫 박 여부)
+    pub fn beats_between(&self, start: f64, end: f64) -> impl Iterator<Item = (f64, u32, bool)> {
+        let grid = *self;
+        let first = (start.max(0.0) / grid.beat_secs()).ceil() as u32;
+        (first..)
+            .map(move |beat| (beat as f64 * grid.beat_secs(), beat))
+            .take_while(move |(time, _)| *time <= end)
+            .map(move |(time, beat)| (time, beat / grid.beats_per_bar + 1, beat % grid.beats_per_bar == 0))
+    }
+}
+
+// 녹음에 섞인 소리 하나만 따로 담은 오디오
+#[derive(Clone)]
+pub struct Stem {
+    pub name: String, // 소리 이름 (마이크, 메트로놈 등)
+    pub blob: web_sys::Blob,
+}
+
+// 녹음 한 번(테이크)의 오디오와 분석 결과
+#[derive(Clone)]
+pub struct Take {
+    pub id: u64, // 녹음 시작 시각 기반 고유 ID (IndexedDB 키)
+    pub name: String,
+    pub starred: bool,
+    pub created_at: f64, // 녹음 시작 시각 (ms)
+    pub duration: f64,   // 녹음 길이 (초)
+    pub audio_blob: web_sys::Blob,
+    pub audio_url: String, // audio_blob의 object URL (테이크를 지울 때 해제)
+    pub history: PitchHistory,
+    pub amplitude_history: AmplitudeHistory,
+    pub stems: Vec<Stem>, // 스템 저장을 켠 경우 소리별 오디오
+    pub beat_grid: Option<BeatGrid>, // 카운트인으로 시작한 녹음의 박자 격자
+    pub latency: f64, // 녹음 파일에서 테이크 0초가 담긴 위치 (초, 카운트인 동안 녹음된 앞부분 + 마이크를 녹음했으면 왕복 지연)
+}
+
+impl Take {
+    pub fn summary(&self) -> TakeSummary {
+        // 프레임마다 가장 강한 주파수만 모아 비교용 통계 계산
+        let mut pitches: Vec<f64> = self
+            .history
+            .iter()
+            .filter_map(|(_, freqs)| freqs.first().map(|(freq, _)| *freq))
+            .filter(|freq| *freq > 0.0)
+            .collect();
+        let voiced_ratio = if self.history.is_empty() {
+            0.0
+        } else {
+            pitches.len() as f64 / self.history.len() as f64
+        };
+        pitches.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+        let median_freq = pitches.get(pitches.len() / 2).copied();
+
+        TakeSummary {
+            id: self.id,
+            name: self.name.clone(),
+            starred: self.starred,
+            created_at: self.created_at,
+            duration: self.duration,
+            size_bytes: self.audio_blob.size(),
+            voiced_ratio,
+            median_freq,
+            stems: self.stems.iter().map(|stem| stem.name.clone()).collect(),
+        }
+    }
+}
+
+// 테이크 목록 표시용 요약
+#[derive(Debug, Clone, PartialEq)]
+pub struct TakeSummary {
+    pub id: u64,
+    pub name: String,
+    pub starred: bool,
+    pub created_at: f64,
+    pub duration: f64,
+    pub size_bytes: f64,
+    pub voiced_ratio: f64,        // 소리가 감지된 프레임 비율
+    pub median_freq: Option<f64>, // 대표 음높이
+    pub stems: Vec<String>,       // 저장된 스템 이름
+}
+
+// 페이지를 이동해도 유지되는 테이크 목록
+#[derive(Default)]
+struct TakeLibrary {
+    takes: Vec<Take>,
+    active_id: Option<u64>,
+    restored: bool, // 저장된 테이크를 이미 불러왔는지
+}
+
+thread_local! {
+    static TAKE_LIBRARY: RefCell<TakeLibrary> = RefCell::new(TakeLibrary::default());
+}
+
+// 변경 후 TakesChanged 이벤트로 남은 테이크 수 알림
+fn update_library<R>(f: impl FnOnce(&mut TakeLibrary) -> R) -> R {
+    let (result, count) = TAKE_LIBRARY.with(|library| {
+        let mut library = library.borrow_mut();
+        let result = f(&mut library);
+        (result, library.takes.len())
+    });
+
+    emit(AppEvent::TakesChanged { count });
+    result
+}
+
+pub fn take_summaries() -> Vec<TakeSummary> {
+    TAKE_LIBRARY.with(|library| library.borrow().takes.iter().map(Take::summary).collect())
+}
+
+pub fn active_take_id() -> Option<u64> {
+    TAKE_LIBRARY.with(|library| library.borrow().active_id)
+}
+
+pub fn active_take() -> Option<Take> {
+    TAKE_LIBRARY.with(|library| {
+        let library = library.borrow();
+        library.active_id.and_then(|id| library.takes.iter().find(|take| take.id == id).cloned())
+    })
+}
+
+pub fn take_by_id(id: u64) -> Option<Take> {
+    TAKE_LIBRARY.with(|library| library.borrow().takes.iter().find(|take| take.id == id).cloned())
+}
+
+// IndexedDB 작업은 백그라운드에서 (실패해도 메모리의 테이크는 유지)
+fn persist(take: Take) {
+    wasm_bindgen_futures::spawn_local(async move {
+        if let Err(err) = take_store::save_take(&take).await {
+            console_error!("테이크 저장 실패: {:?}", err);
+        }
+    });
+}
+
+fn persist_by_id(library: &TakeLibrary, id: u64) {
+    if let Some(take) = library.takes.iter().find(|take| take.id == id) {
+        persist(take.clone());
+    }
+}
+
+fn forget(take: Take) {
+    let _ = web_sys::Url::revoke_object_url(&take.audio_url);
+    wasm_bindgen_futures::spawn_local(async move {
+        if let Err(err) = take_store::delete_take(take.id).await {
+            console_error!("저장된 테이크 삭제 실패: {:?}", err);
+        }
+    });
+}
+
+// 새 녹음을 테이크로 추가하고 선택 (id와 이름은 여기서 부여)
+pub fn add_take(mut take: Take) -> u64 {
+    update_library(|library| {
+        // 녹음 시작 시각을 ID로 사용 (같은 밀리초에 겹치면 1씩 증가)
+        let mut id = take.created_at as u64;
+        while library.takes.iter().any(|t| t.id == id) {
+            id += 1;
+        }
+        take.id = id;
+        take.name = format!("테이크 {}", library.takes.len() + 1);
+        library.active_id = Some(id);
+        library.takes.push(take.clone());
+        persist(take);
+        id
+    })
+}
+
+// 시작 시 IndexedDB에 저장된 테이크를 목록에 합침
+pub async fn restore_saved_takes() {
+    let stored = match take_store::load_takes().await {
+        Ok(stored) => stored,
+        Err(err) => {
+            console_error!("저장된 테이크 불러오기 실패: {:?}", err);
+            return;
+        }
+    };
+
+    update_library(|library| {
+        if library.restored {
+            return;
+        }
+        library.restored = true;
+
+        // 불러오는 동안 새로 녹음한 테이크는 그대로 두고 시간순으로 정렬
+        for take in stored {
+            if library.takes.iter().any(|t| t.id == take.id) {
+                let _ = web_sys::Url::revoke_object_url(&take.audio_url);
+            } else {
+                library.takes.push(take);
+            }
+        }
+        library.takes.sort_by_key(|take| take.id);
+        if library.active_id.is_none() {
+            library.active_id = library.takes.last().map(|take| take.id);
+        }
+    });
+}
+
+pub fn select_take(id: u64) {
+    update_library(|library| {
+        if library.takes.iter().any(|take| take.id == id) {
+            library.active_id = Some(id);
+        }
+    });
+}
+
+pub fn rename_take(id: u64, name: String) {
+    update_library(|library| {
+        if let Some(take) = library.takes.iter_mut().find(|take| take.id == id) {
+            take.name = name;
+        }
+        persist_by_id(library, id);
+    });
+}
+
+pub fn toggle_take_star(id: u64) {
+    update_library(|library| {
+        if let Some(take) = library.takes.iter_mut().find(|take| take.id == id) {
+            take.starred = !take.starred;
+        }
+        persist_by_id(library, id);
+    });
+}
+
+// 오디오 메타데이터로 확인한 실제 길이 반영
+pub fn set_take_duration(id: u64, duration: f64) {
+    update_library(|library| {
+        if let Some(take) = library.takes.iter_mut().find(|take| take.id == id) {
+            take.duration = duration;
+        }
+        persist_by_id(library, id);
+    });
+}
+
+// 녹음이 끝난 뒤 정리된 스템을 테이크에 붙임
+pub fn set_take_stems(id: u64, stems: Vec<Stem>) {
+    update_library(|library| {
+        if let Some(take) = library.takes.iter_mut().find(|take| take.id == id) {
+            take.stems = stems;
+        }
+        persist_by_id(library, id);
+    });
+}
+
+// 스템 하나를 파일로 내려받기
+pub fn download_take_stem(id: u64, index: usize) {
+    let found = TAKE_LIBRARY.with(|library| {
+        let library = library.borrow();
+        let take = library.takes.iter().find(|take| take.id == id)?;
+        let stem = take.stems.get(index)?;
+        Some((format!("{}_{}.webm", take.name, stem.name), stem.blob.clone()))
+    });
+    if let Some((filename, blob)) = found {
+        if let Err(err) = download_blob(&blob, &filename) {
+            console_error!("스템 다운로드 실패: {:?}", err);
+        }
+    }
+}
+
+// 테이크 삭제 (선택된 테이크였다면 가장 최근 테이크를 선택)
+pub fn delete_take(id: u64) {
+    update_library(|library| {
+        if let Some(index) = library.takes.iter().position(|take| take.id == id) {
+            forget(library.takes.remove(index));
+            if library.active_id == Some(id) {
+                library.active_id = library.takes.last().map(|take| take.id);
+            }
+        }
+    });
+}
+
+// 정리 도구: 별표 없는 테이크 삭제 (include_starred면 전부)
+pub fn delete_takes(include_starred: bool) {
+    update_library(|library| {
+        let (removed, kept): (Vec<Take>, Vec<Take>) = std::mem::take(&mut library.takes)
+            .into_iter()
+            .partition(|take| include_starred || !take.starred);
+        library.takes = kept;
+        removed.into_iter().for_each(forget);
+        if !library.takes.iter().any(|take| Some(take.id) == library.active_id) {
+            library.active_id = library.takes.last().map(|take| take.id);
+        }
+    });
+}
+
+fn format_timestamp(timestamp_ms: f64) -> String {
+    let date = js_sys::Date::new(&wasm_bindgen::JsValue::from_f64(timestamp_ms));
+    format!("{:02}:{:02}:{:02}", date.get_hours(), date.get_minutes(), date.get_seconds())
+}
+
+fn format_duration(secs: f64) -> String {
+    let secs = secs.round() as u64;
+    format!("{}:{:02}", secs / 60, secs % 60)
+}
+
+fn format_size(bytes: f64) -> String {
+    if bytes >= 1024.0 * 1024.0 * 1024.0 {
+        format!("{:.1} GB", bytes / (1024.0 * 1024.0 * 1024.0))
+    } else if bytes >= 1024.0 * 1024.0 {
+        format!("{:.1} MB", bytes / (1024.0 * 1024.0))
+    } else {
+        format!("{:.0} KB", bytes / 1024.0)
+    }
+}
+
+#[derive(Properties, PartialEq)]
+pub struct TakeLibraryPanelProps {
+    pub takes: Vec<TakeSummary>,
+    pub active_id: Option<u64>,
+    pub is_recording: bool, // 녹음 중에는 전환/삭제 불가
+}
+
+// 녹음 테이크 목록 (선택한 테이크가 피치 차트에 표시됨)
+#[function_component(TakeLibraryPanel)]
+pub fn take_library_panel(props: &TakeLibraryPanelProps) -> Html {
+    let starred_only = use_state(|| false);
+
+    let storage_usage = use_state(|| None::<StorageUsage>);
+
+    // 테이크가 바뀔 때마다 저장 공간 사용량 다시 확인
+    {
+        let storage_usage = storage_usage.clone();
+        use_effect_with(props.takes.clone(), move |_| {
+            wasm_bindgen_futures::spawn_local(async move {
+                storage_usage.set(take_store::storage_usage().await);
+            });
+            || ()
+        });
+    }
+
+    let on_toggle_filter = {
+        let starred_only = starred_only.clone();
+        Callback::from(move |_: MouseEvent| starred_only.set(!*starred_only))
+    };
+
+    let confirm = |message: &str| {
+        web_sys::window()
+            .and_then(|w| w.confirm_with_message(message).ok())
+            .unwrap_or(false)
+    };
+    let on_delete_unstarred = Callback::from(move |_: MouseEvent| {
+        if confirm("별표가 없는 테이크를 모두 삭제할까요?") {
+            delete_takes(false);
+        }
+    });
+    let on_delete_all = Callback::from(move |_: MouseEvent| {
+        if confirm("저장된 테이크를 모두 삭제할까요?") {
+            delete_takes(true);
+        }
+    });
+
+    // 최신 테이크가 위로
+    let rows = props
+        .takes
+        .iter()
+        .rev()
+        .filter(|take| !*starred_only || take.starred)
+        .map(|take| {
+            let id = take.id;
+            let is_active = props.active_id == Some(id);
+
+            let on_select = Callback::from(move |_: MouseEvent| select_take(id));
+            let on_toggle_star = Callback::from(move |e: MouseEvent| {
+                e.stop_propagation();
+                toggle_take_star(id);
+            });
+            let on_rename = {
+                let current_name = take.name.clone();
+                Callback::from(move |e: Event| {
+                    let input: HtmlInputElement = e.target_unchecked_into();
+                    let name = input.value().trim().to_string();
+                    if name.is_empty() {
+                        // 빈 이름은 허용하지 않고 원래 이름으로 되돌림
+                        input.set_value(&current_name);
+                    } else if name != current_name {
+                        rename_take(id, name);
+                    }
+                })
+            };
+            let on_delete = {
+                let name = take.name.clone();
+                Callback::from(move |e: MouseEvent| {
+                    e.stop_propagation();
+                    let confirmed = web_sys::window()
+                        .and_then(|w| w.confirm_with_message(&format!("'{}'을(를) 삭제할까요?", name)).ok())
+                        .unwrap_or(false);
+                    if confirmed {
+                        delete_take(id);
+                    }
+                })
+            };
+
+            let median_note = take
+                .median_freq
+                .map(|freq| note_name_from_midi(frequency_to_midi(freq).round() as i32))
+                .unwrap_or_else(|| "-".to_string());
+
+            html! {
+                <li
+                    key={id}
+                    class={classes!("take-item", is_active.then_some("active"), props.is_recording.then_some("disabled"))}
+                    onclick={if props.is_recording { Callback::noop() } else { on_select }}
+                >
+                    <button
+                        class={classes!("take-star", take.starred.then_some("starred"))}
+                        onclick={on_toggle_star}
+                        title={if take.starred { "별표 해제" } else { "별표" }}
+                    >
+                        { if take.starred { "★" } else { "☆" } }
+                    </button>
+                    <input
+                        class="take-name"
+                        type="text"
+                        value={take.name.clone()}
+                        onchange={on_rename}
+                        onclick={Callback::from(|e: MouseEvent| e.stop_propagation())}
+                    />
+                    <span class="take-meta">{ format_timestamp(take.created_at) }</span>
+                    <span class="take-meta">{ format_duration(take.duration) }</span>
+                    <span class="take-meta">{ format_size(take.size_bytes) }</span>
+                    <span class="take-meta" title="대표 음높이">{ median_note }</span>
+                    <span class="take-meta" title="소리가 감지된 구간 비율">{ format!("{:.0}%", take.voiced_ratio * 100.0) }</span>
+                    { for take.stems.iter().enumerate().map(|(index, name)| {
+                        let on_download = Callback::from(move |e: MouseEvent| {
+                            e.stop_propagation();
+                            download_take_stem(id, index);
+                        });
+                        html! {
+                            <button class="take-stem" onclick={on_download} title={format!("{} 스템 내려받기", name)}>
+                                { format!("⬇ {}", name) }
+                            </button>
+                        }
+                    }) }
+                    <button
+                        class="take-delete"
+                        onclick={on_delete}
+                        disabled={props.is_recording}
+                        title="테이크 삭제"
+                    >
+                        { "🗑️" }
+                    </button>
+                </li>
+            }
+        })
+        .collect::<Html>();
+
+    html! {
+        <div class="take-library-panel">
+            <div class="take-library-header">
+                <span class="take-library-title">{ format!("🎙️ 테이크 ({})", props.takes.len()) }</span>
+                <button
+                    class={classes!("take-filter", (*starred_only).then_some("active"))}
+                    onclick={on_toggle_filter}
+                >
+                    { "★만 보기" }
+                </button>
+            </div>
+            {
+                if props.takes.is_empty() {
+                    html! {
+                        <p class="take-library-hint">{"녹음할 때마다 테이크가 여기에 쌓입니다."}</p>
+                    }
+                } else {
+                    html! { <ul class="take-list">{ rows }</ul> }
+                }
+            }
+            <div class="take-library-footer">
+                <span class="take-storage">
+                    {
+                        match *storage_usage {
+                            Some(usage) if usage.quota > 0.0 => format!(
+                                "💾 {} / {} ({:.1}%)",
+                                format_size(usage.usage),
+                                format_size(usage.quota),
+                                usage.usage / usage.quota * 100.0
+                            ),
+                            _ => "💾 저장 공간 정보 없음".to_string(),
+                        }
+                    }
+                </span>
+                <button
+                    class="take-cleanup"
+                    onclick={on_delete_unstarred}
+                    disabled={props.is_recording || !props.takes.iter().any(|take| !take.starred)}
+                >
+                    { "별표 없는 테이크 삭제" }
+                </button>
+                <button
+                    class="take-cleanup danger"
+                    onclick={on_delete_all}
+                    disabled={props.is_recording || props.takes.is_empty()}
+                >
+                    { "모두 삭제" }
+                </button>
+            </div>
+        </div>
+    }
+}
@@ -0,0 +1,1004 @@
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::rc::Rc;
+use web_sys::HtmlInputElement;
+use yew::prelude::*;
+
+use crate::event_bus::{emit, AppEvent};
+use crate::pitch_input::{cents_between, midi_to_frequency};
+
+// 조건부 로그 매크로 정의
+#[cfg(debug_assertions)]
+macro_rules! console_log {
+    ($($arg:tt)*) => {
+        web_sys::console::log_1(&format!($($arg)*).into());
+    };
+}
+
+#[cfg(not(debug_assertions))]
+macro_rules! console_log {
+    ($($arg:tt)*) => {};
+}
+
+#[cfg(debug_assertions)]
+macro_rules! console_error {
+    ($($arg:tt)*) => {
+        web_sys::console::error_1(&format!($($arg)*).into());
+    };
+}
+
+#[cfg(not(debug_assertions))]
+macro_rules! console_error {
+    ($($arg:tt)*) => {};
+}
+
+// 음정 판정 허용 오차 기본값 (센트)
+pub const DEFAULT_TOLERANCE_CENTS: f64 = 50.0;
+
+// 타이밍 측정 시 노트 시작 전에 허용하는 여유 시간 (초)
+const TIMING_LOOKAHEAD: f64 = 0.3;
+
+// 이 범위를 벗어난 음은 다른 음을 부른 것으로 보고 센트 평균에서 제외
+const MAX_SCORED_CENTS: f64 = 600.0;
+
+// 목표 멜로디의 한 음 (MIDI 번호, 시작 시간, 길이)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TargetNote {
+    pub midi: i32,     // MIDI 노트 번호 (C4 = 60)
+    pub start: f64,    // 시작 시간 (초, 녹음 시작 기준)
+    pub duration: f64, // 길이 (초)
+}
+
+impl TargetNote {
+    pub fn end(&self) -> f64 {
+        self.start + self.duration
+    }
+
+    pub fn frequency(&self) -> f64 {
+        midi_to_frequency(self.midi)
+    }
+}
+
+// 목표 멜로디 (이름, 음 목록, 녹음 기준 시작 오프셋)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TargetMelody {
+    pub name: String,
+    pub notes: Vec<TargetNote>,
+    #[serde(default)]
+    pub offset: f64, // 녹음 시작 후 멜로디가 시작되는 시간 (초)
+}
+
+impl TargetMelody {
+    // 오프셋을 적용한 음 목록 (피치 플롯과 채점에 사용)
+    pub fn shifted_notes(&self) -> Vec<TargetNote> {
+        self.notes
+            .iter()
+            .map(|note| TargetNote {
+                start: note.start + self.offset,
+                ..note.clone()
+            })
+            .collect()
+    }
+
+    pub fn total_duration(&self) -> f64 {
+        self.notes.iter().map(|n| n.end()).fold(0.0, f64::max)
+    }
+}
+
+// JSON 파일에서 읽어들이는 음 형식 - midi 번호 또는 음 이름("C4", "Eb3") 중 하나 사용
+#[derive(Deserialize)]
+struct JsonNote {
+    midi: Option<i32>,
+    note: Option<String>,
+    start: f64,
+    duration: f64,
+}
+
+#[derive(Deserialize)]
+struct JsonMelody {
+    #[serde(default)]
+    name: Option<String>,
+    notes: Vec<JsonNote>,
+}
+
+// JSON 문자열을 목표 멜로디로 변환
+// 형식: {"name": "...", "notes": [{"note": "C4", "start": 0.0, "duration": 0.5}, ...]}
+// 최상위가 음 배열인 형식도 허용
+pub fn parse_melody_json(text: &str, default_name: &str) -> Result<TargetMelody, String> {
+    let melody: JsonMelody = match serde_json::from_str::<JsonMelody>(text) {
+        Ok(melody) => melody,
+        Err(_) => {
+            let notes = serde_json::from_str::<Vec<JsonNote>>(text)
+                .map_err(|e| format!("JSON 형식 오류: {}", e))?;
+            JsonMelody { name: None, notes }
+        }
+    };
+
+    let mut notes = Vec::with_capacity(melody.notes.len());
+    for (idx, note) in melody.notes.iter().enumerate() {
+        let midi = match (note.midi, note.note.as_deref()) {
+            (Some(midi), _) => midi,
+            (None, Some(name)) => midi_from_note_name(name)
+                .ok_or_else(|| format!("{}번째 음의 이름을 해석할 수 없습니다: {}", idx + 1, name))?,
+            (None, None) => return Err(format!("{}번째 음에 midi 또는 note 값이 없습니다", idx + 1)),
+        };
+
+        if note.duration <= 0.0 || note.start < 0.0 {
+            return Err(format!("{}번째 음의 시간 값이 올바르지 않습니다", idx + 1));
+        }
+
+        notes.push(TargetNote {
+            midi,
+            start: note.start,
+            duration: note.duration,
+        });
+    }
+
+    if notes.is_empty() {
+        return Err("멜로디에 음이 없습니다".to_string());
+    }
+
+    notes.sort_by(|a, b| a.start.partial_cmp(&b.start).unwrap_or(std::cmp::Ordering::Equal));
+
+    Ok(TargetMelody {
+        name: melody.name.unwrap_or_else(|| default_name.to_string()),
+        notes,
+        offset: 0.0,
+    })
+}
+
+// 표준 MIDI 파일(SMF 0/1)을 목표 멜로디로 변환
+// 모든 트랙의 노트를 모으며, 드럼 채널(10번)은 제외
+pub fn parse_midi_file(bytes: &[u8], default_name: &str) -> Result<TargetMelody, String> {
+    let mut reader = MidiReader { data: bytes, pos: 0 };
+
+    if reader.take(4)? != b"MThd" {
+        return Err("MIDI 파일이 아닙니다 (MThd 헤더 없음)".to_string());
+    }
+    let header_len = reader.u32()? as usize;
+    let _format = reader.u16()?;
+    let track_count = reader.u16()?;
+    let division = reader.u16()?;
+    // 헤더에 추가 데이터가 있으면 건너뜀
+    reader.take(header_len.saturating_sub(6))?;
+
+    if division & 0x8000 != 0 {
+        return Err("SMPTE 시간 단위의 MIDI 파일은 지원하지 않습니다".to_string());
+    }
+    let ticks_per_quarter = division.max(1) as f64;
+
+    // (틱, 마이크로초/4분음표) 템포 변경 목록
+    let mut tempo_changes: Vec<(u64, u32)> = Vec::new();
+    // (시작 틱, 끝 틱, MIDI 번호)
+    let mut raw_notes: Vec<(u64, u64, i32)> = Vec::new();
+
+    // MTrk 청크만 트랙 수에 포함
+    let mut tracks_read = 0u16;
+    while tracks_read < track_count && reader.pos < reader.data.len() {
+        // 알 수 없는 청크는 건너뜀
+        let chunk_id = reader.take(4)?;
+        let chunk_len = reader.u32()? as usize;
+        let chunk = reader.take(chunk_len)?;
+        if chunk_id != b"MTrk" {
+            continue;
+        }
+        tracks_read += 1;
+
+        let mut track = MidiReader { data: chunk, pos: 0 };
+        let mut tick: u64 = 0;
+        let mut running_status: u8 = 0;
+        // (채널, 노트) -> 시작 틱
+        let mut open_notes: Vec<(u8, u8, u64)> = Vec::new();
+
+        while track.pos < track.data.len() {
+            tick += track.vlq()? as u64;
+
+            let mut status = track.peek()?;
+            if status & 0x80 != 0 {
+                track.pos += 1;
+            } else {
+                // 러닝 스테이터스 - 이전 상태 바이트 재사용
+                status = running_status;
+            }
+
+            match status {
+                0xFF => {
+                    let meta_type = track.u8()?;
+                    let len = track.vlq()? as usize;
+                    let data = track.take(len)?;
+                    if meta_type == 0x51 && len == 3 {
+                        let tempo = ((data[0] as u32) << 16) | ((data[1] as u32) << 8) | data[2] as u32;
+                        tempo_changes.push((tick, tempo));
+                    } else if meta_type == 0x2F {
+                        break;
+                    }
+                }
+                0xF0 | 0xF7 => {
+                    let len = track.vlq()? as usize;
+                    track.take(len)?;
+                }
+                _ if status & 0x80 == 0 => {
+                    return Err("MIDI 이벤트 형식 오류".to_string());
+                }
+                _ => {
+                    running_status = status;
+                    let kind = status & 0xF0;
+                    let channel = status & 0x0F;
+                    match kind {
+                        0x80 | 0x90 => {
+                            let key = track.u8()?;
+                            let velocity = track.u8()?;
+                            if kind == 0x90 && velocity > 0 {
+                                open_notes.push((channel, key, tick));
+                            } else if let Some(pos) = open_notes
+                                .iter()
+                                .position(|(c, k, _)| *c == channel && *k == key)
+                            {
+                                let (_, _, start) = open_notes.remove(pos);
+                                if channel != 9 && tick > start {
+                                    raw_notes.push((start, tick, key as i32));
+                                }
+                            }
+                        }
+                        0xC0 | 0xD0 => {
+                            track.u8()?;
+                        }
+                        _ => {
+                            track.take(2)?;
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    if raw_notes.is_empty() {
+        return Err("MIDI 파일에 노트가 없습니다".to_string());
+    }
+
+    tempo_changes.sort_by_key(|(tick, _)| *tick);
+    let to_seconds = |target_tick: u64| -> f64 {
+        let mut seconds = 0.0;
+        let mut last_tick = 0u64;
+        let mut tempo = 500_000u32; // 기본 120 BPM
+        for (change_tick, change_tempo) in tempo_changes.iter() {
+            if *change_tick >= target_tick {
+                break;
+            }
+            seconds += (change_tick - last_tick) as f64 / ticks_per_quarter * tempo as f64 / 1_000_000.0;
+            last_tick = *change_tick;
+            tempo = *change_tempo;
+        }
+        seconds + (target_tick - last_tick) as f64 / ticks_per_quarter * tempo as f64 / 1_000_000.0
+    };
+
+    raw_notes.sort_by_key(|(start, _, _)| *start);
+    let notes = raw_notes
+        .iter()
+        .map(|(start, end, midi)| {
+            let start_sec = to_seconds(*start);
+            TargetNote {
+                midi: *midi,
+                start: start_sec,
+                duration: to_seconds(*end) - start_sec,
+            }
+        })
+        .collect();
+
+    Ok(TargetMelody {
+        name: default_name.to_string(),
+        notes,
+        offset: 0.0,
+    })
+}
+
+// MIDI 바이트 읽기 도우미
+struct MidiReader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> MidiReader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], String> {
+        let end = self
+            .pos
+            .checked_add(len)
+            .filter(|end| *end <= self.data.len())
+            .ok_or_else(|| "MIDI 파일이 손상되었습니다 (데이터 부족)".to_string())?;
+        let slice = &self.data[self.pos..end];
+        self.pos = end;
+        Ok(slice)
+    }
+
+    fn peek(&self) -> Result<u8, String> {
+        self.data
+            .get(self.pos)
+            .copied()
+            .ok_or_else(|| "MIDI 파일이 손상되었습니다 (데이터 부족)".to_string())
+    }
+
+    fn u8(&mut self) -> Result<u8, String> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u16(&mut self) -> Result<u16, String> {
+        let b = self.take(2)?;
+        Ok(u16::from_be_bytes([b[0], b[1]]))
+    }
+
+    fn u32(&mut self) -> Result<u32, String> {
+        let b = self.take(4)?;
+        Ok(u32::from_be_bytes([b[0], b[1], b[2], b[3]]))
+    }
+
+    // 가변 길이 값 (Variable Length Quantity)
+    fn vlq(&mut self) -> Result<u32, String> {
+        let mut value = 0u32;
+        for _ in 0..4 {
+            let byte = self.u8()?;
+            value = (value << 7) | (byte & 0x7F) as u32;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err("MIDI 가변 길이 값 형식 오류".to_string())
+    }
+}
+
+// 음 이름("C4", "F#3", "Bb5")을 MIDI 번호로 변환
+pub fn midi_from_note_name(name: &str) -> Option<i32> {
+    let name = name.trim();
+    let mut chars = name.chars();
+    let letter = chars.next()?.to_ascii_uppercase();
+    let base = match letter {
+        'C' => 0,
+        'D' => 2,
+        'E' => 4,
+        'F' => 5,
+        'G' => 7,
+        'A' => 9,
+        'B' => 11,
+        _ => return None,
+    };
+
+    let rest: String = chars.collect();
+    let (accidental, octave_str) = if let Some(stripped) = rest.strip_prefix('#') {
+        (1, stripped)
+    } else if let Some(stripped) = rest.strip_prefix('b') {
+        (-1, stripped)
+    } else {
+        (0, rest.as_str())
+    };
+
+    let octave: i32 = octave_str.parse().ok()?;
+    Some((octave + 1) * 12 + base + accidental)
+}
+
+pub fn note_name_from_midi(midi: i32) -> String {
+    let notes = ["C", "C#", "D", "D#", "E", "F", "F#", "G", "G#", "A", "A#", "B"];
+    let note = notes[midi.rem_euclid(12) as usize];
+    let octave = midi.div_euclid(12) - 1;
+    format!("{}{}", note, octave)
+}
+
+// 한 음에 대한 채점 결과
+#[derive(Debug, Clone, PartialEq)]
+pub struct NoteScore {
+    pub note: TargetNote,
+    pub sample_count: usize,          // 노트 구간의 전체 분석 샘플 수
+    pub voiced_count: usize,          // 소리가 감지된 샘플 수
+    pub in_tune_ratio: f64,           // 허용 오차 안에 든 샘플 비율 (0.0 ~ 1.0)
+    pub mean_cents_error: Option<f64>, // 평균 센트 오차 (+: 높음, -: 낮음)
+    pub timing_offset: Option<f64>,   // 처음 음을 맞춘 시점 - 노트 시작 (초, +: 늦음)
+}
+
+impl NoteScore {
+    // 절반 이상을 정확한 음정으로 부르면 성공으로 판정
+    pub fn is_hit(&self) -> bool {
+        self.in_tune_ratio >= 0.5
+    }
+}
+
+// 전체 멜로디 채점 요약
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScoreSummary {
+    pub scored_notes: usize,          // 채점된 음 수 (구간에 샘플이 있는 음)
+    pub hit_notes: usize,             // 성공한 음 수
+    pub in_tune_ratio: f64,           // 전체 샘플 기준 음정 정확도
+    pub mean_abs_cents: Option<f64>,  // 평균 절대 센트 오차
+    pub mean_timing_offset: Option<f64>, // 평균 타이밍 오프셋 (초)
+}
+
+// 피치 히스토리를 목표 음 목록에 대해 채점
+// 히스토리 각 항목의 첫 번째 주파수(가장 강한 주파수)를 부른 음으로 사용
+pub fn score_notes(
+    notes: &[TargetNote],
+    history: &VecDeque<(f64, Vec<(f64, f32)>)>,
+    tolerance_cents: f64,
+) -> Vec<NoteScore> {
+    notes
+        .iter()
+        .map(|note| {
+            let target_freq = note.frequency();
+            let mut sample_count = 0;
+            let mut voiced_count = 0;
+            let mut in_tune_count = 0;
+            let mut cents_sum = 0.0;
+            let mut cents_count = 0;
+            let mut timing_offset = None;
+
+            for (t, freqs) in history.iter() {
+                if *t < note.start - TIMING_LOOKAHEAD || *t > note.end() {
+                    continue;
+                }
+
+                let cents = freqs
+                    .first()
+                    .filter(|(freq, _)| *freq > 0.0)
+                    .map(|(freq, _)| cents_between(*freq, target_freq));
+
+                // 타이밍은 노트 시작 직전부터 처음 음을 맞춘 시점으로 측정
+                if timing_offset.is_none() {
+                    if let Some(c) = cents {
+                        if c.abs() <= tolerance_cents {
+                            timing_offset = Some(*t - note.start);
+                        }
+                    }
+                }
+
+                if *t < note.start {
+                    continue;
+                }
+
+                sample_count += 1;
+                if let Some(c) = cents {
+                    voiced_count += 1;
+                    if c.abs() <= tolerance_cents {
+                        in_tune_count += 1;
+                    }
+                    if c.abs() <= MAX_SCORED_CENTS {
+                        cents_sum += c;
+                        cents_count += 1;
+                    }
+                }
+            }
+
+            NoteScore {
+                note: note.clone(),
+                sample_count,
+                voiced_count,
+                in_tune_ratio: if sample_count > 0 {
+                    in_tune_count as f64 / sample_count as f64
+                } else {
+                    0.0
+                },
+                mean_cents_error: if cents_count > 0 {
+                    Some(cents_sum / cents_count as f64)
+                } else {
+                    None
+                },
+                timing_offset,
+            }
+        })
+        .collect()
+}
+
+// 음별 채점 결과를 요약
+pub fn summarize_scores(scores: &[NoteScore]) -> ScoreSummary {
+    let scored: Vec<&NoteScore> = scores.iter().filter(|s| s.sample_count > 0).collect();
+    let total_samples: usize = scored.iter().map(|s| s.sample_count).sum();
+    let in_tune_samples: f64 = scored
+        .iter()
+        .map(|s| s.in_tune_ratio * s.sample_count as f64)
+        .sum();
+
+    let cents: Vec<f64> = scored.iter().filter_map(|s| s.mean_cents_error).map(f64::abs).collect();
+    let timings: Vec<f64> = scored.iter().filter_map(|s| s.timing_offset).collect();
+
+    ScoreSummary {
+        scored_notes: scored.len(),
+        hit_notes: scored.iter().filter(|s| s.is_hit()).count(),
+        in_tune_ratio: if total_samples > 0 {
+            in_tune_samples / total_samples as f64
+        } else {
+            0.0
+        },
+        mean_abs_cents: if cents.is_empty() {
+            None
+        } else {
+            Some(cents.iter().sum::<f64>() / cents.len() as f64)
+        },
+        mean_timing_offset: if timings.is_empty() {
+            None
+        } else {
+            Some(timings.iter().sum::<f64>() / timings.len() as f64)
+        },
+    }
+}
+
+// 현재 선택된 목표 멜로디 - 페이지 이동(컴포넌트 재생성) 후에도 유지되도록 전역 보관
+thread_local! {
+    static CURRENT_TARGET: RefCell<Option<TargetMelody>> = const { RefCell::new(None) };
+}
+
+pub fn current_target_melody() -> Option<TargetMelody> {
+    CURRENT_TARGET.with(|target| target.borrow().clone())
+}
+
+// 목표 멜로디를 교체하고 TargetMelodyChanged 이벤트로 알림
+pub fn set_target_melody(melody: Option<TargetMelody>) {
+    CURRENT_TARGET.with(|target| *target.borrow_mut() = melody);
+
+    emit(AppEvent::TargetMelodyChanged);
+}
+
+#[derive(Properties, PartialEq)]
+pub struct TargetMelodyPanelProps {
+    pub history: VecDeque<(f64, Vec<(f64, f32)>)>, // 채점할 피치 히스토리
+    pub melody: Option<TargetMelody>,              // 현재 목표 멜로디
+    pub is_recording: bool,                        // 녹음 중인지 여부
+}
+
+// 목표 멜로디 불러오기 및 채점 결과 패널
+#[function_component(TargetMelodyPanel)]
+pub fn target_melody_panel(props: &TargetMelodyPanelProps) -> Html {
+    let tolerance = use_state(|| DEFAULT_TOLERANCE_CENTS);
+    let load_error = use_state(|| None::<String>);
+    // 파일 읽기가 끝날 때까지 FileReader 핸들 보관
+    let file_reader = use_mut_ref(|| None::<gloo::file::callbacks::FileReader>);
+
+    let on_file_change = {
+        let load_error = load_error.clone();
+        let file_reader = file_reader.clone();
+        Callback::from(move |e: Event| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            let file = match input.files().and_then(|files| files.get(0)) {
+                Some(file) => gloo::file::File::from(file),
+                None => return,
+            };
+            // 같은 파일을 다시 선택해도 change 이벤트가 발생하도록 초기화
+            input.set_value("");
+
+            let file_name = file.name();
+            let melody_name = file_name
+                .rsplit_once('.')
+                .map(|(stem, _)| stem.to_string())
+                .unwrap_or_else(|| file_name.clone());
+            let is_json = file_name.to_lowercase().ends_with(".json");
+
+            let load_error = load_error.clone();
+            let reader_slot = Rc::clone(&file_reader);
+            let reader = gloo::file::callbacks::read_as_bytes(&file, move |result| {
+                let parsed = match result {
+                    Ok(bytes) if is_json => String::from_utf8(bytes)
+                        .map_err(|_| "UTF-8 텍스트가 아닙니다".to_string())
+                        .and_then(|text| parse_melody_json(&text, &melody_name)),
+                    Ok(bytes) => parse_midi_file(&bytes, &melody_name),
+                    Err(err) => Err(format!("파일을 읽을 수 없습니다: {}", err)),
+                };
+
+                match parsed {
+                    Ok(melody) => {
+                        console_log!("목표 멜로디 불러옴: {} ({}개 음)", melody.name, melody.notes.len());
+                        load_error.set(None);
+                        set_target_melody(Some(melody));
+                    }
+                    Err(err) => {
+                        console_error!("목표 멜로디 불러오기 실패: {}", err);
+                        load_error.set(Some(err));
+                    }
+                }
+                reader_slot.borrow_mut().take();
+            });
+            *file_reader.borrow_mut() = Some(reader);
+        })
+    };
+
+    let on_clear = Callback::from(|_: MouseEvent| set_target_melody(None));
+
+    let on_offset_change = {
+        let melody = props.melody.clone();
+        Callback::from(move |e: Event| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            if let (Some(melody), Ok(offset)) = (melody.clone(), input.value().parse::<f64>()) {
+                set_target_melody(Some(TargetMelody {
+                    offset: offset.max(0.0),
+                    ..melody
+                }));
+            }
+        })
+    };
+
+    let on_tolerance_change = {
+        let tolerance = tolerance.clone();
+        Callback::from(move |e: Event| {
+            let select: web_sys::HtmlSelectElement = e.target_unchecked_into();
+            if let Ok(value) = select.value().parse::<f64>() {
+                tolerance.set(value);
+            }
+        })
+    };
+
+    let results = if let Some(melody) = &props.melody {
+        let scores = score_notes(&melody.shifted_notes(), &props.history, *tolerance);
+        let summary = summarize_scores(&scores);
+
+        html! {
+            <>
+                <div class="target-melody-summary">
+                    <div class="summary-item">
+                        <span class="summary-label">{"성공한 음"}</span>
+                        <span class="summary-value">{format!("{} / {}", summary.hit_notes, melody.notes.len())}</span>
+                    </div>
+                    <div class="summary-item">
+                        <span class="summary-label">{"음정 정확도"}</span>
+                        <span class="summary-value">{format!("{:.0}%", summary.in_tune_ratio * 100.0)}</span>
+                    </div>
+                    <div class="summary-item">
+                        <span class="summary-label">{"평균 오차"}</span>
+                        <span class="summary-value">{format_cents(summary.mean_abs_cents)}</span>
+                    </div>
+                    <div class="summary-item">
+                        <span class="summary-label">{"평균 타이밍"}</span>
+                        <span class="summary-value">{format_timing(summary.mean_timing_offset)}</span>
+                    </div>
+                </div>
+                {
+                    // 녹음이 끝난 뒤에만 음별 결과 표시
+                    if !props.is_recording && summary.scored_notes > 0 {
+                        html! {
+                            <table class="target-melody-table">
+                                <thead>
+                                    <tr>
+                                        <th>{"#"}</th>
+                                        <th>{"음"}</th>
+                                        <th>{"시작"}</th>
+                                        <th>{"정확도"}</th>
+                                        <th>{"센트 오차"}</th>
+                                        <th>{"타이밍"}</th>
+                                    </tr>
+                                </thead>
+                                <tbody>
+                                    {
+                                        scores.iter().enumerate().map(|(idx, score)| {
+                                            let row_class = if score.sample_count == 0 {
+                                                "not-scored"
+                                            } else if score.is_hit() {
+                                                "hit"
+                                            } else {
+                                                "miss"
+                                            };
+                                            html! {
+                                                <tr class={row_class}>
+                                                    <td>{idx + 1}</td>
+                                                    <td>{note_name_from_midi(score.note.midi)}</td>
+                                                    <td>{format!("{:.2}s", score.note.start)}</td>
+                                                    <td>{format!("{:.0}%", score.in_tune_ratio * 100.0)}</td>
+                                                    <td>{format_signed_cents(score.mean_cents_error)}</td>
+                                                    <td>{format_timing(score.timing_offset)}</td>
+                                                </tr>
+                                            }
+                                        }).collect::<Html>()
+                                    }
+                                </tbody>
+                            </table>
+                        }
+                    } else {
+                        html! {}
+                    }
+                }
+            </>
+        }
+    } else {
+        html! {
+            <p class="target-melody-hint">
+                {"MIDI(.mid) 또는 JSON 파일을 불러오거나, 스케일 생성기에서 \"🎯 목표로 보내기\"를 누르세요."}
+            </p>
+        }
+    };
+
+    html! {
+        <div class="target-melody-panel">
+            <div class="target-melody-header">
+                <span class="target-melody-title">
+                    {"🎯 목표 멜로디"}
+                    {
+                        if let Some(melody) = &props.melody {
+                            html! {
+                                <span class="target-melody-name">
+                                    {format!(" - {} ({}개 음, {:.1}초)", melody.name, melody.notes.len(), melody.total_duration())}
+                                </span>
+                            }
+                        } else {
+                            html! {}
+                        }
+                    }
+                </span>
+                <div class="target-melody-controls">
+                    <label class="file-button">
+                        {"불러오기"}
+                        <input
+                            type="file"
+                            accept=".mid,.midi,.json"
+                            style="display: none;"
+                            onchange={on_file_change}
+                        />
+                    </label>
+                    <label>
+                        {"시작 오프셋(초): "}
+                        <input
+                            type="number"
+                            min="0"
+                            step="0.1"
+                            disabled={props.melody.is_none()}
+                            value={props.melody.as_ref().map(|m| m.offset).unwrap_or(0.0).to_string()}
+                            onchange={on_offset_change}
+                        />
+                    </label>
+                    <label>
+                        {"허용 오차: "}
+                        <select onchange={on_tolerance_change}>
+                            {
+                                [25.0, 50.0, 100.0].iter().map(|value| {
+                                    html! {
+                                        <option value={value.to_string()} selected={*tolerance == *value}>
+                                            {format!("±{:.0} cents", value)}
+                                        </option>
+                                    }
+                                }).collect::<Html>()
+                            }
+                        </select>
+                    </label>
+                    <button onclick={on_clear} disabled={props.melody.is_none()}>{"지우기"}</button>
+                </div>
+            </div>
+            {
+                if let Some(err) = &*load_error {
+                    html! { <p class="target-melody-error">{err}</p> }
+                } else {
+                    html! {}
+                }
+            }
+            { results }
+        </div>
+    }
+}
+
+fn format_cents(cents: Option<f64>) -> String {
+    cents.map(|c| format!("{:.0} cents", c)).unwrap_or_else(|| "-".to_string())
+}
+
+fn format_signed_cents(cents: Option<f64>) -> String {
+    cents.map(|c| format!("{:+.0} cents", c)).unwrap_or_else(|| "-".to_string())
+}
+
+fn format_timing(offset: Option<f64>) -> String {
+    offset
+        .map(|t| format!("{:+.0} ms", t * 1000.0))
+        .unwrap_or_else(|| "-".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn approx(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-6
+    }
+
+    // 표준 MIDI 파일 바이트 생성 (형식 1, 4분음표당 480틱)
+    fn smf(track_count: u16, chunks: &[(&[u8; 4], Vec<u8>)]) -> Vec<u8> {
+        let mut bytes = b"MThd".to_vec();
+        bytes.extend_from_slice(&6u32.to_be_bytes());
+        bytes.extend_from_slice(&1u16.to_be_bytes());
+        bytes.extend_from_slice(&track_count.to_be_bytes());
+        bytes.extend_from_slice(&480u16.to_be_bytes());
+        for (id, data) in chunks {
+            bytes.extend_from_slice(*id);
+            bytes.extend_from_slice(&(data.len() as u32).to_be_bytes());
+            bytes.extend_from_slice(data);
+        }
+        bytes
+    }
+
+    // C4 4분음표 하나 (120 BPM)
+    fn single_note_track(key: u8) -> Vec<u8> {
+        vec![0x00, 0x90, key, 0x64, 0x83, 0x60, 0x80, key, 0x00, 0x00, 0xFF, 0x2F, 0x00]
+    }
+
+    #[test]
+    fn midi_running_status_and_tempo_change() {
+        let track = vec![
+            0x00, 0xFF, 0x51, 0x03, 0x07, 0xA1, 0x20, // 템포 500000 (120 BPM)
+            0x00, 0x90, 0x3C, 0x64, // C4 켜기
+            0x83, 0x60, 0x3C, 0x00, // 러닝 스테이터스 + 벨로시티 0 = C4 끄기
+            0x00, 0x40, 0x64, // 러닝 스테이터스로 E4 켜기
+            0x00, 0xFF, 0x51, 0x03, 0x0F, 0x42, 0x40, // 템포 1000000 (60 BPM)
+            0x83, 0x60, 0x80, 0x40, 0x00, // E4 끄기
+            0x00, 0xFF, 0x2F, 0x00,
+        ];
+        let melody = parse_midi_file(&smf(1, &[(b"MTrk", track)]), "test").unwrap();
+
+        assert_eq!(melody.name, "test");
+        assert_eq!(melody.notes.len(), 2);
+        assert_eq!(melody.notes[0].midi, 60);
+        assert!(approx(melody.notes[0].start, 0.0));
+        assert!(approx(melody.notes[0].duration, 0.5));
+        assert_eq!(melody.notes[1].midi, 64);
+        assert!(approx(melody.notes[1].start, 0.5));
+        assert!(approx(melody.notes[1].duration, 1.0));
+    }
+
+    #[test]
+    fn midi_unknown_chunk_does_not_count_as_track() {
+        let bytes = smf(
+            2,
+            &[
+                (b"MTrk", single_note_track(60)),
+                (b"XFIH", vec![0x01, 0x02, 0x03]),
+                (b"MTrk", single_note_track(67)),
+            ],
+        );
+        let melody = parse_midi_file(&bytes, "test").unwrap();
+        let midis: Vec<i32> = melody.notes.iter().map(|n| n.midi).collect();
+        assert_eq!(midis, vec![60, 67]);
+    }
+
+    #[test]
+    fn midi_drum_channel_is_ignored() {
+        let drums = vec![0x00, 0x99, 0x24, 0x64, 0x83, 0x60, 0x89, 0x24, 0x00, 0x00, 0xFF, 0x2F, 0x00];
+        let bytes = smf(2, &[(b"MTrk", drums), (b"MTrk", single_note_track(62))]);
+        let melody = parse_midi_file(&bytes, "test").unwrap();
+        assert_eq!(melody.notes.len(), 1);
+        assert_eq!(melody.notes[0].midi, 62);
+    }
+
+    #[test]
+    fn midi_rejects_invalid_input() {
+        assert!(parse_midi_file(b"RIFF0000", "test").is_err());
+        let empty = smf(1, &[(b"MTrk", vec![0x00, 0xFF, 0x2F, 0x00])]);
+        assert!(parse_midi_file(&empty, "test").is_err());
+        let truncated = smf(1, &[(b"MTrk", single_note_track(60))]);
+        assert!(parse_midi_file(&truncated[..truncated.len() - 4], "test").is_err());
+    }
+
+    #[test]
+    fn midi_huge_chunk_length_is_an_error() {
+        let mut bytes = smf(1, &[]);
+        bytes.extend_from_slice(b"MTrk");
+        bytes.extend_from_slice(&u32::MAX.to_be_bytes());
+        bytes.extend_from_slice(&single_note_track(60));
+        assert!(parse_midi_file(&bytes, "test").is_err());
+
+        // wasm32의 32비트 usize에서 pos + len이 넘치는 경우
+        let data = [0u8; 8];
+        let mut reader = MidiReader { data: &data, pos: 4 };
+        assert!(reader.take(usize::MAX).is_err());
+        assert_eq!(reader.pos, 4);
+    }
+
+    #[test]
+    fn json_object_with_note_names() {
+        let text = r#"{"name": "도레미", "notes": [
+            {"note": "E4", "start": 1.0, "duration": 0.5},
+            {"note": "C4", "start": 0.0, "duration": 0.5},
+            {"midi": 62, "start": 0.5, "duration": 0.5},
+            {"note": "Eb3", "start": 1.5, "duration": 0.25}
+        ]}"#;
+        let melody = parse_melody_json(text, "default").unwrap();
+        assert_eq!(melody.name, "도레미");
+        let midis: Vec<i32> = melody.notes.iter().map(|n| n.midi).collect();
+        assert_eq!(midis, vec![60, 62, 64, 51]);
+    }
+
+    #[test]
+    fn json_bare_array_uses_default_name() {
+        let text = r#"[{"note": "A4", "start": 0.0, "duration": 1.0}]"#;
+        let melody = parse_melody_json(text, "default").unwrap();
+        assert_eq!(melody.name, "default");
+        assert_eq!(melody.notes[0].midi, 69);
+    }
+
+    #[test]
+    fn json_rejects_bad_notes() {
+        assert!(parse_melody_json(r#"[{"start": 0.0, "duration": 1.0}]"#, "x").is_err());
+        assert!(parse_melody_json(r#"[{"note": "H4", "start": 0.0, "duration": 1.0}]"#, "x").is_err());
+        assert!(parse_melody_json(r#"[{"midi": 60, "start": 0.0, "duration": 0.0}]"#, "x").is_err());
+        assert!(parse_melody_json(r#"{"notes": []}"#, "x").is_err());
+        assert!(parse_melody_json("not json", "x").is_err());
+    }
+
+    #[test]
+    fn note_names_round_trip() {
+        assert_eq!(midi_from_note_name("C4"), Some(60));
+        assert_eq!(midi_from_note_name("c#4"), Some(61));
+        assert_eq!(midi_from_note_name("Bb2"), Some(46));
+        assert_eq!(note_name_from_midi(61), "C#4");
+        assert_eq!(midi_from_note_name(&note_name_from_midi(47)), Some(47));
+    }
+
+    fn sample(t: f64, freq: Option<f64>) -> (f64, Vec<(f64, f32)>) {
+        (t, freq.map(|f| vec![(f, 1.0)]).unwrap_or_default())
+    }
+
+    fn cents_above(midi: i32, cents: f64) -> f64 {
+        midi_to_frequency(midi) * 2f64.powf(cents / 1200.0)
+    }
+
+    #[test]
+    fn score_notes_counts_samples_in_note_window() {
+        let notes = vec![TargetNote { midi: 60, start: 1.0, duration: 1.0 }];
+        let history: VecDeque<_> = vec![
+            sample(0.5, Some(cents_above(60, 0.0))), // 미리보기 구간 밖
+            sample(0.9, None),
+            sample(1.1, Some(cents_above(60, 10.0))),
+            sample(1.3, Some(cents_above(60, -10.0))),
+            sample(1.5, Some(cents_above(60, 60.0))),
+            sample(1.7, Some(cents_above(60, 700.0))), // 다른 음 - 센트 평균에서 제외
+            sample(1.9, None),
+            sample(2.5, Some(cents_above(60, 0.0))), // 노트 종료 후
+        ]
+        .into_iter()
+        .collect();
+
+        let scores = score_notes(&notes, &history, 25.0);
+        let score = &scores[0];
+        assert_eq!(score.sample_count, 5);
+        assert_eq!(score.voiced_count, 4);
+        assert!(approx(score.in_tune_ratio, 0.4));
+        assert!(!score.is_hit());
+        assert!(approx(score.mean_cents_error.unwrap(), 20.0));
+        assert!(approx(score.timing_offset.unwrap(), 0.1));
+    }
+
+    #[test]
+    fn score_notes_measures_early_entry() {
+        let notes = vec![TargetNote { midi: 69, start: 1.0, duration: 0.5 }];
+        let history: VecDeque<_> = vec![
+            sample(0.8, Some(440.0)),
+            sample(1.0, Some(440.0)),
+            sample(1.2, Some(440.0)),
+        ]
+        .into_iter()
+        .collect();
+
+        let score = &score_notes(&notes, &history, 25.0)[0];
+        assert_eq!(score.sample_count, 2);
+        assert!(score.is_hit());
+        assert!(approx(score.timing_offset.unwrap(), -0.2));
+    }
+
+    #[test]
+    fn summarize_scores_skips_unscored_notes() {
+        let notes = vec![
+            TargetNote { midi: 60, start: 0.0, duration: 1.0 },
+            TargetNote { midi: 62, start: 1.0, duration: 1.0 },
+            TargetNote { midi: 64, start: 5.0, duration: 1.0 },
+        ];
+        let history: VecDeque<_> = vec![
+            sample(0.2, Some(cents_above(60, 10.0))),
+            sample(0.6, Some(cents_above(60, 10.0))),
+            sample(1.2, Some(cents_above(62, -40.0))),
+            sample(1.6, Some(cents_above(62, 20.0))),
+        ]
+        .into_iter()
+        .collect();
+
+        let summary = summarize_scores(&score_notes(&notes, &history, 25.0));
+        assert_eq!(summary.scored_notes, 2);
+        assert_eq!(summary.hit_notes, 2);
+        assert!(approx(summary.in_tune_ratio, 0.75));
+        assert!(approx(summary.mean_abs_cents.unwrap(), 10.0));
+        assert!(approx(summary.mean_timing_offset.unwrap(), 0.4));
+    }
+
+    #[test]
+    fn summarize_scores_empty() {
+        let summary = summarize_scores(&[]);
+        assert_eq!(summary.scored_notes, 0);
+        assert_eq!(summary.in_tune_ratio, 0.0);
+        assert_eq!(summary.mean_abs_cents, None);
+        assert_eq!(summary.mean_timing_offset, None);
+    }
+}